@@ -1,12 +1,17 @@
 //! Provides the `ConfigBuilder` for fluent configuration construction.
 
-use super::loading::{apply_file_config, load_config_file};
-use super::validation::validate_config;
-use super::{Config, ConfigFile, Result};
+use super::file::GenericPrefixes;
+use super::loading::{
+    apply_file_config, env_config_overrides, lenient_config_from_env, load_config_file,
+};
+use super::remote::{default_cache_dir, load_remote_list};
+use super::validation::{collect_config_problems, validate_config};
+use super::{Config, ConfigFile, OutputOrder, ProbeCharset, Result, TlsVersion, VerificationLevel};
+use crate::verification::headless::HeadlessProvider;
 use crate::AppError;
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
-use tracing;
 /// Builder pattern for creating `Config` instances fluently.
 ///
 /// This is the primary way users should create a `Config` object.
@@ -15,7 +20,14 @@ use tracing;
 pub struct ConfigBuilder {
     config: Config,
     config_file_path: Option<String>,
+    lenient_config: Option<bool>,
+    apply_env: bool,
+    local_webdriver: bool,
     overrides: ConfigFile,
+    /// Entries appended to the list-valued settings once everything else is applied.
+    extra_common_pages: Vec<String>,
+    extra_generic_prefixes: Vec<String>,
+    extra_dns_servers: Vec<String>,
 }
 
 impl ConfigBuilder {
@@ -30,12 +42,169 @@ impl ConfigBuilder {
         self
     }
 
+    /// Ignore (with a warning) unknown keys in the config file instead of failing.
+    ///
+    /// Defaults to strict loading unless `EMAIL_SLEUTH_LENIENT_CONFIG` is set.
+    pub fn lenient_config(mut self, lenient: bool) -> Self {
+        self.lenient_config = Some(lenient);
+        self
+    }
+
+    /// Apply `EMAIL_SLEUTH_<KEY>` environment variables (e.g. `EMAIL_SLEUTH_SMTP_TIMEOUT`)
+    /// on top of the config file. Overrides set on the builder still take precedence.
+    pub fn apply_env(mut self) -> Self {
+        self.apply_env = true;
+        self
+    }
+
     pub fn max_concurrency(mut self, value: usize) -> Self {
         self.overrides.verification.max_concurrency = Some(value);
         self
     }
-    pub fn max_verification_attempts(mut self, value: u32) -> Self {
-        self.overrides.smtp.max_verification_attempts = Some(value);
+    /// Sets [`Config::domain_fair_scheduling`].
+    pub fn domain_fair_scheduling(mut self, enabled: bool) -> Self {
+        self.overrides.verification.domain_fair_scheduling = Some(enabled);
+        self
+    }
+    pub fn smtp_max_attempts(mut self, value: u32) -> Self {
+        self.overrides.smtp.smtp_max_attempts = Some(value);
+        self
+    }
+    /// Deprecated alias for [`smtp_max_attempts`](Self::smtp_max_attempts).
+    pub fn max_verification_attempts(self, value: u32) -> Self {
+        self.smtp_max_attempts(value)
+    }
+    pub fn headless_max_attempts(mut self, value: u32) -> Self {
+        self.overrides.advanced_verification.headless_max_attempts = Some(value);
+        self
+    }
+    /// Command-line arguments for the headless browser, replacing the defaults.
+    pub fn headless_browser_args(mut self, args: Vec<String>) -> Self {
+        self.overrides.advanced_verification.headless_browser_args = Some(args);
+        self
+    }
+    /// Confidence given when a headless provider's check reports the address exists.
+    pub fn headless_confidence(mut self, provider: HeadlessProvider, confidence: u8) -> Self {
+        match provider {
+            HeadlessProvider::Microsoft => {
+                self.overrides.headless_confidence.microsoft = Some(confidence)
+            }
+            HeadlessProvider::Yahoo => self.overrides.headless_confidence.yahoo = Some(confidence),
+        }
+        self
+    }
+    /// Sets [`Config::scrape_top_k_pages`]; 0 scrapes every page in `common_pages_to_scrape`.
+    pub fn scrape_top_k_pages(mut self, k: usize) -> Self {
+        self.overrides.scraping.scrape_top_k_pages = Some(k);
+        self
+    }
+    pub fn scraping_max_attempts(mut self, value: u32) -> Self {
+        self.overrides.scraping.scraping_max_attempts = Some(value);
+        self
+    }
+    pub fn prefer_personal_over_role(mut self, enabled: bool) -> Self {
+        self.overrides.scraping.prefer_personal_over_role = Some(enabled);
+        self
+    }
+    pub fn headless_scrape_fallback(mut self, enabled: bool) -> Self {
+        self.overrides.scraping.headless_scrape_fallback = Some(enabled);
+        self
+    }
+    /// Drops scraped addresses at other domains; see [`Config::exclude_off_domain_scraped`].
+    pub fn exclude_off_domain_scraped(mut self, exclude: bool) -> Self {
+        self.overrides.scraping.exclude_off_domain_scraped = Some(exclude);
+        self
+    }
+    /// Sets [`Config::respect_robots_txt`].
+    pub fn respect_robots_txt(mut self, respect: bool) -> Self {
+        self.overrides.scraping.respect_robots_txt = Some(respect);
+        self
+    }
+    /// Sets [`Config::dedup_expected_items`].
+    pub fn dedup_expected_items(mut self, items: usize) -> Self {
+        self.overrides.scraping.dedup_expected_items = Some(items);
+        self
+    }
+    /// Sets [`Config::dedup_false_positive_rate`].
+    pub fn dedup_false_positive_rate(mut self, rate: f64) -> Self {
+        self.overrides.scraping.dedup_false_positive_rate = Some(rate);
+        self
+    }
+    pub fn smtp_rejection_threshold(mut self, value: f32) -> Self {
+        self.overrides.smtp.smtp_rejection_threshold = Some(value);
+        self
+    }
+    pub fn smtp_rejection_window(mut self, value: usize) -> Self {
+        self.overrides.smtp.smtp_rejection_window = Some(value);
+        self
+    }
+    pub fn smtp_rejection_cooldown(mut self, duration: Duration) -> Self {
+        self.overrides.smtp.smtp_rejection_cooldown = Some(duration.as_secs());
+        self
+    }
+    pub fn smtp_breaker_threshold(mut self, value: usize) -> Self {
+        self.overrides.smtp.smtp_breaker_threshold = Some(value);
+        self
+    }
+    pub fn smtp_breaker_cooldown(mut self, duration: Duration) -> Self {
+        self.overrides.smtp.smtp_breaker_cooldown = Some(duration.as_secs());
+        self
+    }
+    /// Caps the SMTP probes sent to any one domain; see [`Config::max_probes_per_domain`].
+    pub fn max_probes_per_domain(mut self, value: usize) -> Self {
+        self.overrides.smtp.max_probes_per_domain = Some(value);
+        self
+    }
+    /// Sets [`Config::smtp_prefetch_catch_all`].
+    pub fn smtp_prefetch_catch_all(mut self, enable: bool) -> Self {
+        self.overrides.smtp.smtp_prefetch_catch_all = Some(enable);
+        self
+    }
+    pub fn smtp_subaddress_probe(mut self, enable: bool) -> Self {
+        self.overrides.smtp.smtp_subaddress_probe = Some(enable);
+        self
+    }
+    pub fn smtp_expn_probe(mut self, enable: bool) -> Self {
+        self.overrides.smtp.smtp_expn_probe = Some(enable);
+        self
+    }
+    pub fn smtp_vrfy_probe(mut self, enable: bool) -> Self {
+        self.overrides.smtp.smtp_vrfy_probe = Some(enable);
+        self
+    }
+    pub fn smtp_probe_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.overrides.smtp.smtp_probe_prefix = Some(prefix.into());
+        self
+    }
+    pub fn smtp_probe_length(mut self, length: usize) -> Self {
+        self.overrides.smtp.smtp_probe_length = Some(length);
+        self
+    }
+    pub fn smtp_probe_charset(mut self, charset: ProbeCharset) -> Self {
+        self.overrides.smtp.smtp_probe_charset = Some(charset);
+        self
+    }
+    pub fn smtp_sender_checks(mut self, enable: bool) -> Self {
+        self.overrides.smtp.smtp_sender_checks = Some(enable);
+        self
+    }
+    pub fn max_concurrency_per_mx(mut self, value: usize) -> Self {
+        self.overrides.smtp.max_concurrency_per_mx = Some(value);
+        self
+    }
+    /// Test-only: routes every SMTP probe to `target` (`host:port`), ignoring real MX records.
+    pub fn smtp_force_target(mut self, target: impl Into<String>) -> Self {
+        self.overrides.smtp.smtp_force_target = Some(target.into());
+        self
+    }
+    /// Skips SMTP probing for domains under these TLDs (e.g. `["ru", "co.jp"]`).
+    pub fn smtp_skip_tlds(mut self, tlds: Vec<String>) -> Self {
+        self.overrides.smtp.smtp_skip_tlds = Some(tlds);
+        self
+    }
+    /// Replaces the SMTP verification dialogue; see [`Config::smtp_dialogue`].
+    pub fn smtp_dialogue(mut self, steps: Vec<String>) -> Self {
+        self.overrides.smtp.smtp_dialogue = Some(steps);
         self
     }
     pub fn sleep_between_requests(mut self, min: f32, max: f32) -> Self {
@@ -47,10 +216,28 @@ impl ConfigBuilder {
         self.overrides.network.request_timeout = Some(duration.as_secs());
         self
     }
+    /// Sets [`Config::rate_limit_max_retries`].
+    pub fn rate_limit_max_retries(mut self, retries: u32) -> Self {
+        self.overrides.network.rate_limit_max_retries = Some(retries);
+        self
+    }
+    /// Sets [`Config::rate_limit_max_wait`].
+    pub fn rate_limit_max_wait(mut self, wait: Duration) -> Self {
+        self.overrides.network.rate_limit_max_wait = Some(wait.as_secs());
+        self
+    }
     pub fn smtp_timeout(mut self, duration: Duration) -> Self {
         self.overrides.smtp.smtp_timeout = Some(duration.as_secs());
         self
     }
+    pub fn smtp_rcpt_timeout(mut self, duration: Duration) -> Self {
+        self.overrides.smtp.smtp_rcpt_timeout = Some(duration.as_secs());
+        self
+    }
+    pub fn smtp_tls_sni(mut self, enable: bool) -> Self {
+        self.overrides.smtp.smtp_tls_sni = Some(enable);
+        self
+    }
     pub fn dns_timeout(mut self, duration: Duration) -> Self {
         self.overrides.dns.dns_timeout = Some(duration.as_secs());
         self
@@ -59,22 +246,103 @@ impl ConfigBuilder {
         self.overrides.scraping.common_pages = Some(pages);
         self
     }
+    /// Adds a page to scrape on top of the configured ones (the defaults, the config
+    /// file's list, or [`common_pages_to_scrape`](Self::common_pages_to_scrape)).
+    pub fn add_common_page(mut self, page: impl Into<String>) -> Self {
+        self.extra_common_pages.push(page.into());
+        self
+    }
+    /// Sets the generic prefixes, each with the default penalty.
     pub fn generic_email_prefixes(mut self, prefixes: Vec<String>) -> Self {
-        self.overrides.scraping.generic_email_prefixes = Some(prefixes);
+        self.overrides.scraping.generic_email_prefixes = Some(GenericPrefixes::List(prefixes));
+        self
+    }
+    /// Fetches the generic prefixes from `url` when the config is built, instead of using
+    /// the configured ones. Prefixes added with [`ConfigBuilder::add_generic_prefix`] are
+    /// still added on top.
+    pub fn generic_email_prefixes_url(mut self, url: Option<impl Into<String>>) -> Self {
+        self.overrides.scraping.generic_email_prefixes_url = url.map(|s| s.into());
+        self
+    }
+    /// Adds a generic prefix, with the default penalty, on top of the configured ones.
+    /// A prefix that is already configured keeps its penalty.
+    pub fn add_generic_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.extra_generic_prefixes.push(prefix.into());
+        self
+    }
+    /// Sets the generic prefixes with an individual confidence penalty for each.
+    /// A penalty of 0 treats the prefix as neutral.
+    pub fn generic_email_prefix_penalties(mut self, penalties: HashMap<String, u8>) -> Self {
+        self.overrides.scraping.generic_email_prefixes =
+            Some(GenericPrefixes::Penalties(penalties.into_iter().collect()));
         self
     }
     pub fn user_agent(mut self, value: impl Into<String>) -> Self {
         self.overrides.network.user_agent = Some(value.into());
         self
     }
+    pub fn min_tls_version(mut self, version: TlsVersion) -> Self {
+        self.overrides.network.min_tls_version = Some(version);
+        self
+    }
+    /// Sets where lists fetched from a URL are cached; see [`Config::remote_list_cache_dir`].
+    pub fn remote_list_cache_dir(mut self, dir: Option<impl Into<String>>) -> Self {
+        self.overrides.network.remote_list_cache_dir = dir.map(|s| s.into());
+        self
+    }
     pub fn smtp_sender_email(mut self, value: impl Into<String>) -> Self {
         self.overrides.smtp.smtp_sender_email = Some(value.into());
         self
     }
+    pub fn smtp_sender_domain(mut self, value: impl Into<String>) -> Self {
+        self.overrides.smtp.smtp_sender_domain = Some(value.into());
+        self
+    }
     pub fn dns_servers(mut self, servers: Vec<String>) -> Self {
         self.overrides.dns.dns_servers = Some(servers);
         self
     }
+    /// Adds a DNS server after the configured ones.
+    pub fn add_dns_server(mut self, server: impl Into<String>) -> Self {
+        self.extra_dns_servers.push(server.into());
+        self
+    }
+    pub fn max_dns_concurrency(mut self, value: usize) -> Self {
+        self.overrides.dns.max_dns_concurrency = Some(value);
+        self
+    }
+    pub fn dnssec_validation(mut self, enable: bool) -> Self {
+        self.overrides.dns.dnssec_validation = Some(enable);
+        self
+    }
+    /// Sets [`Config::skip_dead_domains`].
+    pub fn skip_dead_domains(mut self, enable: bool) -> Self {
+        self.overrides.dns.skip_dead_domains = Some(enable);
+        self
+    }
+    /// Sets [`Config::dead_domain_ttl`].
+    pub fn dead_domain_ttl(mut self, ttl: Duration) -> Self {
+        self.overrides.dns.dead_domain_ttl = Some(ttl.as_secs());
+        self
+    }
+    /// Uses `hosts` as the mail servers for `domain` instead of resolving its MX records.
+    pub fn dns_override(mut self, domain: impl Into<String>, hosts: Vec<String>) -> Self {
+        self.overrides
+            .dns
+            .dns_overrides
+            .get_or_insert_with(Default::default)
+            .insert(domain.into(), hosts);
+        self
+    }
+    /// Reports MX hosts ending in `suffix` as hosted by `provider`; see [`Config::mx_providers`].
+    pub fn mx_provider(mut self, suffix: impl Into<String>, provider: impl Into<String>) -> Self {
+        self.overrides
+            .dns
+            .mx_providers
+            .get_or_insert_with(Default::default)
+            .insert(suffix.into(), provider.into());
+        self
+    }
     pub fn confidence_threshold(mut self, value: u8) -> Self {
         self.overrides.verification.confidence_threshold = Some(value);
         self
@@ -87,6 +355,10 @@ impl ConfigBuilder {
         self.overrides.verification.max_alternatives = Some(value);
         self
     }
+    pub fn min_alternative_confidence(mut self, value: u8) -> Self {
+        self.overrides.verification.min_alternative_confidence = Some(value);
+        self
+    }
     pub fn enable_api_checks(mut self, enable: bool) -> Self {
         self.overrides.advanced_verification.enable_api_checks = Some(enable);
         self
@@ -99,6 +371,79 @@ impl ConfigBuilder {
         self.overrides.verification.early_termination_threshold = Some(value);
         self
     }
+    pub fn verification_level(mut self, level: VerificationLevel) -> Self {
+        self.overrides.verification.verification_level = Some(level);
+        self
+    }
+    /// Sets [`Config::stage_order`].
+    pub fn stage_order(mut self, stages: Vec<String>) -> Self {
+        self.overrides.verification.stage_order = Some(stages);
+        self
+    }
+    pub fn unverified_fallback(mut self, enable: bool) -> Self {
+        self.overrides.verification.unverified_fallback = Some(enable);
+        self
+    }
+    pub fn unverified_fallback_confidence(mut self, value: u8) -> Self {
+        self.overrides.verification.unverified_fallback_confidence = Some(value);
+        self
+    }
+    pub fn return_best_guess_on_failure(mut self, enable: bool) -> Self {
+        self.overrides.verification.return_best_guess_on_failure = Some(enable);
+        self
+    }
+    /// Withholds the Confident verdict from single-method results; see
+    /// [`Config::require_corroboration`].
+    pub fn require_corroboration(mut self, enable: bool) -> Self {
+        self.overrides.verification.require_corroboration = Some(enable);
+        self
+    }
+    /// Whether to lowercase the local part of candidates; see [`Config::lowercase_local_part`].
+    pub fn lowercase_local_part(mut self, lowercase: bool) -> Self {
+        self.overrides.verification.lowercase_local_part = Some(lowercase);
+        self
+    }
+    pub fn keep_subdomains(mut self, keep: bool) -> Self {
+        self.overrides.verification.keep_subdomains = Some(keep);
+        self
+    }
+    pub fn domain_typo_check(mut self, enabled: bool) -> Self {
+        self.overrides.verification.domain_typo_check = Some(enabled);
+        self
+    }
+    /// Sets the domains `domain_typo_check` compares input domains against.
+    pub fn known_domains(mut self, domains: Vec<String>) -> Self {
+        self.overrides.verification.known_domains = Some(domains);
+        self
+    }
+    /// Sets the disposable email domains; contacts at these domains are skipped.
+    pub fn disposable_domains(mut self, domains: Vec<String>) -> Self {
+        self.overrides.verification.disposable_domains = Some(domains);
+        self
+    }
+    /// Fetches the disposable email domains from `url` when the config is built.
+    pub fn disposable_domains_url(mut self, url: Option<impl Into<String>>) -> Self {
+        self.overrides.verification.disposable_domains_url = url.map(|s| s.into());
+        self
+    }
+    /// Makes candidate ordering reproducible; see [`Config::random_seed`].
+    pub fn random_seed(mut self, seed: u64) -> Self {
+        self.overrides.verification.random_seed = Some(seed);
+        self
+    }
+    /// Uses `template` (e.g. `"{f}{last}"`) as the naming convention for `domain`.
+    pub fn domain_pattern(
+        mut self,
+        domain: impl Into<String>,
+        template: impl Into<String>,
+    ) -> Self {
+        self.overrides
+            .verification
+            .domain_patterns
+            .get_or_insert_with(Default::default)
+            .insert(domain.into(), template.into());
+        self
+    }
     pub fn webdriver_url(mut self, url: Option<impl Into<String>>) -> Self {
         self.overrides.advanced_verification.webdriver_url = url.map(|s| s.into());
         self
@@ -107,12 +452,165 @@ impl ConfigBuilder {
         self.overrides.advanced_verification.chromedriver_path = path.map(|s| s.into());
         self
     }
+    pub fn chromedriver_instances(mut self, value: usize) -> Self {
+        self.overrides.advanced_verification.chromedriver_instances = Some(value);
+        self
+    }
+    pub fn webdriver_port(mut self, port: u16) -> Self {
+        self.overrides.advanced_verification.webdriver_port = Some(port);
+        self
+    }
+    pub fn stop_chromedriver_on_exit(mut self, enable: bool) -> Self {
+        self.overrides
+            .advanced_verification
+            .stop_chromedriver_on_exit = Some(enable);
+        self
+    }
+    /// Falls back to the local ChromeDriver at `webdriver_port` (see
+    /// [`Config::default_webdriver_url`]) when no WebDriver URL is configured.
+    pub fn default_to_local_webdriver(mut self, enable: bool) -> Self {
+        self.local_webdriver = enable;
+        self
+    }
+    pub fn sort_output(mut self, order: OutputOrder) -> Self {
+        self.overrides.output.sort_output = Some(order);
+        self
+    }
+    /// Writes records without a likely email to a separate file; see [`Config::failures_output`].
+    pub fn failures_output(mut self, path: Option<impl Into<String>>) -> Self {
+        self.overrides.output.failures_output = path.map(|s| s.into());
+        self
+    }
+    /// Writes a per-domain rollup of the batch to a file; see [`Config::domain_report_output`].
+    pub fn domain_report_output(mut self, path: Option<impl Into<String>>) -> Self {
+        self.overrides.output.domain_report_output = path.map(|s| s.into());
+        self
+    }
+    /// Attaches each failed contact's log lines to its result; see [`Config::capture_failure_logs`].
+    pub fn capture_failure_logs(mut self, enable: bool) -> Self {
+        self.overrides.output.capture_failure_logs = Some(enable);
+        self
+    }
+    /// Attaches a decision trace to every result; see [`Config::capture_trace`].
+    pub fn capture_trace(mut self, enable: bool) -> Self {
+        self.overrides.output.capture_trace = Some(enable);
+        self
+    }
+    /// Adds `key` = `value` to [`Config::run_metadata`].
+    pub fn run_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.overrides
+            .output
+            .run_metadata
+            .get_or_insert_with(Default::default)
+            .insert(key.into(), value.into());
+        self
+    }
     /// Builds the final `Config` object, applying defaults, file settings, overrides, and validation.
+    ///
+    /// Makes no network requests: lists at `generic_email_prefixes_url` and
+    /// `disposable_domains_url` are only fetched by
+    /// [`build_with_remote_lists`](Self::build_with_remote_lists).
     pub fn build(mut self) -> Result<Config> {
+        self.load_and_merge()?;
+        for url in [
+            &self.config.generic_email_prefixes_url,
+            &self.config.disposable_domains_url,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            tracing::warn!(
+                "List URL {} is not fetched by ConfigBuilder::build; use build_with_remote_lists.",
+                url
+            );
+        }
+        validate_config(&mut self.config)?;
+
+        tracing::debug!("Final configuration built successfully.");
+        Ok(self.config)
+    }
+
+    /// Like [`build`](Self::build), but first fetches the lists at
+    /// `generic_email_prefixes_url` and `disposable_domains_url`, where set, and uses them in
+    /// place of `generic_email_prefixes` and `disposable_domains`. Prefixes added with
+    /// [`add_generic_prefix`](Self::add_generic_prefix) are kept.
+    pub async fn build_with_remote_lists(mut self) -> Result<Config> {
+        self.load_and_merge()?;
+        self.load_remote_lists().await?;
+        self.apply_extra_entries();
+        validate_config(&mut self.config)?;
+
+        tracing::debug!("Final configuration built successfully.");
+        Ok(self.config)
+    }
+
+    /// Loads and merges the configuration like [`build`](Self::build), but reports every
+    /// validation problem instead of stopping at the first one.
+    ///
+    /// Useful for checking a configuration file before a run without doing any verification work.
+    pub fn validate(mut self) -> std::result::Result<Config, Vec<String>> {
+        if let Err(e) = self.load_and_merge() {
+            return Err(vec![e.to_string()]);
+        }
+        let problems = collect_config_problems(&mut self.config);
+        if problems.is_empty() {
+            Ok(self.config)
+        } else {
+            Err(problems)
+        }
+    }
+
+    /// Appends the entries given to the `add_*` methods, skipping ones already present.
+    fn apply_extra_entries(&mut self) {
+        for page in &self.extra_common_pages {
+            if !self.config.common_pages_to_scrape.contains(page) {
+                self.config.common_pages_to_scrape.push(page.clone());
+            }
+        }
+        for prefix in &self.extra_generic_prefixes {
+            self.config
+                .generic_email_prefixes
+                .insert(prefix.trim().to_lowercase());
+        }
+        for server in &self.extra_dns_servers {
+            if !self.config.dns_servers.contains(server) {
+                self.config.dns_servers.push(server.clone());
+            }
+        }
+    }
+
+    /// Replaces the generic prefixes and disposable domains with the lists fetched from
+    /// `generic_email_prefixes_url` and `disposable_domains_url`, where set.
+    async fn load_remote_lists(&mut self) -> Result<()> {
+        let cache_dir = self
+            .config
+            .remote_list_cache_dir
+            .as_ref()
+            .map(PathBuf::from)
+            .unwrap_or_else(default_cache_dir);
+
+        if let Some(url) = self.config.generic_email_prefixes_url.clone() {
+            let prefixes = load_remote_list(&url, &self.config.user_agent, &cache_dir).await?;
+            self.config.generic_email_prefixes = prefixes.into_iter().collect();
+            self.config.generic_prefix_penalties.clear();
+        }
+        if let Some(url) = self.config.disposable_domains_url.clone() {
+            let domains = load_remote_list(&url, &self.config.user_agent, &cache_dir).await?;
+            self.config.disposable_domains = domains
+                .into_iter()
+                .map(|domain| domain.trim_end_matches('.').to_string())
+                .collect();
+        }
+        Ok(())
+    }
+
+    /// Applies the config file (explicit or default location) and the builder overrides.
+    fn load_and_merge(&mut self) -> Result<()> {
         let mut loaded_path: Option<String> = None;
+        let lenient = self.lenient_config.unwrap_or_else(lenient_config_from_env);
 
         if let Some(ref path) = self.config_file_path {
-            match load_config_file(path) {
+            match load_config_file(path, lenient) {
                 Ok(file_config) => {
                     apply_file_config(&mut self.config, &file_config);
                     loaded_path = Some(path.clone());
@@ -131,7 +629,7 @@ impl ConfigBuilder {
             for path_str in ["./email-sleuth.toml", "./config.toml"] {
                 if Path::new(path_str).exists() {
                     tracing::debug!("Found potential default config file: {}", path_str);
-                    match load_config_file(path_str) {
+                    match load_config_file(path_str, lenient) {
                         Ok(file_config) => {
                             apply_file_config(&mut self.config, &file_config);
                             loaded_path = Some(path_str.to_string());
@@ -156,11 +654,19 @@ impl ConfigBuilder {
             }
         }
 
+        if self.apply_env {
+            let env_config = env_config_overrides().map_err(|e| AppError::Config(e.to_string()))?;
+            apply_file_config(&mut self.config, &env_config);
+        }
+
         apply_file_config(&mut self.config, &self.overrides);
+        self.apply_extra_entries();
+        if self.local_webdriver && self.config.webdriver_url.is_none() {
+            let url = self.config.default_webdriver_url();
+            tracing::info!("Using default WebDriver URL: {}", url);
+            self.config.webdriver_url = Some(url);
+        }
         self.config.loaded_config_path = loaded_path;
-        validate_config(&mut self.config)?;
-
-        tracing::debug!("Final configuration built successfully.");
-        Ok(self.config)
+        Ok(())
     }
 }
@@ -1,9 +1,14 @@
 //! Defines the structure mirroring the TOML configuration file format.
+//!
+//! Unknown keys are not rejected by serde itself; the loader collects them and
+//! decides whether to fail (strict, the default) or warn (lenient).
 
-use serde::Deserialize;
+use super::{OutputOrder, ProbeCharset, TlsVersion, VerificationLevel};
+use crate::core::error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
-#[derive(Deserialize, Debug, Default, Clone)]
-#[serde(deny_unknown_fields)]
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
 pub struct ConfigFile {
     #[serde(default)]
     pub(crate) network: NetworkConfig,
@@ -17,54 +22,175 @@ pub struct ConfigFile {
     pub(crate) verification: VerificationConfig,
     #[serde(default)]
     pub(crate) advanced_verification: AdvancedVerificationConfig,
+    #[serde(default)]
+    pub(crate) headless_confidence: HeadlessConfidenceConfig,
+    #[serde(default)]
+    pub(crate) output: OutputConfig,
+}
+
+impl ConfigFile {
+    /// Serializes the configuration as a pretty-printed TOML document.
+    pub fn to_toml_string(&self) -> Result<String> {
+        toml::to_string_pretty(self)
+            .map_err(|e| AppError::Config(format!("Failed to serialize configuration: {}", e)))
+    }
 }
 
-#[derive(Deserialize, Debug, Default, Clone)]
-#[serde(deny_unknown_fields)]
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
 pub(crate) struct NetworkConfig {
     pub(crate) request_timeout: Option<u64>,
     pub(crate) min_sleep: Option<f32>,
     pub(crate) max_sleep: Option<f32>,
     pub(crate) user_agent: Option<String>,
+    pub(crate) min_tls_version: Option<TlsVersion>,
+    pub(crate) remote_list_cache_dir: Option<String>,
+    pub(crate) rate_limit_max_retries: Option<u32>,
+    pub(crate) rate_limit_max_wait: Option<u64>,
 }
 
-#[derive(Deserialize, Debug, Default, Clone)]
-#[serde(deny_unknown_fields)]
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
 pub(crate) struct DnsConfig {
     pub(crate) dns_timeout: Option<u64>,
     pub(crate) dns_servers: Option<Vec<String>>,
+    pub(crate) max_dns_concurrency: Option<usize>,
+    pub(crate) dnssec_validation: Option<bool>,
+    pub(crate) skip_dead_domains: Option<bool>,
+    pub(crate) dead_domain_ttl: Option<u64>,
+    pub(crate) dns_overrides: Option<BTreeMap<String, Vec<String>>>,
+    pub(crate) mx_providers: Option<BTreeMap<String, String>>,
 }
 
-#[derive(Deserialize, Debug, Default, Clone)]
-#[serde(deny_unknown_fields)]
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
 pub(crate) struct SmtpConfig {
     pub(crate) smtp_timeout: Option<u64>,
+    pub(crate) smtp_rcpt_timeout: Option<u64>,
+    pub(crate) smtp_tls_sni: Option<bool>,
     pub(crate) smtp_sender_email: Option<String>,
+    pub(crate) smtp_sender_domain: Option<String>,
+    pub(crate) smtp_max_attempts: Option<u32>,
+    /// Deprecated alias for `smtp_max_attempts`; ignored when that is also set.
     pub(crate) max_verification_attempts: Option<u32>,
+    pub(crate) smtp_rejection_threshold: Option<f32>,
+    pub(crate) smtp_rejection_window: Option<usize>,
+    pub(crate) smtp_rejection_cooldown: Option<u64>,
+    pub(crate) smtp_breaker_threshold: Option<usize>,
+    pub(crate) smtp_breaker_cooldown: Option<u64>,
+    pub(crate) max_probes_per_domain: Option<usize>,
+    pub(crate) smtp_prefetch_catch_all: Option<bool>,
+    pub(crate) smtp_subaddress_probe: Option<bool>,
+    pub(crate) smtp_expn_probe: Option<bool>,
+    pub(crate) smtp_vrfy_probe: Option<bool>,
+    pub(crate) smtp_probe_prefix: Option<String>,
+    pub(crate) smtp_probe_length: Option<usize>,
+    pub(crate) smtp_probe_charset: Option<ProbeCharset>,
+    pub(crate) smtp_sender_checks: Option<bool>,
+    pub(crate) max_concurrency_per_mx: Option<usize>,
+    pub(crate) smtp_force_target: Option<String>,
+    pub(crate) smtp_skip_tlds: Option<Vec<String>>,
+    pub(crate) smtp_dialogue: Option<Vec<String>>,
 }
 
-#[derive(Deserialize, Debug, Default, Clone)]
-#[serde(deny_unknown_fields)]
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
 pub(crate) struct ScrapingConfig {
     pub(crate) common_pages: Option<Vec<String>>,
-    pub(crate) generic_email_prefixes: Option<Vec<String>>,
+    pub(crate) generic_email_prefixes: Option<GenericPrefixes>,
+    pub(crate) generic_email_prefixes_url: Option<String>,
+    pub(crate) scrape_top_k_pages: Option<usize>,
+    pub(crate) scraping_max_attempts: Option<u32>,
+    pub(crate) prefer_personal_over_role: Option<bool>,
+    pub(crate) headless_scrape_fallback: Option<bool>,
+    pub(crate) exclude_off_domain_scraped: Option<bool>,
+    pub(crate) respect_robots_txt: Option<bool>,
+    pub(crate) dedup_expected_items: Option<usize>,
+    pub(crate) dedup_false_positive_rate: Option<f64>,
 }
 
-#[derive(Deserialize, Debug, Default, Clone)]
-#[serde(deny_unknown_fields)]
+/// Generic email prefixes: either a plain list, where every prefix gets
+/// [`DEFAULT_GENERIC_PREFIX_PENALTY`](super::DEFAULT_GENERIC_PREFIX_PENALTY),
+/// or a table mapping each prefix to its own penalty.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(untagged)]
+pub(crate) enum GenericPrefixes {
+    List(Vec<String>),
+    Penalties(BTreeMap<String, u8>),
+}
+
+impl GenericPrefixes {
+    /// Returns the prefixes, lowercased.
+    pub(crate) fn prefixes(&self) -> HashSet<String> {
+        match self {
+            GenericPrefixes::List(prefixes) => {
+                prefixes.iter().map(|p| p.trim().to_lowercase()).collect()
+            }
+            GenericPrefixes::Penalties(penalties) => {
+                penalties.keys().map(|p| p.trim().to_lowercase()).collect()
+            }
+        }
+    }
+
+    /// Returns the penalties set for individual prefixes; empty for a plain list.
+    pub(crate) fn penalties(&self) -> HashMap<String, u8> {
+        match self {
+            GenericPrefixes::List(_) => HashMap::new(),
+            GenericPrefixes::Penalties(penalties) => penalties
+                .iter()
+                .map(|(p, penalty)| (p.trim().to_lowercase(), *penalty))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
 pub(crate) struct VerificationConfig {
     pub(crate) confidence_threshold: Option<u8>,
     pub(crate) generic_confidence_threshold: Option<u8>,
     pub(crate) max_alternatives: Option<usize>,
+    pub(crate) min_alternative_confidence: Option<u8>,
     pub(crate) max_concurrency: Option<usize>,
+    pub(crate) domain_fair_scheduling: Option<bool>,
     pub(crate) early_termination_threshold: Option<u8>,
+    pub(crate) verification_level: Option<VerificationLevel>,
+    pub(crate) stage_order: Option<Vec<String>>,
+    pub(crate) unverified_fallback: Option<bool>,
+    pub(crate) unverified_fallback_confidence: Option<u8>,
+    pub(crate) return_best_guess_on_failure: Option<bool>,
+    pub(crate) require_corroboration: Option<bool>,
+    pub(crate) lowercase_local_part: Option<bool>,
+    pub(crate) keep_subdomains: Option<bool>,
+    pub(crate) random_seed: Option<u64>,
+    pub(crate) domain_typo_check: Option<bool>,
+    pub(crate) known_domains: Option<Vec<String>>,
+    pub(crate) disposable_domains: Option<Vec<String>>,
+    pub(crate) disposable_domains_url: Option<String>,
+    pub(crate) domain_patterns: Option<BTreeMap<String, String>>,
 }
 
-#[derive(Deserialize, Debug, Default, Clone)]
-#[serde(deny_unknown_fields)]
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
 pub(crate) struct AdvancedVerificationConfig {
     pub(crate) enable_api_checks: Option<bool>,
     pub(crate) enable_headless_checks: Option<bool>,
     pub(crate) webdriver_url: Option<String>,
     pub(crate) chromedriver_path: Option<String>,
+    pub(crate) chromedriver_instances: Option<usize>,
+    pub(crate) webdriver_port: Option<u16>,
+    pub(crate) stop_chromedriver_on_exit: Option<bool>,
+    pub(crate) headless_max_attempts: Option<u32>,
+    pub(crate) headless_browser_args: Option<Vec<String>>,
+}
+
+/// Confidence per headless provider, keyed by provider name.
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+pub(crate) struct HeadlessConfidenceConfig {
+    pub(crate) microsoft: Option<u8>,
+    pub(crate) yahoo: Option<u8>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+pub(crate) struct OutputConfig {
+    pub(crate) sort_output: Option<OutputOrder>,
+    pub(crate) failures_output: Option<String>,
+    pub(crate) domain_report_output: Option<String>,
+    pub(crate) capture_failure_logs: Option<bool>,
+    pub(crate) capture_trace: Option<bool>,
+    pub(crate) run_metadata: Option<BTreeMap<String, String>>,
 }
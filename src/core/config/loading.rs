@@ -1,33 +1,337 @@
 //! Handles loading configuration from files and applying it to the Config struct.
 
-use super::{Config, ConfigFile};
+use super::file::{
+    AdvancedVerificationConfig, DnsConfig, GenericPrefixes, HeadlessConfidenceConfig,
+    NetworkConfig, OutputConfig, ScrapingConfig, SmtpConfig, VerificationConfig,
+};
+use super::{Config, ConfigFile, DEFAULT_GENERIC_PREFIX_PENALTY};
 use anyhow::Context;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
-use tracing;
 
-/// Loads configuration settings from a TOML file.
+/// Environment variable that enables lenient config loading when not set explicitly.
+pub(crate) const LENIENT_CONFIG_ENV: &str = "EMAIL_SLEUTH_LENIENT_CONFIG";
+
+/// Returns true if lenient config loading is requested via the environment.
+pub(crate) fn lenient_config_from_env() -> bool {
+    std::env::var(LENIENT_CONFIG_ENV)
+        .map(|v| {
+            matches!(
+                v.trim().to_lowercase().as_str(),
+                "1" | "true" | "yes" | "on"
+            )
+        })
+        .unwrap_or(false)
+}
+
+/// Prefix of the environment variables that override configuration keys.
+pub(crate) const ENV_PREFIX: &str = "EMAIL_SLEUTH_";
+
+/// Sections of the configuration file, in the order environment variables are matched against.
+const CONFIG_SECTIONS: [&str; 8] = [
+    "network",
+    "dns",
+    "smtp",
+    "scraping",
+    "verification",
+    "advanced_verification",
+    "headless_confidence",
+    "output",
+];
+
+/// Collects configuration overrides from `EMAIL_SLEUTH_<KEY>` environment variables.
+///
+/// `<KEY>` is any configuration key in upper case (e.g. `EMAIL_SLEUTH_SMTP_TIMEOUT=10`).
+/// Values are read as TOML (numbers, booleans, `["a", "b"]` arrays) and fall back to a plain
+/// string, or a comma-separated list of strings. Variables that name no configuration key
+/// (such as CLI-only flags) are ignored; a value of the wrong type is an error.
+pub(crate) fn env_config_overrides() -> anyhow::Result<ConfigFile> {
+    let mut vars: Vec<(String, String)> = std::env::vars()
+        .filter_map(|(name, value)| {
+            let key = name.strip_prefix(ENV_PREFIX)?.to_lowercase();
+            Some((key, value))
+        })
+        .collect();
+    vars.sort();
+
+    let mut overrides = toml::Table::new();
+    for (key, raw) in vars {
+        let Some((section, value)) = env_config_value(&key, &raw)? else {
+            tracing::trace!(
+                "Ignoring {}{}: not a configuration key",
+                ENV_PREFIX,
+                key.to_uppercase()
+            );
+            continue;
+        };
+        tracing::debug!(
+            "Configuration key {}.{} set from the environment",
+            section,
+            key
+        );
+        let section_table = overrides
+            .entry(section)
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+        if let toml::Value::Table(table) = section_table {
+            table.insert(key, value);
+        }
+    }
+
+    toml::Value::Table(overrides)
+        .try_into::<ConfigFile>()
+        .context("Failed to apply configuration from the environment")
+}
+
+/// Finds the section containing `key` and the first reading of `raw` that it accepts.
+/// Returns `None` if no section has the key.
+fn env_config_value(key: &str, raw: &str) -> anyhow::Result<Option<(&'static str, toml::Value)>> {
+    let mut candidates = Vec::new();
+    if let Ok(mut table) = format!("value = {}", raw).parse::<toml::Table>() {
+        if let Some(value) = table.remove("value") {
+            candidates.push(value);
+        }
+    }
+    candidates.push(toml::Value::String(raw.to_string()));
+    candidates.push(toml::Value::Array(
+        raw.split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(|item| toml::Value::String(item.to_string()))
+            .collect(),
+    ));
+
+    for section in CONFIG_SECTIONS {
+        let mut known = false;
+        for value in &candidates {
+            let mut section_table = toml::Table::new();
+            section_table.insert(key.to_string(), value.clone());
+            let mut table = toml::Table::new();
+            table.insert(section.to_string(), toml::Value::Table(section_table));
+
+            let mut unknown = false;
+            let parsed: Result<ConfigFile, _> =
+                serde_ignored::deserialize(toml::Value::Table(table), |_| unknown = true);
+            if unknown {
+                break;
+            }
+            known = true;
+            if parsed.is_ok() {
+                return Ok(Some((section, value.clone())));
+            }
+        }
+        if known {
+            return Err(anyhow::anyhow!(
+                "Invalid value for {}{} ({}.{}): '{}'",
+                ENV_PREFIX,
+                key.to_uppercase(),
+                section,
+                key,
+                raw
+            ));
+        }
+    }
+    Ok(None)
+}
+
+/// Top-level key naming a parent configuration file to inherit settings from.
+const EXTENDS_KEY: &str = "extends";
+
+/// Loads configuration settings from a TOML, JSON or YAML file (see [`parse_config_table`]).
 /// Returns the parsed `ConfigFile` content.
+/// If the file sets `extends = "base.toml"`, the parent is loaded first (relative paths are
+/// resolved against the extending file's directory) and this file's keys are deep-merged over it.
+/// Unknown keys are an error unless `lenient` is set, in which case they are logged and ignored.
 /// Internal to the builder logic.
-pub(crate) fn load_config_file(file_path: &str) -> anyhow::Result<ConfigFile> {
-    let path = Path::new(file_path);
+pub(crate) fn load_config_file(file_path: &str, lenient: bool) -> anyhow::Result<ConfigFile> {
+    let mut chain = Vec::new();
+    let table = load_config_table(Path::new(file_path), &mut chain)?;
+
+    let mut unknown_keys = Vec::new();
+    let config_file_content: ConfigFile =
+        serde_ignored::deserialize(toml::Value::Table(table), |path| {
+            unknown_keys.push(path.to_string())
+        })
+        .with_context(|| format!("Failed to parse configuration from {}", file_path))?;
+
+    if !unknown_keys.is_empty() {
+        if lenient {
+            for key in &unknown_keys {
+                tracing::warn!(
+                    "Ignoring unknown configuration key '{}' in {}",
+                    key,
+                    file_path
+                );
+            }
+        } else {
+            return Err(anyhow::anyhow!(
+                "Unknown configuration key(s) in {}: {}. Set {}=1 to ignore unknown keys.",
+                file_path,
+                unknown_keys.join(", "),
+                LENIENT_CONFIG_ENV
+            ));
+        }
+    }
+
+    tracing::debug!("Successfully parsed configuration file: {}", file_path);
+    Ok(config_file_content)
+}
+
+/// Reads a config file into a table, resolving its `extends` chain.
+/// `chain` holds the canonical paths of the files currently being loaded, for cycle detection.
+fn load_config_table(path: &Path, chain: &mut Vec<PathBuf>) -> anyhow::Result<toml::Table> {
     if !path.exists() || !path.is_file() {
         return Err(anyhow::anyhow!(
             "File not found or is not a file: {}",
-            file_path
+            path.display()
         ));
     }
-    tracing::debug!("Attempting to read config file: {}", file_path);
+    let canonical = path
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve configuration path: {}", path.display()))?;
+    if chain.contains(&canonical) {
+        let cycle: Vec<String> = chain
+            .iter()
+            .chain(std::iter::once(&canonical))
+            .map(|p| p.display().to_string())
+            .collect();
+        return Err(anyhow::anyhow!(
+            "Configuration 'extends' cycle detected: {}",
+            cycle.join(" -> ")
+        ));
+    }
+
+    tracing::debug!("Attempting to read config file: {}", path.display());
     let content = fs::read_to_string(path)
-        .with_context(|| format!("Failed to read configuration file: {}", file_path))?;
+        .with_context(|| format!("Failed to read configuration file: {}", path.display()))?;
 
-    tracing::debug!("Attempting to parse TOML from: {}", file_path);
-    let config_file_content: ConfigFile = toml::from_str(&content)
-        .with_context(|| format!("Failed to parse TOML configuration from {}", file_path))?;
+    let mut table = parse_config_table(path, &content)?;
 
-    tracing::debug!("Successfully parsed configuration file: {}", file_path);
-    Ok(config_file_content)
+    let parent = match table.remove(EXTENDS_KEY) {
+        None => return Ok(table),
+        Some(toml::Value::String(parent)) => parent,
+        Some(other) => {
+            return Err(anyhow::anyhow!(
+                "'{}' in {} must be a file path string, found {}",
+                EXTENDS_KEY,
+                path.display(),
+                other.type_str()
+            ))
+        }
+    };
+
+    let parent_path = match canonical.parent() {
+        Some(dir) if Path::new(&parent).is_relative() => dir.join(&parent),
+        _ => PathBuf::from(&parent),
+    };
+    tracing::debug!(
+        "Config file {} extends {}",
+        path.display(),
+        parent_path.display()
+    );
+
+    chain.push(canonical);
+    let mut merged = load_config_table(&parent_path, chain)
+        .with_context(|| format!("Failed to load '{}' extended by {}", parent, path.display()))?;
+    chain.pop();
+
+    merge_tables(&mut merged, table);
+    Ok(merged)
+}
+
+/// Parses config file content into a table, picking the format from the extension:
+/// `.json`, `.yaml`/`.yml`, and TOML for `.toml`, any other extension or none.
+///
+/// Every format is read into the same table, so files of different formats can extend
+/// each other and unknown keys are reported the same way. TOML has no null, so a JSON
+/// `null` or YAML `~` (or empty value) leaves the setting unset, as if the key were absent.
+fn parse_config_table(path: &Path, content: &str) -> anyhow::Result<toml::Table> {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase());
+    match extension.as_deref() {
+        Some("json") => {
+            tracing::debug!("Attempting to parse JSON from: {}", path.display());
+            let mut value: serde_json::Value =
+                serde_json::from_str(content).with_context(|| {
+                    format!("Failed to parse JSON configuration from {}", path.display())
+                })?;
+            strip_json_nulls(&mut value);
+            if value.is_null() {
+                return Ok(toml::Table::new());
+            }
+            serde_json::from_value(value).with_context(|| {
+                format!("Failed to parse JSON configuration from {}", path.display())
+            })
+        }
+        Some("yaml") | Some("yml") => {
+            tracing::debug!("Attempting to parse YAML from: {}", path.display());
+            let mut value: serde_yaml::Value =
+                serde_yaml::from_str(content).with_context(|| {
+                    format!("Failed to parse YAML configuration from {}", path.display())
+                })?;
+            strip_yaml_nulls(&mut value);
+            if value.is_null() {
+                return Ok(toml::Table::new());
+            }
+            serde_yaml::from_value(value).with_context(|| {
+                format!("Failed to parse YAML configuration from {}", path.display())
+            })
+        }
+        _ => {
+            tracing::debug!("Attempting to parse TOML from: {}", path.display());
+            content.parse().with_context(|| {
+                format!("Failed to parse TOML configuration from {}", path.display())
+            })
+        }
+    }
+}
+
+/// Removes null object entries and array elements, at any depth.
+fn strip_json_nulls(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            map.retain(|_, v| !v.is_null());
+            map.values_mut().for_each(strip_json_nulls);
+        }
+        serde_json::Value::Array(items) => {
+            items.retain(|v| !v.is_null());
+            items.iter_mut().for_each(strip_json_nulls);
+        }
+        _ => {}
+    }
+}
+
+/// Removes null mapping entries and sequence elements, at any depth.
+fn strip_yaml_nulls(value: &mut serde_yaml::Value) {
+    match value {
+        serde_yaml::Value::Mapping(map) => {
+            map.retain(|_, v| !v.is_null());
+            map.values_mut().for_each(strip_yaml_nulls);
+        }
+        serde_yaml::Value::Sequence(items) => {
+            items.retain(|v| !v.is_null());
+            items.iter_mut().for_each(strip_yaml_nulls);
+        }
+        serde_yaml::Value::Tagged(tagged) => strip_yaml_nulls(&mut tagged.value),
+        _ => {}
+    }
+}
+
+/// Deep-merges `overlay` into `base`. Nested tables are merged key by key;
+/// any other value (including arrays) in `overlay` replaces the one in `base`.
+fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base_table)), toml::Value::Table(overlay_table)) => {
+                merge_tables(base_table, overlay_table);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
 }
 
 /// Applies settings from a parsed `ConfigFile` onto a mutable `Config` instance.
@@ -46,6 +350,22 @@ pub(crate) fn apply_file_config(config: &mut Config, file_config: &ConfigFile) {
     if let Some(ref user_agent) = file_config.network.user_agent {
         config.user_agent = user_agent.clone();
     }
+    if let Some(version) = file_config.network.min_tls_version {
+        config.min_tls_version = version;
+    }
+    if let Some(ref dir) = file_config.network.remote_list_cache_dir {
+        if !dir.trim().is_empty() {
+            config.remote_list_cache_dir = Some(dir.trim().to_string());
+        } else {
+            config.remote_list_cache_dir = None;
+        }
+    }
+    if let Some(retries) = file_config.network.rate_limit_max_retries {
+        config.rate_limit_max_retries = retries;
+    }
+    if let Some(wait) = file_config.network.rate_limit_max_wait {
+        config.rate_limit_max_wait = Duration::from_secs(wait);
+    }
 
     // DNS
     if let Some(timeout) = file_config.dns.dns_timeout {
@@ -56,16 +376,135 @@ pub(crate) fn apply_file_config(config: &mut Config, file_config: &ConfigFile) {
             config.dns_servers = servers.clone();
         }
     }
+    if let Some(concurrency) = file_config.dns.max_dns_concurrency {
+        config.max_dns_concurrency = concurrency;
+    }
+    if let Some(dnssec) = file_config.dns.dnssec_validation {
+        config.dnssec_validation = dnssec;
+    }
+    if let Some(skip) = file_config.dns.skip_dead_domains {
+        config.skip_dead_domains = skip;
+    }
+    if let Some(ttl) = file_config.dns.dead_domain_ttl {
+        config.dead_domain_ttl = Duration::from_secs(ttl);
+    }
+    if let Some(ref overrides) = file_config.dns.dns_overrides {
+        for (domain, hosts) in overrides {
+            config.dns_overrides.insert(
+                domain.trim().trim_end_matches('.').to_lowercase(),
+                hosts
+                    .iter()
+                    .map(|h| h.trim().to_string())
+                    .filter(|h| !h.is_empty())
+                    .collect(),
+            );
+        }
+    }
+    if let Some(ref providers) = file_config.dns.mx_providers {
+        for (suffix, provider) in providers {
+            config.mx_providers.insert(
+                suffix
+                    .trim()
+                    .trim_start_matches('.')
+                    .trim_end_matches('.')
+                    .to_lowercase(),
+                provider.trim().to_string(),
+            );
+        }
+    }
 
     // SMTP
     if let Some(timeout) = file_config.smtp.smtp_timeout {
         config.smtp_timeout = Duration::from_secs(timeout);
     }
+    if let Some(timeout) = file_config.smtp.smtp_rcpt_timeout {
+        config.smtp_rcpt_timeout = Duration::from_secs(timeout);
+    }
+    if let Some(sni) = file_config.smtp.smtp_tls_sni {
+        config.smtp_tls_sni = sni;
+    }
     if let Some(ref sender) = file_config.smtp.smtp_sender_email {
         config.smtp_sender_email = sender.clone();
     }
-    if let Some(attempts) = file_config.smtp.max_verification_attempts {
-        config.max_verification_attempts = attempts;
+    if let Some(ref domain) = file_config.smtp.smtp_sender_domain {
+        if !domain.trim().is_empty() {
+            config.smtp_sender_domain = Some(domain.trim().to_lowercase());
+        } else {
+            config.smtp_sender_domain = None;
+        }
+    }
+    if let Some(attempts) = file_config
+        .smtp
+        .smtp_max_attempts
+        .or(file_config.smtp.max_verification_attempts)
+    {
+        config.smtp_max_attempts = attempts;
+        #[allow(deprecated)]
+        {
+            config.max_verification_attempts = attempts;
+        }
+    }
+    if let Some(threshold) = file_config.smtp.smtp_rejection_threshold {
+        config.smtp_rejection_threshold = threshold;
+    }
+    if let Some(window) = file_config.smtp.smtp_rejection_window {
+        config.smtp_rejection_window = window;
+    }
+    if let Some(cooldown) = file_config.smtp.smtp_rejection_cooldown {
+        config.smtp_rejection_cooldown = Duration::from_secs(cooldown);
+    }
+    if let Some(threshold) = file_config.smtp.smtp_breaker_threshold {
+        config.smtp_breaker_threshold = threshold;
+    }
+    if let Some(cooldown) = file_config.smtp.smtp_breaker_cooldown {
+        config.smtp_breaker_cooldown = Duration::from_secs(cooldown);
+    }
+    if let Some(max) = file_config.smtp.max_probes_per_domain {
+        config.max_probes_per_domain = max;
+    }
+    if let Some(prefetch) = file_config.smtp.smtp_prefetch_catch_all {
+        config.smtp_prefetch_catch_all = prefetch;
+    }
+    if let Some(probe) = file_config.smtp.smtp_subaddress_probe {
+        config.smtp_subaddress_probe = probe;
+    }
+    if let Some(probe) = file_config.smtp.smtp_expn_probe {
+        config.smtp_expn_probe = probe;
+    }
+    if let Some(probe) = file_config.smtp.smtp_vrfy_probe {
+        config.smtp_vrfy_probe = probe;
+    }
+    if let Some(ref prefix) = file_config.smtp.smtp_probe_prefix {
+        config.smtp_probe_prefix = prefix.trim().to_lowercase();
+    }
+    if let Some(length) = file_config.smtp.smtp_probe_length {
+        config.smtp_probe_length = length;
+    }
+    if let Some(charset) = file_config.smtp.smtp_probe_charset {
+        config.smtp_probe_charset = charset;
+    }
+    if let Some(checks) = file_config.smtp.smtp_sender_checks {
+        config.smtp_sender_checks = checks;
+    }
+    if let Some(limit) = file_config.smtp.max_concurrency_per_mx {
+        config.max_concurrency_per_mx = limit;
+    }
+    if let Some(ref target) = file_config.smtp.smtp_force_target {
+        if !target.trim().is_empty() {
+            config.smtp_force_target = Some(target.trim().to_string());
+        } else {
+            config.smtp_force_target = None;
+        }
+    }
+    if let Some(ref tlds) = file_config.smtp.smtp_skip_tlds {
+        config.smtp_skip_tlds = tlds
+            .iter()
+            .map(|tld| tld.trim().trim_start_matches('.').to_lowercase())
+            .filter(|tld| !tld.is_empty())
+            .collect();
+    }
+    if let Some(ref steps) = file_config.smtp.smtp_dialogue {
+        config.smtp_dialogue = steps.iter().map(|step| step.trim().to_string()).collect();
     }
 
     // Scraping
@@ -75,7 +514,39 @@ pub(crate) fn apply_file_config(config: &mut Config, file_config: &ConfigFile) {
         }
     }
     if let Some(ref prefixes) = file_config.scraping.generic_email_prefixes {
-        config.generic_email_prefixes = prefixes.iter().cloned().collect();
+        config.generic_email_prefixes = prefixes.prefixes();
+        config.generic_prefix_penalties = prefixes.penalties();
+    }
+    if let Some(ref url) = file_config.scraping.generic_email_prefixes_url {
+        if !url.trim().is_empty() {
+            config.generic_email_prefixes_url = Some(url.trim().to_string());
+        } else {
+            config.generic_email_prefixes_url = None;
+        }
+    }
+    if let Some(k) = file_config.scraping.scrape_top_k_pages {
+        config.scrape_top_k_pages = (k > 0).then_some(k);
+    }
+    if let Some(attempts) = file_config.scraping.scraping_max_attempts {
+        config.scraping_max_attempts = attempts;
+    }
+    if let Some(prefer) = file_config.scraping.prefer_personal_over_role {
+        config.prefer_personal_over_role = prefer;
+    }
+    if let Some(fallback) = file_config.scraping.headless_scrape_fallback {
+        config.headless_scrape_fallback = fallback;
+    }
+    if let Some(exclude) = file_config.scraping.exclude_off_domain_scraped {
+        config.exclude_off_domain_scraped = exclude;
+    }
+    if let Some(respect) = file_config.scraping.respect_robots_txt {
+        config.respect_robots_txt = respect;
+    }
+    if let Some(items) = file_config.scraping.dedup_expected_items {
+        config.dedup_expected_items = items;
+    }
+    if let Some(rate) = file_config.scraping.dedup_false_positive_rate {
+        config.dedup_false_positive_rate = rate;
     }
 
     // Verification
@@ -88,12 +559,80 @@ pub(crate) fn apply_file_config(config: &mut Config, file_config: &ConfigFile) {
     if let Some(max_alt) = file_config.verification.max_alternatives {
         config.max_alternatives = max_alt;
     }
+    if let Some(min_confidence) = file_config.verification.min_alternative_confidence {
+        config.min_alternative_confidence = min_confidence;
+    }
     if let Some(concurrency) = file_config.verification.max_concurrency {
         config.max_concurrency = concurrency;
     }
+    if let Some(fair) = file_config.verification.domain_fair_scheduling {
+        config.domain_fair_scheduling = fair;
+    }
     if let Some(early_term) = file_config.verification.early_termination_threshold {
         config.early_termination_threshold = early_term;
     }
+    if let Some(level) = file_config.verification.verification_level {
+        config.verification_level = level;
+    }
+    if let Some(ref order) = file_config.verification.stage_order {
+        config.stage_order = order
+            .iter()
+            .map(|stage| stage.trim().to_lowercase())
+            .filter(|stage| !stage.is_empty())
+            .collect();
+    }
+    if let Some(fallback) = file_config.verification.unverified_fallback {
+        config.unverified_fallback = fallback;
+    }
+    if let Some(confidence) = file_config.verification.unverified_fallback_confidence {
+        config.unverified_fallback_confidence = confidence;
+    }
+    if let Some(best_guess) = file_config.verification.return_best_guess_on_failure {
+        config.return_best_guess_on_failure = best_guess;
+    }
+    if let Some(require) = file_config.verification.require_corroboration {
+        config.require_corroboration = require;
+    }
+    if let Some(lowercase) = file_config.verification.lowercase_local_part {
+        config.lowercase_local_part = lowercase;
+    }
+    if let Some(keep) = file_config.verification.keep_subdomains {
+        config.keep_subdomains = keep;
+    }
+    if let Some(seed) = file_config.verification.random_seed {
+        config.random_seed = Some(seed);
+    }
+    if let Some(check) = file_config.verification.domain_typo_check {
+        config.domain_typo_check = check;
+    }
+    if let Some(ref domains) = file_config.verification.known_domains {
+        config.known_domains = domains
+            .iter()
+            .map(|domain| domain.trim().trim_end_matches('.').to_lowercase())
+            .collect();
+    }
+    if let Some(ref domains) = file_config.verification.disposable_domains {
+        config.disposable_domains = domains
+            .iter()
+            .map(|domain| domain.trim().trim_end_matches('.').to_lowercase())
+            .filter(|domain| !domain.is_empty())
+            .collect();
+    }
+    if let Some(ref url) = file_config.verification.disposable_domains_url {
+        if !url.trim().is_empty() {
+            config.disposable_domains_url = Some(url.trim().to_string());
+        } else {
+            config.disposable_domains_url = None;
+        }
+    }
+    if let Some(ref patterns) = file_config.verification.domain_patterns {
+        for (domain, template) in patterns {
+            config.domain_patterns.insert(
+                domain.trim().trim_end_matches('.').to_lowercase(),
+                template.trim().to_lowercase(),
+            );
+        }
+    }
 
     // Advanced Verification
     if let Some(enable) = file_config.advanced_verification.enable_api_checks {
@@ -116,4 +655,256 @@ pub(crate) fn apply_file_config(config: &mut Config, file_config: &ConfigFile) {
             config.chromedriver_path = None;
         }
     }
+    if let Some(instances) = file_config.advanced_verification.chromedriver_instances {
+        config.chromedriver_instances = instances;
+    }
+    if let Some(port) = file_config.advanced_verification.webdriver_port {
+        config.webdriver_port = port;
+    }
+    if let Some(stop) = file_config.advanced_verification.stop_chromedriver_on_exit {
+        config.stop_chromedriver_on_exit = stop;
+    }
+    if let Some(attempts) = file_config.advanced_verification.headless_max_attempts {
+        config.headless_max_attempts = attempts;
+    }
+    if let Some(ref args) = file_config.advanced_verification.headless_browser_args {
+        config.headless_browser_args = args
+            .iter()
+            .map(|arg| arg.trim().to_string())
+            .filter(|arg| !arg.is_empty())
+            .collect();
+    }
+
+    // Headless confidence
+    if let Some(confidence) = file_config.headless_confidence.microsoft {
+        config.headless_confidence.microsoft = confidence;
+    }
+    if let Some(confidence) = file_config.headless_confidence.yahoo {
+        config.headless_confidence.yahoo = confidence;
+    }
+
+    // Output
+    if let Some(order) = file_config.output.sort_output {
+        config.sort_output = order;
+    }
+    if let Some(ref path) = file_config.output.failures_output {
+        if !path.trim().is_empty() {
+            config.failures_output = Some(path.trim().to_string());
+        } else {
+            config.failures_output = None;
+        }
+    }
+    if let Some(ref path) = file_config.output.domain_report_output {
+        if !path.trim().is_empty() {
+            config.domain_report_output = Some(path.trim().to_string());
+        } else {
+            config.domain_report_output = None;
+        }
+    }
+    if let Some(capture) = file_config.output.capture_failure_logs {
+        config.capture_failure_logs = capture;
+    }
+    if let Some(capture) = file_config.output.capture_trace {
+        config.capture_trace = capture;
+    }
+    if let Some(ref metadata) = file_config.output.run_metadata {
+        for (key, value) in metadata {
+            config
+                .run_metadata
+                .insert(key.trim().to_string(), value.trim().to_string());
+        }
+    }
+}
+
+/// Builds a `ConfigFile` holding every effective setting of `config`.
+/// The inverse of [`apply_file_config`]; used to dump the merged configuration.
+pub(crate) fn config_to_file(config: &Config) -> ConfigFile {
+    let generic_prefixes = if config
+        .generic_email_prefixes
+        .iter()
+        .all(|prefix| config.generic_prefix_penalty(prefix) == DEFAULT_GENERIC_PREFIX_PENALTY)
+    {
+        let mut prefixes: Vec<String> = config.generic_email_prefixes.iter().cloned().collect();
+        prefixes.sort();
+        GenericPrefixes::List(prefixes)
+    } else {
+        GenericPrefixes::Penalties(
+            config
+                .generic_email_prefixes
+                .iter()
+                .map(|prefix| (prefix.clone(), config.generic_prefix_penalty(prefix)))
+                .collect(),
+        )
+    };
+
+    ConfigFile {
+        network: NetworkConfig {
+            request_timeout: Some(config.request_timeout.as_secs()),
+            min_sleep: Some(config.sleep_between_requests.0),
+            max_sleep: Some(config.sleep_between_requests.1),
+            user_agent: Some(config.user_agent.clone()),
+            min_tls_version: Some(config.min_tls_version),
+            remote_list_cache_dir: config.remote_list_cache_dir.clone(),
+            rate_limit_max_retries: Some(config.rate_limit_max_retries),
+            rate_limit_max_wait: Some(config.rate_limit_max_wait.as_secs()),
+        },
+        dns: DnsConfig {
+            dns_timeout: Some(config.dns_timeout.as_secs()),
+            dns_servers: Some(config.dns_servers.clone()),
+            max_dns_concurrency: Some(config.max_dns_concurrency),
+            dnssec_validation: Some(config.dnssec_validation),
+            skip_dead_domains: Some(config.skip_dead_domains),
+            dead_domain_ttl: Some(config.dead_domain_ttl.as_secs()),
+            dns_overrides: Some(
+                config
+                    .dns_overrides
+                    .iter()
+                    .map(|(domain, hosts)| (domain.clone(), hosts.clone()))
+                    .collect(),
+            ),
+            mx_providers: Some(
+                config
+                    .mx_providers
+                    .iter()
+                    .map(|(suffix, provider)| (suffix.clone(), provider.clone()))
+                    .collect(),
+            ),
+        },
+        smtp: SmtpConfig {
+            smtp_timeout: Some(config.smtp_timeout.as_secs()),
+            smtp_rcpt_timeout: Some(config.smtp_rcpt_timeout.as_secs()),
+            smtp_tls_sni: Some(config.smtp_tls_sni),
+            smtp_sender_email: Some(config.smtp_sender_email.clone()),
+            smtp_sender_domain: config.smtp_sender_domain.clone(),
+            smtp_max_attempts: Some(config.smtp_max_attempts),
+            max_verification_attempts: None,
+            smtp_rejection_threshold: Some(config.smtp_rejection_threshold),
+            smtp_rejection_window: Some(config.smtp_rejection_window),
+            smtp_rejection_cooldown: Some(config.smtp_rejection_cooldown.as_secs()),
+            smtp_breaker_threshold: Some(config.smtp_breaker_threshold),
+            smtp_breaker_cooldown: Some(config.smtp_breaker_cooldown.as_secs()),
+            max_probes_per_domain: Some(config.max_probes_per_domain),
+            smtp_prefetch_catch_all: Some(config.smtp_prefetch_catch_all),
+            smtp_subaddress_probe: Some(config.smtp_subaddress_probe),
+            smtp_expn_probe: Some(config.smtp_expn_probe),
+            smtp_vrfy_probe: Some(config.smtp_vrfy_probe),
+            smtp_probe_prefix: Some(config.smtp_probe_prefix.clone()),
+            smtp_probe_length: Some(config.smtp_probe_length),
+            smtp_probe_charset: Some(config.smtp_probe_charset),
+            smtp_sender_checks: Some(config.smtp_sender_checks),
+            max_concurrency_per_mx: Some(config.max_concurrency_per_mx),
+            smtp_force_target: config.smtp_force_target.clone(),
+            smtp_skip_tlds: Some(config.smtp_skip_tlds.clone()),
+            smtp_dialogue: Some(config.smtp_dialogue.clone()),
+        },
+        scraping: ScrapingConfig {
+            common_pages: Some(config.common_pages_to_scrape.clone()),
+            generic_email_prefixes: Some(generic_prefixes),
+            generic_email_prefixes_url: config.generic_email_prefixes_url.clone(),
+            scrape_top_k_pages: config.scrape_top_k_pages,
+            scraping_max_attempts: Some(config.scraping_max_attempts),
+            prefer_personal_over_role: Some(config.prefer_personal_over_role),
+            headless_scrape_fallback: Some(config.headless_scrape_fallback),
+            exclude_off_domain_scraped: Some(config.exclude_off_domain_scraped),
+            respect_robots_txt: Some(config.respect_robots_txt),
+            dedup_expected_items: Some(config.dedup_expected_items),
+            dedup_false_positive_rate: Some(config.dedup_false_positive_rate),
+        },
+        verification: VerificationConfig {
+            confidence_threshold: Some(config.confidence_threshold),
+            generic_confidence_threshold: Some(config.generic_confidence_threshold),
+            max_alternatives: Some(config.max_alternatives),
+            min_alternative_confidence: Some(config.min_alternative_confidence),
+            max_concurrency: Some(config.max_concurrency),
+            domain_fair_scheduling: Some(config.domain_fair_scheduling),
+            early_termination_threshold: Some(config.early_termination_threshold),
+            verification_level: Some(config.verification_level),
+            stage_order: Some(config.stage_order.clone()),
+            unverified_fallback: Some(config.unverified_fallback),
+            unverified_fallback_confidence: Some(config.unverified_fallback_confidence),
+            return_best_guess_on_failure: Some(config.return_best_guess_on_failure),
+            require_corroboration: Some(config.require_corroboration),
+            lowercase_local_part: Some(config.lowercase_local_part),
+            keep_subdomains: Some(config.keep_subdomains),
+            random_seed: config.random_seed,
+            domain_typo_check: Some(config.domain_typo_check),
+            known_domains: Some(config.known_domains.clone()),
+            disposable_domains: Some({
+                let mut domains: Vec<String> = config.disposable_domains.iter().cloned().collect();
+                domains.sort();
+                domains
+            }),
+            disposable_domains_url: config.disposable_domains_url.clone(),
+            domain_patterns: Some(
+                config
+                    .domain_patterns
+                    .iter()
+                    .map(|(domain, template)| (domain.clone(), template.clone()))
+                    .collect(),
+            ),
+        },
+        advanced_verification: AdvancedVerificationConfig {
+            enable_api_checks: Some(config.enable_api_checks),
+            enable_headless_checks: Some(config.enable_headless_checks),
+            webdriver_url: config.webdriver_url.clone(),
+            chromedriver_path: config.chromedriver_path.clone(),
+            chromedriver_instances: Some(config.chromedriver_instances),
+            webdriver_port: Some(config.webdriver_port),
+            stop_chromedriver_on_exit: Some(config.stop_chromedriver_on_exit),
+            headless_max_attempts: Some(config.headless_max_attempts),
+            headless_browser_args: Some(config.headless_browser_args.clone()),
+        },
+        headless_confidence: HeadlessConfidenceConfig {
+            microsoft: Some(config.headless_confidence.microsoft),
+            yahoo: Some(config.headless_confidence.yahoo),
+        },
+        output: OutputConfig {
+            sort_output: Some(config.sort_output),
+            failures_output: config.failures_output.clone(),
+            domain_report_output: config.domain_report_output.clone(),
+            capture_failure_logs: Some(config.capture_failure_logs),
+            capture_trace: Some(config.capture_trace),
+            run_metadata: Some(
+                config
+                    .run_metadata
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect(),
+            ),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config_table_json_null_is_unset() {
+        let content = r#"{
+            "network": { "user_agent": null, "request_timeout": 20 },
+            "smtp": { "smtp_sender_domain": null },
+            "dns": { "dns_servers": ["1.1.1.1", null] }
+        }"#;
+        let table = parse_config_table(Path::new("config.json"), content).unwrap();
+        let file: ConfigFile = table.try_into().unwrap();
+        assert_eq!(file.network.user_agent, None);
+        assert_eq!(file.network.request_timeout, Some(20));
+        assert_eq!(file.smtp.smtp_sender_domain, None);
+        assert_eq!(file.dns.dns_servers, Some(vec!["1.1.1.1".to_string()]));
+    }
+
+    #[test]
+    fn test_parse_config_table_yaml_null_is_unset() {
+        let content =
+            "network:\n  user_agent: ~\n  request_timeout: 20\nsmtp:\n  smtp_sender_domain:\n";
+        let table = parse_config_table(Path::new("config.yaml"), content).unwrap();
+        let file: ConfigFile = table.try_into().unwrap();
+        assert_eq!(file.network.user_agent, None);
+        assert_eq!(file.network.request_timeout, Some(20));
+        assert_eq!(file.smtp.smtp_sender_domain, None);
+
+        let empty = parse_config_table(Path::new("config.yml"), "").unwrap();
+        assert!(empty.is_empty());
+    }
 }
@@ -4,109 +4,705 @@
 pub(crate) mod builder;
 pub(crate) mod file;
 pub(crate) mod loading;
+pub(crate) mod remote;
 pub(crate) mod validation;
 
 pub use builder::ConfigBuilder;
 pub use file::ConfigFile;
 
-use crate::core::error::Result;
+use crate::core::error::{AppError, Result};
+use crate::verification::headless::HeadlessProvider;
 use regex::Regex;
-use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::time::Duration;
 
+/// Default for `smtp_max_attempts`.
+pub(crate) const DEFAULT_SMTP_MAX_ATTEMPTS: u32 = 2;
+
+/// Confidence penalty for a generic prefix listed without an explicit penalty.
+pub const DEFAULT_GENERIC_PREFIX_PENALTY: u8 = 3;
+
+/// Port of the managed ChromeDriver when `webdriver_port` is not set.
+const DEFAULT_WEBDRIVER_PORT: u16 = 4444;
+
+/// Pages scraped on each contact website unless `common_pages_to_scrape` is set.
+const DEFAULT_COMMON_PAGES: &[&str] = &[
+    "/contact",
+    "/contact-us",
+    "/contactus",
+    "/contact_us",
+    "/about",
+    "/about-us",
+    "/aboutus",
+    "/about_us",
+    "/team",
+    "/our-team",
+    "/our_team",
+    "/meet-the-team",
+    "/people",
+    "/staff",
+    "/company",
+];
+
+/// Popular domains that `domain_typo_check` compares input domains against unless
+/// `known_domains` is set.
+const DEFAULT_KNOWN_DOMAINS: &[&str] = &[
+    "google.com",
+    "microsoft.com",
+    "apple.com",
+    "amazon.com",
+    "facebook.com",
+    "meta.com",
+    "linkedin.com",
+    "salesforce.com",
+    "oracle.com",
+    "adobe.com",
+    "netflix.com",
+    "nvidia.com",
+    "intel.com",
+    "cisco.com",
+    "deloitte.com",
+    "accenture.com",
+    "gmail.com",
+    "outlook.com",
+    "hotmail.com",
+    "yahoo.com",
+    "icloud.com",
+];
+
+/// The stages `stage_order` may list, in their default order.
+pub const PIPELINE_STAGES: &[&str] = &["scrape", "dns", "api", "headless", "smtp"];
+
+/// MX host suffixes of well-known mail providers, with the provider name reported in
+/// `mx_provider`. Entries in `mx_providers` are added on top.
+const DEFAULT_MX_PROVIDERS: &[(&str, &str)] = &[
+    ("google.com", "google"),
+    ("googlemail.com", "google"),
+    ("protection.outlook.com", "microsoft_365"),
+    ("olc.protection.outlook.com", "microsoft_consumer"),
+    ("yahoodns.net", "yahoo"),
+    ("mx.aol.com", "yahoo"),
+    ("zoho.com", "zoho"),
+    ("zoho.eu", "zoho"),
+    ("zoho.in", "zoho"),
+    ("zohomail.com", "zoho"),
+    ("protonmail.ch", "proton"),
+    ("messagingengine.com", "fastmail"),
+    ("mail.icloud.com", "icloud"),
+    ("yandex.net", "yandex"),
+    ("gmx.net", "gmx"),
+    ("secureserver.net", "godaddy"),
+    ("kundenserver.de", "ionos"),
+    ("ionos.com", "ionos"),
+    ("ovh.net", "ovh"),
+    ("amazonaws.com", "amazon_ses"),
+    ("mimecast.com", "mimecast"),
+    ("pphosted.com", "proofpoint"),
+    ("ppe-hosted.com", "proofpoint"),
+    ("barracudanetworks.com", "barracuda"),
+];
+
+/// Local parts treated as role addresses unless `generic_email_prefixes` is set.
+const DEFAULT_GENERIC_PREFIXES: &[&str] = &[
+    "info",
+    "contact",
+    "hello",
+    "help",
+    "support",
+    "admin",
+    "office",
+    "sales",
+    "press",
+    "media",
+    "marketing",
+    "jobs",
+    "careers",
+    "hiring",
+    "privacy",
+    "security",
+    "legal",
+    "membership",
+    "team",
+    "people",
+    "general",
+    "feedback",
+    "enquiries",
+    "inquiries",
+    "mail",
+    "email",
+    "pitch",
+    "invest",
+    "investors",
+    "ir",
+    "webmaster",
+    "newsletter",
+    "apply",
+    "partner",
+    "partners",
+    "ventures",
+];
+
+/// Command-line arguments for the headless browser unless `headless_browser_args` is set.
+const DEFAULT_HEADLESS_BROWSER_ARGS: &[&str] = &[
+    "--headless=new",
+    "--no-sandbox",
+    "--disable-gpu",
+    "--disable-dev-shm-usage",
+    "--window-size=1024,768",
+    "--disable-extensions",
+    "--disable-background-networking",
+    "--disable-sync",
+    "--disable-translate",
+    "--mute-audio",
+    "--safebrowsing-disable-auto-update",
+    "--ignore-certificate-errors",
+    "--log-level=1",
+];
+
+/// Ordering applied to batch results before they are written.
+///
+/// Only affects buffered output (the results file written once processing finishes);
+/// output streamed as results complete is never reordered.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputOrder {
+    /// Highest confidence first; contacts without an email last.
+    Confidence,
+    /// Same order as the input file.
+    Input,
+    /// By domain, then last, first and full name.
+    #[default]
+    Domain,
+}
+
+impl std::str::FromStr for OutputOrder {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "confidence" => Ok(OutputOrder::Confidence),
+            "input" => Ok(OutputOrder::Input),
+            "domain" => Ok(OutputOrder::Domain),
+            other => Err(AppError::Config(format!(
+                "Invalid output order '{}' (expected confidence, input or domain)",
+                other
+            ))),
+        }
+    }
+}
+
+/// How much verification work is done per candidate.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationLevel {
+    /// Only check that the domain accepts mail (MX lookup); no per-address probing.
+    MxOnly,
+    /// SMTP probing only; API and headless checks are skipped.
+    Smtp,
+    /// Every enabled method (SMTP, API, headless).
+    #[default]
+    Full,
+}
+
+impl std::fmt::Display for VerificationLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VerificationLevel::MxOnly => write!(f, "mx_only"),
+            VerificationLevel::Smtp => write!(f, "smtp"),
+            VerificationLevel::Full => write!(f, "full"),
+        }
+    }
+}
+
+impl std::str::FromStr for VerificationLevel {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().replace('-', "_").as_str() {
+            "mx_only" | "mx" => Ok(VerificationLevel::MxOnly),
+            "smtp" => Ok(VerificationLevel::Smtp),
+            "full" => Ok(VerificationLevel::Full),
+            other => Err(AppError::Config(format!(
+                "Invalid verification level '{}' (expected mx_only, smtp or full)",
+                other
+            ))),
+        }
+    }
+}
+
+/// Oldest TLS protocol version accepted for HTTPS requests and SMTP STARTTLS.
+///
+/// Both connectors use rustls, which never negotiates below TLS 1.2, so `1.0` and `1.1`
+/// behave like `1.2`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum TlsVersion {
+    #[serde(rename = "1.0")]
+    Tls1_0,
+    #[serde(rename = "1.1")]
+    Tls1_1,
+    #[default]
+    #[serde(rename = "1.2")]
+    Tls1_2,
+    #[serde(rename = "1.3")]
+    Tls1_3,
+}
+
+impl TlsVersion {
+    /// The version actually enforced: rustls has no TLS 1.0 or 1.1, so those mean 1.2.
+    pub(crate) fn enforced(self) -> Self {
+        self.max(TlsVersion::Tls1_2)
+    }
+
+    /// If `error` (an error message, including its causes) shows a handshake failed because
+    /// the server does not offer this version or newer, explains that.
+    pub(crate) fn explain_handshake_error(self, host: &str, error: &str) -> Option<String> {
+        let error = error.to_lowercase().replace(' ', "");
+        let version_mismatch = [
+            "protocolversion",
+            "doesnotsupporttls12or13",
+            "unsupportedprotocol",
+        ]
+        .iter()
+        .any(|needle| error.contains(needle));
+        version_mismatch.then(|| {
+            format!(
+                "{} does not offer TLS {} or newer (min_tls_version = \"{}\")",
+                host,
+                self.enforced(),
+                self
+            )
+        })
+    }
+}
+
+impl std::fmt::Display for TlsVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TlsVersion::Tls1_0 => write!(f, "1.0"),
+            TlsVersion::Tls1_1 => write!(f, "1.1"),
+            TlsVersion::Tls1_2 => write!(f, "1.2"),
+            TlsVersion::Tls1_3 => write!(f, "1.3"),
+        }
+    }
+}
+
+/// Characters used for the random part of probe addresses (see `smtp_probe_charset`).
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ProbeCharset {
+    /// Lowercase letters and digits.
+    #[default]
+    Alphanumeric,
+    /// Lowercase letters only.
+    Letters,
+    /// Digits only.
+    Digits,
+    /// Lowercase hexadecimal digits.
+    Hex,
+}
+
+impl ProbeCharset {
+    /// The characters the random part is drawn from.
+    #[cfg(feature = "smtp")]
+    pub(crate) fn alphabet(self) -> &'static [u8] {
+        match self {
+            ProbeCharset::Alphanumeric => b"abcdefghijklmnopqrstuvwxyz0123456789",
+            ProbeCharset::Letters => b"abcdefghijklmnopqrstuvwxyz",
+            ProbeCharset::Digits => b"0123456789",
+            ProbeCharset::Hex => b"0123456789abcdef",
+        }
+    }
+}
+
+impl std::fmt::Display for ProbeCharset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProbeCharset::Alphanumeric => write!(f, "alphanumeric"),
+            ProbeCharset::Letters => write!(f, "letters"),
+            ProbeCharset::Digits => write!(f, "digits"),
+            ProbeCharset::Hex => write!(f, "hex"),
+        }
+    }
+}
+
+impl std::str::FromStr for ProbeCharset {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "alphanumeric" => Ok(ProbeCharset::Alphanumeric),
+            "letters" => Ok(ProbeCharset::Letters),
+            "digits" => Ok(ProbeCharset::Digits),
+            "hex" => Ok(ProbeCharset::Hex),
+            _ => Err(AppError::Config(format!(
+                "Invalid probe charset '{}' (expected alphanumeric, letters, digits or hex)",
+                s.trim()
+            ))),
+        }
+    }
+}
+
+/// Placeholders `smtp_dialogue` commands may contain: the address being verified, the
+/// MAIL FROM address, the HELO name, the address's domain, and a random address on that
+/// domain that should not exist.
+pub const SMTP_DIALOGUE_PLACEHOLDERS: &[&str] =
+    &["{email}", "{sender}", "{helo}", "{domain}", "{probe}"];
+
+/// One step of a custom SMTP dialogue (see `smtp_dialogue`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SmtpDialogueStep {
+    /// A command line, sent once its placeholders are filled in.
+    Command(String),
+    /// `WAIT <milliseconds>`: a pause before the next step.
+    Wait(Duration),
+}
+
+impl SmtpDialogueStep {
+    /// Whether this step mentions the address being verified; the reply to the first
+    /// such step decides the result.
+    pub fn targets_email(&self) -> bool {
+        matches!(self, SmtpDialogueStep::Command(line) if line.contains("{email}"))
+    }
+}
+
+impl std::str::FromStr for SmtpDialogueStep {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let line = s.trim();
+        if line.is_empty() {
+            return Err(AppError::Config("SMTP dialogue step is empty".to_string()));
+        }
+        if line.contains(['\r', '\n']) {
+            return Err(AppError::Config(format!(
+                "SMTP dialogue step '{}' contains a line break",
+                line.escape_debug()
+            )));
+        }
+        let mut words = line.split_whitespace();
+        let verb = words.next().unwrap_or("").to_uppercase();
+        if verb == "WAIT" {
+            return match (words.next().map(str::parse::<u64>), words.next()) {
+                (Some(Ok(millis)), None) => {
+                    Ok(SmtpDialogueStep::Wait(Duration::from_millis(millis)))
+                }
+                _ => Err(AppError::Config(format!(
+                    "Invalid SMTP dialogue step '{}' (expected WAIT <milliseconds>)",
+                    line
+                ))),
+            };
+        }
+        if matches!(verb.as_str(), "DATA" | "BDAT") {
+            return Err(AppError::Config(format!(
+                "SMTP dialogue step '{}' would start sending a message",
+                line
+            )));
+        }
+        let mut rest = line;
+        while let Some(start) = rest.find('{') {
+            let placeholder = match rest[start..].find('}') {
+                Some(end) => &rest[start..=start + end],
+                None => &rest[start..],
+            };
+            if !SMTP_DIALOGUE_PLACEHOLDERS.contains(&placeholder) {
+                return Err(AppError::Config(format!(
+                    "Unknown placeholder '{}' in SMTP dialogue step '{}' (expected one of {})",
+                    placeholder,
+                    line,
+                    SMTP_DIALOGUE_PLACEHOLDERS.join(", ")
+                )));
+            }
+            rest = &rest[start + placeholder.len()..];
+        }
+        Ok(SmtpDialogueStep::Command(line.to_string()))
+    }
+}
+
+impl std::str::FromStr for TlsVersion {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let version = s.trim().to_lowercase();
+        match version
+            .trim_start_matches("tlsv")
+            .trim_start_matches("tls")
+            .trim()
+        {
+            "1.0" | "1" => Ok(TlsVersion::Tls1_0),
+            "1.1" => Ok(TlsVersion::Tls1_1),
+            "1.2" => Ok(TlsVersion::Tls1_2),
+            "1.3" => Ok(TlsVersion::Tls1_3),
+            _ => Err(AppError::Config(format!(
+                "Invalid TLS version '{}' (expected 1.0, 1.1, 1.2 or 1.3)",
+                s.trim()
+            ))),
+        }
+    }
+}
+
+/// Confidence (0-10) given to an address that a headless provider check reports as existing.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeadlessConfidence {
+    pub microsoft: u8,
+    pub yahoo: u8,
+}
+
+impl Default for HeadlessConfidence {
+    fn default() -> Self {
+        Self {
+            microsoft: 7,
+            yahoo: 8,
+        }
+    }
+}
+
+impl HeadlessConfidence {
+    /// Returns the configured confidence for `provider`'s verdicts.
+    pub fn for_provider(&self, provider: HeadlessProvider) -> u8 {
+        match provider {
+            HeadlessProvider::Microsoft => self.microsoft,
+            HeadlessProvider::Yahoo => self.yahoo,
+        }
+    }
+}
+
 /// Runtime configuration settings used by the email-sleuth core logic.
 pub struct Config {
     pub request_timeout: Duration,
     pub sleep_between_requests: (f32, f32),
     pub user_agent: String,
+    /// Oldest TLS version accepted for HTTPS requests and SMTP STARTTLS.
+    pub min_tls_version: TlsVersion,
+    /// Directory for cached copies of lists fetched from `generic_email_prefixes_url` and
+    /// `disposable_domains_url`. `None` uses `~/.cache/email-sleuth/lists`.
+    pub remote_list_cache_dir: Option<String>,
+    /// How many times a scraping or API request answered with `429 Too Many Requests` is
+    /// retried after the delay in its `Retry-After` header. 0 disables these retries.
+    pub rate_limit_max_retries: u32,
+    /// Longest `Retry-After` delay honoured. A server asking for a longer wait is not retried.
+    pub rate_limit_max_wait: Duration,
 
     pub dns_timeout: Duration,
     pub dns_servers: Vec<String>,
+    pub max_dns_concurrency: usize,
+    pub dnssec_validation: bool,
+    /// Once a domain's lookup finds it does not exist (NXDOMAIN) or has no MX or A/AAAA
+    /// records, skip its remaining contacts in the run with a `domain_dead` result instead
+    /// of running any stages for them.
+    pub skip_dead_domains: bool,
+    /// How long a domain stays dead for `skip_dead_domains`. When that is enabled, this also
+    /// caps how long the resolver caches negative answers, so the first lookup after a domain
+    /// stops being dead asks the DNS servers again.
+    pub dead_domain_ttl: Duration,
+    /// Mail servers to use for specific domains instead of resolving MX records,
+    /// e.g. to point a test domain at a local SMTP sink. The first host is the primary and
+    /// later hosts are backups in priority order.
+    /// Domains listed here are accepted as contact domains even if their suffix is not on
+    /// the public suffix list (such as `.test`).
+    pub dns_overrides: HashMap<String, Vec<String>>,
+    /// Mail provider per MX host suffix (e.g. `"zoho.com"` → `"zoho"`), used to fill
+    /// `mx_provider` in results. The longest matching suffix wins. Starts with a bundled list
+    /// of well-known providers; entries from the config file are added or replace them.
+    pub mx_providers: HashMap<String, String>,
 
     pub smtp_timeout: Duration,
+    /// Read deadline for the reply to RCPT TO. Shorter than `smtp_timeout` so servers that
+    /// accept the connection and then stall do not use up the full timeout.
+    pub smtp_rcpt_timeout: Duration,
+    /// Send the MX hostname as SNI in the STARTTLS handshake. Servers hosting several
+    /// domains use it to pick the certificate; without it they may present the wrong one.
+    pub smtp_tls_sni: bool,
     pub smtp_sender_email: String,
+    pub smtp_sender_domain: Option<String>,
+    /// Attempts per SMTP check when results are inconclusive (e.g. greylisting).
+    pub smtp_max_attempts: u32,
+    /// Former name of `smtp_max_attempts`. Validation copies a value set here to
+    /// `smtp_max_attempts` unless that one was set too, and keeps the two equal.
+    #[deprecated(note = "use `smtp_max_attempts`")]
     pub max_verification_attempts: u32,
+    pub smtp_rejection_threshold: f32,
+    pub smtp_rejection_window: usize,
+    pub smtp_rejection_cooldown: Duration,
+    /// Consecutive failed probes (timeouts, connection errors, temporary failures) after
+    /// which a mail server host is skipped for `smtp_breaker_cooldown`. 0 disables this.
+    pub smtp_breaker_threshold: usize,
+    /// How long a failing host is skipped at first; doubles each time it fails again
+    /// straight after a cooldown, up to 30 minutes.
+    pub smtp_breaker_cooldown: Duration,
+    /// Most SMTP probes sent to any one domain in a run, across all its contacts
+    /// (including the catch-all probe). Once reached, the domain's remaining candidates
+    /// are checked with the other methods only. 0 means no limit.
+    pub max_probes_per_domain: usize,
+    /// When a batch reaches a new domain, send its catch-all probe once as part of the
+    /// domain warmup and share the answer with every contact there. Off by default, in
+    /// which case the warmup only resolves the mail server.
+    pub smtp_prefetch_catch_all: bool,
+    /// After an accepted RCPT, also probe `<localpart>+<random tag>@domain` and report the
+    /// answer as `subaddressing`. It does not change whether the domain counts as a catch-all.
+    pub smtp_subaddress_probe: bool,
+    /// After an accepted RCPT, ask the server to expand the address (EXPN, then VRFY) to
+    /// spot distribution lists. Most servers refuse; the probe is skipped for catch-alls.
+    pub smtp_expn_probe: bool,
+    /// After RCPT, ask servers that advertise VRFY to verify the address. The answer only
+    /// adjusts confidence; it never decides the verification status on its own.
+    pub smtp_vrfy_probe: bool,
+    /// Fixed start of the local part of random probe addresses (catch-all checks).
+    pub smtp_probe_prefix: String,
+    /// Number of random characters after `smtp_probe_prefix`.
+    pub smtp_probe_length: usize,
+    /// Characters the random part is drawn from.
+    pub smtp_probe_charset: ProbeCharset,
+    /// Before a run, require that the sender domain has SPF and the outbound IP has
+    /// forward-confirmed reverse DNS, and refuse to start otherwise.
+    pub smtp_sender_checks: bool,
+    /// Maximum simultaneous SMTP connections to any one mail server host.
+    pub max_concurrency_per_mx: usize,
+    /// Test-only: send every SMTP probe to this `host:port` instead of the domain's real
+    /// mail server. Bypasses normal routing; never set this in production.
+    pub smtp_force_target: Option<String>,
+    /// TLDs (e.g. "ru", "co.jp"; lowercase, no leading dot) whose domains are never
+    /// probed over SMTP. Other verification methods still run.
+    pub smtp_skip_tlds: Vec<String>,
+    /// Replaces the built-in EHLO / MAIL FROM / RCPT TO sequence with these steps (see
+    /// [`SmtpDialogueStep`]), for servers that need a nonstandard exchange. Empty uses
+    /// the built-in sequence.
+    pub smtp_dialogue: Vec<String>,
 
     pub common_pages_to_scrape: Vec<String>,
+    /// When set, fetch the homepage first and then only the `k` internal pages it links to
+    /// that look most likely to list contacts (contact, team, about, imprint, ...), instead
+    /// of every page in `common_pages_to_scrape`.
+    pub scrape_top_k_pages: Option<usize>,
+    /// Attempts per page fetch when scraping hits a transport error or error status.
+    pub scraping_max_attempts: u32,
+    /// When several scraped addresses are listed next to the contact's name (e.g. a
+    /// personal and a role address in one bio), try the non-generic one first and still
+    /// verify the others so they are kept as alternatives.
+    pub prefer_personal_over_role: bool,
+    /// When the pages of a site load but yield no address, render them in the headless
+    /// browser (at `webdriver_url`) and scrape the rendered DOM, for addresses that are
+    /// only inserted by JavaScript.
+    pub headless_scrape_fallback: bool,
+    /// Drop scraped addresses whose registrable domain differs from the contact's (e.g.
+    /// a partner's or a gmail.com address) instead of keeping them as flagged,
+    /// low-confidence candidates.
+    pub exclude_off_domain_scraped: bool,
+    /// Skip pages the site's robots.txt disallows for all user agents (`User-agent: *`).
+    /// Each skipped page is reported as a scraping warning.
+    pub respect_robots_txt: bool,
+    /// Number of distinct scraped addresses the run-wide dedup filter is sized for. Only
+    /// used in builds with the `bloom` feature.
+    pub dedup_expected_items: usize,
+    /// False-positive rate the dedup filter keeps to while it holds at most
+    /// `dedup_expected_items` addresses: the share of new addresses wrongly treated as
+    /// already seen. Only used in builds with the `bloom` feature.
+    pub dedup_false_positive_rate: f64,
     pub email_regex: Regex,
+    /// Generic local parts (e.g. "info"). Each incurs [`DEFAULT_GENERIC_PREFIX_PENALTY`]
+    /// unless `generic_prefix_penalties` sets its own.
     pub generic_email_prefixes: HashSet<String>,
+    /// Confidence penalty for individual entries of `generic_email_prefixes`, in place of
+    /// the default. A penalty of 0 makes the prefix neutral: it is not treated as generic.
+    pub generic_prefix_penalties: HashMap<String, u8>,
+    /// Fetch the generic prefixes from this URL at load time, replacing
+    /// `generic_email_prefixes` (each prefix gets the default penalty).
+    pub generic_email_prefixes_url: Option<String>,
 
     pub confidence_threshold: u8,
     pub generic_confidence_threshold: u8,
     pub max_alternatives: usize,
+    /// Alternatives below this confidence are left out, even when under `max_alternatives`.
+    pub min_alternative_confidence: u8,
     pub max_concurrency: usize,
+    /// Start contacts so the concurrency budget is spread over distinct domains: the next
+    /// contact comes from the domain with the fewest contacts in flight, instead of strictly
+    /// in input order. Results are still returned in input order.
+    pub domain_fair_scheduling: bool,
 
     pub enable_api_checks: bool,
     pub enable_headless_checks: bool,
     pub webdriver_url: Option<String>,
     pub chromedriver_path: Option<String>,
+    pub chromedriver_instances: usize,
+    /// Port the managed ChromeDriver (first instance) listens on, used when no
+    /// `webdriver_url` is given and for [`Config::default_webdriver_url`].
+    pub webdriver_port: u16,
+    /// Stop the ChromeDriver instances this run started when it exits; others keep running.
+    pub stop_chromedriver_on_exit: bool,
+    /// Attempts per headless check when the browser session fails (not when blocked).
+    pub headless_max_attempts: u32,
+    /// Command-line arguments Chrome is started with for headless checks and page
+    /// rendering. Defaults to [`default_headless_browser_args`].
+    pub headless_browser_args: Vec<String>,
+    /// How far each headless provider's "exists" verdict is trusted.
+    pub headless_confidence: HeadlessConfidence,
 
     pub early_termination_threshold: u8,
+    pub verification_level: VerificationLevel,
+    /// The order stages run in: any of `scrape`, `dns`, `api`, `headless` and `smtp`.
+    /// Stages left out do not run.
+    /// `api`, `headless` and `smtp` check each candidate in this order until one gives a
+    /// definitive answer. Listing `scrape` after one of them checks the pattern candidates
+    /// first and only scrapes the website if none was verified. Listing `dns` before
+    /// `scrape` resolves the domain first and skips scraping when it has no mail server.
+    /// `dns` must come before `api`, `headless` and `smtp`.
+    pub stage_order: Vec<String>,
+    pub unverified_fallback: bool,
+    pub unverified_fallback_confidence: u8,
+    /// When no candidate is selected at all, return the top-ranked candidate that was not
+    /// rejected as a guess at confidence 1, rather than no email.
+    pub return_best_guess_on_failure: bool,
+    /// Only give the Confident verdict when at least two independent methods (e.g. SMTP
+    /// and a headless recovery check) confirmed the address; otherwise it is Probable.
+    pub require_corroboration: bool,
+    /// Lowercase the local part of candidate addresses (the domain is always lowercased).
+    /// Turn off for the rare server whose mailbox names are case-sensitive.
+    pub lowercase_local_part: bool,
+    pub keep_subdomains: bool,
+    /// Before processing a contact, check whether its domain looks like a misspelling of
+    /// one in `known_domains` and warn with the likely intended domain.
+    pub domain_typo_check: bool,
+    /// Domains input domains are compared against by `domain_typo_check`.
+    pub known_domains: Vec<String>,
+    /// Domains of disposable email providers. Contacts at these domains are skipped.
+    pub disposable_domains: HashSet<String>,
+    /// Fetch `disposable_domains` from this URL at load time, replacing the configured list.
+    pub disposable_domains_url: Option<String>,
+    /// Seed for the order in which a contact's pattern candidates are tried. Each contact's
+    /// order is derived from this seed and its name and domain, so a run is reproducible
+    /// while contacts still differ. Unset, the order is random.
+    pub random_seed: Option<u64>,
+    /// Known naming convention per domain (e.g. `"acme.com"` → `"{f}{last}"`), used instead
+    /// of the convention inferred from confirmed addresses.
+    pub domain_patterns: HashMap<String, String>,
+
+    pub sort_output: OutputOrder,
+    /// When set, records without a likely email (skipped, errored or inconclusive) are
+    /// written to this file instead of the results file.
+    pub failures_output: Option<String>,
+    /// When set, a per-domain [`crate::DomainReport`] rollup of the batch is written to
+    /// this file as a JSON array.
+    pub domain_report_output: Option<String>,
+    /// Attach the log lines of a contact's task to its result when no likely email is found.
+    /// Requires [`crate::contact_log_layer`] to be installed in the tracing subscriber.
+    pub capture_failure_logs: bool,
+    /// Attach a [`crate::DecisionTrace`] of everything each stage returned to every result.
+    pub capture_trace: bool,
+    /// Free-form labels for the run (e.g. campaign id, config hash, operator), copied into
+    /// every result as `run_metadata` and shown in the run summary.
+    pub run_metadata: HashMap<String, String>,
 
     pub loaded_config_path: Option<String>,
 }
 
 impl Config {
+    #[allow(deprecated)]
     fn build_default() -> Self {
-        let common_pages = vec![
-            "/contact",
-            "/contact-us",
-            "/contactus",
-            "/contact_us",
-            "/about",
-            "/about-us",
-            "/aboutus",
-            "/about_us",
-            "/team",
-            "/our-team",
-            "/our_team",
-            "/meet-the-team",
-            "/people",
-            "/staff",
-            "/company",
-        ];
-        let generic_prefixes: HashSet<String> = [
-            "info",
-            "contact",
-            "hello",
-            "help",
-            "support",
-            "admin",
-            "office",
-            "sales",
-            "press",
-            "media",
-            "marketing",
-            "jobs",
-            "careers",
-            "hiring",
-            "privacy",
-            "security",
-            "legal",
-            "membership",
-            "team",
-            "people",
-            "general",
-            "feedback",
-            "enquiries",
-            "inquiries",
-            "mail",
-            "email",
-            "pitch",
-            "invest",
-            "investors",
-            "ir",
-            "webmaster",
-            "newsletter",
-            "apply",
-            "partner",
-            "partners",
-            "ventures",
-        ]
-        .iter()
-        .map(|s| s.to_string())
-        .collect();
-        let email_regex_pattern = r"\b[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Z|a-z]{2,}\b";
+        // Apostrophes are allowed inside the local part (o'brien@), not at its edges, so
+        // text quoted in single quotes does not pull the quote into the address.
+        let email_regex_pattern =
+            r"\b[A-Za-z0-9._%+-]+(?:'[A-Za-z0-9._%+-]+)*@[A-Za-z0-9.-]+\.[A-Z|a-z]{2,}\b";
         let email_regex = Regex::new(email_regex_pattern)
             .expect("Default email regex pattern failed to compile. This is a bug.");
         let dns_servers = vec![
@@ -120,30 +716,207 @@ impl Config {
             request_timeout: Duration::from_secs(10),
             sleep_between_requests: (0.1, 0.5),
             user_agent: format!("email-sleuth-core/{}", env!("CARGO_PKG_VERSION")),
+            min_tls_version: TlsVersion::default(),
+            remote_list_cache_dir: None,
+            rate_limit_max_retries: 3,
+            rate_limit_max_wait: Duration::from_secs(60),
             dns_timeout: Duration::from_secs(5),
             dns_servers,
+            max_dns_concurrency: 16,
+            dnssec_validation: false,
+            skip_dead_domains: false,
+            dead_domain_ttl: Duration::from_secs(300),
+            dns_overrides: HashMap::new(),
+            mx_providers: DEFAULT_MX_PROVIDERS
+                .iter()
+                .map(|(suffix, provider)| (suffix.to_string(), provider.to_string()))
+                .collect(),
             smtp_timeout: Duration::from_secs(5),
+            smtp_rcpt_timeout: Duration::from_secs(3),
+            smtp_tls_sni: true,
             smtp_sender_email: "verify-probe@example.com".to_string(),
-            max_verification_attempts: 2,
-            common_pages_to_scrape: common_pages.iter().map(|s| s.to_string()).collect(),
+            smtp_sender_domain: None,
+            smtp_max_attempts: DEFAULT_SMTP_MAX_ATTEMPTS,
+            max_verification_attempts: DEFAULT_SMTP_MAX_ATTEMPTS,
+            smtp_rejection_threshold: 0.5,
+            smtp_rejection_window: 20,
+            smtp_rejection_cooldown: Duration::from_secs(300),
+            smtp_breaker_threshold: 5,
+            smtp_breaker_cooldown: Duration::from_secs(60),
+            max_probes_per_domain: 0,
+            smtp_prefetch_catch_all: false,
+            smtp_subaddress_probe: false,
+            smtp_expn_probe: false,
+            smtp_vrfy_probe: false,
+            smtp_probe_prefix: "no-reply-does-not-exist-".to_string(),
+            smtp_probe_length: 12,
+            smtp_probe_charset: ProbeCharset::default(),
+            smtp_sender_checks: false,
+            max_concurrency_per_mx: 2,
+            smtp_force_target: None,
+            smtp_skip_tlds: Vec::new(),
+            smtp_dialogue: Vec::new(),
+            common_pages_to_scrape: default_common_pages(),
+            scrape_top_k_pages: None,
+            scraping_max_attempts: 2,
+            prefer_personal_over_role: true,
+            headless_scrape_fallback: false,
+            exclude_off_domain_scraped: false,
+            respect_robots_txt: false,
+            dedup_expected_items: 1_000_000,
+            dedup_false_positive_rate: 0.001,
             email_regex,
-            generic_email_prefixes: generic_prefixes,
+            generic_email_prefixes: default_generic_prefixes().into_iter().collect(),
+            generic_prefix_penalties: HashMap::new(),
+            generic_email_prefixes_url: None,
             confidence_threshold: 4,
             generic_confidence_threshold: 7,
             max_alternatives: 5,
+            min_alternative_confidence: 0,
             max_concurrency: std::thread::available_parallelism()
                 .map_or(1, |n| n.get())
                 .max(1),
+            domain_fair_scheduling: true,
             enable_api_checks: false,
             enable_headless_checks: false,
             webdriver_url: None,
             chromedriver_path: None,
+            chromedriver_instances: 1,
+            webdriver_port: DEFAULT_WEBDRIVER_PORT,
+            stop_chromedriver_on_exit: false,
+            headless_max_attempts: 2,
+            headless_browser_args: default_headless_browser_args(),
+            headless_confidence: HeadlessConfidence::default(),
             early_termination_threshold: 9,
+            verification_level: VerificationLevel::default(),
+            stage_order: default_stage_order(),
+            unverified_fallback: false,
+            unverified_fallback_confidence: 3,
+            return_best_guess_on_failure: false,
+            require_corroboration: false,
+            lowercase_local_part: true,
+            keep_subdomains: false,
+            random_seed: None,
+            domain_typo_check: false,
+            known_domains: DEFAULT_KNOWN_DOMAINS
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            disposable_domains: HashSet::new(),
+            disposable_domains_url: None,
+            domain_patterns: HashMap::new(),
+            sort_output: OutputOrder::default(),
+            failures_output: None,
+            domain_report_output: None,
+            capture_failure_logs: false,
+            capture_trace: false,
+            run_metadata: HashMap::new(),
             loaded_config_path: None,
         }
     }
 }
 
+impl Config {
+    /// Builds a validated configuration from the defaults, the config file and the environment,
+    /// in that order of precedence (later wins).
+    ///
+    /// With `path` unset, `./email-sleuth.toml` or `./config.toml` is used if present.
+    /// Environment variables are named `EMAIL_SLEUTH_<KEY>` after the config key, e.g.
+    /// `EMAIL_SLEUTH_SMTP_TIMEOUT=10`. For anything more, use [`ConfigBuilder`].
+    pub fn from_env_and_file(path: Option<&std::path::Path>) -> Result<Config> {
+        let mut builder = ConfigBuilder::new().apply_env();
+        if let Some(path) = path {
+            builder = builder.config_file(path.to_string_lossy());
+        }
+        builder.build()
+    }
+
+    /// Returns a copy of this configuration with `overrides` applied and validated again,
+    /// e.g. to change `max_concurrency` for one request without going through the builder.
+    ///
+    /// Settings an [`EmailSleuth`](crate::EmailSleuth) reads when it is created (HTTP client,
+    /// DNS resolver, SMTP verifier, WebDriver URLs) only take effect for a new instance.
+    #[allow(deprecated)]
+    pub fn with_overrides(&self, overrides: impl FnOnce(&mut Config)) -> Result<Config> {
+        let mut config = self.clone();
+        overrides(&mut config);
+        if config.max_verification_attempts != self.max_verification_attempts
+            && config.smtp_max_attempts == self.smtp_max_attempts
+        {
+            config.smtp_max_attempts = config.max_verification_attempts;
+        }
+        validation::validate_config(&mut config)?;
+        Ok(config)
+    }
+
+    /// Returns the effective settings as a `ConfigFile`, e.g. for dumping the merged configuration.
+    pub fn to_config_file(&self) -> ConfigFile {
+        loading::config_to_file(self)
+    }
+
+    /// The confidence penalty for an address with local part `local_part`: its entry in
+    /// `generic_prefix_penalties`, [`DEFAULT_GENERIC_PREFIX_PENALTY`] for other generic
+    /// prefixes, and 0 if the prefix is not generic.
+    pub fn generic_prefix_penalty(&self, local_part: &str) -> u8 {
+        let local_part = local_part.to_lowercase();
+        if !self.generic_email_prefixes.contains(&local_part) {
+            return 0;
+        }
+        self.generic_prefix_penalties
+            .get(&local_part)
+            .copied()
+            .unwrap_or(DEFAULT_GENERIC_PREFIX_PENALTY)
+    }
+
+    /// Position of `stage` in `stage_order`, or `None` if it does not run.
+    pub(crate) fn stage_position(&self, stage: &str) -> Option<usize> {
+        self.stage_order.iter().position(|s| s == stage)
+    }
+
+    /// Returns the entry of `smtp_skip_tlds` that `domain` falls under, if any.
+    pub fn smtp_skipped_tld(&self, domain: &str) -> Option<&str> {
+        let domain = domain.trim_end_matches('.').to_lowercase();
+        self.smtp_skip_tlds
+            .iter()
+            .find(|tld| {
+                domain == **tld
+                    || domain
+                        .strip_suffix(tld.as_str())
+                        .is_some_and(|rest| rest.ends_with('.'))
+            })
+            .map(|tld| tld.as_str())
+    }
+
+    /// The URL of a ChromeDriver on this host at `webdriver_port`.
+    pub fn default_webdriver_url(&self) -> String {
+        format!("http://localhost:{}", self.webdriver_port)
+    }
+
+    /// Returns the WebDriver URLs that headless checks are spread across.
+    ///
+    /// With `chromedriver_instances` > 1, `webdriver_url` names the first instance and the
+    /// others listen on the following ports. Empty if no WebDriver URL is configured.
+    pub fn webdriver_urls(&self) -> Vec<String> {
+        let Some(ref base) = self.webdriver_url else {
+            return Vec::new();
+        };
+        let instances = self.chromedriver_instances.max(1);
+        let parsed = url::Url::parse(base)
+            .ok()
+            .and_then(|u| u.port_or_known_default().map(|port| (u, port)));
+        match parsed {
+            Some((url, port)) if instances > 1 => (0..instances as u16)
+                .filter_map(|i| {
+                    let mut instance_url = url.clone();
+                    instance_url.set_port(Some(port.checked_add(i)?)).ok()?;
+                    Some(instance_url.as_str().trim_end_matches('/').to_string())
+                })
+                .collect(),
+            _ => vec![base.clone()],
+        }
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         Config::build_default()
@@ -151,28 +924,99 @@ impl Default for Config {
 }
 
 impl Clone for Config {
+    #[allow(deprecated)]
     fn clone(&self) -> Self {
         Self {
             request_timeout: self.request_timeout,
             sleep_between_requests: self.sleep_between_requests,
             user_agent: self.user_agent.clone(),
+            min_tls_version: self.min_tls_version,
+            remote_list_cache_dir: self.remote_list_cache_dir.clone(),
+            rate_limit_max_retries: self.rate_limit_max_retries,
+            rate_limit_max_wait: self.rate_limit_max_wait,
             dns_timeout: self.dns_timeout,
             dns_servers: self.dns_servers.clone(),
+            max_dns_concurrency: self.max_dns_concurrency,
+            dnssec_validation: self.dnssec_validation,
+            skip_dead_domains: self.skip_dead_domains,
+            dead_domain_ttl: self.dead_domain_ttl,
+            dns_overrides: self.dns_overrides.clone(),
+            mx_providers: self.mx_providers.clone(),
             smtp_timeout: self.smtp_timeout,
+            smtp_rcpt_timeout: self.smtp_rcpt_timeout,
+            smtp_tls_sni: self.smtp_tls_sni,
             smtp_sender_email: self.smtp_sender_email.clone(),
+            smtp_sender_domain: self.smtp_sender_domain.clone(),
+            smtp_max_attempts: self.smtp_max_attempts,
             max_verification_attempts: self.max_verification_attempts,
+            smtp_rejection_threshold: self.smtp_rejection_threshold,
+            smtp_rejection_window: self.smtp_rejection_window,
+            smtp_rejection_cooldown: self.smtp_rejection_cooldown,
+            smtp_breaker_threshold: self.smtp_breaker_threshold,
+            smtp_breaker_cooldown: self.smtp_breaker_cooldown,
+            max_probes_per_domain: self.max_probes_per_domain,
+            smtp_prefetch_catch_all: self.smtp_prefetch_catch_all,
+            smtp_subaddress_probe: self.smtp_subaddress_probe,
+            smtp_expn_probe: self.smtp_expn_probe,
+            smtp_vrfy_probe: self.smtp_vrfy_probe,
+            smtp_probe_prefix: self.smtp_probe_prefix.clone(),
+            smtp_probe_length: self.smtp_probe_length,
+            smtp_probe_charset: self.smtp_probe_charset,
+            smtp_sender_checks: self.smtp_sender_checks,
+            max_concurrency_per_mx: self.max_concurrency_per_mx,
+            smtp_force_target: self.smtp_force_target.clone(),
+            smtp_skip_tlds: self.smtp_skip_tlds.clone(),
+            smtp_dialogue: self.smtp_dialogue.clone(),
             common_pages_to_scrape: self.common_pages_to_scrape.clone(),
+            scrape_top_k_pages: self.scrape_top_k_pages,
+            scraping_max_attempts: self.scraping_max_attempts,
+            prefer_personal_over_role: self.prefer_personal_over_role,
+            headless_scrape_fallback: self.headless_scrape_fallback,
+            exclude_off_domain_scraped: self.exclude_off_domain_scraped,
+            respect_robots_txt: self.respect_robots_txt,
+            dedup_expected_items: self.dedup_expected_items,
+            dedup_false_positive_rate: self.dedup_false_positive_rate,
             email_regex: self.email_regex.clone(),
             generic_email_prefixes: self.generic_email_prefixes.clone(),
+            generic_prefix_penalties: self.generic_prefix_penalties.clone(),
+            generic_email_prefixes_url: self.generic_email_prefixes_url.clone(),
             confidence_threshold: self.confidence_threshold,
             generic_confidence_threshold: self.generic_confidence_threshold,
             max_alternatives: self.max_alternatives,
+            min_alternative_confidence: self.min_alternative_confidence,
             max_concurrency: self.max_concurrency,
+            domain_fair_scheduling: self.domain_fair_scheduling,
             enable_api_checks: self.enable_api_checks,
             enable_headless_checks: self.enable_headless_checks,
             webdriver_url: self.webdriver_url.clone(),
             chromedriver_path: self.chromedriver_path.clone(),
+            chromedriver_instances: self.chromedriver_instances,
+            webdriver_port: self.webdriver_port,
+            stop_chromedriver_on_exit: self.stop_chromedriver_on_exit,
+            headless_max_attempts: self.headless_max_attempts,
+            headless_browser_args: self.headless_browser_args.clone(),
+            headless_confidence: self.headless_confidence,
             early_termination_threshold: self.early_termination_threshold,
+            verification_level: self.verification_level,
+            stage_order: self.stage_order.clone(),
+            unverified_fallback: self.unverified_fallback,
+            unverified_fallback_confidence: self.unverified_fallback_confidence,
+            return_best_guess_on_failure: self.return_best_guess_on_failure,
+            require_corroboration: self.require_corroboration,
+            lowercase_local_part: self.lowercase_local_part,
+            keep_subdomains: self.keep_subdomains,
+            random_seed: self.random_seed,
+            domain_typo_check: self.domain_typo_check,
+            known_domains: self.known_domains.clone(),
+            disposable_domains: self.disposable_domains.clone(),
+            disposable_domains_url: self.disposable_domains_url.clone(),
+            domain_patterns: self.domain_patterns.clone(),
+            sort_output: self.sort_output,
+            failures_output: self.failures_output.clone(),
+            domain_report_output: self.domain_report_output.clone(),
+            capture_failure_logs: self.capture_failure_logs,
+            capture_trace: self.capture_trace,
+            run_metadata: self.run_metadata.clone(),
             loaded_config_path: self.loaded_config_path.clone(),
         }
     }
@@ -184,40 +1028,156 @@ impl std::fmt::Debug for Config {
             .field("request_timeout", &self.request_timeout)
             .field("sleep_between_requests", &self.sleep_between_requests)
             .field("user_agent", &self.user_agent)
+            .field("min_tls_version", &self.min_tls_version)
+            .field("remote_list_cache_dir", &self.remote_list_cache_dir)
+            .field("rate_limit_max_retries", &self.rate_limit_max_retries)
+            .field("rate_limit_max_wait", &self.rate_limit_max_wait)
             .field("dns_timeout", &self.dns_timeout)
             .field("dns_servers_count", &self.dns_servers.len())
+            .field("max_dns_concurrency", &self.max_dns_concurrency)
+            .field("dnssec_validation", &self.dnssec_validation)
+            .field("skip_dead_domains", &self.skip_dead_domains)
+            .field("dead_domain_ttl", &self.dead_domain_ttl)
+            .field("dns_overrides", &self.dns_overrides)
+            .field("mx_providers_count", &self.mx_providers.len())
             .field("smtp_timeout", &self.smtp_timeout)
+            .field("smtp_rcpt_timeout", &self.smtp_rcpt_timeout)
+            .field("smtp_tls_sni", &self.smtp_tls_sni)
             .field("smtp_sender_email", &self.smtp_sender_email)
-            .field("max_verification_attempts", &self.max_verification_attempts)
+            .field("smtp_sender_domain", &self.smtp_sender_domain)
+            .field("smtp_max_attempts", &self.smtp_max_attempts)
+            .field("smtp_rejection_threshold", &self.smtp_rejection_threshold)
+            .field("smtp_rejection_window", &self.smtp_rejection_window)
+            .field("smtp_rejection_cooldown", &self.smtp_rejection_cooldown)
+            .field("smtp_breaker_threshold", &self.smtp_breaker_threshold)
+            .field("smtp_breaker_cooldown", &self.smtp_breaker_cooldown)
+            .field("max_probes_per_domain", &self.max_probes_per_domain)
+            .field("smtp_prefetch_catch_all", &self.smtp_prefetch_catch_all)
+            .field("smtp_subaddress_probe", &self.smtp_subaddress_probe)
+            .field("smtp_expn_probe", &self.smtp_expn_probe)
+            .field("smtp_vrfy_probe", &self.smtp_vrfy_probe)
+            .field("smtp_probe_prefix", &self.smtp_probe_prefix)
+            .field("smtp_probe_length", &self.smtp_probe_length)
+            .field("smtp_probe_charset", &self.smtp_probe_charset)
+            .field("smtp_sender_checks", &self.smtp_sender_checks)
+            .field("max_concurrency_per_mx", &self.max_concurrency_per_mx)
+            .field("smtp_force_target", &self.smtp_force_target)
+            .field("smtp_skip_tlds", &self.smtp_skip_tlds)
+            .field("smtp_dialogue", &self.smtp_dialogue)
             .field(
                 "common_pages_to_scrape_count",
                 &self.common_pages_to_scrape.len(),
             )
+            .field("scrape_top_k_pages", &self.scrape_top_k_pages)
+            .field("scraping_max_attempts", &self.scraping_max_attempts)
+            .field("prefer_personal_over_role", &self.prefer_personal_over_role)
+            .field("headless_scrape_fallback", &self.headless_scrape_fallback)
+            .field(
+                "exclude_off_domain_scraped",
+                &self.exclude_off_domain_scraped,
+            )
+            .field("respect_robots_txt", &self.respect_robots_txt)
+            .field("dedup_expected_items", &self.dedup_expected_items)
+            .field("dedup_false_positive_rate", &self.dedup_false_positive_rate)
             .field("email_regex", &self.email_regex.as_str())
             .field(
                 "generic_email_prefixes_count",
                 &self.generic_email_prefixes.len(),
             )
+            .field("generic_prefix_penalties", &self.generic_prefix_penalties)
+            .field(
+                "generic_email_prefixes_url",
+                &self.generic_email_prefixes_url,
+            )
             .field("confidence_threshold", &self.confidence_threshold)
             .field(
                 "generic_confidence_threshold",
                 &self.generic_confidence_threshold,
             )
             .field("max_alternatives", &self.max_alternatives)
+            .field(
+                "min_alternative_confidence",
+                &self.min_alternative_confidence,
+            )
             .field("max_concurrency", &self.max_concurrency)
+            .field("domain_fair_scheduling", &self.domain_fair_scheduling)
             .field("enable_api_checks", &self.enable_api_checks)
             .field("enable_headless_checks", &self.enable_headless_checks)
             .field("webdriver_url", &self.webdriver_url)
             .field("chromedriver_path", &self.chromedriver_path)
+            .field("chromedriver_instances", &self.chromedriver_instances)
+            .field("webdriver_port", &self.webdriver_port)
+            .field("stop_chromedriver_on_exit", &self.stop_chromedriver_on_exit)
+            .field("headless_max_attempts", &self.headless_max_attempts)
+            .field("headless_browser_args", &self.headless_browser_args)
+            .field("headless_confidence", &self.headless_confidence)
             .field(
                 "early_termination_threshold",
                 &self.early_termination_threshold,
             )
+            .field("verification_level", &self.verification_level)
+            .field("stage_order", &self.stage_order)
+            .field("unverified_fallback", &self.unverified_fallback)
+            .field(
+                "unverified_fallback_confidence",
+                &self.unverified_fallback_confidence,
+            )
+            .field(
+                "return_best_guess_on_failure",
+                &self.return_best_guess_on_failure,
+            )
+            .field("require_corroboration", &self.require_corroboration)
+            .field("lowercase_local_part", &self.lowercase_local_part)
+            .field("keep_subdomains", &self.keep_subdomains)
+            .field("random_seed", &self.random_seed)
+            .field("domain_typo_check", &self.domain_typo_check)
+            .field("known_domains_count", &self.known_domains.len())
+            .field("disposable_domains_count", &self.disposable_domains.len())
+            .field("disposable_domains_url", &self.disposable_domains_url)
+            .field("domain_patterns", &self.domain_patterns)
+            .field("sort_output", &self.sort_output)
+            .field("failures_output", &self.failures_output)
+            .field("domain_report_output", &self.domain_report_output)
+            .field("capture_failure_logs", &self.capture_failure_logs)
+            .field("capture_trace", &self.capture_trace)
+            .field("run_metadata", &self.run_metadata)
             .field("loaded_config_path", &self.loaded_config_path)
             .finish()
     }
 }
 
+/// The pages scraped by default (`common_pages_to_scrape`), as paths relative to the
+/// contact's website. Extend it and pass it to [`ConfigBuilder::common_pages_to_scrape`]
+/// to add pages without dropping the defaults.
+pub fn default_common_pages() -> Vec<String> {
+    DEFAULT_COMMON_PAGES.iter().map(|s| s.to_string()).collect()
+}
+
+/// The default `stage_order`: scraping, DNS, then the API, headless and SMTP checks.
+pub fn default_stage_order() -> Vec<String> {
+    PIPELINE_STAGES.iter().map(|s| s.to_string()).collect()
+}
+
+/// The generic (role) prefixes used by default (`generic_email_prefixes`), each of which
+/// carries [`DEFAULT_GENERIC_PREFIX_PENALTY`]. Extend it and pass it to
+/// [`ConfigBuilder::generic_email_prefixes`] to add prefixes.
+pub fn default_generic_prefixes() -> Vec<String> {
+    DEFAULT_GENERIC_PREFIXES
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// The arguments the headless browser is started with by default (`headless_browser_args`).
+/// Filter it and pass it to [`ConfigBuilder::headless_browser_args`] to drop flags such as
+/// `--no-sandbox` while keeping the rest.
+pub fn default_headless_browser_args() -> Vec<String> {
+    DEFAULT_HEADLESS_BROWSER_ARGS
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
 /// Utility function to get a random sleep duration based on [`Config`].
 ///
 /// Uses the `sleep_between_requests` setting from the provided configuration.
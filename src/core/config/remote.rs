@@ -0,0 +1,148 @@
+//! Lists fetched from a URL by [`ConfigBuilder::build_with_remote_lists`]
+//! (`generic_email_prefixes_url`, `disposable_domains_url`).
+//!
+//! [`ConfigBuilder::build_with_remote_lists`]: super::ConfigBuilder::build_with_remote_lists
+//!
+//! A list is plain text, one entry per line; blank lines and `#` comments are ignored.
+//! Every successful fetch is written to a cache file, which is used instead when a later
+//! fetch fails, so a network blip does not stop startup.
+
+use crate::core::error::{AppError, Result};
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Time allowed for fetching one list.
+const FETCH_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Longest readable part of a cache file name; a hash of the full URL follows it.
+const MAX_CACHE_NAME_LEN: usize = 80;
+
+/// Where fetched lists are cached unless `remote_list_cache_dir` is set:
+/// `$XDG_CACHE_HOME/email-sleuth/lists`, `~/.cache/email-sleuth/lists`, or the
+/// temporary directory if neither is known.
+pub(crate) fn default_cache_dir() -> PathBuf {
+    let base = std::env::var_os("XDG_CACHE_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME")
+                .filter(|dir| !dir.is_empty())
+                .map(|home| PathBuf::from(home).join(".cache"))
+        })
+        .unwrap_or_else(std::env::temp_dir);
+    base.join("email-sleuth").join("lists")
+}
+
+/// Fetches the list at `url`, caching it in `cache_dir`. If the fetch fails, the cached copy
+/// from an earlier run is used; with no cached copy either, this is a configuration error.
+pub(crate) async fn load_remote_list(
+    url: &str,
+    user_agent: &str,
+    cache_dir: &Path,
+) -> Result<Vec<String>> {
+    if !(url.starts_with("http://") || url.starts_with("https://")) {
+        return Err(AppError::Config(format!(
+            "List URL must start with http:// or https://: '{}'",
+            url
+        )));
+    }
+    let cache_path = cache_dir.join(cache_file_name(url));
+    match fetch(url, user_agent).await {
+        Ok(body) => {
+            if let Err(e) =
+                fs::create_dir_all(cache_dir).and_then(|_| fs::write(&cache_path, &body))
+            {
+                tracing::warn!(
+                    "Could not cache list from {} at {}: {}",
+                    url,
+                    cache_path.display(),
+                    e
+                );
+            }
+            let entries = parse_list(&body);
+            tracing::info!("Loaded {} entries from {}", entries.len(), url);
+            Ok(entries)
+        }
+        Err(fetch_error) => match fs::read_to_string(&cache_path) {
+            Ok(body) => {
+                tracing::warn!(
+                    "Failed to fetch list from {} ({}); using the cached copy at {}",
+                    url,
+                    fetch_error,
+                    cache_path.display()
+                );
+                Ok(parse_list(&body))
+            }
+            Err(_) => Err(AppError::Config(format!(
+                "Failed to fetch list from {} ({}) and no cached copy exists at {}",
+                url,
+                fetch_error,
+                cache_path.display()
+            ))),
+        },
+    }
+}
+
+/// Downloads `url` as text.
+async fn fetch(url: &str, user_agent: &str) -> Result<String> {
+    let client = reqwest::Client::builder()
+        .user_agent(user_agent)
+        .timeout(FETCH_TIMEOUT)
+        .build()?;
+    let response = client.get(url).send().await?.error_for_status()?;
+    Ok(response.text().await?)
+}
+
+/// Splits a list into lowercase entries, skipping blank lines and `#` comments.
+fn parse_list(body: &str) -> Vec<String> {
+    body.lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim().to_lowercase())
+        .filter(|entry| !entry.is_empty())
+        .collect()
+}
+
+/// A file name for the cached copy of `url`: the start of the URL without its scheme, with
+/// every character other than letters, digits, `.`, `-` and `_` replaced by `_`, followed by
+/// a hash of the whole URL so long URLs sharing a prefix get different files.
+fn cache_file_name(url: &str) -> String {
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let mut name: String = without_scheme
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(MAX_CACHE_NAME_LEN)
+        .collect();
+    name.push_str(&format!("-{:016x}.txt", fnv1a(url.as_bytes())));
+    name
+}
+
+/// 64-bit FNV-1a, which unlike `DefaultHasher` is the same across Rust releases, so cache
+/// files keep their names between builds.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_file_name() {
+        let base = format!("https://lists.example.com/{}", "a".repeat(150));
+        let first = cache_file_name(&format!("{}/prefixes.txt", base));
+        let second = cache_file_name(&format!("{}/domains.txt", base));
+        assert_ne!(first, second);
+        assert!(first.starts_with("lists.example.com_aaa"));
+        assert!(first.ends_with(".txt"));
+        assert_eq!(first, cache_file_name(&format!("{}/prefixes.txt", base)));
+    }
+}
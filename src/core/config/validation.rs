@@ -1,20 +1,31 @@
 //! Contains validation logic for the final Config struct.
 
-use super::{Config, Result};
+use super::{
+    Config, Result, SmtpDialogueStep, VerificationLevel, DEFAULT_SMTP_MAX_ATTEMPTS, PIPELINE_STAGES,
+};
 use crate::core::error::AppError;
-use tracing;
+use crate::utils::patterns::{is_valid_naming_convention, CONVENTION_PLACEHOLDERS};
 
 /// Validates the configuration settings after loading and potential overrides.
 /// Mutates the config to clamp values or set defaults where applicable and logical.
 /// Internal helper for the builder's `build` method.
 pub(crate) fn validate_config(config: &mut Config) -> Result<()> {
-    // Returns library Result<()>
-    if config.sleep_between_requests.0 < 0.0 || config.sleep_between_requests.1 < 0.0 {
-        return Err(AppError::Config(
-            "Sleep durations cannot be negative.".to_string(),
-        ));
+    let problems = collect_config_problems(config);
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::Config(problems.join("; ")))
     }
-    if config.sleep_between_requests.0 > config.sleep_between_requests.1 {
+}
+
+/// Runs every validation rule and returns all problems found instead of stopping at the first.
+/// Applies the same clamping and defaulting as [`validate_config`].
+pub(crate) fn collect_config_problems(config: &mut Config) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    if config.sleep_between_requests.0 < 0.0 || config.sleep_between_requests.1 < 0.0 {
+        problems.push("Sleep durations cannot be negative.".to_string());
+    } else if config.sleep_between_requests.0 > config.sleep_between_requests.1 {
         tracing::warn!(
             "Min sleep ({:.2}s) > Max sleep ({:.2}s). Setting max sleep = min sleep.",
             config.sleep_between_requests.0,
@@ -46,29 +57,273 @@ pub(crate) fn validate_config(config: &mut Config) -> Result<()> {
         );
         config.generic_confidence_threshold = config.confidence_threshold;
     }
+    if config.unverified_fallback_confidence > 10 {
+        tracing::warn!(
+            "Unverified fallback confidence ({}) > 10. Clamping to 10.",
+            config.unverified_fallback_confidence
+        );
+        config.unverified_fallback_confidence = 10;
+    }
+    if config.min_alternative_confidence > 10 {
+        tracing::warn!(
+            "Minimum alternative confidence ({}) > 10. Clamping to 10.",
+            config.min_alternative_confidence
+        );
+        config.min_alternative_confidence = 10;
+    }
+    for (provider, confidence) in [
+        ("microsoft", &mut config.headless_confidence.microsoft),
+        ("yahoo", &mut config.headless_confidence.yahoo),
+    ] {
+        if *confidence > 10 {
+            tracing::warn!(
+                "Headless confidence for {} ({}) > 10. Clamping to 10.",
+                provider,
+                confidence
+            );
+            *confidence = 10;
+        }
+    }
+    for (prefix, penalty) in config.generic_prefix_penalties.iter_mut() {
+        if *penalty > 10 {
+            tracing::warn!(
+                "Penalty for generic prefix '{}' ({}) > 10. Clamping to 10.",
+                prefix,
+                penalty
+            );
+            *penalty = 10;
+        }
+    }
     if config.max_concurrency == 0 {
         tracing::warn!("Max concurrency was set to 0. Setting to 1.");
         config.max_concurrency = 1;
     }
+    for (domain, hosts) in &config.dns_overrides {
+        if hosts.is_empty() {
+            problems.push(format!(
+                "DNS override for '{}' has no mail server hosts.",
+                domain
+            ));
+        }
+    }
+    if config.skip_dead_domains && config.dead_domain_ttl.is_zero() {
+        problems.push(
+            "dead_domain_ttl must be at least 1 second when skip_dead_domains is enabled."
+                .to_string(),
+        );
+    }
+    if config.run_metadata.keys().any(String::is_empty) {
+        problems.push("run_metadata keys must not be empty.".to_string());
+    }
+    for (suffix, provider) in &config.mx_providers {
+        if provider.is_empty() {
+            problems.push(format!("MX provider for '{}' has no name.", suffix));
+        }
+    }
+    if config.smtp_probe_length == 0 {
+        problems.push("SMTP probe length must be at least 1.".to_string());
+    }
+    if config.smtp_probe_prefix.len() + config.smtp_probe_length > 64 {
+        problems.push(format!(
+            "SMTP probe local part is {} characters long; the limit is 64 (smtp_probe_prefix + smtp_probe_length).",
+            config.smtp_probe_prefix.len() + config.smtp_probe_length
+        ));
+    }
+    if !config
+        .smtp_probe_prefix
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        problems.push(format!(
+            "SMTP probe prefix '{}' may only contain letters, digits, '-', '_' and '.'.",
+            config.smtp_probe_prefix
+        ));
+    }
+    if !config.smtp_dialogue.is_empty() {
+        let mut targets_email = false;
+        for step in &config.smtp_dialogue {
+            match step.parse::<SmtpDialogueStep>() {
+                Ok(step) => targets_email |= step.targets_email(),
+                Err(AppError::Config(message)) => problems.push(format!("{}.", message)),
+                Err(e) => problems.push(e.to_string()),
+            }
+        }
+        if !targets_email {
+            problems.push(
+                "smtp_dialogue must contain a command with {email} (e.g. \"RCPT TO:<{email}>\"); its reply decides the result.".to_string(),
+            );
+        }
+    }
+    for (domain, template) in &config.domain_patterns {
+        if !is_valid_naming_convention(template) {
+            problems.push(format!(
+                "Domain pattern '{}' for '{}' is not a valid template. Use {} placeholders, e.g. \"{{first}}.{{last}}\".",
+                template,
+                domain,
+                CONVENTION_PLACEHOLDERS.join(", ")
+            ));
+        }
+    }
+    for (index, stage) in config.stage_order.iter().enumerate() {
+        if !PIPELINE_STAGES.contains(&stage.as_str()) {
+            problems.push(format!(
+                "Unknown stage '{}' in stage_order. Valid stages: {}.",
+                stage,
+                PIPELINE_STAGES.join(", ")
+            ));
+        } else if config.stage_order[..index].contains(stage) {
+            problems.push(format!("Stage '{}' is listed twice in stage_order.", stage));
+        }
+    }
+    let dns_position = config.stage_position("dns");
+    for stage in ["api", "headless", "smtp"] {
+        if let Some(position) = config.stage_position(stage) {
+            if dns_position.is_none_or(|dns| dns > position) {
+                problems.push(format!(
+                    "Stage '{}' in stage_order needs the mail server; list 'dns' before it.",
+                    stage
+                ));
+            }
+        }
+    }
+    #[allow(deprecated)]
+    {
+        // Only one of the two was set: the one moved away from the default wins.
+        if config.max_verification_attempts != config.smtp_max_attempts
+            && config.smtp_max_attempts == DEFAULT_SMTP_MAX_ATTEMPTS
+        {
+            config.smtp_max_attempts = config.max_verification_attempts;
+        }
+    }
+    for (stage, attempts) in [
+        ("SMTP", &mut config.smtp_max_attempts),
+        ("headless", &mut config.headless_max_attempts),
+        ("scraping", &mut config.scraping_max_attempts),
+    ] {
+        if *attempts == 0 {
+            tracing::warn!("Max {} attempts was set to 0. Setting to 1.", stage);
+            *attempts = 1;
+        }
+    }
+    #[allow(deprecated)]
+    {
+        config.max_verification_attempts = config.smtp_max_attempts;
+    }
+    if (config.enable_headless_checks || config.headless_scrape_fallback)
+        && !config
+            .headless_browser_args
+            .iter()
+            .any(|arg| arg.starts_with("--headless"))
+    {
+        tracing::warn!(
+            "headless_browser_args has no --headless flag; Chrome will open visible windows."
+        );
+    }
+    if config.smtp_rcpt_timeout.is_zero() || config.smtp_rcpt_timeout > config.smtp_timeout {
+        tracing::warn!(
+            "SMTP RCPT timeout ({:?}) must be between 1s and smtp_timeout ({:?}). Using {:?}.",
+            config.smtp_rcpt_timeout,
+            config.smtp_timeout,
+            config.smtp_timeout
+        );
+        config.smtp_rcpt_timeout = config.smtp_timeout;
+    }
+    if config.max_concurrency_per_mx == 0 {
+        tracing::warn!("Max concurrency per MX was set to 0. Setting to 1.");
+        config.max_concurrency_per_mx = 1;
+    }
+    if config.max_dns_concurrency == 0 {
+        tracing::warn!("Max DNS concurrency was set to 0. Setting to 1.");
+        config.max_dns_concurrency = 1;
+    }
+    if config.dedup_expected_items == 0 {
+        tracing::warn!("Dedup expected items was set to 0. Setting to 1.");
+        config.dedup_expected_items = 1;
+    }
+    if !(config.dedup_false_positive_rate > 0.0 && config.dedup_false_positive_rate < 1.0) {
+        problems.push(format!(
+            "dedup_false_positive_rate must be between 0 and 1 (exclusive), got {}",
+            config.dedup_false_positive_rate
+        ));
+    }
     if !config.smtp_sender_email.contains('@') || !config.smtp_sender_email.contains('.') {
-        return Err(AppError::Config(format!(
+        problems.push(format!(
             "Invalid SMTP sender email format: {}",
             config.smtp_sender_email
-        )));
+        ));
     }
-    if config.enable_headless_checks && config.webdriver_url.is_none() {
-        return Err(AppError::Config(
-            "WebDriver URL is required when headless checks are enabled.".to_string(),
+    if let Some(ref domain) = config.smtp_sender_domain {
+        if domain.contains('@') || !domain.contains('.') {
+            problems.push(format!("Invalid SMTP sender domain: {}", domain));
+        }
+    }
+    if let Some(ref target) = config.smtp_force_target {
+        match target.rsplit_once(':') {
+            Some((host, port)) if !host.is_empty() && port.parse::<u16>().is_ok() => {
+                tracing::warn!(
+                    "smtp_force_target is set: ALL SMTP probes will go to {} instead of each domain's real mail server. Use this for testing only.",
+                    target
+                );
+            }
+            _ => problems.push(format!(
+                "Invalid smtp_force_target '{}': expected host:port",
+                target
+            )),
+        }
+    }
+    if !(0.0..=1.0).contains(&config.smtp_rejection_threshold) {
+        problems.push(format!(
+            "SMTP rejection threshold must be between 0.0 and 1.0: {}",
+            config.smtp_rejection_threshold
         ));
     }
-    if !config.enable_headless_checks && config.webdriver_url.is_some() {
+    if config.smtp_rejection_window == 0 {
+        tracing::warn!("SMTP rejection window was set to 0. Setting to 1.");
+        config.smtp_rejection_window = 1;
+    }
+    if !cfg!(feature = "smtp") {
+        if config.smtp_sender_checks {
+            tracing::warn!("SMTP sender checks are enabled, but this build does not include the 'smtp' feature. They will be skipped.");
+            config.smtp_sender_checks = false;
+        }
+        if config.verification_level == VerificationLevel::Smtp {
+            tracing::warn!("Verification level is smtp, but this build does not include the 'smtp' feature. Candidates will only be scored.");
+        }
+    }
+    if config.enable_headless_checks && !cfg!(feature = "headless") {
+        tracing::warn!("Headless checks are enabled, but this build does not include the 'headless' feature. They will be skipped.");
+        config.enable_headless_checks = false;
+    }
+    if config.enable_headless_checks && config.webdriver_url.is_none() {
+        problems.push("WebDriver URL is required when headless checks are enabled.".to_string());
+    }
+    if config.headless_scrape_fallback && !cfg!(feature = "headless") {
+        tracing::warn!("Headless scrape fallback is enabled, but this build does not include the 'headless' feature. It will be skipped.");
+        config.headless_scrape_fallback = false;
+    }
+    if config.headless_scrape_fallback && config.webdriver_url.is_none() {
+        problems.push(
+            "WebDriver URL is required when headless_scrape_fallback is enabled.".to_string(),
+        );
+    }
+    if !config.enable_headless_checks
+        && !config.headless_scrape_fallback
+        && config.webdriver_url.is_some()
+    {
         tracing::warn!("A WebDriver URL was provided, but headless checks are disabled. The URL will be ignored.");
     }
+    if config.webdriver_port == 0 {
+        problems.push("WebDriver port must be between 1 and 65535.".to_string());
+    }
+    if config.chromedriver_instances == 0 {
+        tracing::warn!("ChromeDriver instances was set to 0. Setting to 1.");
+        config.chromedriver_instances = 1;
+    }
     if let Some(ref path) = config.chromedriver_path {
         if path.is_empty() {
             tracing::warn!("Provided ChromeDriver path is empty. It will be ignored.");
             config.chromedriver_path = None;
         }
     }
-    Ok(())
+    problems
 }
@@ -1,5 +1,6 @@
 //! Defines the custom error types for the email-sleuth application.
 
+#[cfg(feature = "headless")]
 use fantoccini::error::{CmdError, NewSessionError};
 use std::{io, net::AddrParseError};
 use thiserror::Error;
@@ -48,15 +49,26 @@ pub enum AppError {
     #[error("No DNS Records Found (MX/A): {0}")]
     NoDnsRecords(String),
 
+    /// DNSSEC validation of the domain's records failed (only when validation is enabled).
+    #[error("DNSSEC Validation Failed: {0}")]
+    DnssecValidation(String),
+
+    /// The domain was found dead (NXDOMAIN or no MX/A records) earlier in the run, so its
+    /// contact was skipped (only with `skip_dead_domains`).
+    #[error("Domain Dead: {0}")]
+    DomainDead(String),
+
     /// DNS operation timed out.
     #[error("DNS Timeout for domain: {0}")]
     DnsTimeout(String),
 
     /// Error during SMTP communication setup or command execution.
+    #[cfg(feature = "smtp")]
     #[error("SMTP Error: {0}")]
     Smtp(#[from] lettre::transport::smtp::Error),
 
     /// Error related to SMTP command execution details.
+    #[cfg(feature = "smtp")]
     #[error("SMTP Command Error: Code={code}, Message='{message}'")]
     SmtpCommand {
         /// The SMTP status code.
@@ -69,6 +81,10 @@ pub enum AppError {
     #[error("SMTP STARTTLS Error: {0}")]
     SmtpTls(String),
 
+    /// The server only offers TLS versions older than the configured `min_tls_version`.
+    #[error("TLS Version Rejected: {0}")]
+    TlsVersion(String),
+
     /// Error parsing an IP address or socket address.
     #[error("Address Parsing Error: {0}")]
     AddrParse(#[from] AddrParseError),
@@ -86,9 +102,19 @@ pub enum AppError {
     InsufficientInput(String),
 
     /// Failed to extract a domain from the provided URL.
+    #[deprecated(note = "domain extraction failures are returned as `AppError::InvalidDomain`")]
     #[error("Failed to extract domain from URL: {0}")]
     DomainExtraction(String),
 
+    /// Failed to extract a domain from the provided domain or URL input.
+    #[error("Failed to extract domain from URL ({kind}): {detail}")]
+    InvalidDomain {
+        /// What was wrong with the input, for grouping bad-input reasons.
+        kind: DomainErrorKind,
+        /// The offending input and any further detail.
+        detail: String,
+    },
+
     /// SMTP verification returned a temporary failure (e.g., 4xx code).
     #[error("SMTP Temporary Failure: {0}")]
     SmtpTemporaryFailure(String),
@@ -114,7 +140,50 @@ pub enum AppError {
     FantocciniCmd(String),
 }
 
+impl AppError {
+    /// A stable, machine-readable code for the error variant (e.g. `"dns_timeout"`).
+    ///
+    /// Written alongside failed records so they can be grouped or filtered for a retry queue.
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::Config(_) => "config",
+            AppError::Initialization(_) => "initialization",
+            AppError::Io(_) => "io",
+            AppError::Json(_) => "json",
+            AppError::UrlParse(_) => "url_parse",
+            AppError::Request(_) => "request",
+            AppError::HtmlParse(_) => "html_parse",
+            AppError::Dns(_) => "dns",
+            AppError::NxDomain(_) => "nx_domain",
+            AppError::NoDnsRecords(_) => "no_dns_records",
+            AppError::DnssecValidation(_) => "dnssec_validation",
+            AppError::DomainDead(_) => "domain_dead",
+            AppError::DnsTimeout(_) => "dns_timeout",
+            #[cfg(feature = "smtp")]
+            AppError::Smtp(_) => "smtp",
+            #[cfg(feature = "smtp")]
+            AppError::SmtpCommand { .. } => "smtp_command",
+            AppError::SmtpTls(_) => "smtp_tls",
+            AppError::TlsVersion(_) => "tls_version",
+            AppError::AddrParse(_) => "addr_parse",
+            AppError::Task(_) => "task",
+            AppError::Generic(_) => "generic",
+            AppError::InsufficientInput(_) => "insufficient_input",
+            #[allow(deprecated)]
+            AppError::DomainExtraction(_) => "domain_extraction",
+            AppError::InvalidDomain { .. } => "domain_extraction",
+            AppError::SmtpTemporaryFailure(_) => "smtp_temporary_failure",
+            AppError::SmtpPermanentFailure(_) => "smtp_permanent_failure",
+            AppError::SmtpInconclusive(_) => "smtp_inconclusive",
+            AppError::WebDriverConnection(_) => "webdriver_connection",
+            AppError::VerificationBlocked(_) => "verification_blocked",
+            AppError::FantocciniCmd(_) => "fantoccini_cmd",
+        }
+    }
+}
+
 // From implementations for Fantoccini errors
+#[cfg(feature = "headless")]
 impl From<CmdError> for AppError {
     fn from(err: CmdError) -> Self {
         let msg = err.to_string();
@@ -129,10 +198,45 @@ impl From<CmdError> for AppError {
     }
 }
 
+#[cfg(feature = "headless")]
 impl From<NewSessionError> for AppError {
     fn from(err: NewSessionError) -> Self {
         AppError::WebDriverConnection(err.to_string())
     }
 }
 
+/// Why a domain could not be extracted from a domain or URL input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DomainErrorKind {
+    /// The input was empty or whitespace.
+    EmptyInput,
+    /// The input could not be parsed as a URL at all.
+    InvalidUrl,
+    /// The input parsed, but has no host part.
+    NoHost,
+    /// The URL uses a scheme other than http or https.
+    InvalidScheme,
+    /// The host is an IP address rather than a domain name.
+    IpAddress,
+    /// The host does not end in a known public suffix (e.g. "localhost", "example.invalid").
+    UnrecognizedSuffix,
+    /// The host is malformed (e.g. empty labels or a trailing dot).
+    InvalidHost,
+}
+
+impl std::fmt::Display for DomainErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            DomainErrorKind::EmptyInput => "empty input",
+            DomainErrorKind::InvalidUrl => "invalid URL",
+            DomainErrorKind::NoHost => "no host",
+            DomainErrorKind::InvalidScheme => "invalid scheme",
+            DomainErrorKind::IpAddress => "IP address",
+            DomainErrorKind::UnrecognizedSuffix => "unrecognized suffix",
+            DomainErrorKind::InvalidHost => "invalid host",
+        };
+        f.write_str(label)
+    }
+}
+
 pub type Result<T> = std::result::Result<T, AppError>;
@@ -0,0 +1,303 @@
+//! Readers for contact input files.
+//!
+//! JSON array files are read whole. NDJSON (one contact object per line) and CSV (a header
+//! row naming the contact fields) are read one record at a time, so arbitrarily large
+//! inputs can be fed through [`crate::process_contacts_streaming`] in constant memory.
+
+use crate::core::error::{AppError, Result};
+use crate::core::models::Contact;
+
+use serde_json::{Map, Value};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+/// The layout of a contact input file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputFormat {
+    /// A JSON array of contact objects.
+    Json,
+    /// One JSON contact object per line.
+    NdJson,
+    /// Comma-separated values with a header row of contact field names.
+    Csv,
+}
+
+impl InputFormat {
+    /// Picks the format from the file extension: `.ndjson`/`.jsonl` and `.csv`;
+    /// anything else is read as a JSON array.
+    pub fn from_path(path: &Path) -> Self {
+        match path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("ndjson") | Some("jsonl") => InputFormat::NdJson,
+            Some("csv") => InputFormat::Csv,
+            _ => InputFormat::Json,
+        }
+    }
+
+    /// Whether records of this format can be read one at a time.
+    pub fn is_streamable(self) -> bool {
+        !matches!(self, InputFormat::Json)
+    }
+}
+
+/// Iterates over the contacts in an input file.
+///
+/// Each item is one record; a malformed record yields an error (with its line number)
+/// without ending the iteration.
+pub struct ContactReader {
+    inner: Inner,
+}
+
+enum Inner {
+    Buffered(std::vec::IntoIter<Contact>),
+    NdJson {
+        reader: BufReader<File>,
+        line_no: usize,
+    },
+    Csv {
+        reader: BufReader<File>,
+        header: Vec<String>,
+        line_no: usize,
+    },
+}
+
+impl ContactReader {
+    /// Opens `path`, detecting the format from its extension.
+    ///
+    /// A JSON array is parsed completely here; other formats are read lazily.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let mut reader = BufReader::new(File::open(path)?);
+        let inner = match InputFormat::from_path(path) {
+            InputFormat::Json => {
+                Inner::Buffered(serde_json::from_reader::<_, Vec<Contact>>(reader)?.into_iter())
+            }
+            InputFormat::NdJson => Inner::NdJson { reader, line_no: 0 },
+            InputFormat::Csv => {
+                let mut line_no = 0;
+                let header = read_csv_record(&mut reader, &mut line_no)?
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|name| name.trim().to_lowercase())
+                    .collect();
+                Inner::Csv {
+                    reader,
+                    header,
+                    line_no,
+                }
+            }
+        };
+        Ok(Self { inner })
+    }
+}
+
+impl Iterator for ContactReader {
+    type Item = Result<Contact>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.inner {
+            Inner::Buffered(contacts) => contacts.next().map(Ok),
+            Inner::NdJson { reader, line_no } => loop {
+                let mut line = String::new();
+                match reader.read_line(&mut line) {
+                    Ok(0) => return None,
+                    Ok(_) => *line_no += 1,
+                    Err(e) => return Some(Err(e.into())),
+                }
+                if line.trim().is_empty() {
+                    continue;
+                }
+                return Some(
+                    serde_json::from_str(&line)
+                        .map_err(|e| invalid_record(*line_no, &e.to_string())),
+                );
+            },
+            Inner::Csv {
+                reader,
+                header,
+                line_no,
+            } => loop {
+                let start_line = *line_no + 1;
+                let fields = match read_csv_record(reader, line_no) {
+                    Ok(Some(fields)) => fields,
+                    Ok(None) => return None,
+                    Err(e) => return Some(Err(e)),
+                };
+                if fields.iter().all(|field| field.trim().is_empty()) {
+                    continue;
+                }
+                return Some(csv_contact(header, fields, start_line));
+            },
+        }
+    }
+}
+
+/// Builds a contact from one CSV record, treating empty cells as missing.
+/// `websites` may list several URLs separated by `;`.
+fn csv_contact(header: &[String], fields: Vec<String>, line_no: usize) -> Result<Contact> {
+    if fields.len() > header.len() {
+        return Err(invalid_record(
+            line_no,
+            &format!(
+                "{} fields but the header has {} columns",
+                fields.len(),
+                header.len()
+            ),
+        ));
+    }
+    let mut object = Map::new();
+    for (name, value) in header.iter().zip(fields) {
+        let value = value.trim();
+        if name.is_empty() || value.is_empty() {
+            continue;
+        }
+        let value = if name == "websites" {
+            Value::Array(
+                value
+                    .split(';')
+                    .map(str::trim)
+                    .filter(|url| !url.is_empty())
+                    .map(|url| Value::String(url.to_string()))
+                    .collect(),
+            )
+        } else {
+            Value::String(value.to_string())
+        };
+        object.insert(name.clone(), value);
+    }
+    serde_json::from_value(Value::Object(object))
+        .map_err(|e| invalid_record(line_no, &e.to_string()))
+}
+
+/// Reads one CSV record (RFC 4180: quoted fields may contain commas, doubled quotes and
+/// line breaks). Returns `None` at end of input.
+fn read_csv_record(reader: &mut impl BufRead, line_no: &mut usize) -> Result<Option<Vec<String>>> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            if in_quotes {
+                return Err(invalid_record(*line_no, "unterminated quoted field"));
+            }
+            if fields.is_empty() && field.is_empty() {
+                return Ok(None);
+            }
+            fields.push(field);
+            return Ok(Some(fields));
+        }
+        *line_no += 1;
+
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            match (c, in_quotes) {
+                ('"', true) if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                ('"', true) => in_quotes = false,
+                ('"', false) if field.is_empty() => in_quotes = true,
+                (',', false) => fields.push(std::mem::take(&mut field)),
+                ('\r' | '\n', false) => {}
+                (c, _) => field.push(c),
+            }
+        }
+        if !in_quotes {
+            fields.push(field);
+            return Ok(Some(fields));
+        }
+    }
+}
+
+fn invalid_record(line_no: usize, detail: &str) -> AppError {
+    AppError::Io(io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("Invalid record at line {}: {}", line_no, detail),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(input: &str) -> Vec<Vec<String>> {
+        let mut reader = input.as_bytes();
+        let mut line_no = 0;
+        let mut out = Vec::new();
+        while let Some(record) = read_csv_record(&mut reader, &mut line_no).unwrap() {
+            out.push(record);
+        }
+        out
+    }
+
+    #[test]
+    fn test_read_csv_record_quoting() {
+        let parsed =
+            records("first_name,domain\n\"Smith, John\",acme.com\r\n\"say \"\"hi\"\"\",x.com\n");
+        assert_eq!(
+            parsed,
+            vec![
+                vec!["first_name".to_string(), "domain".to_string()],
+                vec!["Smith, John".to_string(), "acme.com".to_string()],
+                vec!["say \"hi\"".to_string(), "x.com".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_csv_record_multiline_field() {
+        let parsed = records("a,\"line one\nline two\",c\n");
+        assert_eq!(parsed, vec![vec!["a", "line one\nline two", "c"]]);
+    }
+
+    #[test]
+    fn test_read_csv_record_unterminated_quote() {
+        let mut reader = "a,\"open\n".as_bytes();
+        let mut line_no = 0;
+        assert!(read_csv_record(&mut reader, &mut line_no).is_err());
+    }
+
+    #[test]
+    fn test_csv_contact_fields() {
+        let header: Vec<String> = ["first_name", "last_name", "domain", "websites", "region"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let fields = vec![
+            "John",
+            "Doe",
+            "acme.com",
+            "https://acme.com/team; https://acme.com/about",
+            "",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+        let contact = csv_contact(&header, fields, 2).unwrap();
+        assert_eq!(contact.first_name.as_deref(), Some("John"));
+        assert_eq!(contact.domain.as_deref(), Some("acme.com"));
+        assert_eq!(contact.websites.len(), 2);
+        assert!(!contact.other_fields.contains_key("region"));
+    }
+
+    #[test]
+    fn test_input_format_from_path() {
+        assert_eq!(InputFormat::from_path(Path::new("a.csv")), InputFormat::Csv);
+        assert_eq!(
+            InputFormat::from_path(Path::new("a.JSONL")),
+            InputFormat::NdJson
+        );
+        assert_eq!(
+            InputFormat::from_path(Path::new("a.json")),
+            InputFormat::Json
+        );
+    }
+}
@@ -0,0 +1,201 @@
+//! Per-contact log capture.
+//!
+//! Each contact is processed inside a `contact` span. When the span is created with
+//! `capture_logs = true` (see `capture_failure_logs`), [`ContactLogLayer`] keeps every log
+//! event emitted inside it, at any level, so the lines for one failed contact can be attached
+//! to its result instead of being picked out of the interleaved log.
+
+use std::fmt::{self, Write as _};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::{Event, Metadata, Subscriber};
+use tracing_subscriber::filter::{DynFilterFn, Filtered};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Registry;
+
+/// Name of the span each contact is processed in.
+pub(crate) const CONTACT_SPAN: &str = "contact";
+
+/// Lines kept per contact; later lines are counted but dropped.
+const MAX_CAPTURED_LINES: usize = 500;
+
+/// Log lines collected for one contact span, stored in the span's extensions.
+#[derive(Default)]
+struct CapturedLogs {
+    lines: Vec<String>,
+    dropped: usize,
+}
+
+impl CapturedLogs {
+    fn into_lines(mut self) -> Vec<String> {
+        if self.dropped > 0 {
+            self.lines
+                .push(format!("... {} more log lines not captured", self.dropped));
+        }
+        self.lines
+    }
+}
+
+/// Collects the log events of `contact` spans that have `capture_logs = true`.
+///
+/// Install it next to the usual formatting layer; it only sees events inside capturing
+/// spans, so the rest of the log is unaffected. Use [`contact_log_layer`] to get it with
+/// that filter applied.
+pub struct ContactLogLayer;
+
+/// Returns a [`ContactLogLayer`] filtered to capturing contact spans and the events in them.
+pub fn contact_log_layer<S>() -> Filtered<ContactLogLayer, DynFilterFn<S>, S>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    ContactLogLayer.with_filter(DynFilterFn::new(
+        is_captured as fn(&Metadata<'_>, &Context<'_, S>) -> bool,
+    ))
+}
+
+/// Enables contact spans, and events inside a contact span that is capturing.
+fn is_captured<S>(metadata: &Metadata<'_>, cx: &Context<'_, S>) -> bool
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    if metadata.is_span() {
+        return metadata.name() == CONTACT_SPAN;
+    }
+    cx.lookup_current().is_some_and(|span| {
+        span.scope()
+            .any(|s| s.extensions().get::<CapturedLogs>().is_some())
+    })
+}
+
+impl<S> Layer<S> for ContactLogLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut visitor = CaptureFlag(false);
+        attrs.record(&mut visitor);
+        if !visitor.0 {
+            return;
+        }
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(CapturedLogs::default());
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let Some(scope) = ctx.event_scope(event) else {
+            return;
+        };
+        for span in scope {
+            let mut extensions = span.extensions_mut();
+            if let Some(logs) = extensions.get_mut::<CapturedLogs>() {
+                if logs.lines.len() >= MAX_CAPTURED_LINES {
+                    logs.dropped += 1;
+                } else {
+                    logs.lines.push(format_event(event));
+                }
+                return;
+            }
+        }
+    }
+}
+
+/// Removes and returns the lines captured for the contact span the caller is running in.
+///
+/// Returns `None` outside a capturing span or when no [`ContactLogLayer`] is installed.
+pub(crate) fn take_contact_logs() -> Option<Vec<String>> {
+    let id = tracing::Span::current().id()?;
+    tracing::dispatcher::get_default(|dispatch| {
+        let registry = dispatch.downcast_ref::<Registry>()?;
+        let span = registry.span(&id)?;
+        span.scope()
+            .find_map(|s| s.extensions_mut().remove::<CapturedLogs>())
+            .map(CapturedLogs::into_lines)
+    })
+}
+
+fn format_event(event: &Event<'_>) -> String {
+    let metadata = event.metadata();
+    let mut visitor = EventFormatter::default();
+    event.record(&mut visitor);
+    format!(
+        "{} {} {}: {}{}",
+        chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        metadata.level(),
+        metadata.target(),
+        visitor.message,
+        visitor.fields
+    )
+}
+
+/// Reads the `capture_logs` field of a contact span.
+struct CaptureFlag(bool);
+
+impl Visit for CaptureFlag {
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        if field.name() == "capture_logs" {
+            self.0 = value;
+        }
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn fmt::Debug) {}
+}
+
+#[derive(Default)]
+struct EventFormatter {
+    message: String,
+    fields: String,
+}
+
+impl Visit for EventFormatter {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_captures_only_inside_capturing_span() {
+        let subscriber = Registry::default().with(contact_log_layer());
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("outside any contact");
+
+            let quiet = tracing::debug_span!(CONTACT_SPAN, capture_logs = false);
+            quiet.in_scope(|| {
+                tracing::info!("not captured");
+                assert!(take_contact_logs().is_none());
+            });
+
+            let span = tracing::debug_span!(CONTACT_SPAN, capture_logs = true);
+            span.in_scope(|| {
+                tracing::trace!(target: "smtp_task", attempt = 2, "probing");
+                let nested = tracing::trace_span!("inner");
+                nested.in_scope(|| tracing::warn!("from a child span"));
+
+                let lines = take_contact_logs().unwrap();
+                assert_eq!(lines.len(), 2);
+                assert!(lines[0].contains("TRACE smtp_task: probing attempt=2"));
+                assert!(lines[1].contains("WARN"));
+                assert!(lines[1].ends_with("from a child span"));
+                assert!(take_contact_logs().is_none());
+            });
+        });
+    }
+}
@@ -1,4 +1,11 @@
 pub(crate) mod config;
 pub(crate) mod error;
+pub(crate) mod input;
+pub(crate) mod log_capture;
 pub(crate) mod models;
+pub(crate) mod output;
+pub(crate) mod report;
+pub(crate) mod scheduling;
+pub(crate) mod scoring;
 pub(crate) mod sleuth;
+pub(crate) mod stage;
@@ -1,12 +1,17 @@
 //! Defines the core data structures used in the email-sleuth application.
 
+use crate::core::config::Config;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::Duration;
 use url::Url;
 
 /// Represents the input contact record read from the JSON file.
 /// Allows for flexibility if some fields are missing.
-#[derive(Serialize, Deserialize, Debug, Clone)]
+///
+/// Can also be built programmatically, e.g. with `..Default::default()` for unused fields.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct Contact {
     /// The contact's first name.
     pub first_name: Option<String>,
@@ -20,11 +25,103 @@ pub struct Contact {
     #[serde(alias = "company_domain")]
     #[serde(skip_serializing)]
     pub company_domain: Option<String>,
+    /// Additional website URLs for the contact's company (e.g., a careers or team page),
+    /// scraped alongside the domain's homepage.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub websites: Vec<String>,
+    /// The contact's job title (optional input, carried through to the output).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
     // Allow capturing other fields from the input JSON
     #[serde(flatten)]
     pub other_fields: HashMap<String, serde_json::Value>,
 }
 
+impl Contact {
+    /// A key identifying this contact by name and domain, ignoring case and surrounding
+    /// whitespace. Used to match input records against results from an earlier run.
+    pub fn identity_key(&self) -> String {
+        let normalize = |value: Option<&str>| value.unwrap_or("").trim().to_lowercase();
+        [
+            normalize(self.first_name.as_deref()),
+            normalize(self.last_name.as_deref()),
+            normalize(self.full_name.as_deref()),
+            normalize(self.domain.as_deref().or(self.company_domain.as_deref())),
+        ]
+        .join("|")
+    }
+}
+
+/// The pipeline stage that produced the final verdict for a candidate email.
+///
+/// Unlike the free-form `source` string, this is a closed set suitable for
+/// aggregating results (e.g. "how many answers came from headless checks").
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ResolutionStage {
+    /// Only DNS information was available; no per-address check ran.
+    DnsOnly,
+    /// The email was found by scraping the company website.
+    Scraping,
+    /// The email was generated from name patterns and checked via SMTP.
+    PatternSmtp,
+    /// The email was checked via a provider API (e.g. Microsoft 365).
+    Api,
+    /// The email was checked via a headless browser flow.
+    Headless,
+    /// The email was generated from name patterns without any conclusive check.
+    #[default]
+    Pattern,
+    /// The email was checked by a user-supplied verification stage.
+    Custom,
+}
+
+/// Low-level signals observed during an SMTP RCPT TO check.
+///
+/// Servers that accept every recipient at SMTP time but bounce later can't be told
+/// apart from real mailboxes by the response code alone; the timing of the candidate
+/// RCPT compared with an obviously-invalid one is sometimes a useful hint.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SmtpProbeSignals {
+    /// How long the server took to answer RCPT TO for the candidate address, in milliseconds.
+    pub rcpt_latency_ms: u64,
+    /// Whether the server also accepted a random, certainly-invalid address on the same
+    /// domain. `None` if that probe was not sent (e.g. the candidate was rejected).
+    pub invalid_probe_accepted: Option<bool>,
+    /// How long the server took to answer RCPT TO for the invalid address, in milliseconds.
+    pub invalid_probe_latency_ms: Option<u64>,
+    /// `rcpt_latency_ms - invalid_probe_latency_ms`, when both were measured.
+    pub latency_delta_ms: Option<i64>,
+    /// Whether the server accepted the plus-tagged address (`local+tag@domain`) in the
+    /// optional subaddress probe. `None` if the probe was not run. Read it together with
+    /// `invalid_probe_accepted`: on a catch-all domain it says little about the mailbox.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subaddressing: Option<bool>,
+    /// How many recipients the server listed when asked to expand the address (EXPN/VRFY).
+    /// `None` if the probe was not run or the server declined to expand it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expansion_members: Option<u32>,
+    /// Whether the server confirmed the address with VRFY, from the optional VRFY probe.
+    /// `None` if the probe was not run or the server would not say.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vrfy_accepted: Option<bool>,
+}
+
+impl SmtpProbeSignals {
+    /// Builds the signals from the measured RCPT timings.
+    pub fn new(rcpt_latency_ms: u64, invalid_probe: Option<(bool, u64)>) -> Self {
+        Self {
+            rcpt_latency_ms,
+            invalid_probe_accepted: invalid_probe.map(|(accepted, _)| accepted),
+            invalid_probe_latency_ms: invalid_probe.map(|(_, ms)| ms),
+            latency_delta_ms: invalid_probe.map(|(_, ms)| rcpt_latency_ms as i64 - ms as i64),
+            subaddressing: None,
+            expansion_members: None,
+            vrfy_accepted: None,
+        }
+    }
+}
+
 /// Represents a single email address found and its associated metadata.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct FoundEmailData {
@@ -36,10 +133,189 @@ pub struct FoundEmailData {
     pub source: String, // Could be an enum: Source { Pattern, Scraped }
     /// Indicates if the email address uses a common generic prefix (e.g., info@, contact@).
     pub is_generic: bool,
-    /// The result of the SMTP verification attempt (True=Verified, False=Rejected, None=Inconclusive/Untested).
+    /// The outcome of verification, always written to the output:
+    /// `true` = verified to exist, `false` = verified not to exist (do not retry),
+    /// `null` = inconclusive or not checked (e.g. greylisted, timed out, catch-all), which
+    /// a later retry may resolve. Both `false` and `null` usually come with low confidence,
+    /// so use this field (or [`FoundEmailData::is_inconclusive`]) to tell them apart.
     pub verification_status: Option<bool>,
     /// A message accompanying the verification status (e.g., error details, OK message).
     pub verification_message: String,
+    /// The pipeline stage that produced this result.
+    #[serde(default)]
+    pub stage: ResolutionStage,
+    /// RCPT TO signals from the SMTP check, if one reached the RCPT stage.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub smtp_signals: Option<SmtpProbeSignals>,
+    /// When the check that produced a conclusive `verification_status` completed.
+    /// `None` if no check was conclusive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verified_at: Option<DateTime<Utc>>,
+    /// The MX host whose SMTP response produced `verification_status`, if the SMTP check ran.
+    /// Differs from the primary MX when a backup MX gave the definitive answer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mx_host: Option<String>,
+    /// The address likely delivers to a distribution list or shared alias rather than one person.
+    #[serde(default)]
+    pub likely_distribution_list: bool,
+    /// The independent methods that each confirmed the address exists, in the order they
+    /// ran. Only filled in when `require_corroboration` is enabled.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub corroborated_by: Vec<String>,
+    /// The address was scraped from the contact's website but is at another registrable
+    /// domain (e.g. a partner's or a gmail.com address). Such addresses are not verified,
+    /// carry a confidence penalty and are never selected as `most_likely_email`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub off_domain: bool,
+    /// The address as it was found (e.g. scraped), when normalizing its casing changed it.
+    /// `email` is the normalized form, which is the one that was probed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normalized_from: Option<String>,
+}
+
+/// The three values of [`FoundEmailData::verification_status`], by name.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationState {
+    /// Verified to exist (`Some(true)`).
+    Verified,
+    /// Verified not to exist (`Some(false)`); retrying will not change that.
+    Rejected,
+    /// No conclusive answer (`None`); a retry may produce one.
+    Inconclusive,
+}
+
+impl From<Option<bool>> for VerificationState {
+    fn from(status: Option<bool>) -> Self {
+        match status {
+            Some(true) => VerificationState::Verified,
+            Some(false) => VerificationState::Rejected,
+            None => VerificationState::Inconclusive,
+        }
+    }
+}
+
+/// A shared interpretation of a candidate's confidence and verification status.
+///
+/// Ordered from strongest to weakest.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    /// Verified to exist and at or above the confidence threshold.
+    Confident,
+    /// At or above the confidence threshold, but not verified.
+    Probable,
+    /// Below the confidence threshold.
+    Uncertain,
+    /// Verified not to exist.
+    Rejected,
+}
+
+impl Verdict {
+    /// Whether the candidate is good enough to be reported as a contact's email.
+    pub fn is_acceptable(self) -> bool {
+        matches!(self, Verdict::Confident | Verdict::Probable)
+    }
+}
+
+impl FoundEmailData {
+    /// [`FoundEmailData::verification_status`] as a [`VerificationState`].
+    pub fn verification_state(&self) -> VerificationState {
+        self.verification_status.into()
+    }
+
+    /// Whether verification gave no answer for this address (`verification_status` is
+    /// `None`), as opposed to rejecting it. Such addresses are worth retrying later.
+    pub fn is_inconclusive(&self) -> bool {
+        self.verification_status.is_none()
+    }
+
+    /// The confidence threshold that applies to this candidate: the generic threshold
+    /// for generic addresses, the base threshold otherwise.
+    pub fn confidence_threshold(&self, config: &Config) -> u8 {
+        if self.is_generic {
+            config.generic_confidence_threshold
+        } else {
+            config.confidence_threshold
+        }
+    }
+
+    /// Classifies this candidate using the configured thresholds and its verification status.
+    /// With `require_corroboration`, a verified address is only Confident if at least two
+    /// methods confirmed it.
+    pub fn verdict(&self, config: &Config) -> Verdict {
+        if self.verification_status == Some(false) {
+            Verdict::Rejected
+        } else if self.confidence < self.confidence_threshold(config) {
+            Verdict::Uncertain
+        } else if self.verification_status == Some(true)
+            && (!config.require_corroboration || self.corroborated_by.len() >= 2)
+        {
+            Verdict::Confident
+        } else {
+            Verdict::Probable
+        }
+    }
+}
+
+/// The area a [`Warning`] comes from.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningCategory {
+    /// A page could not be fetched while scraping, or robots.txt disallowed it.
+    Scraping,
+    /// A domain had no usable mail server records, or only an implicit MX.
+    Dns,
+    /// A headless check was blocked (e.g. by a CAPTCHA) or could not run.
+    Headless,
+    /// Something looks wrong with the input, such as a domain that is probably misspelt.
+    Input,
+    /// A processing task panicked or was cancelled.
+    Task,
+    /// A custom verification stage returned an error.
+    Stage,
+    /// An SMTP check had to retry over STARTTLS, or its TLS handshake failed.
+    Smtp,
+    /// A supporting service needed attention, e.g. a stale ChromeDriver PID file was removed.
+    Service,
+}
+
+impl std::fmt::Display for WarningCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WarningCategory::Scraping => write!(f, "scraping"),
+            WarningCategory::Dns => write!(f, "dns"),
+            WarningCategory::Headless => write!(f, "headless"),
+            WarningCategory::Input => write!(f, "input"),
+            WarningCategory::Task => write!(f, "task"),
+            WarningCategory::Stage => write!(f, "stage"),
+            WarningCategory::Smtp => write!(f, "smtp"),
+            WarningCategory::Service => write!(f, "service"),
+        }
+    }
+}
+
+/// A non-fatal issue met during a run. The run carries on; the warning is collected so
+/// callers can surface it (see [`crate::EmailSleuth::take_warnings`]).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    pub category: WarningCategory,
+    pub message: String,
+    /// Full name of the contact being processed, if the issue is tied to one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contact: Option<String>,
+    /// Domain the issue concerns, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+}
+
+/// A verification method that was not run for a contact, and why.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SkippedMethod {
+    /// The method name, using the same names as `methods_used` (e.g., "smtp_verification").
+    pub method: String,
+    /// Why the method was not run (e.g., "disabled in config").
+    pub reason: String,
 }
 
 /// Contains the results of the email finding process for a single contact.
@@ -52,10 +328,143 @@ pub struct EmailResult {
     pub most_likely_email: Option<String>,
     /// The confidence score associated with the most_likely_email.
     pub confidence_score: u8,
+    /// The pipeline stage that produced the most_likely_email, if one was selected.
+    #[serde(default)]
+    pub resolution_stage: Option<ResolutionStage>,
+    /// True if most_likely_email is an unverified but plausible guess: the domain accepts
+    /// mail, but every probe was inconclusive (see `unverified_fallback` in the config).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub unverified_fallback: bool,
+    /// True if most_likely_email is a guessed, unverified candidate returned because
+    /// nothing else was selected (see `return_best_guess_on_failure` in the config).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub best_guess: bool,
+    /// A well-known domain the contact's domain looks like a misspelling of (see
+    /// `domain_typo_check` in the config). The contact is still processed as given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggested_domain: Option<String>,
+    /// The mail provider the domain's primary MX host belongs to (e.g. `google`, `zoho`,
+    /// `self_hosted`, see `mx_providers` in the config). `None` if no mail server was resolved.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mx_provider: Option<String>,
     /// List of methods used during the discovery process (e.g., "pattern_generation", "website_scraping", "smtp_verification").
     pub methods_used: Vec<String>,
+    /// Methods that produced a result for this contact: an answer about a candidate, or
+    /// addresses found by scraping. Methods that failed before answering are not listed.
+    #[serde(default)]
+    pub attempted_methods: Vec<String>,
+    /// Methods that were not run for this contact, with the reason for each.
+    #[serde(default)]
+    pub skipped_methods: Vec<SkippedMethod>,
     /// A log of verification attempts and their outcomes for specific emails.
     pub verification_log: HashMap<String, String>,
+    /// Time spent in each pipeline stage ("scraping", "dns", "smtp", "api", "headless"),
+    /// summed over all candidates. Stages that did not run are absent. Serialized in
+    /// milliseconds.
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        with = "duration_ms_map"
+    )]
+    pub stage_timings: HashMap<String, Duration>,
+    /// Everything each stage returned for this contact, when `capture_trace` is enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<DecisionTrace>,
+}
+
+/// The full record of what each pipeline stage returned for one contact, attached to
+/// [`EmailResult::trace`] when `capture_trace` is enabled. Meant for auditing and as
+/// training data; it can be large.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct DecisionTrace {
+    /// The mail server used for the domain, if one was found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mail_server: Option<TraceMailServer>,
+    /// Every address scraped from the company website on the contact's domain, with the
+    /// text around it.
+    #[serde(default)]
+    pub scraped: Vec<TraceScrapedEmail>,
+    /// Every candidate that went through verification, in order. Empty at the `mx_only`
+    /// verification level, where no stage runs per candidate.
+    #[serde(default)]
+    pub candidates: Vec<TraceCandidate>,
+}
+
+/// The mail server recorded in a [`DecisionTrace`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TraceMailServer {
+    pub exchange: String,
+    pub preference: u16,
+    /// No MX records; the domain's own A/AAAA host is used.
+    pub implicit: bool,
+    /// Lower-priority MX hosts, most preferred first.
+    pub backups: Vec<String>,
+}
+
+/// A scraped address recorded in a [`DecisionTrace`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TraceScrapedEmail {
+    pub email: String,
+    /// Text of the element around the address, e.g. a team member's card.
+    pub context: String,
+}
+
+/// One candidate's path through the pipeline, recorded in a [`DecisionTrace`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TraceCandidate {
+    pub email: String,
+    /// What each stage that ran returned, in order.
+    pub steps: Vec<TraceStep>,
+    /// The status after all stages.
+    pub verification_status: Option<bool>,
+    /// The confidence after all stages (0-10).
+    pub confidence: u8,
+    /// The final verification message, including skip reasons.
+    pub message: String,
+    /// False if the candidate was discarded rather than listed in `found_emails`.
+    pub kept: bool,
+}
+
+/// What one stage returned for a candidate.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TraceStep {
+    /// "alternative", "smtp", or the name of a custom stage.
+    pub stage: String,
+    /// The method that answered (e.g. "smtp", "api_m365", "headless_yahoo").
+    pub source: String,
+    pub status: Option<bool>,
+    pub message: String,
+    /// The SMTP reply code found in the message, for SMTP steps.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub smtp_code: Option<u16>,
+    pub definitive: bool,
+}
+
+/// (De)serializes a map of durations as whole milliseconds.
+mod duration_ms_map {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(
+        timings: &HashMap<String, Duration>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        timings
+            .iter()
+            .map(|(stage, duration)| (stage, duration.as_millis() as u64))
+            .collect::<HashMap<_, _>>()
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<String, Duration>, D::Error> {
+        Ok(HashMap::<String, u64>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(stage, ms)| (stage, Duration::from_millis(ms)))
+            .collect())
+    }
 }
 
 /// Represents the final output structure for each record, combining input and results.
@@ -94,9 +503,127 @@ pub struct ProcessingResult {
     #[serde(skip_serializing_if = "std::ops::Not::not")]
     #[serde(default)]
     pub email_verification_failed: bool,
+    /// Flag indicating no email was selected while at least one candidate was inconclusive
+    /// (see [`FoundEmailData::is_inconclusive`]), so retrying the contact may help.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    #[serde(default)]
+    pub email_verification_inconclusive: bool,
     /// Error message if processing failed unexpectedly.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub email_finding_error: Option<String>,
+    /// [`AppError::code`](crate::AppError::code) of the error that caused a skip or failure.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email_finding_error_code: Option<String>,
+    /// Log lines from this contact's task, kept for failed contacts when
+    /// `capture_failure_logs` is enabled.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub debug_log: Vec<String>,
+    /// Labels of the run that produced this record, copied from `run_metadata`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub run_metadata: HashMap<String, String>,
+}
+
+/// Why a record ended up in the failures output instead of the results output.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureKind {
+    /// The input was missing data or had an unusable domain.
+    Skipped,
+    /// Processing failed with an error.
+    Error,
+    /// Processing finished without a likely email.
+    Inconclusive,
+}
+
+/// Failure reason of an inconclusive result that had candidates, none confident enough.
+const NO_CONFIDENT_CANDIDATE_REASON: &str =
+    "Candidates found, but none reached the confidence threshold";
+
+/// A record written to the failures output.
+///
+/// The original input fields are kept at the top level, so a failures file can be
+/// used directly as the input file of a later run.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FailureRecord {
+    #[serde(flatten)]
+    pub contact_input: Contact,
+    pub failure_kind: FailureKind,
+    /// [`AppError::code`](crate::AppError::code), when an error caused the failure.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<String>,
+    /// Human-readable explanation of the failure.
+    pub reason: String,
+    /// Log lines from the contact's task, when `capture_failure_logs` is enabled.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub debug_log: Vec<String>,
+    /// Labels of the run that produced this record, copied from `run_metadata`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub run_metadata: HashMap<String, String>,
+}
+
+impl FailureRecord {
+    /// Builds a failure record from a result without a likely email.
+    /// Returns `None` for conclusive results.
+    pub fn from_result(result: &ProcessingResult) -> Option<Self> {
+        let (failure_kind, reason) = if result.email_finding_skipped {
+            (
+                FailureKind::Skipped,
+                result.email_finding_reason.clone().unwrap_or_default(),
+            )
+        } else if let Some(ref error) = result.email_finding_error {
+            (FailureKind::Error, error.clone())
+        } else if result.email.is_none() {
+            let reason = if result.email_verification_failed {
+                NO_CONFIDENT_CANDIDATE_REASON
+            } else {
+                "No email found"
+            };
+            (FailureKind::Inconclusive, reason.to_string())
+        } else {
+            return None;
+        };
+
+        let mut contact_input = result.contact_input.clone();
+        // `company_domain` is not serialized; carry it over so the record can be re-run.
+        if contact_input.domain.is_none() {
+            contact_input.domain = contact_input.company_domain.clone();
+        }
+        Some(Self {
+            contact_input,
+            failure_kind,
+            error_code: result.email_finding_error_code.clone(),
+            reason,
+            debug_log: result.debug_log.clone(),
+            run_metadata: result.run_metadata.clone(),
+        })
+    }
+
+    /// Rebuilds the result this record was written from, as far as the record tells, so
+    /// a resumed run can count the contact as done (or retry it, for an error).
+    pub fn into_result(self) -> ProcessingResult {
+        let (skipped, error) = match self.failure_kind {
+            FailureKind::Skipped => (true, None),
+            FailureKind::Error => (false, Some(self.reason.clone())),
+            FailureKind::Inconclusive => (false, None),
+        };
+        ProcessingResult {
+            contact_input: self.contact_input,
+            email_discovery_results: None,
+            email: None,
+            email_confidence: None,
+            email_verification_method: None,
+            email_alternatives: vec![],
+            email_finding_skipped: skipped,
+            email_finding_reason: skipped.then(|| self.reason.clone()),
+            email_verification_failed: self.failure_kind == FailureKind::Inconclusive
+                && self.reason == NO_CONFIDENT_CANDIDATE_REASON,
+            email_verification_inconclusive: false,
+            email_finding_error: error,
+            email_finding_error_code: self.error_code,
+            debug_log: self.debug_log,
+            run_metadata: self.run_metadata,
+        }
+    }
 }
 
 /// Internal representation after validating input Contact
@@ -109,6 +636,8 @@ pub struct ValidatedContact {
     pub full_name: String,
     /// The base URL derived from the input domain, used for scraping.
     pub website_url: Url,
+    /// Extra pages to scrape from the contact's `websites`, excluding `website_url`.
+    pub additional_websites: Vec<Url>,
     /// The extracted, lowercase domain name used for patterns and verification.
     pub domain: String,
     // Keep original contact for outputting all original fields.
@@ -0,0 +1,88 @@
+//! Output writers for batch results.
+//!
+//! [`OutputWriter`] is the extension point for output formats: the batch runner hands each
+//! [`ProcessingResult`] to a writer and calls [`OutputWriter::finish`] once every result has
+//! been written. [`JsonWriter`] is the built-in implementation.
+
+use crate::core::error::Result;
+use crate::core::models::ProcessingResult;
+
+use serde::Serialize;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Serializes batch results to some destination.
+pub trait OutputWriter {
+    /// Writes one result. Results arrive in the order they should appear in the output.
+    fn write_result(&mut self, record: &ProcessingResult) -> Result<()>;
+
+    /// Completes the output (closing brackets, footers) and flushes it.
+    /// No results are written after this is called.
+    fn finish(&mut self) -> Result<()>;
+}
+
+/// Writes records as a pretty-printed JSON array, one element at a time.
+///
+/// The output is the same as serializing the whole slice with `serde_json::to_writer_pretty`.
+pub struct JsonWriter<W: Write> {
+    writer: W,
+    written: usize,
+}
+
+impl<W: Write> JsonWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer, written: 0 }
+    }
+
+    /// Writes any serializable record as the next array element.
+    ///
+    /// Used for records other than [`ProcessingResult`], such as failure records.
+    pub fn write_record<T: Serialize>(&mut self, record: &T) -> Result<()> {
+        let element = serde_json::to_string_pretty(record)?;
+        self.writer
+            .write_all(if self.written == 0 { b"[\n" } else { b",\n" })?;
+        for (i, line) in element.lines().enumerate() {
+            if i > 0 {
+                self.writer.write_all(b"\n")?;
+            }
+            write!(self.writer, "  {}", line)?;
+        }
+        self.written += 1;
+        Ok(())
+    }
+
+    /// Pushes buffered records through to the destination, leaving the array open.
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Number of records written so far.
+    pub fn written(&self) -> usize {
+        self.written
+    }
+}
+
+impl JsonWriter<BufWriter<File>> {
+    /// Creates (or truncates) the file at `path` and writes to it.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write> OutputWriter for JsonWriter<W> {
+    fn write_result(&mut self, record: &ProcessingResult) -> Result<()> {
+        self.write_record(record)
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        if self.written == 0 {
+            self.writer.write_all(b"[]")?;
+        } else {
+            self.writer.write_all(b"\n]")?;
+        }
+        self.writer.flush()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,268 @@
+//! Per-domain rollup of batch results.
+//!
+//! [`DomainReports`] collects results one at a time, so it works for streamed batches as well
+//! as finished ones; [`DomainReport::from_results`] is the shortcut for a finished batch.
+
+use crate::core::models::ProcessingResult;
+use crate::utils::domain::get_domain_from_url;
+use crate::utils::patterns::infer_naming_convention;
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// What a batch found out about one domain.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DomainReport {
+    /// The company domain, as given in the contacts' input (reduced to its host).
+    pub domain: String,
+    /// Number of contacts at this domain.
+    pub contacts: usize,
+    /// Number of those contacts for which a likely email was found.
+    pub resolved: usize,
+    /// The naming convention (e.g. `{first}.{last}`) behind most of the found emails, if any
+    /// matched a known template.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub naming_convention: Option<String>,
+    /// Number of found emails that follow `naming_convention`.
+    #[serde(default)]
+    pub naming_convention_matches: usize,
+    /// Whether the domain accepts mail for any address. `None` if no check could tell.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub catch_all: Option<bool>,
+    /// The MX host that answered most SMTP checks for this domain.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mx_host: Option<String>,
+    /// The mail provider most of the domain's results named in their `mx_provider` (e.g.
+    /// `google`, `zoho`, `self_hosted`, see `mx_providers` in the config).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mx_provider: Option<String>,
+}
+
+impl DomainReport {
+    /// Builds one report per domain from a finished batch, sorted by domain.
+    pub fn from_results(results: &[ProcessingResult]) -> Vec<DomainReport> {
+        let mut reports = DomainReports::new();
+        for result in results {
+            reports.add(result);
+        }
+        reports.finish()
+    }
+}
+
+/// Counts gathered for one domain while results are added.
+#[derive(Debug, Default)]
+struct DomainTally {
+    contacts: usize,
+    resolved: usize,
+    conventions: HashMap<&'static str, usize>,
+    catch_all: Option<bool>,
+    mx_hosts: HashMap<String, usize>,
+    mx_providers: HashMap<String, usize>,
+}
+
+/// Accumulates [`DomainReport`]s from results as they arrive.
+#[derive(Debug, Default)]
+pub struct DomainReports {
+    domains: BTreeMap<String, DomainTally>,
+}
+
+impl DomainReports {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds one contact's result to its domain's report.
+    pub fn add(&mut self, result: &ProcessingResult) {
+        let contact = &result.contact_input;
+        let Some(raw_domain) = contact
+            .domain
+            .as_deref()
+            .or(contact.company_domain.as_deref())
+            .map(str::trim)
+            .filter(|domain| !domain.is_empty())
+        else {
+            return;
+        };
+        let domain = get_domain_from_url(raw_domain).unwrap_or_else(|_| raw_domain.to_lowercase());
+        let tally = self.domains.entry(domain).or_default();
+        tally.contacts += 1;
+
+        if let Some(ref email) = result.email {
+            tally.resolved += 1;
+            let convention = infer_naming_convention(
+                contact.first_name.as_deref().unwrap_or(""),
+                contact.last_name.as_deref().unwrap_or(""),
+                email,
+            );
+            if let Some(convention) = convention {
+                *tally.conventions.entry(convention).or_default() += 1;
+            }
+        }
+
+        let Some(ref discovery) = result.email_discovery_results else {
+            return;
+        };
+        let skipped_as_catch_all = discovery
+            .skipped_methods
+            .iter()
+            .any(|skipped| skipped.reason.contains("catch-all"));
+        for found in &discovery.found_emails {
+            if let Some(ref host) = found.mx_host {
+                *tally.mx_hosts.entry(host.clone()).or_default() += 1;
+                if found.verification_status.is_some() && tally.catch_all.is_none() {
+                    tally.catch_all = Some(false);
+                }
+            }
+            if found
+                .verification_message
+                .to_lowercase()
+                .contains("catch-all")
+            {
+                tally.catch_all = Some(true);
+            }
+        }
+        if skipped_as_catch_all {
+            tally.catch_all = Some(true);
+        }
+        if let Some(ref provider) = discovery.mx_provider {
+            *tally.mx_providers.entry(provider.clone()).or_default() += 1;
+        }
+        if let Some(server) = discovery
+            .trace
+            .as_ref()
+            .and_then(|trace| trace.mail_server.as_ref())
+        {
+            tally.mx_hosts.entry(server.exchange.clone()).or_default();
+        }
+    }
+
+    /// The reports gathered so far, sorted by domain.
+    pub fn finish(self) -> Vec<DomainReport> {
+        self.domains
+            .into_iter()
+            .map(|(domain, tally)| {
+                let (naming_convention, naming_convention_matches) = most_common(
+                    tally
+                        .conventions
+                        .into_iter()
+                        .map(|(convention, count)| (convention.to_string(), count)),
+                )
+                .map_or((None, 0), |(convention, count)| (Some(convention), count));
+                let mx_host = most_common(tally.mx_hosts.into_iter()).map(|(host, _)| host);
+                let mx_provider =
+                    most_common(tally.mx_providers.into_iter()).map(|(provider, _)| provider);
+                DomainReport {
+                    domain,
+                    contacts: tally.contacts,
+                    resolved: tally.resolved,
+                    naming_convention,
+                    naming_convention_matches,
+                    catch_all: tally.catch_all,
+                    mx_host,
+                    mx_provider,
+                }
+            })
+            .collect()
+    }
+}
+
+/// The entry with the highest count; ties go to the alphabetically first one, so the
+/// report does not depend on the order results arrived in.
+fn most_common(counts: impl Iterator<Item = (String, usize)>) -> Option<(String, usize)> {
+    counts.max_by(|(a, a_count), (b, b_count)| a_count.cmp(b_count).then_with(|| b.cmp(a)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::{Contact, EmailResult, FoundEmailData, ResolutionStage};
+
+    fn result(first: &str, last: &str, domain: &str, email: Option<&str>) -> ProcessingResult {
+        let mx_provider = email.map(|_| "google".to_string());
+        let found_emails = email
+            .map(|email| FoundEmailData {
+                email: email.to_string(),
+                confidence: 9,
+                source: "pattern".to_string(),
+                is_generic: false,
+                verification_status: Some(true),
+                verification_message: "SMTP Verified: 250 OK".to_string(),
+                stage: ResolutionStage::PatternSmtp,
+                smtp_signals: None,
+                verified_at: None,
+                mx_host: Some("aspmx.l.google.com".to_string()),
+                likely_distribution_list: false,
+                corroborated_by: Vec::new(),
+                off_domain: false,
+                normalized_from: None,
+            })
+            .into_iter()
+            .collect();
+        ProcessingResult {
+            contact_input: Contact {
+                first_name: Some(first.to_string()),
+                last_name: Some(last.to_string()),
+                domain: Some(domain.to_string()),
+                ..Default::default()
+            },
+            email_discovery_results: Some(EmailResult {
+                found_emails,
+                mx_provider,
+                ..Default::default()
+            }),
+            email: email.map(str::to_string),
+            email_confidence: email.map(|_| 9),
+            email_verification_method: None,
+            email_alternatives: Vec::new(),
+            email_finding_skipped: false,
+            email_finding_reason: None,
+            email_verification_failed: false,
+            email_verification_inconclusive: false,
+            email_finding_error: None,
+            email_finding_error_code: None,
+            debug_log: Vec::new(),
+            run_metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_domain_report_rolls_up_per_domain() {
+        let results = vec![
+            result("John", "Doe", "acme.com", Some("john.doe@acme.com")),
+            result(
+                "Jane",
+                "Roe",
+                "https://acme.com/about",
+                Some("jane.roe@acme.com"),
+            ),
+            result(
+                "Max",
+                "Mustermann",
+                "acme.com",
+                Some("mmustermann@acme.com"),
+            ),
+            result("Ann", "Lee", "acme.com", None),
+            result("Bob", "Ray", "other.org", None),
+        ];
+
+        let reports = DomainReport::from_results(&results);
+
+        assert_eq!(reports.len(), 2);
+        let acme = &reports[0];
+        assert_eq!(acme.domain, "acme.com");
+        assert_eq!(acme.contacts, 4);
+        assert_eq!(acme.resolved, 3);
+        assert_eq!(acme.naming_convention.as_deref(), Some("{first}.{last}"));
+        assert_eq!(acme.naming_convention_matches, 2);
+        assert_eq!(acme.catch_all, Some(false));
+        assert_eq!(acme.mx_host.as_deref(), Some("aspmx.l.google.com"));
+        assert_eq!(acme.mx_provider.as_deref(), Some("google"));
+
+        let other = &reports[1];
+        assert_eq!(other.domain, "other.org");
+        assert_eq!(other.resolved, 0);
+        assert_eq!(other.naming_convention, None);
+        assert_eq!(other.catch_all, None);
+        assert_eq!(other.mx_provider, None);
+    }
+}
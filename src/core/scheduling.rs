@@ -0,0 +1,138 @@
+//! Domain-fair ordering of contacts for the batch runners.
+//!
+//! With `max_concurrency` alone, a batch sorted by domain spends the whole concurrency
+//! budget on one domain's mail server before moving on. [`DomainFairQueue`] instead hands
+//! out the next contact from whichever domain has the fewest contacts in flight.
+
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+/// Pending work for one domain.
+struct DomainQueue<T> {
+    /// Items in arrival order, with their arrival sequence number.
+    items: VecDeque<(u64, T)>,
+    in_flight: usize,
+}
+
+/// A queue that hands out items from the domain with the fewest items in flight, oldest
+/// first among equals. Within a domain, items come out in the order they were pushed.
+///
+/// Callers report finished items with [`DomainFairQueue::finish`]. Pushing every item
+/// under the same key makes it a plain FIFO queue.
+pub(crate) struct DomainFairQueue<T> {
+    domains: HashMap<String, DomainQueue<T>>,
+    /// Domains with pending items, keyed by (in flight, sequence of the oldest pending item).
+    ready: BTreeSet<(usize, u64, String)>,
+    next_seq: u64,
+    pending: usize,
+}
+
+impl<T> DomainFairQueue<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            domains: HashMap::new(),
+            ready: BTreeSet::new(),
+            next_seq: 0,
+            pending: 0,
+        }
+    }
+
+    /// Number of items waiting to be handed out.
+    pub(crate) fn pending(&self) -> usize {
+        self.pending
+    }
+
+    /// Queues `item` under `domain`.
+    pub(crate) fn push(&mut self, domain: &str, item: T) {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.pending += 1;
+        let queue = self
+            .domains
+            .entry(domain.to_string())
+            .or_insert_with(|| DomainQueue {
+                items: VecDeque::new(),
+                in_flight: 0,
+            });
+        if queue.items.is_empty() {
+            self.ready
+                .insert((queue.in_flight, seq, domain.to_string()));
+        }
+        queue.items.push_back((seq, item));
+    }
+
+    /// Takes the next item and counts it as in flight for its domain, which is returned
+    /// with it.
+    pub(crate) fn pop(&mut self) -> Option<(String, T)> {
+        let (_, _, domain) = self.ready.pop_first()?;
+        let queue = self.domains.get_mut(&domain)?;
+        let (_, item) = queue.items.pop_front()?;
+        queue.in_flight += 1;
+        self.pending -= 1;
+        if let Some((head, _)) = queue.items.front() {
+            self.ready.insert((queue.in_flight, *head, domain.clone()));
+        }
+        Some((domain, item))
+    }
+
+    /// Records that an item handed out for `domain` has finished.
+    pub(crate) fn finish(&mut self, domain: &str) {
+        let Some(queue) = self.domains.get_mut(domain) else {
+            return;
+        };
+        if let Some((head, _)) = queue.items.front() {
+            let head = *head;
+            self.ready
+                .remove(&(queue.in_flight, head, domain.to_string()));
+            queue.in_flight = queue.in_flight.saturating_sub(1);
+            self.ready
+                .insert((queue.in_flight, head, domain.to_string()));
+        } else {
+            queue.in_flight = queue.in_flight.saturating_sub(1);
+            if queue.in_flight == 0 {
+                self.domains.remove(domain);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_domain_fair_queue_interleaves_domains() {
+        let mut queue = DomainFairQueue::new();
+        for (domain, item) in [
+            ("a.com", 1),
+            ("a.com", 2),
+            ("a.com", 3),
+            ("b.com", 4),
+            ("b.com", 5),
+            ("c.com", 6),
+        ] {
+            queue.push(domain, item);
+        }
+
+        let started: Vec<i32> = (0..3).map(|_| queue.pop().unwrap().1).collect();
+        assert_eq!(started, vec![1, 4, 6]);
+
+        // a.com's contact finishes first, so a.com has the fewest in flight and goes next.
+        queue.finish("a.com");
+        assert_eq!(queue.pop(), Some(("a.com".to_string(), 2)));
+        // Now a.com and b.com both have one in flight; the older pending contact wins.
+        assert_eq!(queue.pop(), Some(("a.com".to_string(), 3)));
+        assert_eq!(queue.pop(), Some(("b.com".to_string(), 5)));
+        assert_eq!(queue.pop(), None);
+        assert_eq!(queue.pending(), 0);
+    }
+
+    #[test]
+    fn test_domain_fair_queue_single_key_is_fifo() {
+        let mut queue = DomainFairQueue::new();
+        for item in 0..5 {
+            queue.push("", item);
+        }
+        let order: Vec<i32> = std::iter::from_fn(|| queue.pop().map(|(_, item)| item)).collect();
+        assert_eq!(order, vec![0, 1, 2, 3, 4]);
+    }
+}
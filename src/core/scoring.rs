@@ -0,0 +1,104 @@
+//! Candidate confidence scoring.
+//!
+//! Every candidate address gets a confidence from 0 to 10. [`CandidateScorer`] decides it:
+//! [`initial_score`](CandidateScorer::initial_score) sets the starting point before any
+//! verification stage runs, the stages then adjust it, and
+//! [`final_score`](CandidateScorer::final_score) has the last word once they are done.
+//! [`EmailSleuth::with_scorer`](crate::EmailSleuth::with_scorer) installs a custom scorer,
+//! for example one backed by a trained model; [`DefaultScorer`] keeps the built-in policy.
+
+use crate::core::stage::StageEvidence;
+
+/// What is known about a candidate from its address alone.
+#[derive(Debug, Clone, Copy)]
+pub struct CandidateSignals<'a> {
+    /// The candidate address.
+    pub email: &'a str,
+    /// The contact's first or last name appears in the local part.
+    pub name_in_email: bool,
+    /// Penalty for a generic prefix such as `info@` (see `generic_email_prefixes`);
+    /// 0 if the prefix is not generic.
+    pub generic_penalty: u8,
+    /// The domain has a mail server.
+    pub mx_exists: bool,
+    /// The address follows the naming convention known for the domain.
+    pub matches_convention: bool,
+    /// The domain was known to accept mail for any address before this candidate was checked.
+    pub known_catch_all: bool,
+}
+
+/// Decides the confidence of candidate addresses.
+///
+/// Both methods have defaults that implement the built-in policy, so an implementation
+/// only overrides the part it wants to change.
+pub trait CandidateScorer: Send + Sync {
+    /// Confidence (0-10) before any verification stage runs.
+    ///
+    /// The default starts at 2 for a domain with a mail server (0 without one), adds 1 when
+    /// the name appears in the address and subtracts the generic prefix penalty. Whatever the
+    /// scorer, a candidate matching the domain's naming convention then gets 1 more before
+    /// verification starts.
+    fn initial_score(&self, signals: &CandidateSignals<'_>) -> i16 {
+        let mut score: i16 = if signals.mx_exists { 2 } else { 0 };
+        if signals.name_in_email {
+            score += 1;
+        }
+        if signals.generic_penalty > 0 {
+            score = (score - signals.generic_penalty as i16).max(0);
+        }
+        score.clamp(0, 10)
+    }
+
+    /// Confidence (0-10) once verification is done. A candidate scored 0 is discarded,
+    /// unless it was confirmed not to exist.
+    ///
+    /// `evidence.confidence` is the score the stages arrived at from
+    /// [`initial_score`](CandidateScorer::initial_score); `corroborated_by` lists the methods
+    /// that confirmed the address exists. The default returns `evidence.confidence` as is.
+    fn final_score(
+        &self,
+        signals: &CandidateSignals<'_>,
+        evidence: &StageEvidence,
+        corroborated_by: &[String],
+    ) -> u8 {
+        let _ = (signals, corroborated_by);
+        evidence.confidence
+    }
+}
+
+/// The built-in scoring policy.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultScorer;
+
+impl CandidateScorer for DefaultScorer {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signals(
+        name_in_email: bool,
+        generic_penalty: u8,
+        mx_exists: bool,
+    ) -> CandidateSignals<'static> {
+        CandidateSignals {
+            email: "john.doe@example.com",
+            name_in_email,
+            generic_penalty,
+            mx_exists,
+            matches_convention: false,
+            known_catch_all: false,
+        }
+    }
+
+    #[test]
+    fn test_default_initial_score() {
+        let scorer = DefaultScorer;
+        assert_eq!(scorer.initial_score(&signals(true, 0, true)), 3);
+        assert_eq!(scorer.initial_score(&signals(false, 0, true)), 2);
+        assert_eq!(scorer.initial_score(&signals(true, 0, false)), 1);
+        assert_eq!(scorer.initial_score(&signals(false, 0, false)), 0);
+        assert_eq!(scorer.initial_score(&signals(false, 1, true)), 1);
+        assert_eq!(scorer.initial_score(&signals(true, 5, true)), 0);
+    }
+}
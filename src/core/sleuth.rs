@@ -1,21 +1,91 @@
-use crate::core::config::{get_random_sleep_duration, Config};
+use crate::core::config::{get_random_sleep_duration, Config, VerificationLevel};
 use crate::core::error::{AppError, Result};
-use crate::core::models::{EmailResult, FoundEmailData, ValidatedContact};
-use crate::utils::dns::{create_resolver, resolve_mail_server, MailServer};
-use crate::utils::patterns::generate_email_patterns;
-use crate::utils::smtp::SmtpVerifier;
+use crate::core::models::{
+    DecisionTrace, EmailResult, FoundEmailData, ResolutionStage, SkippedMethod, SmtpProbeSignals,
+    TraceCandidate, TraceMailServer, TraceScrapedEmail, TraceStep, ValidatedContact, Warning,
+    WarningCategory,
+};
+use crate::core::scoring::{CandidateScorer, CandidateSignals, DefaultScorer};
+use crate::core::stage::{PipelineStage, StageContext, StageEvidence};
+#[cfg(feature = "bloom")]
+use crate::utils::bloom::BloomFilter;
+use crate::utils::dns::{classify_mx_provider, create_resolver, resolve_mail_server, MailServer};
+use crate::utils::domain::{likely_typo_of, registrable_domain};
+use crate::utils::names::{name_similarity, NAME_MATCH_THRESHOLD};
+use crate::utils::patterns::{
+    apply_naming_convention, generate_email_patterns, infer_naming_convention, normalize_email,
+    pattern_rank,
+};
+use crate::utils::scraping::{
+    build_http_client, robots_disallowed_paths, scrape_website_for_emails, ScrapedEmail,
+};
+#[cfg(feature = "smtp")]
+use crate::utils::smtp::{random_probe_local_part, SmtpVerifier};
+use crate::verification::headless::HeadlessProvider;
 use crate::verification::{api as verification_api, headless as verification_headless};
 
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
 use reqwest::Client;
 use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
+use tokio::sync::{OnceCell, Semaphore};
 use tokio::time::sleep;
-use tracing;
 use trust_dns_resolver::TokioAsyncResolver;
+use url::Url;
+
+/// Local parts that are typically distribution lists.
+const DISTRIBUTION_LIST_NAMES: &[&str] = &[
+    "all",
+    "allstaff",
+    "all-staff",
+    "all.staff",
+    "everyone",
+    "staff",
+    "team",
+    "employees",
+    "list",
+    "group",
+];
+
+/// Local-part prefixes that typically name a distribution list (e.g. `dl-sales`, `all-hands`).
+const DISTRIBUTION_LIST_PREFIXES: &[&str] =
+    &["all-", "all.", "all_", "dl-", "dl.", "list-", "group-"];
+
+/// Confidence reported for a `return_best_guess_on_failure` guess.
+const BEST_GUESS_CONFIDENCE: u8 = 1;
+
+/// Verification message given to a `return_best_guess_on_failure` guess.
+const BEST_GUESS_MESSAGE: &str = "Guessed, unverified";
+
+/// Confidence penalty for a scraped address at another domain than the contact's.
+const OFF_DOMAIN_PENALTY: i16 = 2;
+
+/// Finds the first SMTP reply code (e.g. 550) in an SMTP result message, for traces.
+fn smtp_reply_code(message: &str) -> Option<u16> {
+    message
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|part| part.len() == 3 && matches!(part.as_bytes()[0], b'2' | b'4' | b'5'))
+        .find_map(|part| part.parse().ok())
+}
+
+/// Whether `email` is at another registrable domain than the contact's.
+fn is_off_domain(email: &str, contact: &ValidatedContact) -> bool {
+    let email_domain = email.rsplit('@').next().unwrap_or("").to_lowercase();
+    email_domain != contact.domain
+        && registrable_domain(&email_domain) != registrable_domain(&contact.domain)
+}
+
+/// Adds the time since `started` to `stage` in a result's `stage_timings`.
+fn record_stage(timings: &mut HashMap<String, Duration>, stage: &str, started: Instant) {
+    *timings.entry(stage.to_string()).or_default() += started.elapsed();
+}
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum ProviderType {
+pub(crate) enum ProviderType {
     Gmail,
     HotmailB2C,
     M365,
@@ -31,6 +101,30 @@ struct VerificationAttemptOutcome {
     confidence_boost: i16,
     definitive: bool,
     is_catch_all: bool,
+    stage: ResolutionStage,
+    smtp_signals: Option<SmtpProbeSignals>,
+    verified_at: Option<DateTime<Utc>>,
+    mx_host: Option<String>,
+}
+
+/// Addresses found on each website, keyed by site.
+type ScrapedSites = parking_lot::Mutex<HashMap<String, Arc<OnceCell<Vec<ScrapedEmail>>>>>;
+
+/// The robots.txt `Disallow` paths of each site, keyed by origin.
+type RobotsRules = parking_lot::Mutex<HashMap<String, Arc<OnceCell<Vec<String>>>>>;
+
+/// One contact's verification run: what each candidate check reads, and the parts of the
+/// result it records into.
+struct CandidateRun<'a> {
+    config: &'a Config,
+    contact: &'a ValidatedContact,
+    mail_server_info: &'a Option<MailServer>,
+    provider_type: Option<ProviderType>,
+    task_label: &'a str,
+    verification_log: &'a mut HashMap<String, String>,
+    methods_used: &'a mut Vec<String>,
+    stage_timings: &'a mut HashMap<String, Duration>,
+    trace: Option<&'a mut DecisionTrace>,
 }
 
 /// The main struct orchestrating the email discovery and verification process.
@@ -38,37 +132,456 @@ struct VerificationAttemptOutcome {
 pub struct EmailSleuth {
     http_client: Arc<Client>,
     dns_resolver: Arc<TokioAsyncResolver>,
+    dns_semaphore: Arc<Semaphore>,
+    mx_cache: Arc<parking_lot::RwLock<HashMap<String, MailServer>>>,
+    /// Domains whose lookup found them nonexistent or without mail records, with when and
+    /// why, for `skip_dead_domains`.
+    dead_domains: Arc<parking_lot::RwLock<HashMap<String, (Instant, &'static str)>>>,
+    #[cfg(feature = "smtp")]
     smtp_verifier: SmtpVerifier,
     catch_all_domains: Arc<parking_lot::RwLock<HashSet<String>>>,
+    /// Catch-all status determined once per domain by [`EmailSleuth::settle_domain`]; these
+    /// domains keep their status across contacts instead of being re-detected.
+    settled_catch_all: Arc<parking_lot::RwLock<HashMap<String, bool>>>,
+    /// SMTP probes sent to each domain so far, for `max_probes_per_domain`.
+    domain_probes: Arc<parking_lot::Mutex<HashMap<String, usize>>>,
+    /// One warmup per domain (see [`EmailSleuth::warm_domain`]); contacts arriving while it
+    /// runs wait on the same cell instead of starting their own.
+    domain_warmups: Arc<parking_lot::Mutex<HashMap<String, Arc<OnceCell<()>>>>>,
+    /// Addresses found on each website, scraped once per run and shared by every contact
+    /// at it; contacts arriving while a scrape runs wait on the same cell.
+    scraped_sites: Arc<ScrapedSites>,
+    /// Each site's robots.txt rules, fetched once per run (during the domain warmup when
+    /// possible) when `respect_robots_txt` is set.
+    robots_rules: Arc<RobotsRules>,
+    /// Addresses scraped so far in the run, in a fixed-size filter instead of full strings,
+    /// for telling which of a site's addresses were already found on an earlier site.
+    #[cfg(feature = "bloom")]
+    seen_scraped: Arc<parking_lot::Mutex<BloomFilter>>,
+    /// Naming convention (e.g. `{first}.{last}`) inferred per domain from confirmed addresses.
+    domain_conventions: Arc<parking_lot::RwLock<HashMap<String, &'static str>>>,
+    /// WebDriver endpoints for headless checks, used round-robin.
+    webdriver_urls: Arc<Vec<String>>,
+    next_webdriver: Arc<AtomicUsize>,
+    /// Non-fatal issues collected since the last [`EmailSleuth::take_warnings`].
+    warnings: Arc<parking_lot::Mutex<Vec<Warning>>>,
+    /// The stages each candidate runs through, in order, when set with
+    /// [`EmailSleuth::with_pipeline`]; otherwise they follow `stage_order`.
+    pipeline: Option<Arc<Vec<PipelineStage>>>,
+    /// Decides each candidate's confidence.
+    scorer: Arc<dyn CandidateScorer>,
 }
 
 impl EmailSleuth {
     /// Creates a new EmailSleuth instance.
     pub(crate) async fn new(config: &Config) -> Result<Self> {
         tracing::debug!("Initializing EmailSleuth components...");
-        let http_client = Arc::new(
-            Client::builder()
-                .user_agent(&config.user_agent)
-                .timeout(config.request_timeout)
-                .build()
-                .map_err(|e| {
-                    AppError::Initialization(format!("Failed to build HTTP client: {}", e))
-                })?,
-        );
+        let http_client = build_http_client(config)?;
         tracing::debug!("HTTP client initialized.");
-        let dns_resolver = Arc::new(create_resolver(config).await?);
-        tracing::debug!("DNS resolver initialized.");
+        Self::with_http_client(config, http_client).await
+    }
+
+    /// Creates a new EmailSleuth instance that uses `http_client` for scraping and API checks.
+    pub(crate) async fn with_http_client(config: &Config, http_client: Client) -> Result<Self> {
+        Self::with_clients(config, http_client, None).await
+    }
 
+    /// Creates a new EmailSleuth instance that uses `http_client` for scraping and API checks
+    /// and `dns_resolver` for mail server lookups. Without a resolver, one is built from
+    /// `dns_servers`, `dns_timeout` and the other DNS settings.
+    pub(crate) async fn with_clients(
+        config: &Config,
+        http_client: Client,
+        dns_resolver: Option<TokioAsyncResolver>,
+    ) -> Result<Self> {
+        let http_client = Arc::new(http_client);
+        let dns_resolver = match dns_resolver {
+            Some(resolver) => {
+                tracing::debug!("Using the supplied DNS resolver; dns_servers is ignored.");
+                Arc::new(resolver)
+            }
+            None => {
+                let resolver = create_resolver(config).await?;
+                tracing::debug!("DNS resolver initialized.");
+                Arc::new(resolver)
+            }
+        };
+
+        #[cfg(feature = "smtp")]
         let smtp_verifier = SmtpVerifier::new(Arc::new(config.clone()));
+        #[cfg(feature = "smtp")]
         tracing::debug!("SMTP verifier initialized.");
 
+        // Overrides are seeded into the MX cache so they are used before any DNS lookup.
+        let mut mx_cache = HashMap::new();
+        for (domain, hosts) in &config.dns_overrides {
+            if let Some(host) = hosts.first() {
+                tracing::info!("Using DNS override for {}: mail server {}", domain, host);
+                mx_cache.insert(
+                    domain.clone(),
+                    MailServer {
+                        exchange: host.clone(),
+                        preference: 0,
+                        implicit: false,
+                        backups: hosts[1..].to_vec(),
+                    },
+                );
+            }
+        }
+
+        #[cfg(feature = "bloom")]
+        let seen_scraped = BloomFilter::new(
+            config.dedup_expected_items,
+            config.dedup_false_positive_rate,
+        );
+        #[cfg(feature = "bloom")]
+        tracing::debug!(
+            "Scraped-address dedup filter initialized ({} bytes).",
+            seen_scraped.memory_bytes()
+        );
+
         tracing::info!("EmailSleuth initialized successfully.");
         Ok(Self {
             http_client,
             dns_resolver,
+            dns_semaphore: Arc::new(Semaphore::new(config.max_dns_concurrency.max(1))),
+            mx_cache: Arc::new(parking_lot::RwLock::new(mx_cache)),
+            dead_domains: Arc::new(parking_lot::RwLock::new(HashMap::new())),
+            #[cfg(feature = "smtp")]
             smtp_verifier,
             catch_all_domains: Arc::new(parking_lot::RwLock::new(HashSet::new())),
+            settled_catch_all: Arc::new(parking_lot::RwLock::new(HashMap::new())),
+            domain_probes: Arc::new(parking_lot::Mutex::new(HashMap::new())),
+            domain_warmups: Arc::new(parking_lot::Mutex::new(HashMap::new())),
+            scraped_sites: Arc::new(parking_lot::Mutex::new(HashMap::new())),
+            robots_rules: Arc::new(parking_lot::Mutex::new(HashMap::new())),
+            #[cfg(feature = "bloom")]
+            seen_scraped: Arc::new(parking_lot::Mutex::new(seen_scraped)),
+            domain_conventions: Arc::new(parking_lot::RwLock::new(HashMap::new())),
+            webdriver_urls: Arc::new(config.webdriver_urls()),
+            next_webdriver: Arc::new(AtomicUsize::new(0)),
+            warnings: Arc::new(parking_lot::Mutex::new(Vec::new())),
+            pipeline: None,
+            scorer: Arc::new(DefaultScorer),
+        })
+    }
+
+    /// Replaces the per-candidate verification pipeline, e.g. to run a
+    /// [`VerificationStage`](crate::VerificationStage) between the alternative checks and SMTP.
+    ///
+    /// The pipeline replaces `stage_order`, and built-in stages left out of it do not run.
+    /// A pipeline that names neither [`PipelineStage::Scrape`] nor [`PipelineStage::Dns`]
+    /// runs both first, as the default order does. Verification stops at the first stage
+    /// with a definitive answer.
+    pub fn with_pipeline(mut self, mut pipeline: Vec<PipelineStage>) -> Self {
+        if !pipeline.iter().any(PipelineStage::is_per_contact) {
+            pipeline.splice(0..0, [PipelineStage::Scrape, PipelineStage::Dns]);
+        }
+        self.pipeline = Some(Arc::new(pipeline));
+        self
+    }
+
+    /// The pipeline: the one set with [`EmailSleuth::with_pipeline`], or the built-in
+    /// stages in `stage_order`.
+    fn pipeline(&self, config: &Config) -> Arc<Vec<PipelineStage>> {
+        self.pipeline
+            .clone()
+            .unwrap_or_else(|| Arc::new(PipelineStage::for_stage_order(&config.stage_order)))
+    }
+
+    /// Whether the pipeline looks up the contact's mail server.
+    pub(crate) fn runs_dns(&self, config: &Config) -> bool {
+        self.pipeline(config)
+            .iter()
+            .any(|stage| matches!(stage, PipelineStage::Dns))
+    }
+
+    /// Replaces the confidence scoring policy, e.g. with a model-based
+    /// [`CandidateScorer`](crate::CandidateScorer). The default is [`DefaultScorer`](crate::DefaultScorer).
+    pub fn with_scorer(mut self, scorer: Arc<dyn CandidateScorer>) -> Self {
+        self.scorer = scorer;
+        self
+    }
+
+    /// Removes and returns the warnings collected so far: non-fatal issues such as pages
+    /// that failed to load or blocked headless checks, which are otherwise only logged.
+    ///
+    /// Clones of this `EmailSleuth` share one collection.
+    pub fn take_warnings(&self) -> Vec<Warning> {
+        std::mem::take(&mut *self.warnings.lock())
+    }
+
+    /// Closes every WebDriver session still open, e.g. those of headless checks that were
+    /// cancelled before they could close their own. Call it once processing has finished
+    /// or been aborted so no browser processes are left behind under ChromeDriver.
+    ///
+    /// Returns the number of sessions closed. Sessions are tracked process-wide, so this
+    /// also closes sessions opened through other `EmailSleuth` instances.
+    pub async fn shutdown(&self) -> usize {
+        verification_headless::close_open_sessions().await
+    }
+
+    /// Adds a warning from outside the sleuth, e.g. one met while starting services, so it
+    /// is returned by [`EmailSleuth::take_warnings`] with the rest.
+    pub fn add_warning(&self, warning: Warning) {
+        let mut warnings = self.warnings.lock();
+        if !warnings.contains(&warning) {
+            warnings.push(warning);
+        }
+    }
+
+    /// Records a warning, tied to `contact` when given.
+    pub(crate) fn record_warning(
+        &self,
+        category: WarningCategory,
+        message: String,
+        contact: Option<&ValidatedContact>,
+    ) {
+        self.add_warning(Warning {
+            category,
+            message,
+            contact: contact.map(|c| c.full_name.clone()),
+            domain: contact.map(|c| c.domain.clone()),
+        });
+    }
+
+    /// Records a warning about `domain` that is not tied to one contact.
+    #[cfg(feature = "smtp")]
+    pub(crate) fn record_domain_warning(
+        &self,
+        category: WarningCategory,
+        message: String,
+        domain: &str,
+    ) {
+        self.add_warning(Warning {
+            category,
+            message,
+            contact: None,
+            domain: Some(domain.to_string()),
+        });
+    }
+
+    /// Resolves the mail server for each distinct domain up front and stores it in the MX cache,
+    /// so later verification does not wait on DNS. Lookups are bounded by `max_dns_concurrency`.
+    pub(crate) async fn prefetch_mail_servers(&self, domains: impl IntoIterator<Item = String>) {
+        let unique: HashSet<String> = domains.into_iter().collect();
+        if unique.is_empty() {
+            return;
+        }
+        tracing::info!("Pre-resolving mail servers for {} domains...", unique.len());
+        let start_time = Instant::now();
+
+        stream::iter(unique)
+            .for_each_concurrent(None, |domain| async move {
+                if let Err(e) = self.lookup_mail_server(&domain).await {
+                    tracing::debug!("Pre-resolution failed for {}: {}", domain, e);
+                }
+            })
+            .await;
+
+        tracing::info!(
+            "Pre-resolved {} mail servers in {:.2?}.",
+            self.mx_cache.read().len(),
+            start_time.elapsed()
+        );
+    }
+
+    /// Does the domain-level work for `domain` once: resolves its mail server and, when SMTP
+    /// probing applies, probes a random address to learn whether it is a catch-all. Contacts
+    /// processed afterwards reuse both instead of re-detecting catch-all per contact.
+    ///
+    /// Returns the catch-all status, or `None` if it could not be determined.
+    async fn settle_domain(&self, config: &Config, domain: &str) -> Option<bool> {
+        let mail_server = match self.lookup_mail_server(domain).await {
+            Ok(mail_server) => mail_server,
+            Err(e) => {
+                tracing::warn!("Could not resolve a mail server for {}: {}", domain, e);
+                return None;
+            }
+        };
+        if config.verification_level == VerificationLevel::MxOnly
+            || config.smtp_skipped_tld(domain).is_some()
+        {
+            return None;
+        }
+
+        if !self.take_probe(config, domain) {
+            return None;
+        }
+        let catch_all = self.probe_catch_all(config, domain, &mail_server).await?;
+        tracing::info!(
+            "Domain {} (MX: {}) is {}a catch-all; sharing this with its contacts.",
+            domain,
+            mail_server.exchange,
+            if catch_all { "" } else { "not " }
+        );
+        self.settled_catch_all
+            .write()
+            .insert(domain.to_string(), catch_all);
+        if catch_all {
+            self.catch_all_domains.write().insert(domain.to_string());
+        }
+        Some(catch_all)
+    }
+
+    /// Counts an SMTP probe against `domain`'s `max_probes_per_domain` budget. Returns
+    /// false, without counting, once the budget is used up.
+    fn take_probe(&self, config: &Config, domain: &str) -> bool {
+        if config.max_probes_per_domain == 0 {
+            return true;
+        }
+        let mut probes = self.domain_probes.lock();
+        let count = probes.entry(domain.to_string()).or_default();
+        if *count >= config.max_probes_per_domain {
+            return false;
+        }
+        *count += 1;
+        if *count == config.max_probes_per_domain {
+            tracing::warn!(
+                "Reached max_probes_per_domain ({}) for {}; its remaining candidates will not be probed over SMTP.",
+                config.max_probes_per_domain,
+                domain
+            );
+        }
+        true
+    }
+
+    /// Whether `domain` has used up its `max_probes_per_domain` budget.
+    fn probe_budget_exhausted(&self, config: &Config, domain: &str) -> bool {
+        config.max_probes_per_domain > 0
+            && self.domain_probes.lock().get(domain).copied().unwrap_or(0)
+                >= config.max_probes_per_domain
+    }
+
+    /// Probes a random, certainly-invalid address on `domain`: if it is accepted the domain
+    /// is a catch-all.
+    #[cfg(feature = "smtp")]
+    async fn probe_catch_all(
+        &self,
+        config: &Config,
+        domain: &str,
+        mail_server: &MailServer,
+    ) -> Option<bool> {
+        let probe = format!("{}@{}", random_probe_local_part(config), domain);
+        let result = self
+            .smtp_verifier
+            .verify_with_retries(&probe, domain, &mail_server.exchange)
+            .await;
+        match result.exists {
+            Some(true) => Some(true),
+            Some(false) => {
+                self.smtp_verifier.mark_not_catch_all(domain);
+                Some(false)
+            }
+            None if result.message.to_lowercase().contains("catch-all") => Some(true),
+            None => None,
+        }
+    }
+
+    /// Stand-in for builds without the `smtp` feature: catch-all status stays unknown.
+    #[cfg(not(feature = "smtp"))]
+    async fn probe_catch_all(
+        &self,
+        _config: &Config,
+        _domain: &str,
+        _mail_server: &MailServer,
+    ) -> Option<bool> {
+        None
+    }
+
+    /// Warms up `domain` the first time any contact at it is processed, so the per-candidate
+    /// work finds its mail server (and, with `smtp_prefetch_catch_all`, its catch-all
+    /// status) already cached, and with `respect_robots_txt` the robots.txt of `website`
+    /// too; these run concurrently. Concurrent contacts at the same domain wait for the one
+    /// warmup in flight; later ones return at once.
+    pub(crate) async fn warm_domain(&self, config: &Config, domain: &str, website: &Url) {
+        let cell = Arc::clone(
+            self.domain_warmups
+                .lock()
+                .entry(domain.to_string())
+                .or_default(),
+        );
+        cell.get_or_init(|| async {
+            let started = Instant::now();
+            tokio::join!(
+                async {
+                    if config.smtp_prefetch_catch_all {
+                        self.settle_domain(config, domain).await;
+                    } else if let Err(e) = self.lookup_mail_server(domain).await {
+                        tracing::warn!("Could not resolve a mail server for {}: {}", domain, e);
+                    }
+                },
+                self.robots_rules(config, website),
+            );
+            tracing::debug!("Warmed up domain {} in {:.2?}", domain, started.elapsed());
         })
+        .await;
+    }
+
+    /// The paths `website`'s robots.txt disallows, fetched the first time any contact needs
+    /// them and cached per site. Empty unless `respect_robots_txt` is set.
+    async fn robots_rules(&self, config: &Config, website: &Url) -> Vec<String> {
+        if !config.respect_robots_txt {
+            return Vec::new();
+        }
+        let cell = Arc::clone(
+            self.robots_rules
+                .lock()
+                .entry(website.origin().ascii_serialization())
+                .or_default(),
+        );
+        cell.get_or_init(|| robots_disallowed_paths(&self.http_client, config, website))
+            .await
+            .clone()
+    }
+
+    /// Looks up the mail server for a domain, using the MX cache when possible.
+    /// Only successful lookups are cached; a domain found not to exist or to have no mail
+    /// records is marked dead instead (see [`EmailSleuth::dead_domain`]).
+    async fn lookup_mail_server(&self, domain: &str) -> Result<MailServer> {
+        if let Some(ms) = self.mx_cache.read().get(domain) {
+            tracing::trace!("MX cache hit for {}", domain);
+            return Ok(ms.clone());
+        }
+
+        let _permit = self
+            .dns_semaphore
+            .acquire()
+            .await
+            .map_err(|e| AppError::Initialization(format!("DNS semaphore closed: {}", e)))?;
+        let ms = match resolve_mail_server(&self.dns_resolver, domain).await {
+            Ok(ms) => ms,
+            Err(e) => {
+                let reason = match e {
+                    AppError::NxDomain(_) => Some("does not exist (NXDOMAIN)"),
+                    AppError::NoDnsRecords(_) => Some("has no MX or A/AAAA records"),
+                    _ => None,
+                };
+                if let Some(reason) = reason {
+                    self.dead_domains
+                        .write()
+                        .insert(domain.to_string(), (Instant::now(), reason));
+                }
+                return Err(e);
+            }
+        };
+        self.dead_domains.write().remove(domain);
+        self.mx_cache.write().insert(domain.to_string(), ms.clone());
+        Ok(ms)
+    }
+
+    /// Why `domain` is dead, if a lookup in the last `dead_domain_ttl` found that it does not
+    /// exist or has no mail records. Expired marks are dropped, so the domain's next contact
+    /// looks it up again.
+    pub(crate) fn dead_domain(&self, config: &Config, domain: &str) -> Option<&'static str> {
+        let (marked_at, reason) = *self.dead_domains.read().get(domain)?;
+        if marked_at.elapsed() < config.dead_domain_ttl {
+            return Some(reason);
+        }
+        // The resolver built from the config never caches a negative answer for longer
+        // than `dead_domain_ttl`, so the next lookup reaches DNS.
+        self.dead_domains.write().remove(domain);
+        None
     }
 
     /// Finds and verifies email addresses for a given validated contact. (High Level)
@@ -82,48 +595,235 @@ impl EmailSleuth {
         let start_time = Instant::now();
 
         let mut email_result = EmailResult::default();
+        let mut trace = config.capture_trace.then(DecisionTrace::default);
+
+        if config.domain_typo_check {
+            if let Some(known) = likely_typo_of(&contact.domain, &config.known_domains) {
+                tracing::warn!(target: "find_email_task", "[{}] Domain {} looks like a misspelling of {}. Processing it as given.", task_label, contact.domain, known);
+                self.record_warning(
+                    WarningCategory::Input,
+                    format!(
+                        "Domain {} looks like a misspelling of {}",
+                        contact.domain, known
+                    ),
+                    Some(contact),
+                );
+                email_result.suggested_domain = Some(known.to_string());
+            }
+        }
 
         // Clear catch-all cache for this domain to ensure a fresh start FOR THIS TASK
         // Note: This ensures we don't carry over catch-all status from a *previous* contact
         // processed by the same EmailSleuth instance if that contact happened to have the same domain.
         // If multiple concurrent tasks process the *same* domain, they might still share the cache,
         // which is generally okay as catch-all status is domain-specific.
-        {
+        if !self.settled_catch_all.read().contains_key(&contact.domain) {
             let mut cache = self.catch_all_domains.write();
             cache.remove(&contact.domain);
             tracing::trace!(target: "find_email_task", "[{}] Cleared catch-all status for domain from cache (if existed).", task_label);
         }
 
-        let candidates = self.gather_candidates(config, contact, &mut email_result.methods_used);
+        let pipeline = self.pipeline(config);
+        let scrape_position = pipeline
+            .iter()
+            .position(|stage| matches!(stage, PipelineStage::Scrape));
+        let dns_position = pipeline
+            .iter()
+            .position(|stage| matches!(stage, PipelineStage::Dns));
+        let first_check_position = pipeline.iter().position(|stage| !stage.is_per_contact());
+        let dns_before_scrape =
+            matches!((dns_position, scrape_position), (Some(dns), Some(scrape)) if dns < scrape);
+        let mut scraping_skipped = scrape_position
+            .is_none()
+            .then_some("not in the pipeline (stage_order)");
+
+        let mut resolved = None;
+        if dns_before_scrape {
+            self.warm_domain(config, &contact.domain, &contact.website_url)
+                .await;
+            let dns = self
+                .run_dns_stage(config, contact, &mut email_result, trace.as_mut())
+                .await;
+            if dns.0.is_none() {
+                tracing::info!(target: "find_email_task", "[{}] No mail server; skipping website scraping (dns runs before scrape).", task_label);
+                scraping_skipped = Some("no mail server (DNS lookup failed)");
+            }
+            resolved = Some(dns);
+        }
+        // With `scrape` after a verification stage, the pattern candidates are checked first
+        // and the website is only scraped if none of them is good enough.
+        let mut scrape_deferred = scraping_skipped.is_none()
+            && config.verification_level != VerificationLevel::MxOnly
+            && matches!((first_check_position, scrape_position), (Some(check), Some(scrape)) if check < scrape);
+        let scrape_now = scraping_skipped.is_none() && !scrape_deferred;
+
+        // The domain warmup (MX, catch-all probe) runs while the website is scraped.
+        let warm_now = dns_position.is_some() && !dns_before_scrape;
+        let patterns = self.pattern_candidates(config, contact, &mut email_result.methods_used);
+        let (scraped, ()) = tokio::join!(
+            async {
+                if scrape_now {
+                    self.scrape_contact_sites(
+                        config,
+                        contact,
+                        &mut email_result.stage_timings,
+                        trace.as_mut(),
+                    )
+                    .await
+                } else {
+                    Vec::new()
+                }
+            },
+            async {
+                if warm_now {
+                    self.warm_domain(config, &contact.domain, &contact.website_url)
+                        .await;
+                }
+            },
+        );
+        let (mut candidates, mut contact_addresses, mut normalized_from) = self.combine_candidates(
+            config,
+            contact,
+            &patterns,
+            scraped,
+            &mut email_result.methods_used,
+        );
+        if candidates.is_empty() && scrape_deferred {
+            // No pattern candidates to check first.
+            let scraped = self
+                .scrape_contact_sites(
+                    config,
+                    contact,
+                    &mut email_result.stage_timings,
+                    trace.as_mut(),
+                )
+                .await;
+            (candidates, contact_addresses, normalized_from) = self.combine_candidates(
+                config,
+                contact,
+                &patterns,
+                scraped,
+                &mut email_result.methods_used,
+            );
+            scrape_deferred = false;
+        }
         if candidates.is_empty() {
             tracing::warn!(target: "find_email_task", "[{}] No email candidates generated or found.", task_label);
             email_result
                 .verification_log
                 .insert(contact.domain.clone(), "No candidates found".to_string());
+            email_result.attempted_methods = email_result.methods_used.clone();
+            if let Some(reason) = scraping_skipped {
+                email_result.skipped_methods.push(SkippedMethod {
+                    method: "website_scraping".to_string(),
+                    reason: reason.to_string(),
+                });
+            }
+            email_result.skipped_methods.push(SkippedMethod {
+                method: "smtp_verification".to_string(),
+                reason: "no candidates to verify".to_string(),
+            });
+            email_result.trace = trace;
             return Ok(email_result);
         }
         tracing::info!(target: "find_email_task", "[{}] Combined {} unique candidates to assess.", task_label, candidates.len());
         tracing::trace!(target: "find_email_task", "[{}] Candidate list (ordered): {:?}", task_label, candidates);
 
-        let (mail_server_info, provider_type) = self
-            .resolve_and_identify_provider(&contact.domain, &mut email_result.verification_log)
-            .await;
+        let (mail_server_info, provider_type) = match resolved {
+            Some(resolved) => resolved,
+            None if dns_position.is_some() => {
+                self.run_dns_stage(config, contact, &mut email_result, trace.as_mut())
+                    .await
+            }
+            None => (None, None),
+        };
 
-        let verified_data = self
-            .evaluate_candidates(
+        let mut run = CandidateRun {
+            config,
+            contact,
+            mail_server_info: &mail_server_info,
+            provider_type,
+            task_label: &task_label,
+            verification_log: &mut email_result.verification_log,
+            methods_used: &mut email_result.methods_used,
+            stage_timings: &mut email_result.stage_timings,
+            trace: trace.as_mut(),
+        };
+        let mut verified_data = if config.verification_level == VerificationLevel::MxOnly {
+            self.score_candidates_mx_only(
                 config,
                 contact,
                 &candidates,
                 &mail_server_info,
-                provider_type,
-                &task_label,
-                &mut email_result.verification_log,
-                &mut email_result.methods_used,
+                run.verification_log,
+                run.methods_used,
             )
-            .await?;
+        } else {
+            self.evaluate_candidates(&mut run, &candidates, &contact_addresses)
+                .await?
+        };
+
+        if scrape_deferred {
+            if verified_data
+                .iter()
+                .any(|found| found.verdict(config).is_acceptable())
+            {
+                tracing::info!(target: "find_email_task", "[{}] A pattern candidate was verified; skipping website scraping (stage_order).", task_label);
+                scraping_skipped = Some("a pattern candidate was already verified");
+            } else {
+                let checked = candidates;
+                let scraped = self
+                    .scrape_contact_sites(
+                        config,
+                        contact,
+                        run.stage_timings,
+                        run.trace.as_deref_mut(),
+                    )
+                    .await;
+                (candidates, contact_addresses, normalized_from) =
+                    self.combine_candidates(config, contact, &patterns, scraped, run.methods_used);
+                let scraped: Vec<String> = candidates
+                    .iter()
+                    .filter(|candidate| !checked.contains(candidate))
+                    .cloned()
+                    .collect();
+                if !scraped.is_empty() {
+                    tracing::info!(target: "find_email_task", "[{}] Checking {} scraped candidates.", task_label, scraped.len());
+                    verified_data.extend(
+                        self.evaluate_candidates(&mut run, &scraped, &contact_addresses)
+                            .await?,
+                    );
+                }
+            }
+        }
 
         email_result.found_emails = verified_data;
-        self.finalize_results(config, &mut email_result);
+        for found in &mut email_result.found_emails {
+            if let Some(original) = normalized_from.get(&found.email) {
+                found.normalized_from = Some(original.clone());
+                email_result
+                    .verification_log
+                    .entry(found.email.clone())
+                    .and_modify(|entry| *entry = format!("{}; normalized from {}", entry, original))
+                    .or_insert_with(|| format!("normalized from {}", original));
+            }
+        }
+        email_result.trace = trace;
+        self.finalize_results(
+            config,
+            contact,
+            &mut email_result,
+            &candidates,
+            mail_server_info.is_some(),
+        );
+        self.record_method_coverage(
+            config,
+            contact,
+            &mail_server_info,
+            provider_type,
+            scraping_skipped,
+            &mut email_result,
+        );
 
         let total_duration = start_time.elapsed();
         tracing::info!(target: "find_email_task", "[{}] Email discovery finished in {:.2?}. Result: {:?}",
@@ -132,13 +832,99 @@ impl EmailSleuth {
         Ok(email_result)
     }
 
-    fn gather_candidates(
+    /// The `dns` stage: resolves the contact's mail server and records it, with its provider,
+    /// on `email_result` and the trace.
+    async fn run_dns_stage(
+        &self,
+        config: &Config,
+        contact: &ValidatedContact,
+        email_result: &mut EmailResult,
+        trace: Option<&mut DecisionTrace>,
+    ) -> (Option<MailServer>, Option<ProviderType>) {
+        let dns_started = Instant::now();
+        let (mail_server_info, provider_type) = self
+            .resolve_and_identify_provider(contact, &mut email_result.verification_log)
+            .await;
+        record_stage(&mut email_result.stage_timings, "dns", dns_started);
+        email_result.mx_provider = mail_server_info
+            .as_ref()
+            .map(|ms| classify_mx_provider(config, &contact.domain, &ms.exchange));
+        if let (Some(trace), Some(ms)) = (trace, mail_server_info.as_ref()) {
+            trace.mail_server = Some(TraceMailServer {
+                exchange: ms.exchange.clone(),
+                preference: ms.preference,
+                implicit: ms.implicit,
+                backups: ms.backups.clone(),
+            });
+        }
+        (mail_server_info, provider_type)
+    }
+
+    /// Scrapes `website` for addresses the first time any contact needs it and returns the
+    /// cached result afterwards. Pages that failed to load are reported once, against the
+    /// contact whose lookup scraped the site.
+    async fn scrape_site(
+        &self,
+        config: &Config,
+        contact: &ValidatedContact,
+        website: &Url,
+    ) -> Vec<ScrapedEmail> {
+        let cell = Arc::clone(
+            self.scraped_sites
+                .lock()
+                .entry(website.as_str().to_string())
+                .or_default(),
+        );
+        cell.get_or_init(|| async {
+            let webdriver_url = if config.headless_scrape_fallback {
+                self.next_webdriver_url()
+            } else {
+                None
+            };
+            let disallowed = self.robots_rules(config, website).await;
+            let mut failed_pages = Vec::new();
+            let found = scrape_website_for_emails(
+                &self.http_client,
+                config,
+                website,
+                &disallowed,
+                webdriver_url,
+                &mut failed_pages,
+            )
+            .await;
+            for failure in failed_pages {
+                self.record_warning(WarningCategory::Scraping, failure, Some(contact));
+            }
+            #[cfg(feature = "bloom")]
+            {
+                let mut seen = self.seen_scraped.lock();
+                let repeated = found
+                    .iter()
+                    .filter(|scraped| !seen.insert(&scraped.email.to_lowercase()))
+                    .count();
+                if repeated > 0 {
+                    tracing::debug!(
+                        "{} of the {} addresses on {} were already found on an earlier site.",
+                        repeated,
+                        found.len(),
+                        website
+                    );
+                }
+            }
+            found
+        })
+        .await
+        .clone()
+    }
+
+    /// The addresses generated from the contact's name.
+    fn pattern_candidates(
         &self,
         config: &Config,
         contact: &ValidatedContact,
         methods_used: &mut Vec<String>,
     ) -> Vec<String> {
-        tracing::debug!(target: "find_email_task", "Gathering candidates for {}...", contact.full_name);
+        tracing::debug!(target: "find_email_task", "Generating patterns for {}...", contact.full_name);
         let patterns = generate_email_patterns(
             config,
             &contact.first_name,
@@ -148,69 +934,253 @@ impl EmailSleuth {
         if !patterns.is_empty() && !methods_used.contains(&"pattern_generation".to_string()) {
             methods_used.push("pattern_generation".to_string());
         }
+        patterns
+    }
 
-        // #TODO: Let's look at here again.
-        // I keep this here to not break back compatibility
-        // But I think I'll remove this feature soon.
-        let scraped_emails: Vec<String> = Vec::new();
-        // if !scraped_emails.is_empty() && !methods_used.contains(&"website_scraping".to_string()) {
-        //     methods_used.push("website_scraping".to_string());
-        // }
+    /// Scrapes the contact's websites for addresses.
+    async fn scrape_contact_sites(
+        &self,
+        config: &Config,
+        contact: &ValidatedContact,
+        stage_timings: &mut HashMap<String, Duration>,
+        trace: Option<&mut DecisionTrace>,
+    ) -> Vec<ScrapedEmail> {
+        tracing::debug!(target: "find_email_task", "Scraping websites for {}...", contact.full_name);
+        let scraping_started = Instant::now();
+        let mut scraped: Vec<ScrapedEmail> = Vec::new();
+        let websites = std::iter::once(&contact.website_url).chain(&contact.additional_websites);
+        for website in websites {
+            scraped.extend(
+                self.scrape_site(config, contact, website)
+                    .await
+                    .into_iter()
+                    .filter(|found| {
+                        !config.exclude_off_domain_scraped || !is_off_domain(&found.email, contact)
+                    }),
+            );
+        }
+        record_stage(stage_timings, "scraping", scraping_started);
+        if let Some(trace) = trace {
+            trace.scraped = scraped
+                .iter()
+                .map(|found| TraceScrapedEmail {
+                    email: found.email.clone(),
+                    context: found.context.clone(),
+                })
+                .collect();
+        }
+        scraped
+    }
+
+    /// Returns the ordered candidates from `patterns` and `scraped`; the scraped addresses
+    /// that all belong to the contact when `prefer_personal_over_role` found more than one,
+    /// which are verified even after early termination so the rest are kept as
+    /// alternatives; and, for candidates whose casing was normalized, the form they were
+    /// found in.
+    fn combine_candidates(
+        &self,
+        config: &Config,
+        contact: &ValidatedContact,
+        patterns: &[String],
+        scraped: Vec<ScrapedEmail>,
+        methods_used: &mut Vec<String>,
+    ) -> (Vec<String>, Vec<String>, HashMap<String, String>) {
+        // Scraped addresses listed next to the contact's name (e.g. on a team page card),
+        // best match first.
+        let mut named: Vec<(f64, String)> = scraped
+            .iter()
+            .map(|found| {
+                let score =
+                    name_similarity(&contact.first_name, &contact.last_name, &found.context);
+                (
+                    score,
+                    normalize_email(&found.email, config.lowercase_local_part),
+                )
+            })
+            .filter(|(score, _)| *score >= NAME_MATCH_THRESHOLD)
+            .collect();
+        named.sort_by(|a, b| b.0.total_cmp(&a.0));
+        if config.prefer_personal_over_role {
+            // Stable, so each group keeps its best-match-first order.
+            named.sort_by_key(|(_, email)| self.generic_prefix_penalty(config, email) > 0);
+        } else {
+            named.truncate(1);
+        }
+        let scraped_emails: Vec<String> = scraped.into_iter().map(|found| found.email).collect();
+        if !scraped_emails.is_empty() && !methods_used.contains(&"website_scraping".to_string()) {
+            methods_used.push("website_scraping".to_string());
+        }
 
         let mut all_candidates = Vec::new();
         let mut seen_candidates = HashSet::new();
+        let mut normalized_from = HashMap::new();
         let first_lower = contact.first_name.to_lowercase();
         let last_lower = contact.last_name.to_lowercase();
 
-        let add_candidate = |email: &str, list: &mut Vec<String>, seen: &mut HashSet<String>| {
-            let lower_email = email.trim().to_lowercase();
-            if lower_email.contains('@')
-                && lower_email.contains('.')
-                && !lower_email.starts_with('@')
-                && !lower_email.ends_with('@')
-                && seen.insert(lower_email.clone())
+        // Deduplicated case-insensitively, whatever casing is kept for probing.
+        let mut add_candidate = |email: &str, list: &mut Vec<String>| {
+            let normalized = normalize_email(email, config.lowercase_local_part);
+            if normalized.contains('@')
+                && normalized.contains('.')
+                && !normalized.starts_with('@')
+                && !normalized.ends_with('@')
+                && seen_candidates.insert(normalized.to_lowercase())
             {
-                list.push(lower_email);
+                if normalized != email.trim() {
+                    normalized_from.insert(normalized.clone(), email.trim().to_string());
+                }
+                list.push(normalized);
             }
         };
 
-        for p in &patterns {
-            if p.contains(&first_lower) || p.contains(&last_lower) {
-                add_candidate(p, &mut all_candidates, &mut seen_candidates);
+        let mentions_name = |email: &str| {
+            let lower = email.to_lowercase();
+            lower.contains(&first_lower) || lower.contains(&last_lower)
+        };
+        for p in patterns {
+            if mentions_name(p) {
+                add_candidate(p, &mut all_candidates);
             }
         }
         for s in &scraped_emails {
-            if s.contains(&first_lower) || s.contains(&last_lower) {
-                add_candidate(s, &mut all_candidates, &mut seen_candidates);
+            if mentions_name(s) {
+                add_candidate(s, &mut all_candidates);
             }
         }
-        for p in &patterns {
-            if !p.contains(&first_lower) && !p.contains(&last_lower) {
-                add_candidate(p, &mut all_candidates, &mut seen_candidates);
+        for p in patterns {
+            if !mentions_name(p) {
+                add_candidate(p, &mut all_candidates);
             }
         }
         for s in &scraped_emails {
-            if !s.contains(&first_lower) && !s.contains(&last_lower) {
-                add_candidate(s, &mut all_candidates, &mut seen_candidates);
+            if !mentions_name(s) {
+                add_candidate(s, &mut all_candidates);
+            }
+        }
+
+        if let Some(expected) = self.convention_candidate(config, contact) {
+            if let Some(pos) = all_candidates.iter().position(|c| *c == expected) {
+                tracing::debug!(target: "find_email_task", "Moving {} to the front (matches known convention for {}).", expected, contact.domain);
+                let candidate = all_candidates.remove(pos);
+                all_candidates.insert(0, candidate);
+            } else if config.email_regex.is_match(&expected) {
+                // A configured template may produce an address no generated pattern covers.
+                tracing::debug!(target: "find_email_task", "Adding {} at the front (configured convention for {}).", expected, contact.domain);
+                all_candidates.insert(0, expected);
             }
         }
 
-        all_candidates
+        for (score, email) in named.iter().rev() {
+            if let Some(pos) = all_candidates.iter().position(|c| c == email) {
+                tracing::debug!(target: "find_email_task", "Moving {} to the front (listed next to '{}' on the website, name match {:.2}).", email, contact.full_name, score);
+                let candidate = all_candidates.remove(pos);
+                all_candidates.insert(0, candidate);
+            }
+        }
+
+        let contact_addresses = if config.prefer_personal_over_role && named.len() > 1 {
+            named.into_iter().map(|(_, email)| email).collect()
+        } else {
+            Vec::new()
+        };
+        (all_candidates, contact_addresses, normalized_from)
+    }
+
+    /// Picks the WebDriver endpoint for the next headless check, rotating across instances.
+    fn next_webdriver_url(&self) -> Option<&str> {
+        if self.webdriver_urls.is_empty() {
+            return None;
+        }
+        let index = self.next_webdriver.fetch_add(1, Ordering::Relaxed) % self.webdriver_urls.len();
+        Some(&self.webdriver_urls[index])
+    }
+
+    /// Returns the address the domain's naming convention predicts for this contact.
+    ///
+    /// A convention configured in `domain_patterns` takes precedence over an inferred one.
+    fn convention_candidate(&self, config: &Config, contact: &ValidatedContact) -> Option<String> {
+        let convention = match config.domain_patterns.get(&contact.domain) {
+            Some(template) => template.clone(),
+            None => self
+                .domain_conventions
+                .read()
+                .get(&contact.domain)?
+                .to_string(),
+        };
+        apply_naming_convention(
+            &convention,
+            &contact.first_name,
+            &contact.last_name,
+            &contact.domain,
+        )
+    }
+
+    /// Infers the naming convention from a confirmed address and remembers it for the domain.
+    /// Domains with a configured convention are left alone.
+    fn record_domain_convention(
+        &self,
+        config: &Config,
+        contact: &ValidatedContact,
+        email: &str,
+        label: &str,
+    ) {
+        if !email.ends_with(&format!("@{}", contact.domain))
+            || config.domain_patterns.contains_key(&contact.domain)
+        {
+            return;
+        }
+        if let Some(convention) =
+            infer_naming_convention(&contact.first_name, &contact.last_name, email)
+        {
+            let previous = self
+                .domain_conventions
+                .write()
+                .insert(contact.domain.clone(), convention);
+            if previous != Some(convention) {
+                tracing::info!(target: "find_email_task", "{} Learned naming convention '{}' for domain {}", label, convention, contact.domain);
+            }
+        }
     }
 
     async fn resolve_and_identify_provider(
         &self,
-        domain: &str,
+        contact: &ValidatedContact,
         verification_log: &mut HashMap<String, String>,
     ) -> (Option<MailServer>, Option<ProviderType>) {
+        let domain = contact.domain.as_str();
         tracing::debug!(target: "find_email_task", "Resolving DNS MX for {}...", domain);
-        let mail_server_info = match resolve_mail_server(&self.dns_resolver, domain).await {
+        let mail_server_info = match self.lookup_mail_server(domain).await {
             Ok(ms) => {
                 tracing::info!(target: "find_email_task", "Using mail server {} for domain {}", ms.exchange, domain);
+                if ms.implicit {
+                    let note = format!(
+                        "No MX records; using implicit MX (A/AAAA record of {})",
+                        ms.exchange
+                    );
+                    self.record_warning(WarningCategory::Dns, note.clone(), Some(contact));
+                    verification_log.entry(domain.to_string()).or_insert(note);
+                }
                 Some(ms)
             }
             Err(e @ AppError::NxDomain(_)) | Err(e @ AppError::NoDnsRecords(_)) => {
                 tracing::error!(target: "find_email_task", "DNS lookup failed (No MX/A or NXDOMAIN): {}. Cannot perform SMTP checks.", e);
+                self.record_warning(
+                    WarningCategory::Dns,
+                    format!("DNS resolution failed: {}", e),
+                    Some(contact),
+                );
+                verification_log
+                    .entry(domain.to_string())
+                    .or_insert(format!("DNS resolution failed: {}", e));
+                None
+            }
+            Err(e @ AppError::DnssecValidation(_)) => {
+                tracing::error!(target: "find_email_task", "{}. Refusing to use unvalidated records; skipping SMTP checks.", e);
+                self.record_warning(
+                    WarningCategory::Dns,
+                    format!("DNS resolution failed: {}", e),
+                    Some(contact),
+                );
                 verification_log
                     .entry(domain.to_string())
                     .or_insert(format!("DNS resolution failed: {}", e));
@@ -218,6 +1188,11 @@ impl EmailSleuth {
             }
             Err(e) => {
                 tracing::warn!(target: "find_email_task", "DNS lookup warning: {}. SMTP checks might fail.", e);
+                self.record_warning(
+                    WarningCategory::Dns,
+                    format!("DNS resolution warning: {}", e),
+                    Some(contact),
+                );
                 verification_log
                     .entry(domain.to_string())
                     .or_insert(format!("DNS resolution warning: {}", e));
@@ -227,7 +1202,7 @@ impl EmailSleuth {
 
         let provider_type = mail_server_info
             .as_ref()
-            .map(|ms| self.identify_provider(domain, &ms.exchange));
+            .map(|ms| Self::identify_provider(domain, &ms.exchange));
         if let Some(pt) = provider_type {
             tracing::debug!(target: "find_email_task", "Identified provider as: {:?}", pt);
         } else if mail_server_info.is_some() {
@@ -240,15 +1215,11 @@ impl EmailSleuth {
     /// Evaluates candidates, performing verification and scoring. Handles early termination.
     async fn evaluate_candidates(
         &self,
-        config: &Config,
-        contact: &ValidatedContact,
+        run: &mut CandidateRun<'_>,
         candidates: &[String],
-        mail_server_info: &Option<MailServer>,
-        provider_type: Option<ProviderType>,
-        task_label: &str,
-        verification_log: &mut HashMap<String, String>,
-        methods_used: &mut Vec<String>,
+        contact_addresses: &[String],
     ) -> Result<Vec<FoundEmailData>> {
+        let (config, contact, task_label) = (run.config, run.contact, run.task_label);
         let mut verified_data = Vec::with_capacity(candidates.len());
         let total_candidates = candidates.len();
 
@@ -258,6 +1229,9 @@ impl EmailSleuth {
         let mut found_high_confidence_match = false;
 
         for (index, email) in candidates.iter().enumerate() {
+            if found_high_confidence_match && !contact_addresses.contains(email) {
+                continue;
+            }
             let candidate_label = format!(
                 "[{}:{}/{}] {}",
                 task_label,
@@ -272,22 +1246,13 @@ impl EmailSleuth {
                 cache.contains(&contact.domain)
             };
 
-            let alternative_first = is_known_catch_all_before_check
-                && (config.enable_api_checks
-                    || (config.enable_headless_checks && config.webdriver_url.is_some()));
-
             match self
                 .verify_and_score_candidate(
-                    config,
-                    contact,
+                    run,
                     email,
-                    mail_server_info,
-                    provider_type,
+                    candidates,
                     &candidate_label,
-                    verification_log,
-                    methods_used,
                     is_known_catch_all_before_check,
-                    alternative_first,
                 )
                 .await
             {
@@ -303,15 +1268,26 @@ impl EmailSleuth {
                     let is_currently_catch_all =
                         self.catch_all_domains.read().contains(&contact.domain);
 
+                    if data.verification_status == Some(true) && !is_currently_catch_all {
+                        self.record_domain_convention(
+                            config,
+                            contact,
+                            &data.email,
+                            &candidate_label,
+                        );
+                    }
+
                     if data.confidence >= early_termination_threshold
                         && data.verification_status == Some(true)
+                        && (!config.require_corroboration || data.corroborated_by.len() >= 2)
                         && !is_currently_catch_all
+                        && !found_high_confidence_match
                     {
                         tracing::info!(target: "find_email_task",
                             "{} Found high-confidence match (confidence: {}/10) on non-catch-all domain. Early termination triggered, skipping {} remaining candidates.",
                             candidate_label, data.confidence, total_candidates - (index + 1));
 
-                        verification_log.entry("early_termination".to_string())
+                        run.verification_log.entry("early_termination".to_string())
                             .or_insert(format!(
                                 "Verification stopped early after finding high-confidence match on non-catch-all domain: {} (confidence: {}/10)",
                                 email, data.confidence
@@ -335,13 +1311,17 @@ impl EmailSleuth {
                 }
                 Err(e) => {
                     tracing::error!(target: "find_email_task", "{} Critical error during verification: {}", candidate_label, e);
-                    verification_log
+                    run.verification_log
                         .entry(email.to_string())
                         .or_insert(format!("Processing Error: {}", e));
                 }
             }
 
-            if found_high_confidence_match {
+            if found_high_confidence_match
+                && !candidates[index + 1..]
+                    .iter()
+                    .any(|c| contact_addresses.contains(c))
+            {
                 break;
             }
         }
@@ -359,7 +1339,105 @@ impl EmailSleuth {
         Ok(verified_data)
     }
 
-    fn finalize_results(&self, config: &Config, results: &mut EmailResult) {
+    /// Scores candidates from name heuristics and MX presence alone, without probing any address.
+    /// Used for the `mx_only` verification level.
+    fn score_candidates_mx_only(
+        &self,
+        config: &Config,
+        contact: &ValidatedContact,
+        candidates: &[String],
+        mail_server_info: &Option<MailServer>,
+        verification_log: &mut HashMap<String, String>,
+        methods_used: &mut Vec<String>,
+    ) -> Vec<FoundEmailData> {
+        if !methods_used.contains(&"mx_check".to_string()) {
+            methods_used.push("mx_check".to_string());
+        }
+        let Some(mail_server) = mail_server_info else {
+            tracing::info!(target: "find_email_task", "MX-only: {} has no mail server, no candidates returned.", contact.domain);
+            return Vec::new();
+        };
+
+        let message = format!(
+            "MX-only: domain accepts mail via {} (address not verified)",
+            mail_server.exchange
+        );
+        let convention = self.convention_candidate(config, contact);
+        let known_catch_all = self.catch_all_domains.read().contains(&contact.domain);
+        candidates
+            .iter()
+            .filter(|email| config.email_regex.is_match(email))
+            .filter(|email| !config.exclude_off_domain_scraped || !is_off_domain(email, contact))
+            .map(|email| {
+                let generic_penalty = self.generic_prefix_penalty(config, email);
+                let is_generic = generic_penalty > 0;
+                // The contact's mail server says nothing about another domain's addresses.
+                let off_domain = is_off_domain(email, contact);
+                let (penalty, message) = if off_domain {
+                    (
+                        OFF_DOMAIN_PENALTY,
+                        format!(
+                            "Off-domain address (not at {}); not verified",
+                            contact.domain
+                        ),
+                    )
+                } else {
+                    (0, message.clone())
+                };
+                let signals = CandidateSignals {
+                    email,
+                    name_in_email: self.check_name_in_email(contact, email),
+                    generic_penalty,
+                    mx_exists: true,
+                    matches_convention: convention.as_deref() == Some(email.as_str()),
+                    known_catch_all,
+                };
+                let initial = self.scorer.initial_score(&signals).clamp(0, 10);
+                let evidence = StageEvidence {
+                    status: None,
+                    confidence: if off_domain {
+                        (initial - penalty).clamp(1, 10) as u8
+                    } else {
+                        initial as u8
+                    },
+                    source: "mx_only".to_string(),
+                    message: message.clone(),
+                    stage: ResolutionStage::DnsOnly,
+                };
+                let confidence = self.scorer.final_score(&signals, &evidence, &[]).min(10);
+                verification_log
+                    .entry(email.to_string())
+                    .or_insert(format!("{} (Final Conf: {})", message, confidence));
+                FoundEmailData {
+                    email: email.to_string(),
+                    confidence,
+                    source: "mx_only".to_string(),
+                    is_generic,
+                    verification_status: None,
+                    verification_message: message,
+                    stage: ResolutionStage::DnsOnly,
+                    smtp_signals: None,
+                    verified_at: None,
+                    mx_host: None,
+                    likely_distribution_list: self
+                        .is_likely_distribution_list(email, is_generic, None, None),
+                    corroborated_by: Vec::new(),
+                    off_domain,
+                    normalized_from: None,
+                }
+            })
+            .filter(|data| data.confidence > 0)
+            .collect()
+    }
+
+    fn finalize_results(
+        &self,
+        config: &Config,
+        contact: &ValidatedContact,
+        results: &mut EmailResult,
+        candidates: &[String],
+        has_mail_server: bool,
+    ) {
         tracing::debug!(target: "find_email_task", "Sorting {} verified email data entries...", results.found_emails.len());
 
         results.found_emails.sort_by(|a, b| {
@@ -372,27 +1450,30 @@ impl EmailSleuth {
 
         results.most_likely_email = None;
         results.confidence_score = 0;
+        results.resolution_stage = None;
+        results.unverified_fallback = false;
+        results.best_guess = false;
 
-        for email_data in &results.found_emails {
-            if email_data.verification_status == Some(false) {
-                continue;
-            }
-
-            let threshold = if email_data.is_generic {
-                config.generic_confidence_threshold
-            } else {
-                config.confidence_threshold
-            };
-
-            if email_data.confidence >= threshold {
+        // Off-domain addresses stay in `found_emails` for reference but are never selected.
+        for email_data in results.found_emails.iter().filter(|d| !d.off_domain) {
+            if email_data.verdict(config).is_acceptable() {
                 results.most_likely_email = Some(email_data.email.clone());
                 results.confidence_score = email_data.confidence;
+                results.resolution_stage = Some(email_data.stage);
                 tracing::info!(target: "find_email_task", "Selected best candidate: {} (Conf: {}, Generic: {}, Src: {}, Status: {:?})",
                     email_data.email, email_data.confidence, email_data.is_generic, email_data.source, email_data.verification_status);
                 break;
             }
         }
 
+        if results.most_likely_email.is_none() && has_mail_server {
+            self.apply_unverified_fallback(config, results);
+        }
+
+        if results.most_likely_email.is_none() && config.return_best_guess_on_failure {
+            self.apply_best_guess(config, contact, results, candidates);
+        }
+
         if results.most_likely_email.is_none() {
             if !results.found_emails.is_empty() {
                 tracing::info!(target: "find_email_task", "No candidate met confidence thresholds (Base: {}, Generic: {}).",
@@ -410,68 +1491,416 @@ impl EmailSleuth {
         }
     }
 
-    /// Verifies and scores a single email candidate.
-    /// Takes `is_known_catch_all` which reflects the cache state *before* this check runs.
-    async fn verify_and_score_candidate(
+    /// Selects the best non-generic pattern candidate as an unverified guess when enabled and
+    /// no candidate got a conclusive answer. Candidates are assumed to be sorted already.
+    fn apply_unverified_fallback(&self, config: &Config, results: &mut EmailResult) {
+        if !config.unverified_fallback || config.verification_level == VerificationLevel::MxOnly {
+            return;
+        }
+        if results
+            .found_emails
+            .iter()
+            .any(|d| d.verification_status == Some(true))
+        {
+            return;
+        }
+
+        let Some(candidate) = results
+            .found_emails
+            .iter()
+            .filter(|d| d.verification_status.is_none() && !d.off_domain)
+            .find(|d| !d.is_generic)
+        else {
+            return;
+        };
+
+        tracing::info!(target: "find_email_task", "All probes inconclusive; returning '{}' as an unverified fallback (Conf: {}).",
+            candidate.email, config.unverified_fallback_confidence);
+        results.most_likely_email = Some(candidate.email.clone());
+        results.confidence_score = config.unverified_fallback_confidence;
+        results.resolution_stage = Some(ResolutionStage::Pattern);
+        results.unverified_fallback = true;
+        let note = "selected as unverified fallback (domain accepts mail, all probes inconclusive)";
+        results
+            .verification_log
+            .entry(candidate.email.clone())
+            .and_modify(|entry| *entry = format!("{}; {}", entry, note))
+            .or_insert_with(|| note.to_string());
+    }
+
+    /// Selects the top-ranked candidate that was not rejected as a low-confidence guess, for
+    /// `return_best_guess_on_failure`. Candidates are probed in shuffled order, so they are
+    /// ranked by how common their pattern is (see [`pattern_rank`]); addresses that follow
+    /// no known pattern come last. Prefers scored candidates over ones that were discarded,
+    /// and personal addresses over generic ones.
+    fn apply_best_guess(
+        &self,
+        config: &Config,
+        contact: &ValidatedContact,
+        results: &mut EmailResult,
+        candidates: &[String],
+    ) {
+        let rank = |email: &str| {
+            pattern_rank(&contact.first_name, &contact.last_name, email).unwrap_or(usize::MAX)
+        };
+        let guess = results
+            .found_emails
+            .iter()
+            .filter(|d| d.verification_status != Some(false) && !d.off_domain)
+            .min_by_key(|d| {
+                (
+                    d.is_generic,
+                    rank(&d.email),
+                    std::cmp::Reverse(d.confidence),
+                )
+            })
+            .map(|d| d.email.clone())
+            .or_else(|| {
+                candidates
+                    .iter()
+                    .filter(|email| config.email_regex.is_match(email))
+                    .filter(|email| !results.found_emails.iter().any(|d| &d.email == *email))
+                    .min_by_key(|email| rank(email))
+                    .cloned()
+            });
+        let Some(email) = guess else {
+            tracing::debug!(target: "find_email_task", "No unrejected candidate left to return as a best guess.");
+            return;
+        };
+
+        tracing::info!(target: "find_email_task", "Nothing selected; returning '{}' as a best guess (Conf: {}).",
+            email, BEST_GUESS_CONFIDENCE);
+        match results.found_emails.iter_mut().find(|d| d.email == email) {
+            Some(data) => data.verification_message = BEST_GUESS_MESSAGE.to_string(),
+            None => {
+                let generic_penalty = self.generic_prefix_penalty(config, &email);
+                results.found_emails.push(FoundEmailData {
+                    email: email.clone(),
+                    confidence: BEST_GUESS_CONFIDENCE,
+                    source: "best_guess".to_string(),
+                    is_generic: generic_penalty > 0,
+                    verification_status: None,
+                    verification_message: BEST_GUESS_MESSAGE.to_string(),
+                    stage: ResolutionStage::Pattern,
+                    smtp_signals: None,
+                    verified_at: None,
+                    mx_host: None,
+                    likely_distribution_list: false,
+                    corroborated_by: Vec::new(),
+                    off_domain: false,
+                    normalized_from: None,
+                });
+            }
+        }
+        results.most_likely_email = Some(email.clone());
+        results.confidence_score = BEST_GUESS_CONFIDENCE;
+        results.resolution_stage = Some(ResolutionStage::Pattern);
+        results.best_guess = true;
+        let note = "selected as best guess (nothing verified)";
+        results
+            .verification_log
+            .entry(email)
+            .and_modify(|entry| *entry = format!("{}; {}", entry, note))
+            .or_insert_with(|| note.to_string());
+    }
+
+    /// Fills `attempted_methods` and `skipped_methods` once verification has finished.
+    ///
+    /// Attempts are taken from `methods_used`, which only lists methods that produced a
+    /// result (scraping that found addresses, a check that got an answer); skip reasons are
+    /// derived from the config and what DNS and provider detection found. A method attempted
+    /// for any candidate is not reported as skipped.
+    fn record_method_coverage(
         &self,
         config: &Config,
         contact: &ValidatedContact,
-        email: &str,
         mail_server_info: &Option<MailServer>,
         provider_type: Option<ProviderType>,
+        scraping_skipped: Option<&str>,
+        results: &mut EmailResult,
+    ) {
+        let attempted = results.methods_used.clone();
+        let mut skipped: Vec<(&str, String)> = Vec::new();
+        if let Some(reason) = scraping_skipped {
+            skipped.push(("website_scraping", reason.to_string()));
+        }
+        let pipeline = self.pipeline(config);
+        let not_in_pipeline = "not in the pipeline (stage_order)";
+
+        if !cfg!(feature = "smtp") {
+            skipped.push((
+                "smtp_verification",
+                "not included in this build".to_string(),
+            ));
+        } else if config.verification_level == VerificationLevel::MxOnly {
+            skipped.push((
+                "smtp_verification",
+                format!("verification level is {}", config.verification_level),
+            ));
+        } else if !pipeline
+            .iter()
+            .any(|stage| matches!(stage, PipelineStage::Smtp))
+        {
+            skipped.push(("smtp_verification", not_in_pipeline.to_string()));
+        } else if let Some(tld) = config.smtp_skipped_tld(&contact.domain) {
+            skipped.push((
+                "smtp_verification",
+                format!("skipped by policy for TLD .{}", tld),
+            ));
+        } else if mail_server_info.is_none() {
+            skipped.push((
+                "smtp_verification",
+                "no mail server (DNS lookup failed)".to_string(),
+            ));
+        } else if self.catch_all_domains.read().contains(&contact.domain) {
+            skipped.push(("smtp_verification", "domain is catch-all".to_string()));
+        } else if self.probe_budget_exhausted(config, &contact.domain) {
+            skipped.push((
+                "smtp_verification",
+                format!(
+                    "max_probes_per_domain ({}) reached",
+                    config.max_probes_per_domain
+                ),
+            ));
+        } else if results
+            .found_emails
+            .iter()
+            .any(|d| d.verification_status.is_some())
+        {
+            skipped.push((
+                "smtp_verification",
+                "definitive result from an earlier check".to_string(),
+            ));
+        } else {
+            skipped.push((
+                "smtp_verification",
+                "no answer from the mail server".to_string(),
+            ));
+        }
+
+        if config.verification_level != VerificationLevel::Full {
+            let reason = format!("verification level is {}", config.verification_level);
+            skipped.push(("api", reason.clone()));
+            skipped.push(("headless", reason));
+        } else {
+            match provider_type {
+                _ if !pipeline.iter().any(PipelineStage::runs_api) => {
+                    skipped.push(("api", not_in_pipeline.to_string()));
+                }
+                Some(ProviderType::M365) if !config.enable_api_checks => {
+                    skipped.push(("api_m365", "disabled in config".to_string()));
+                }
+                Some(ProviderType::M365) => {}
+                Some(pt) => skipped.push(("api", format!("not supported for provider {:?}", pt))),
+                None => skipped.push(("api", "provider unknown".to_string())),
+            }
+
+            let headless_method = match provider_type {
+                Some(ProviderType::M365) => Some("headless_microsoft"),
+                Some(ProviderType::HotmailB2C) => Some("headless_hotmail"),
+                Some(ProviderType::Yahoo) => Some("headless_yahoo"),
+                _ => None,
+            };
+            match headless_method {
+                _ if !pipeline.iter().any(PipelineStage::runs_headless) => {
+                    skipped.push(("headless", not_in_pipeline.to_string()));
+                }
+                Some(method) if !config.enable_headless_checks => {
+                    skipped.push((method, "disabled in config".to_string()));
+                }
+                Some(method) if config.webdriver_url.is_none() => {
+                    skipped.push((method, "WebDriver URL missing".to_string()));
+                }
+                Some(_) => {}
+                None => skipped.push((
+                    "headless",
+                    match provider_type {
+                        Some(pt) => format!("not supported for provider {:?}", pt),
+                        None => "provider unknown".to_string(),
+                    },
+                )),
+            }
+        }
+
+        results.skipped_methods = skipped
+            .into_iter()
+            .filter(|(method, _)| !attempted.iter().any(|a| a == method))
+            .map(|(method, reason)| SkippedMethod {
+                method: method.to_string(),
+                reason,
+            })
+            .collect();
+        results.attempted_methods = attempted;
+    }
+
+    /// Verifies and scores a single email candidate.
+    /// Takes `is_known_catch_all` which reflects the cache state *before* this check runs.
+    async fn verify_and_score_candidate(
+        &self,
+        run: &mut CandidateRun<'_>,
+        email: &str,
+        candidates: &[String],
         candidate_label: &str,
-        verification_log: &mut HashMap<String, String>,
-        methods_used: &mut Vec<String>,
         is_known_catch_all: bool,
-        alternative_first: bool,
     ) -> Result<Option<FoundEmailData>> {
+        let (config, contact, mail_server_info) = (run.config, run.contact, run.mail_server_info);
         tracing::debug!(target: "find_email_task", "{}", candidate_label);
 
         if !config.email_regex.is_match(email) {
-            verification_log
+            run.verification_log
                 .entry(email.to_string())
                 .or_insert("Skipped: Invalid format".to_string());
+            if let Some(trace) = run.trace.as_deref_mut() {
+                trace.candidates.push(TraceCandidate {
+                    email: email.to_string(),
+                    steps: Vec::new(),
+                    verification_status: None,
+                    confidence: 0,
+                    message: "Skipped: Invalid format".to_string(),
+                    kept: false,
+                });
+            }
             return Ok(None);
         }
 
         let email_domain = email.split('@').nth(1).unwrap_or("");
-        let is_generic = self.is_generic_prefix(config, email);
+        let generic_penalty = self.generic_prefix_penalty(config, email);
+        let is_generic = generic_penalty > 0;
         let name_in_email = self.check_name_in_email(contact, email);
 
         let mut current_status: Option<bool> = None;
         let mut current_message: String = "Verification pending".to_string();
         let mut current_source: String = "initial".to_string();
-        let mut confidence_score: i16 = self.calculate_initial_confidence(
+        let mut current_stage = ResolutionStage::Pattern;
+        let mut smtp_signals: Option<SmtpProbeSignals> = None;
+        let mut verified_at: Option<DateTime<Utc>> = None;
+        let mut mx_host: Option<String> = None;
+        let signals = CandidateSignals {
+            email,
             name_in_email,
-            is_generic,
-            mail_server_info.is_some(),
-        );
-        let mut skip_smtp = false;
-
-        let verification_steps = if alternative_first {
-            vec!["alternative", "smtp"]
-        } else {
-            vec!["alternative", "smtp"]
+            generic_penalty,
+            mx_exists: mail_server_info.is_some(),
+            matches_convention: self.convention_candidate(config, contact).as_deref()
+                == Some(email),
+            known_catch_all: is_known_catch_all,
         };
+        let mut confidence_score: i16 = self.scorer.initial_score(&signals).clamp(0, 10);
+        if signals.mx_exists && signals.matches_convention {
+            tracing::debug!(target: "find_email_task", "{} Matches the domain's known naming convention, boosting confidence.", candidate_label);
+            confidence_score = (confidence_score + 1).clamp(0, 10);
+        }
+        if email_domain != contact.domain {
+            // The contact's mail server and provider say nothing about another domain's
+            // addresses, so these are kept unverified; off-domain ones with a penalty.
+            let off_domain = is_off_domain(email, contact);
+            let (penalty, message) = if off_domain {
+                (
+                    OFF_DOMAIN_PENALTY,
+                    format!(
+                        "Off-domain address (not at {}); not verified",
+                        contact.domain
+                    ),
+                )
+            } else {
+                (
+                    0,
+                    format!(
+                        "Address at {}; not verified against the mail server of {}",
+                        email_domain, contact.domain
+                    ),
+                )
+            };
+            let evidence = StageEvidence {
+                status: None,
+                confidence: (confidence_score - penalty).clamp(1, 10) as u8,
+                source: "skipped_other_domain".to_string(),
+                message: message.clone(),
+                stage: ResolutionStage::Scraping,
+            };
+            let confidence = self.scorer.final_score(&signals, &evidence, &[]).min(10);
+            tracing::debug!(target: "find_email_task", "{} {}.", candidate_label, message);
+            run.verification_log
+                .entry(email.to_string())
+                .or_insert(format!(
+                    "skipped_other_domain: {} (Final Conf: {})",
+                    message, confidence
+                ));
+            if let Some(trace) = run.trace.as_deref_mut() {
+                trace.candidates.push(TraceCandidate {
+                    email: email.to_string(),
+                    steps: Vec::new(),
+                    verification_status: None,
+                    confidence,
+                    message: message.clone(),
+                    kept: true,
+                });
+            }
+            return Ok(Some(FoundEmailData {
+                email: email.to_string(),
+                confidence,
+                source: "skipped_other_domain".to_string(),
+                is_generic,
+                verification_status: None,
+                verification_message: message,
+                stage: ResolutionStage::Scraping,
+                smtp_signals: None,
+                verified_at: None,
+                mx_host: None,
+                likely_distribution_list: false,
+                corroborated_by: Vec::new(),
+                off_domain,
+                normalized_from: None,
+            }));
+        }
+        let mut steps: Vec<TraceStep> = Vec::new();
+        // With `require_corroboration`, a definitive "exists" does not end the pipeline:
+        // later stages still run, but only to confirm it (they no longer change the result).
+        let corroborate = config.require_corroboration;
+        let mut confirmations: Vec<String> = Vec::new();
+        let mut corroborating_only = false;
 
-        tracing::trace!(target: "find_email_task", "{} Verification order: {:?}. Known Catch-all (prior): {}", candidate_label, verification_steps, is_known_catch_all);
+        let pipeline = self.pipeline(config);
+        tracing::trace!(target: "find_email_task", "{} Verification order: {:?}. Known Catch-all (prior): {}", candidate_label, pipeline, is_known_catch_all);
 
-        for step in verification_steps {
+        for step in pipeline.iter() {
             match step {
-                "alternative" => {
+                PipelineStage::Scrape | PipelineStage::Dns => {}
+                PipelineStage::Alternative | PipelineStage::Api | PipelineStage::Headless => {
                     if let Some(alt_outcome) = self
                         .run_alternative_verifications(
-                            config,
-                            provider_type,
+                            run,
                             email,
-                            methods_used,
                             candidate_label,
+                            step.runs_api(),
+                            step.runs_headless(),
                         )
                         .await?
                     {
+                        let trace_stage = match step {
+                            PipelineStage::Api => "api",
+                            PipelineStage::Headless => "headless",
+                            _ => "alternative",
+                        };
+                        steps.push(TraceStep {
+                            stage: trace_stage.to_string(),
+                            source: alt_outcome.source.clone(),
+                            status: alt_outcome.status,
+                            message: alt_outcome.message.clone(),
+                            smtp_code: None,
+                            definitive: alt_outcome.definitive,
+                        });
+                        if corroborate && alt_outcome.status == Some(true) {
+                            confirmations.push(alt_outcome.source.clone());
+                        }
+                        if corroborating_only {
+                            continue;
+                        }
                         if current_status.is_none() || alt_outcome.definitive {
                             current_status = alt_outcome.status;
                             current_source = alt_outcome.source.clone();
+                            current_stage = alt_outcome.stage;
+                            verified_at = alt_outcome.verified_at;
+                            mx_host = alt_outcome.mx_host;
                             if alt_outcome.definitive {
                                 confidence_score = alt_outcome.confidence_boost;
                             } else {
@@ -481,20 +1910,27 @@ impl EmailSleuth {
                         }
                         current_message = alt_outcome.message;
 
-                        if alt_outcome.definitive {
-                            skip_smtp = true;
+                        if alt_outcome.definitive && corroborate && current_status == Some(true) {
+                            tracing::debug!(target: "find_email_task", "{} Definitive {} result; running remaining stages for corroboration.", candidate_label, current_source);
+                            corroborating_only = true;
+                        } else if alt_outcome.definitive {
                             tracing::debug!(target: "find_email_task", "{} Skipping subsequent SMTP based on definitive {} result.", candidate_label, current_source);
                             break;
                         }
                     }
                 }
-                "smtp" => {
+                PipelineStage::Smtp => {
                     // Skip SMTP if:
-                    // - Already skipped by a definitive alternative result
+                    // - The domain's TLD is excluded by policy
                     // - The domain was known to be catch-all *before* this verification run
                     // - No mail server info is available
-                    if skip_smtp {
-                        tracing::debug!(target: "find_email_task", "{} SMTP check skipped (previous definitive result).", candidate_label);
+                    // - The domain's probe budget is spent
+                    if let Some(tld) = config.smtp_skipped_tld(email_domain) {
+                        tracing::debug!(target: "find_email_task", "{} SMTP check skipped (policy: .{} is in smtp_skip_tlds).", candidate_label, tld);
+                        if current_source == "initial" {
+                            current_message = format!("SMTP: Skipped (policy for .{})", tld);
+                            current_source = "skipped_smtp_policy".to_string();
+                        }
                         continue;
                     }
                     if is_known_catch_all {
@@ -510,7 +1946,6 @@ impl EmailSleuth {
                                 current_source
                             };
                         }
-                        skip_smtp = true;
                         continue;
                     }
                     if mail_server_info.is_none() {
@@ -518,31 +1953,63 @@ impl EmailSleuth {
                         if current_source == "initial" {
                             current_message = "SMTP: Skipped (DNS Lookup Failed)".to_string();
                             current_source = "skipped_smtp_dns".to_string();
+                            current_stage = ResolutionStage::DnsOnly;
                             confidence_score = 0;
                         }
-                        skip_smtp = true;
+                        continue;
+                    }
+                    if !self.take_probe(config, &contact.domain) {
+                        tracing::debug!(target: "find_email_task", "{} SMTP check skipped (max_probes_per_domain reached for {}).", candidate_label, contact.domain);
+                        if current_source == "initial" {
+                            current_message =
+                                "SMTP: Skipped (max_probes_per_domain reached)".to_string();
+                            current_source = "skipped_smtp_budget".to_string();
+                        }
                         continue;
                     }
 
+                    let smtp_started = Instant::now();
                     let smtp_outcome = self
                         .run_smtp_verification(
                             email,
                             email_domain,
                             mail_server_info.as_ref().unwrap(),
-                            methods_used,
+                            run.methods_used,
                             candidate_label,
                         )
                         .await;
-
+                    record_stage(run.stage_timings, "smtp", smtp_started);
+
+                    let smtp_definitive = smtp_outcome.definitive;
+                    steps.push(TraceStep {
+                        stage: "smtp".to_string(),
+                        source: smtp_outcome.source.clone(),
+                        status: smtp_outcome.status,
+                        message: smtp_outcome.message.clone(),
+                        smtp_code: smtp_reply_code(&smtp_outcome.message),
+                        definitive: smtp_definitive,
+                    });
                     if smtp_outcome.is_catch_all {
                         tracing::info!(target: "find_email_task", "{} SMTP detected domain as catch-all, marking cache.", candidate_label);
                         let mut cache = self.catch_all_domains.write();
                         cache.insert(contact.domain.clone());
                     }
+                    if corroborate
+                        && smtp_outcome.status == Some(true)
+                        && !smtp_outcome.is_catch_all
+                    {
+                        confirmations.push(smtp_outcome.source.clone());
+                    }
+                    if corroborating_only {
+                        continue;
+                    }
 
                     if smtp_outcome.definitive || current_status.is_none() {
                         current_status = smtp_outcome.status;
                         current_source = smtp_outcome.source;
+                        current_stage = smtp_outcome.stage;
+                        verified_at = smtp_outcome.verified_at;
+                        mx_host = smtp_outcome.mx_host;
                         confidence_score =
                             (confidence_score + smtp_outcome.confidence_boost).clamp(0, 10);
                     } else {
@@ -550,18 +2017,135 @@ impl EmailSleuth {
                             (confidence_score + smtp_outcome.confidence_boost).clamp(0, 10);
                     }
                     current_message = smtp_outcome.message;
+                    smtp_signals = smtp_outcome.smtp_signals;
+                    if smtp_definitive && corroborate && current_status == Some(true) {
+                        tracing::debug!(target: "find_email_task", "{} Definitive SMTP result; running remaining stages for corroboration.", candidate_label);
+                        corroborating_only = true;
+                    } else if smtp_definitive {
+                        break;
+                    }
+                }
+                PipelineStage::Custom(stage) => {
+                    let name = stage.name();
+                    let evidence = StageEvidence {
+                        status: current_status,
+                        confidence: confidence_score.clamp(0, 10) as u8,
+                        source: current_source.clone(),
+                        message: current_message.clone(),
+                        stage: current_stage,
+                    };
+                    let context = StageContext {
+                        contact: &contact.original_contact,
+                        full_name: &contact.full_name,
+                        domain: &contact.domain,
+                        email,
+                        candidates,
+                        evidence: &evidence,
+                    };
+                    let stage_started = Instant::now();
+                    let result = stage.verify(context).await;
+                    record_stage(run.stage_timings, name, stage_started);
+                    if matches!(result, Ok(Some(_)))
+                        && !run.methods_used.iter().any(|method| method == name)
+                    {
+                        run.methods_used.push(name.to_string());
+                    }
+
+                    match result {
+                        Ok(Some(outcome)) => {
+                            tracing::debug!(target: "find_email_task", "{} Stage {} returned: Status={:?}, Definitive={}, Msg='{}'", candidate_label, name, outcome.status, outcome.definitive, outcome.message);
+                            steps.push(TraceStep {
+                                stage: name.to_string(),
+                                source: name.to_string(),
+                                status: outcome.status,
+                                message: outcome.message.clone(),
+                                smtp_code: None,
+                                definitive: outcome.definitive,
+                            });
+                            if corroborate && outcome.status == Some(true) {
+                                confirmations.push(name.to_string());
+                            }
+                            if corroborating_only {
+                                continue;
+                            }
+                            if current_status.is_none() || outcome.definitive {
+                                current_status = outcome.status;
+                                current_source = name.to_string();
+                                current_stage = ResolutionStage::Custom;
+                                verified_at = outcome.status.map(|_| Utc::now());
+                                mx_host = None;
+                                if outcome.definitive {
+                                    confidence_score = outcome.confidence_boost.clamp(0, 10);
+                                } else {
+                                    confidence_score =
+                                        (confidence_score + outcome.confidence_boost).clamp(0, 10);
+                                }
+                            }
+                            current_message = outcome.message;
+
+                            if outcome.definitive && corroborate && current_status == Some(true) {
+                                tracing::debug!(target: "find_email_task", "{} Definitive {} result; running remaining stages for corroboration.", candidate_label, name);
+                                corroborating_only = true;
+                            } else if outcome.definitive {
+                                tracing::debug!(target: "find_email_task", "{} Skipping remaining stages based on definitive {} result.", candidate_label, name);
+                                break;
+                            }
+                        }
+                        Ok(None) => {
+                            tracing::trace!(target: "find_email_task", "{} Stage {} had no answer.", candidate_label, name);
+                        }
+                        Err(e) => {
+                            tracing::warn!(target: "find_email_task", "{} Stage {} failed: {}", candidate_label, name, e);
+                            self.record_warning(
+                                WarningCategory::Stage,
+                                format!("Verification stage {} failed for {}: {}", name, email, e),
+                                Some(contact),
+                            );
+                        }
+                    }
                 }
-                _ => unreachable!("Invalid verification step"),
             }
         }
 
-        verification_log.entry(email.to_string()).or_insert(format!(
-            "{}: {} (Final Conf: {})",
-            current_source, current_message, confidence_score
-        ));
-
-        let final_confidence = confidence_score as u8;
-        if final_confidence > 0 || current_status == Some(false) {
+        let evidence = StageEvidence {
+            status: current_status,
+            confidence: confidence_score.clamp(0, 10) as u8,
+            source: current_source.clone(),
+            message: current_message.clone(),
+            stage: current_stage,
+        };
+        let final_confidence = self
+            .scorer
+            .final_score(&signals, &evidence, &confirmations)
+            .min(10);
+        run.verification_log
+            .entry(email.to_string())
+            .or_insert(format!(
+                "{}: {} (Final Conf: {})",
+                current_source, current_message, final_confidence
+            ));
+
+        let kept = final_confidence > 0 || current_status == Some(false);
+        if let Some(trace) = run.trace.as_deref_mut() {
+            trace.candidates.push(TraceCandidate {
+                email: email.to_string(),
+                steps,
+                verification_status: current_status,
+                confidence: final_confidence,
+                message: current_message.clone(),
+                kept,
+            });
+        }
+        if kept {
+            let likely_distribution_list = self.is_likely_distribution_list(
+                email,
+                is_generic,
+                current_status,
+                smtp_signals.as_ref(),
+            );
+            if likely_distribution_list {
+                tracing::debug!(target: "find_email_task", "{} Flagged as a likely distribution list.", candidate_label);
+            }
             Ok(Some(FoundEmailData {
                 email: email.to_string(),
                 confidence: final_confidence,
@@ -569,6 +2153,14 @@ impl EmailSleuth {
                 is_generic,
                 verification_status: current_status,
                 verification_message: current_message,
+                stage: current_stage,
+                smtp_signals,
+                verified_at,
+                mx_host,
+                likely_distribution_list,
+                corroborated_by: confirmations,
+                off_domain: false,
+                normalized_from: None,
             }))
         } else {
             tracing::debug!(target: "find_email_task", "{} Discarding (Confidence: {}, Status: {:?})", candidate_label, final_confidence, current_status);
@@ -579,23 +2171,32 @@ impl EmailSleuth {
     /// Runs applicable alternative verification methods (API, Headless).
     async fn run_alternative_verifications(
         &self,
-        config: &Config,
-        provider_type: Option<ProviderType>,
+        run: &mut CandidateRun<'_>,
         email: &str,
-        methods_used: &mut Vec<String>,
         candidate_label: &str,
+        run_api: bool,
+        run_headless: bool,
     ) -> Result<Option<VerificationAttemptOutcome>> {
+        let (config, contact, provider_type) = (run.config, run.contact, run.provider_type);
+        if config.verification_level != VerificationLevel::Full {
+            tracing::trace!(target:"find_email_task", "{} Skipping alternative checks (verification level: {}).", candidate_label, config.verification_level);
+            return Ok(None);
+        }
         if let Some(pt) = provider_type {
             match pt {
                 ProviderType::M365 => {
-                    if config.enable_api_checks {
-                        if !methods_used.contains(&"api_m365".to_string()) {
-                            methods_used.push("api_m365".to_string());
-                        }
+                    if run_api && config.enable_api_checks {
                         tracing::debug!(target:"find_email_task", "{} Performing Microsoft 365 API check...", candidate_label);
-                        match verification_api::check_m365_api(config, email, &self.http_client)
-                            .await
+                        let api_started = Instant::now();
+                        let api_result =
+                            verification_api::check_m365_api(config, email, &self.http_client)
+                                .await;
+                        record_stage(run.stage_timings, "api", api_started);
+                        if api_result.is_ok() && !run.methods_used.contains(&"api_m365".to_string())
                         {
+                            run.methods_used.push("api_m365".to_string());
+                        }
+                        match api_result {
                             Ok(Some(data)) => {
                                 return Ok(Some(VerificationAttemptOutcome {
                                     status: data.verification_status,
@@ -604,6 +2205,10 @@ impl EmailSleuth {
                                     confidence_boost: data.confidence as i16,
                                     definitive: data.verification_status.is_some(),
                                     is_catch_all: false,
+                                    stage: data.stage,
+                                    smtp_signals: data.smtp_signals,
+                                    verified_at: data.verified_at,
+                                    mx_host: None,
                                 }));
                             }
                             Ok(None) => {
@@ -618,18 +2223,27 @@ impl EmailSleuth {
                         tracing::trace!(target:"find_email_task", "{} Skipping Microsoft 365 API check (disabled in config).", candidate_label);
                     }
 
-                    if config.enable_headless_checks {
-                        if let Some(ref webdriver_url) = config.webdriver_url {
-                            if !methods_used.contains(&"headless_microsoft".to_string()) {
-                                methods_used.push("headless_microsoft".to_string());
-                            }
+                    if run_headless && config.enable_headless_checks {
+                        if let Some(webdriver_url) = self.next_webdriver_url() {
                             tracing::debug!(target:"find_email_task", "{} Performing Microsoft 365 headless check...", candidate_label);
-                            match verification_headless::check_hotmail_headless(
-                                email,
-                                webdriver_url,
-                            )
-                            .await
+                            let headless_started = Instant::now();
+                            let headless_result = self
+                                .run_headless_check(
+                                    config,
+                                    candidate_label,
+                                    email,
+                                    webdriver_url,
+                                    HeadlessProvider::Microsoft,
+                                    verification_headless::check_hotmail_headless,
+                                )
+                                .await;
+                            record_stage(run.stage_timings, "headless", headless_started);
+                            if headless_result.is_ok()
+                                && !run.methods_used.contains(&"headless_microsoft".to_string())
                             {
+                                run.methods_used.push("headless_microsoft".to_string());
+                            }
+                            match headless_result {
                                 Ok(Some(data)) => {
                                     let mut modified_data = data.clone();
                                     modified_data.source = "headless_microsoft".to_string();
@@ -644,6 +2258,10 @@ impl EmailSleuth {
                                         confidence_boost: modified_data.confidence as i16,
                                         definitive: modified_data.verification_status.is_some(),
                                         is_catch_all: false,
+                                        stage: modified_data.stage,
+                                        smtp_signals: modified_data.smtp_signals,
+                                        verified_at: modified_data.verified_at,
+                                        mx_host: None,
                                     }));
                                 }
                                 Ok(None) => {
@@ -652,6 +2270,14 @@ impl EmailSleuth {
                                 Err(e) => {
                                     if let AppError::VerificationBlocked(reason) = e {
                                         tracing::warn!(target:"find_email_task", "{} Microsoft 365 headless check blocked: {}", candidate_label, reason);
+                                        self.record_warning(
+                                            WarningCategory::Headless,
+                                            format!(
+                                                "Microsoft 365 headless check for {} blocked: {}",
+                                                email, reason
+                                            ),
+                                            Some(contact),
+                                        );
                                         return Ok(None); // Treat as inconclusive if blocked
                                     } else {
                                         tracing::error!(target:"find_email_task", "{} Microsoft 365 headless failed critically: {}", candidate_label, e);
@@ -661,19 +2287,37 @@ impl EmailSleuth {
                             }
                         } else {
                             tracing::warn!(target:"find_email_task", "{} Skipping Microsoft 365 headless check - WebDriver URL missing.", candidate_label);
+                            self.record_warning(
+                                WarningCategory::Headless,
+                                "Microsoft 365 headless check skipped: WebDriver URL missing"
+                                    .to_string(),
+                                Some(contact),
+                            );
                         }
                     }
                 }
 
-                ProviderType::HotmailB2C if config.enable_headless_checks => {
-                    if let Some(ref webdriver_url) = config.webdriver_url {
-                        if !methods_used.contains(&"headless_hotmail".to_string()) {
-                            methods_used.push("headless_hotmail".to_string());
-                        }
+                ProviderType::HotmailB2C if run_headless && config.enable_headless_checks => {
+                    if let Some(webdriver_url) = self.next_webdriver_url() {
                         tracing::debug!(target:"find_email_task", "{} Performing Microsoft consumer headless check...", candidate_label);
-                        match verification_headless::check_hotmail_headless(email, webdriver_url)
-                            .await
+                        let headless_started = Instant::now();
+                        let headless_result = self
+                            .run_headless_check(
+                                config,
+                                candidate_label,
+                                email,
+                                webdriver_url,
+                                HeadlessProvider::Microsoft,
+                                verification_headless::check_hotmail_headless,
+                            )
+                            .await;
+                        record_stage(run.stage_timings, "headless", headless_started);
+                        if headless_result.is_ok()
+                            && !run.methods_used.contains(&"headless_hotmail".to_string())
                         {
+                            run.methods_used.push("headless_hotmail".to_string());
+                        }
+                        match headless_result {
                             Ok(Some(data)) => {
                                 return Ok(Some(VerificationAttemptOutcome {
                                     status: data.verification_status,
@@ -682,6 +2326,10 @@ impl EmailSleuth {
                                     confidence_boost: data.confidence as i16,
                                     definitive: data.verification_status.is_some(),
                                     is_catch_all: false,
+                                    stage: data.stage,
+                                    smtp_signals: data.smtp_signals,
+                                    verified_at: data.verified_at,
+                                    mx_host: None,
                                 }))
                             }
                             Ok(None) => {
@@ -690,6 +2338,14 @@ impl EmailSleuth {
                             Err(e) => {
                                 if let AppError::VerificationBlocked(reason) = e {
                                     tracing::warn!(target:"find_email_task", "{} Microsoft consumer headless check blocked: {}", candidate_label, reason);
+                                    self.record_warning(
+                                        WarningCategory::Headless,
+                                        format!(
+                                            "Microsoft consumer headless check for {} blocked: {}",
+                                            email, reason
+                                        ),
+                                        Some(contact),
+                                    );
                                     return Ok(None); // Treat as inconclusive if blocked
                                 } else {
                                     tracing::error!(target:"find_email_task", "{} Microsoft consumer headless failed critically: {}", candidate_label, e);
@@ -699,18 +2355,36 @@ impl EmailSleuth {
                         }
                     } else {
                         tracing::warn!(target:"find_email_task", "{} Skipping Microsoft consumer headless check - WebDriver URL missing.", candidate_label);
+                        self.record_warning(
+                            WarningCategory::Headless,
+                            "Microsoft consumer headless check skipped: WebDriver URL missing"
+                                .to_string(),
+                            Some(contact),
+                        );
                     }
                 }
 
-                ProviderType::Yahoo if config.enable_headless_checks => {
-                    if let Some(ref webdriver_url) = config.webdriver_url {
-                        if !methods_used.contains(&"headless_yahoo".to_string()) {
-                            methods_used.push("headless_yahoo".to_string());
-                        }
+                ProviderType::Yahoo if run_headless && config.enable_headless_checks => {
+                    if let Some(webdriver_url) = self.next_webdriver_url() {
                         tracing::debug!(target:"find_email_task", "{} Performing Yahoo headless check...", candidate_label);
-                        match verification_headless::check_yahoo_headless(email, webdriver_url)
-                            .await
+                        let headless_started = Instant::now();
+                        let headless_result = self
+                            .run_headless_check(
+                                config,
+                                candidate_label,
+                                email,
+                                webdriver_url,
+                                HeadlessProvider::Yahoo,
+                                verification_headless::check_yahoo_headless,
+                            )
+                            .await;
+                        record_stage(run.stage_timings, "headless", headless_started);
+                        if headless_result.is_ok()
+                            && !run.methods_used.contains(&"headless_yahoo".to_string())
                         {
+                            run.methods_used.push("headless_yahoo".to_string());
+                        }
+                        match headless_result {
                             Ok(Some(data)) => {
                                 return Ok(Some(VerificationAttemptOutcome {
                                     status: data.verification_status,
@@ -719,6 +2393,10 @@ impl EmailSleuth {
                                     confidence_boost: data.confidence as i16,
                                     definitive: data.verification_status.is_some(),
                                     is_catch_all: false,
+                                    stage: data.stage,
+                                    smtp_signals: data.smtp_signals,
+                                    verified_at: data.verified_at,
+                                    mx_host: None,
                                 }))
                             }
                             Ok(None) => {
@@ -727,6 +2405,14 @@ impl EmailSleuth {
                             Err(e) => {
                                 if let AppError::VerificationBlocked(reason) = e {
                                     tracing::warn!(target:"find_email_task", "{} Yahoo headless check blocked: {}", candidate_label, reason);
+                                    self.record_warning(
+                                        WarningCategory::Headless,
+                                        format!(
+                                            "Yahoo headless check for {} blocked: {}",
+                                            email, reason
+                                        ),
+                                        Some(contact),
+                                    );
                                     return Ok(None);
                                 } else {
                                     tracing::error!(target:"find_email_task", "{} Yahoo headless failed critically: {}", candidate_label, e);
@@ -736,6 +2422,11 @@ impl EmailSleuth {
                         }
                     } else {
                         tracing::warn!(target:"find_email_task", "{} Skipping Yahoo headless check - WebDriver URL missing.", candidate_label);
+                        self.record_warning(
+                            WarningCategory::Headless,
+                            "Yahoo headless check skipped: WebDriver URL missing".to_string(),
+                            Some(contact),
+                        );
                     }
                 }
 
@@ -760,7 +2451,59 @@ impl EmailSleuth {
         Ok(None)
     }
 
+    /// Runs a headless check, retrying up to `headless_max_attempts` times on browser
+    /// or WebDriver failures. Retries move on to the next WebDriver instance; a blocked
+    /// check is returned immediately. An "exists" verdict gets the provider's
+    /// `headless_confidence`.
+    async fn run_headless_check<'a, F, Fut>(
+        &'a self,
+        config: &'a Config,
+        candidate_label: &str,
+        email: &'a str,
+        webdriver_url: &'a str,
+        provider: HeadlessProvider,
+        check: F,
+    ) -> Result<Option<FoundEmailData>>
+    where
+        F: Fn(&'a str, &'a str, &'a [String]) -> Fut,
+        Fut: Future<Output = Result<Option<FoundEmailData>>>,
+    {
+        let max_attempts = config.headless_max_attempts.max(1);
+        let mut url = webdriver_url;
+        let mut attempt = 1;
+        loop {
+            match check(email, url, &config.headless_browser_args).await {
+                Err(e)
+                    if attempt < max_attempts && !matches!(e, AppError::VerificationBlocked(_)) =>
+                {
+                    tracing::warn!(target:"find_email_task", "{} Headless attempt {}/{} failed: {}. Retrying.", candidate_label, attempt, max_attempts, e);
+                    sleep(get_random_sleep_duration(config)).await;
+                    url = self.next_webdriver_url().unwrap_or(webdriver_url);
+                    attempt += 1;
+                }
+                outcome => {
+                    return outcome.map(|found| {
+                        found.map(|mut data| {
+                            if data.verification_status == Some(true) {
+                                data.confidence = config.headless_confidence.for_provider(provider);
+                            }
+                            data
+                        })
+                    })
+                }
+            }
+        }
+    }
+
     /// Runs SMTP verification.
+    ///
+    /// When the primary MX gives no usable session (refused, timed out, failed TLS,
+    /// stalled), an inconclusive answer or a catch-all acceptance, and the domain has a
+    /// backup MX, the next-priority host is probed as well; backups are sometimes stricter
+    /// and answer definitively. A backup's rejection only counts when the server says the
+    /// mailbox is unknown, since backups often refuse to relay for addresses they can't
+    /// check. The host whose answer is kept is recorded.
+    #[cfg(feature = "smtp")]
     async fn run_smtp_verification(
         &self,
         email: &str,
@@ -769,19 +2512,60 @@ impl EmailSleuth {
         methods_used: &mut Vec<String>,
         candidate_label: &str,
     ) -> VerificationAttemptOutcome {
-        if !methods_used.contains(&"smtp_verification".to_string()) {
-            methods_used.push("smtp_verification".to_string());
-        }
         tracing::debug!(target: "find_email_task", "{} Performing SMTP check via {}...", candidate_label, mail_server.exchange);
 
-        let (smtp_status, smtp_message) = self
+        let primary = self
             .smtp_verifier
             .verify_with_retries(email, domain, &mail_server.exchange)
             .await;
+        if let Some(ref warning) = primary.tls_warning {
+            self.record_domain_warning(WarningCategory::Smtp, warning.clone(), domain);
+        }
+        let mut smtp_status = primary.exists;
+        let mut smtp_message = primary.message.clone();
+        let mut smtp_signals = primary.signals;
+        let mut is_catch_all =
+            primary.is_catch_all || smtp_message.to_lowercase().contains("catch-all");
+        let mut mx_host = mail_server.exchange.clone();
+
+        if primary.wants_backup_mx() {
+            if let Some(backup) = mail_server.backups.first() {
+                let reason = if primary.is_connection_failure() {
+                    "gave no session"
+                } else if is_catch_all {
+                    "is catch-all"
+                } else {
+                    "was inconclusive"
+                };
+                tracing::debug!(target: "find_email_task", "{} Primary MX {} {}, probing backup MX {}...", candidate_label, mail_server.exchange, reason, backup);
+                let backup_result = self
+                    .smtp_verifier
+                    .verify_with_retries(email, domain, backup)
+                    .await;
+                if let Some(ref warning) = backup_result.tls_warning {
+                    self.record_domain_warning(WarningCategory::Smtp, warning.clone(), domain);
+                }
+                if backup_result.is_definitive_backup_answer() {
+                    tracing::info!(target: "find_email_task", "{} Backup MX {} gave a definitive answer: {:?}", candidate_label, backup, backup_result.exists);
+                    smtp_status = backup_result.exists;
+                    smtp_message = format!("{} (via backup MX {})", backup_result.message, backup);
+                    smtp_signals = backup_result.signals;
+                    mx_host = backup.clone();
+                    is_catch_all = false;
+                } else {
+                    tracing::debug!(target: "find_email_task", "{} Backup MX {} was not definitive either, keeping primary result.", candidate_label, backup);
+                }
+            }
+        }
 
-        let is_catch_all = smtp_message.to_lowercase().contains("catch-all");
+        // Only a check that got an answer about the mailbox counts as having run.
+        if (smtp_status.is_some() || smtp_signals.is_some() || is_catch_all)
+            && !methods_used.contains(&"smtp_verification".to_string())
+        {
+            methods_used.push("smtp_verification".to_string());
+        }
 
-        let confidence_boost = match smtp_status {
+        let mut confidence_boost = match smtp_status {
             Some(true) => {
                 if is_catch_all {
                     1
@@ -790,15 +2574,28 @@ impl EmailSleuth {
                 }
             }
             Some(false) => -10,
-            None => {
-                if is_catch_all {
-                    0
-                } else {
-                    0
-                }
-            }
+            None => 0,
         };
 
+        // VRFY only corroborates: it shifts confidence but never sets the status.
+        if let Some(vrfy_accepted) = smtp_signals.and_then(|signals| signals.vrfy_accepted) {
+            if !methods_used.contains(&"smtp_vrfy".to_string()) {
+                methods_used.push("smtp_vrfy".to_string());
+            }
+            confidence_boost += match smtp_status {
+                Some(status) if status == vrfy_accepted => 1,
+                Some(_) => -2,
+                None if vrfy_accepted => 1,
+                None => -1,
+            };
+            tracing::debug!(target: "find_email_task", "{} VRFY {} the address.", candidate_label, if vrfy_accepted { "confirmed" } else { "denied" });
+            smtp_message = format!(
+                "{}; VRFY: {}",
+                smtp_message,
+                if vrfy_accepted { "confirmed" } else { "denied" }
+            );
+        }
+
         VerificationAttemptOutcome {
             status: smtp_status,
             message: smtp_message,
@@ -806,34 +2603,34 @@ impl EmailSleuth {
             confidence_boost,
             definitive: smtp_status.is_some(),
             is_catch_all,
+            stage: ResolutionStage::PatternSmtp,
+            smtp_signals,
+            verified_at: smtp_status.map(|_| Utc::now()),
+            mx_host: Some(mx_host),
         }
     }
 
-    /// Calculates the initial confidence score before specific network checks.
-    fn calculate_initial_confidence(
+    /// Stand-in for builds without the `smtp` feature: reports the SMTP step as skipped.
+    #[cfg(not(feature = "smtp"))]
+    async fn run_smtp_verification(
         &self,
-        name_in_email: bool,
-        is_generic: bool,
-        mx_exists: bool,
-    ) -> i16 {
-        // Base score + adjustments
-        let mut score: i16 = 1; // Start with a minimal base score
-        if mx_exists {
-            score += 1;
-        } else {
-            score = 0;
-        } // MX record is crucial
-        if name_in_email {
-            score += 1;
+        _email: &str,
+        _domain: &str,
+        _mail_server: &MailServer,
+        _methods_used: &mut Vec<String>,
+        candidate_label: &str,
+    ) -> VerificationAttemptOutcome {
+        tracing::debug!(target: "find_email_task", "{} SMTP check skipped (built without the 'smtp' feature).", candidate_label);
+        VerificationAttemptOutcome {
+            message: "SMTP: Skipped (not included in this build)".to_string(),
+            source: "skipped_smtp_unavailable".to_string(),
+            stage: ResolutionStage::Pattern,
+            ..Default::default()
         }
-        if is_generic {
-            score = (score - 3).max(0);
-        } // Penalize generic heavily
-        score.clamp(0, 10)
     }
 
     /// Identifies the likely email provider based on domain and MX record.
-    fn identify_provider(&self, domain: &str, mx_host: &str) -> ProviderType {
+    pub(crate) fn identify_provider(domain: &str, mx_host: &str) -> ProviderType {
         let domain_lower = domain.trim().to_lowercase();
         let host_lower_no_dot = mx_host.trim().trim_end_matches('.').to_lowercase();
         let host_lower_with_dot = format!("{}.", host_lower_no_dot);
@@ -892,7 +2689,7 @@ impl EmailSleuth {
         }
 
         // Default if no specific pattern matches
-        return ProviderType::Other;
+        ProviderType::Other
     }
 
     /// Checks if the contact's name parts are present in the email's local part.
@@ -908,12 +2705,41 @@ impl EmailSleuth {
         }
     }
 
-    /// Checks if an email uses a known generic prefix.
-    fn is_generic_prefix(&self, config: &Config, email: &str) -> bool {
-        email.split('@').next().map_or(false, |local| {
-            config
-                .generic_email_prefixes
-                .contains(&local.to_lowercase())
-        })
+    /// Whether `email` looks like a distribution list or shared alias rather than a person.
+    ///
+    /// Any one of these is enough: the server expanded the address to several recipients,
+    /// the local part is a typical list name, or the address has a generic prefix and the
+    /// server confirmed that it exists.
+    fn is_likely_distribution_list(
+        &self,
+        email: &str,
+        is_generic: bool,
+        verification_status: Option<bool>,
+        smtp_signals: Option<&SmtpProbeSignals>,
+    ) -> bool {
+        if smtp_signals
+            .and_then(|signals| signals.expansion_members)
+            .is_some_and(|members| members > 1)
+        {
+            return true;
+        }
+        let local = email.split('@').next().unwrap_or("").to_lowercase();
+        if DISTRIBUTION_LIST_NAMES.contains(&local.as_str())
+            || DISTRIBUTION_LIST_PREFIXES
+                .iter()
+                .any(|prefix| local.starts_with(prefix))
+        {
+            return true;
+        }
+        is_generic && verification_status == Some(true)
+    }
+
+    /// Returns the confidence penalty for an email's generic prefix, or 0 if the prefix
+    /// is not generic (or is configured as neutral).
+    fn generic_prefix_penalty(&self, config: &Config, email: &str) -> u8 {
+        email
+            .split('@')
+            .next()
+            .map_or(0, |local| config.generic_prefix_penalty(local))
     }
 }
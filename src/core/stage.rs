@@ -0,0 +1,150 @@
+//! Custom verification stages.
+//!
+//! Each contact runs through an ordered pipeline of [`PipelineStage`]s. By default it
+//! follows `stage_order` in the config (scraping, DNS, provider APIs, headless browser,
+//! then SMTP); [`EmailSleuth::with_pipeline`](crate::EmailSleuth::with_pipeline) replaces
+//! it with any order of built-in and [`VerificationStage`] implementations, for example to
+//! consult an internal directory before probing SMTP.
+
+use crate::core::error::Result;
+use crate::core::models::{Contact, ResolutionStage};
+
+use futures::future::BoxFuture;
+use std::fmt;
+use std::sync::Arc;
+
+/// What the stages that already ran found out about a candidate.
+#[derive(Debug, Clone)]
+pub struct StageEvidence {
+    /// The current verification status (True=Exists, False=Does not exist, None=Unknown).
+    pub status: Option<bool>,
+    /// Confidence so far (0-10).
+    pub confidence: u8,
+    /// The method that produced `status` ("initial" if none has yet).
+    pub source: String,
+    /// The most recent stage message.
+    pub message: String,
+    /// The pipeline stage that produced `status`.
+    pub stage: ResolutionStage,
+}
+
+/// The inputs a [`VerificationStage`] sees for one candidate.
+#[derive(Debug, Clone, Copy)]
+pub struct StageContext<'a> {
+    /// The contact as given in the input.
+    pub contact: &'a Contact,
+    /// The contact's full name, constructed from first and last name if not given.
+    pub full_name: &'a str,
+    /// The normalized company domain.
+    pub domain: &'a str,
+    /// The candidate address being verified.
+    pub email: &'a str,
+    /// Every candidate for this contact, in the order they are verified.
+    pub candidates: &'a [String],
+    /// What earlier stages found for `email`.
+    pub evidence: &'a StageEvidence,
+}
+
+/// The result of a [`VerificationStage`] for one candidate.
+#[derive(Debug, Clone, Default)]
+pub struct StageOutcome {
+    /// True = Exists, False = Does not exist, None = No answer.
+    pub status: Option<bool>,
+    /// A short explanation, recorded in the verification log.
+    pub message: String,
+    /// Added to the candidate's confidence; replaces it when `definitive` is set.
+    pub confidence_boost: i16,
+    /// The answer is authoritative: `status` wins over earlier answers and no later stage runs.
+    pub definitive: bool,
+}
+
+/// A user-supplied step in the per-candidate verification pipeline.
+pub trait VerificationStage: Send + Sync {
+    /// Identifies the stage in `source`, `methods_used` and `stage_timings`.
+    fn name(&self) -> &str;
+
+    /// Checks one candidate. Returning `Ok(None)` leaves the evidence unchanged; an error
+    /// is recorded as a warning and the pipeline continues with the next stage.
+    fn verify<'a>(
+        &'a self,
+        context: StageContext<'a>,
+    ) -> BoxFuture<'a, Result<Option<StageOutcome>>>;
+}
+
+/// One step of the pipeline. `Scrape` and `Dns` run once per contact; the others check
+/// each candidate in turn.
+#[derive(Clone)]
+pub enum PipelineStage {
+    /// Website scraping. Listed after a verification stage, the pattern candidates are
+    /// checked first and the website is only scraped if none of them is verified.
+    Scrape,
+    /// MX lookup of the contact's domain. Listed before `Scrape`, scraping is skipped for
+    /// domains without a mail server.
+    Dns,
+    /// Provider API and headless browser checks, where enabled and applicable.
+    Alternative,
+    /// Provider API checks only.
+    Api,
+    /// Headless browser checks only.
+    Headless,
+    /// SMTP RCPT TO probing.
+    Smtp,
+    /// A user-supplied stage.
+    Custom(Arc<dyn VerificationStage>),
+}
+
+impl PipelineStage {
+    /// The built-in pipeline: scraping, DNS, alternative checks, then SMTP.
+    pub fn default_pipeline() -> Vec<PipelineStage> {
+        vec![
+            PipelineStage::Scrape,
+            PipelineStage::Dns,
+            PipelineStage::Alternative,
+            PipelineStage::Smtp,
+        ]
+    }
+
+    /// The built-in stages named in `stage_order`, in that order. Unknown names are skipped.
+    pub fn for_stage_order(stage_order: &[String]) -> Vec<PipelineStage> {
+        stage_order
+            .iter()
+            .filter_map(|stage| match stage.as_str() {
+                "scrape" => Some(PipelineStage::Scrape),
+                "dns" => Some(PipelineStage::Dns),
+                "api" => Some(PipelineStage::Api),
+                "headless" => Some(PipelineStage::Headless),
+                "smtp" => Some(PipelineStage::Smtp),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Whether this stage runs once per contact rather than for each candidate.
+    pub(crate) fn is_per_contact(&self) -> bool {
+        matches!(self, PipelineStage::Scrape | PipelineStage::Dns)
+    }
+
+    /// Whether this stage runs the provider API checks.
+    pub(crate) fn runs_api(&self) -> bool {
+        matches!(self, PipelineStage::Alternative | PipelineStage::Api)
+    }
+
+    /// Whether this stage runs the headless browser checks.
+    pub(crate) fn runs_headless(&self) -> bool {
+        matches!(self, PipelineStage::Alternative | PipelineStage::Headless)
+    }
+}
+
+impl fmt::Debug for PipelineStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PipelineStage::Scrape => write!(f, "Scrape"),
+            PipelineStage::Dns => write!(f, "Dns"),
+            PipelineStage::Alternative => write!(f, "Alternative"),
+            PipelineStage::Api => write!(f, "Api"),
+            PipelineStage::Headless => write!(f, "Headless"),
+            PipelineStage::Smtp => write!(f, "Smtp"),
+            PipelineStage::Custom(stage) => write!(f, "Custom({})", stage.name()),
+        }
+    }
+}
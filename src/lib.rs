@@ -10,16 +10,40 @@ mod core;
 mod utils;
 mod verification;
 
-pub use crate::core::config::{Config, ConfigBuilder, ConfigFile};
-pub use crate::core::error::{AppError, Result};
-pub use crate::core::models::{Contact, EmailResult, FoundEmailData, ProcessingResult};
+pub use crate::core::config::{
+    default_common_pages, default_generic_prefixes, default_headless_browser_args,
+    default_stage_order, Config, ConfigBuilder, ConfigFile, HeadlessConfidence, OutputOrder,
+    ProbeCharset, SmtpDialogueStep, TlsVersion, VerificationLevel, DEFAULT_GENERIC_PREFIX_PENALTY,
+};
+pub use crate::core::error::{AppError, DomainErrorKind, Result};
+pub use crate::core::input::{ContactReader, InputFormat};
+pub use crate::core::log_capture::{contact_log_layer, ContactLogLayer};
+pub use crate::core::models::{
+    Contact, DecisionTrace, EmailResult, FailureKind, FailureRecord, FoundEmailData,
+    ProcessingResult, ResolutionStage, SkippedMethod, SmtpProbeSignals, TraceCandidate,
+    TraceMailServer, TraceScrapedEmail, TraceStep, Verdict, VerificationState, Warning,
+    WarningCategory,
+};
+pub use crate::core::output::{JsonWriter, OutputWriter};
+pub use crate::core::report::{DomainReport, DomainReports};
+pub use crate::core::scoring::{CandidateScorer, CandidateSignals, DefaultScorer};
 pub use crate::core::sleuth::EmailSleuth;
+pub use crate::core::stage::{
+    PipelineStage, StageContext, StageEvidence, StageOutcome, VerificationStage,
+};
+#[cfg(feature = "smtp")]
+pub use crate::utils::smtp::SenderCheckReport;
+pub use crate::verification::headless::HeadlessProvider;
+#[cfg(feature = "headless")]
+pub use crate::verification::headless::{SelectorCheck, SelectorCheckStatus, SelfTestAddresses};
 
 use crate::core::models::ValidatedContact;
-use crate::utils::smtp::test_smtp_connectivity;
+use crate::core::scheduling::DomainFairQueue;
 use futures::stream::{FuturesUnordered, StreamExt};
+use std::collections::HashMap;
 use std::sync::Arc;
-use tracing;
+use tracing::{self, Instrument};
+use url::Url;
 
 /// Initializes shared resources like HTTP client and DNS resolver.
 /// Essential for creating an `EmailSleuth` instance.
@@ -27,9 +51,80 @@ pub async fn initialize_sleuth(config: &Config) -> Result<EmailSleuth> {
     EmailSleuth::new(config).await
 }
 
+/// Like [`initialize_sleuth`], but uses the given HTTP client for website scraping and
+/// API checks instead of building one from `config`.
+///
+/// Use this to apply organization-specific networking policy (TLS pinning, proxies,
+/// default auth headers). Start from [`http_client_builder`] to keep the user agent,
+/// timeout and response decompression settings the scraper relies on.
+pub async fn initialize_sleuth_with_http_client(
+    config: &Config,
+    http_client: reqwest::Client,
+) -> Result<EmailSleuth> {
+    EmailSleuth::with_http_client(config, http_client).await
+}
+
+/// Like [`initialize_sleuth`], but with the HTTP client and DNS resolver supplied by the
+/// caller where given. Each one left as `None` is built from `config` as usual.
+///
+/// A supplied resolver is used for every mail server lookup, so `dns_servers`,
+/// `dns_timeout` and the other DNS settings do not apply to it; `dns_overrides`,
+/// `max_dns_concurrency` and `dead_domain_ttl` still do. A supplied resolver keeps its own
+/// negative caching, so set its `negative_max_ttl` to at most `dead_domain_ttl` for a
+/// domain's lookup after its dead mark expires to reach DNS. Useful for tests (a resolver
+/// pointed at a local DNS server) and for environments with a preconfigured resolver.
+pub async fn initialize_sleuth_with_clients(
+    config: &Config,
+    http_client: Option<reqwest::Client>,
+    dns_resolver: Option<trust_dns_resolver::TokioAsyncResolver>,
+) -> Result<EmailSleuth> {
+    let http_client = match http_client {
+        Some(client) => client,
+        None => crate::utils::scraping::build_http_client(config)?,
+    };
+    EmailSleuth::with_clients(config, http_client, dns_resolver).await
+}
+
+/// Returns a `reqwest::ClientBuilder` with the settings Email Sleuth uses for its own HTTP
+/// client, for customizing before passing the result to [`initialize_sleuth_with_http_client`].
+pub fn http_client_builder(config: &Config) -> reqwest::ClientBuilder {
+    crate::utils::scraping::http_client_builder(config)
+}
+
 /// Performs an early check for SMTP connectivity.
+#[cfg(feature = "smtp")]
 pub async fn check_smtp_connectivity() -> Result<()> {
-    test_smtp_connectivity().await
+    crate::utils::smtp::test_smtp_connectivity().await
+}
+
+/// Checks that the sender domain publishes SPF and this host's public IP has
+/// forward-confirmed reverse DNS, so SMTP probes are not rejected on sight.
+///
+/// Problems are returned in the report rather than as an error; callers decide whether
+/// to continue (the CLI refuses to start when `smtp_sender_checks` is set).
+#[cfg(feature = "smtp")]
+pub async fn check_sender_setup(config: &Config) -> Result<SenderCheckReport> {
+    crate::utils::smtp::check_sender_setup(config).await
+}
+
+/// Runs the headless selector self-test against the first configured WebDriver instance.
+///
+/// Each provider's flow is run with a made-up address and, if given, an address known
+/// to exist; checks with [`SelectorCheckStatus::Stale`] point at selectors that need updating.
+#[cfg(feature = "headless")]
+pub async fn run_selector_self_test(
+    config: &Config,
+    addresses: &SelfTestAddresses,
+) -> Result<Vec<SelectorCheck>> {
+    let webdriver_url = config.webdriver_urls().into_iter().next().ok_or_else(|| {
+        AppError::Config("A WebDriver URL is required for the selector self-test.".to_string())
+    })?;
+    crate::verification::headless::run_selector_self_test(
+        &webdriver_url,
+        &config.headless_browser_args,
+        addresses,
+    )
+    .await
 }
 
 /// Processes a single contact to find an email address.
@@ -44,6 +139,10 @@ pub async fn check_smtp_connectivity() -> Result<()> {
 ///
 /// # Returns
 /// * `ProcessingResult` containing the outcome.
+///
+/// The work runs in a `contact` tracing span named after the task. With
+/// `capture_failure_logs` enabled and [`contact_log_layer`] installed, the span's log lines
+/// are attached to the result when no likely email is found.
 pub async fn find_single_email(
     config: &Config,
     sleuth: &EmailSleuth,
@@ -58,18 +157,50 @@ pub async fn find_single_email(
             .or(contact.company_domain.as_deref())
             .unwrap_or("N/A")
     );
+    let span = tracing::debug_span!(
+        crate::core::log_capture::CONTACT_SPAN,
+        task = %task_id,
+        capture_logs = config.capture_failure_logs
+    );
+
+    async {
+        let mut result = process_single_contact(config, sleuth, contact, &task_id).await;
+        if let Some(lines) = crate::core::log_capture::take_contact_logs() {
+            if result.email.is_none() {
+                // Input from an earlier failures file carries that run's log; replace it.
+                result.contact_input.other_fields.remove("debug_log");
+                result.debug_log = lines;
+            }
+        }
+        result
+    }
+    .instrument(span)
+    .await
+}
+
+async fn process_single_contact(
+    config: &Config,
+    sleuth: &EmailSleuth,
+    contact: Contact,
+    task_id: &str,
+) -> ProcessingResult {
     tracing::info!(target: "find_single_email", "[{}] Starting processing.", task_id);
 
-    let validation_result = validate_contact_input(&contact);
+    let validation_result = validate_contact_input(&contact, config);
 
     let validated_contact = match validation_result {
         Ok(vc) => vc,
-        Err(reason) => {
-            tracing::warn!(target: "find_single_email", "[{}] Skipping record. Reason: {}", task_id, reason);
-            return ProcessingResult::skipped(contact, reason);
+        Err(e) => {
+            tracing::warn!(target: "find_single_email", "[{}] Skipping record. Reason: {}", task_id, e);
+            return ProcessingResult::skipped(contact, &e, config);
         }
     };
 
+    if let Some(skipped) = dead_domain_skip(config, sleuth, &contact, &validated_contact.domain) {
+        tracing::info!(target: "find_single_email", "[{}] Skipping record. Reason: domain {} is dead.", task_id, validated_contact.domain);
+        return skipped;
+    }
+
     tracing::debug!(target: "find_single_email", "[{}] Contact validated, proceeding to find_email.", task_id);
 
     let find_result: std::result::Result<EmailResult, AppError> =
@@ -78,15 +209,17 @@ pub async fn find_single_email(
     match find_result {
         Ok(results) => {
             let mut final_record = ProcessingResult::success(contact, results.clone(), config);
-            if final_record.email.is_some() {
+            if let Some(email) = final_record.email.as_deref() {
                 tracing::info!(target: "find_single_email",
                     "[{}] ✓ Found likely email: {} (Confidence: {}/10)",
-                    task_id, final_record.email.as_ref().unwrap(), final_record.email_confidence.unwrap_or(0)
+                    task_id, email, final_record.email_confidence.unwrap_or(0)
                 );
             } else {
                 tracing::info!(target: "find_single_email", "[{}] No high-confidence email found.", task_id);
                 if !results.found_emails.is_empty() {
                     final_record.email_verification_failed = true;
+                    final_record.email_verification_inconclusive =
+                        results.found_emails.iter().any(|e| e.is_inconclusive());
                 }
             }
             tracing::info!(target: "find_single_email", "[{}] Finished processing.", task_id);
@@ -96,7 +229,7 @@ pub async fn find_single_email(
             tracing::error!(target: "find_single_email",
                 "[{}] !!! Error during core email finding: {}", task_id, e
             );
-            ProcessingResult::error(contact, format!("Core processing error: {}", e))
+            ProcessingResult::error(contact, &e, config)
         }
     }
 }
@@ -105,71 +238,331 @@ pub async fn find_single_email(
 /// # Arguments
 /// * `config` - The application configuration.
 /// * `sleuth` - An Arc-wrapped, initialized `EmailSleuth` instance for sharing.
-/// * `contacts` - A vector of input `Contact` records.
+/// * `contacts` - The input `Contact` records.
 ///
 /// # Returns
-/// * `Vec<ProcessingResult>` containing outcomes for all input contacts.
+/// * `Vec<ProcessingResult>` containing outcomes for all input contacts, in input order.
 pub async fn process_contacts(
     config: Arc<Config>,
     sleuth: Arc<EmailSleuth>,
-    contacts: Vec<Contact>,
+    contacts: &[Contact],
 ) -> Vec<ProcessingResult> {
+    process_contacts_with_progress(config, sleuth, contacts, |_| {}).await
+}
+
+/// Like [`process_contacts`], but passes each result to `on_result` as soon as it completes,
+/// for progress reporting or snapshots of a long run.
+///
+/// Results reach `on_result` in completion order; the returned results are in input order.
+pub async fn process_contacts_with_progress<F>(
+    config: Arc<Config>,
+    sleuth: Arc<EmailSleuth>,
+    contacts: &[Contact],
+    mut on_result: F,
+) -> Vec<ProcessingResult>
+where
+    F: FnMut(&ProcessingResult),
+{
     let total_records = contacts.len();
     if total_records == 0 {
         return Vec::new();
     }
 
+    if sleuth.runs_dns(&config) {
+        let domains = contacts
+            .iter()
+            .filter_map(|contact| validate_contact_input(contact, &config).ok())
+            .map(|vc| vc.domain);
+        sleuth.prefetch_mail_servers(domains).await;
+    }
+
     let mut tasks = FuturesUnordered::new();
     let mut results = Vec::with_capacity(total_records);
+    let mut queue = DomainFairQueue::new();
 
-    for contact in contacts {
-        let validation_result = validate_contact_input(&contact);
-
-        if let Err(reason) = validation_result {
-            results.push(ProcessingResult::skipped(contact, reason));
-            continue;
+    for (index, contact) in contacts.iter().enumerate() {
+        match validate_contact_input(contact, &config) {
+            Ok(valid) => queue.push(
+                scheduling_key(&config, &valid),
+                (index, valid.domain.clone()),
+            ),
+            Err(e) => {
+                let skipped = ProcessingResult::skipped(contact.clone(), &e, &config);
+                on_result(&skipped);
+                results.push((index, skipped));
+            }
         }
+    }
 
-        while tasks.len() >= config.max_concurrency {
-            if let Some(join_handle_result) = tasks.next().await {
-                match join_handle_result {
-                    Ok(processing_result) => {
-                        results.push(processing_result);
-                    }
-                    Err(e) => {
-                        tracing::error!("A processing task failed to join: {}", e);
-                    }
-                }
-            } else {
-                tracing::warn!("Task queue unexpectedly empty while limiting concurrency.");
+    loop {
+        while tasks.len() < config.max_concurrency {
+            let Some((key, (index, domain))) = queue.pop() else {
                 break;
+            };
+            if let Some(skipped) = dead_domain_skip(&config, &sleuth, &contacts[index], &domain) {
+                queue.finish(&key);
+                on_result(&skipped);
+                results.push((index, skipped));
+                continue;
             }
-        }
+            let sleuth_clone = Arc::clone(&sleuth);
+            let config_clone = Arc::clone(&config);
+            let valid_contact = contacts[index].clone();
 
-        let sleuth_clone = Arc::clone(&sleuth);
-        let config_clone = Arc::clone(&config);
-        let valid_contact = contact;
+            // The key and index stay out here so a panicked task still frees its slot.
+            let task = tokio::spawn(async move {
+                find_single_email(&config_clone, &sleuth_clone, valid_contact).await
+            });
+            tasks.push(async move { (key, index, task.await) });
+        }
 
-        tasks.push(tokio::spawn(async move {
-            find_single_email(&config_clone, &sleuth_clone, valid_contact).await
-        }));
+        match tasks.next().await {
+            Some((key, index, joined)) => {
+                queue.finish(&key);
+                let processing_result = joined.unwrap_or_else(|e| {
+                    failed_task_result(&config, &sleuth, contacts[index].clone(), e)
+                });
+                on_result(&processing_result);
+                results.push((index, processing_result));
+            }
+            None => break,
+        }
     }
 
-    while let Some(join_handle_result) = tasks.next().await {
-        match join_handle_result {
-            Ok(processing_result) => {
-                results.push(processing_result);
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Processes contacts who all work at `domain`, doing the domain-level work once.
+///
+/// The mail server is resolved and, when SMTP probing applies, the domain is probed once
+/// for catch-all behaviour before any contact runs. Every contact then shares those
+/// facts: a catch-all domain skips per-contact SMTP probes, and on a domain that rejects
+/// unknown recipients each probe skips its own catch-all check. Contacts without a
+/// domain are given `domain`; contacts with a different one are processed as usual.
+///
+/// # Returns
+/// * `Vec<ProcessingResult>` containing outcomes for all input contacts, in input order.
+pub async fn find_for_domain(
+    domain: &str,
+    contacts: &[Contact],
+    config: Arc<Config>,
+    sleuth: Arc<EmailSleuth>,
+) -> Result<Vec<ProcessingResult>> {
+    let domain = normalize_domain(domain, &config)?;
+    tracing::info!(
+        "Settling domain-level facts for {} before processing {} contacts...",
+        domain,
+        contacts.len()
+    );
+    let website = crate::utils::domain::normalize_url(&domain)?;
+    sleuth.warm_domain(&config, &domain, &website).await;
+
+    let contacts: Vec<Contact> = contacts
+        .iter()
+        .map(|contact| {
+            let mut contact = contact.clone();
+            if contact.domain.is_none() && contact.company_domain.is_none() {
+                contact.domain = Some(domain.clone());
             }
-            Err(e) => {
-                tracing::error!("A processing task failed to join during final drain: {}", e);
+            contact
+        })
+        .collect();
+    Ok(process_contacts(config, sleuth, &contacts).await)
+}
+
+/// Processes contacts as they are pulled from `contacts`, passing each result to `sink`
+/// as soon as it completes.
+///
+/// At most `max_concurrency` contacts are in flight, and only a bounded window of upcoming
+/// contacts is read ahead (see [`Config::domain_fair_scheduling`]), so memory use does not
+/// grow with the size of the input. Results arrive
+/// in completion order, not input order. Mail servers are not prefetched up front;
+/// each domain is warmed up when its first contact starts.
+///
+/// # Returns
+/// * The number of results passed to `sink`, or the first error `sink` returned.
+pub async fn process_contacts_streaming<I, F>(
+    config: Arc<Config>,
+    sleuth: Arc<EmailSleuth>,
+    contacts: I,
+    mut sink: F,
+) -> Result<usize>
+where
+    I: IntoIterator<Item = Contact>,
+    F: FnMut(ProcessingResult) -> Result<()>,
+{
+    let mut tasks = FuturesUnordered::new();
+    let mut delivered = 0;
+    let mut queue = DomainFairQueue::new();
+    let lookahead = config.max_concurrency.max(1) * STREAM_LOOKAHEAD_PER_SLOT;
+    let mut contacts = contacts.into_iter().fuse();
+
+    loop {
+        while queue.pending() < lookahead {
+            let Some(contact) = contacts.next() else {
+                break;
+            };
+            match validate_contact_input(&contact, &config) {
+                Ok(valid) => queue.push(
+                    scheduling_key(&config, &valid),
+                    (contact, valid.domain.clone()),
+                ),
+                Err(e) => {
+                    sink(ProcessingResult::skipped(contact, &e, &config))?;
+                    delivered += 1;
+                }
             }
         }
+
+        while tasks.len() < config.max_concurrency {
+            let Some((key, (contact, domain))) = queue.pop() else {
+                break;
+            };
+            if let Some(skipped) = dead_domain_skip(&config, &sleuth, &contact, &domain) {
+                queue.finish(&key);
+                sink(skipped)?;
+                delivered += 1;
+                continue;
+            }
+            let sleuth_clone = Arc::clone(&sleuth);
+            let config_clone = Arc::clone(&config);
+            let task_contact = contact.clone();
+            // The key and contact stay out here so a panicked task still frees its slot.
+            let task = tokio::spawn(async move {
+                find_single_email(&config_clone, &sleuth_clone, task_contact).await
+            });
+            tasks.push(async move { (key, contact, task.await) });
+        }
+
+        match tasks.next().await {
+            Some((key, contact, joined)) => {
+                queue.finish(&key);
+                let processing_result =
+                    joined.unwrap_or_else(|e| failed_task_result(&config, &sleuth, contact, e));
+                sink(processing_result)?;
+                delivered += 1;
+            }
+            None => break,
+        }
+    }
+
+    Ok(delivered)
+}
+
+/// The result for a contact whose processing task panicked or was cancelled. The failure
+/// is also collected as a `task` warning.
+fn failed_task_result(
+    config: &Config,
+    sleuth: &EmailSleuth,
+    contact: Contact,
+    error: tokio::task::JoinError,
+) -> ProcessingResult {
+    tracing::error!("A processing task failed to join: {}", error);
+    sleuth.record_warning(
+        WarningCategory::Task,
+        format!("A processing task failed to join: {}", error),
+        None,
+    );
+    ProcessingResult::error(contact, &AppError::Task(error.to_string()), config)
+}
+
+/// The skipped result for a contact at a domain found dead earlier in the run, when
+/// `skip_dead_domains` is on.
+///
+/// Batches check this when they hand a contact out as well as when it starts, so contacts
+/// queued behind the one that found the domain dead never take a concurrency slot.
+fn dead_domain_skip(
+    config: &Config,
+    sleuth: &EmailSleuth,
+    contact: &Contact,
+    domain: &str,
+) -> Option<ProcessingResult> {
+    if !config.skip_dead_domains {
+        return None;
+    }
+    let reason = sleuth.dead_domain(config, domain)?;
+    let e = AppError::DomainDead(format!("{} {}", domain, reason));
+    Some(ProcessingResult::skipped(contact.clone(), &e, config))
+}
+
+/// How many contacts per concurrency slot [`process_contacts_streaming`] reads ahead, so
+/// domain-fair scheduling has other domains to pick from.
+const STREAM_LOOKAHEAD_PER_SLOT: usize = 16;
+
+/// The key contacts are spread across when scheduling a batch: the domain when
+/// [`Config::domain_fair_scheduling`] is on, otherwise one shared key (plain input order).
+fn scheduling_key<'a>(config: &Config, contact: &'a ValidatedContact) -> &'a str {
+    if config.domain_fair_scheduling {
+        &contact.domain
+    } else {
+        ""
     }
+}
 
-    results
+/// Writes `results` to `writer` in order, then finishes the output.
+///
+/// Any [`OutputWriter`] can be used, so custom formats plug in alongside the built-in
+/// [`JsonWriter`].
+pub fn write_results(results: &[ProcessingResult], writer: &mut dyn OutputWriter) -> Result<()> {
+    for result in results {
+        writer.write_result(result)?;
+    }
+    writer.finish()
+}
+
+/// Orders batch results for output according to `order`.
+///
+/// Results from [`process_contacts`] are already in input order, so
+/// [`OutputOrder::Input`] leaves them unchanged.
+pub fn sort_results(results: &mut [ProcessingResult], order: OutputOrder) {
+    fn domain_key(r: &ProcessingResult) -> (&str, &str, &str, &str) {
+        let contact = &r.contact_input;
+        (
+            contact
+                .domain
+                .as_deref()
+                .or(contact.company_domain.as_deref())
+                .unwrap_or(""),
+            contact.last_name.as_deref().unwrap_or(""),
+            contact.first_name.as_deref().unwrap_or(""),
+            contact.full_name.as_deref().unwrap_or(""),
+        )
+    }
+
+    match order {
+        OutputOrder::Input => {}
+        OutputOrder::Domain => results.sort_by(|a, b| domain_key(a).cmp(&domain_key(b))),
+        OutputOrder::Confidence => results.sort_by(|a, b| {
+            b.email
+                .is_some()
+                .cmp(&a.email.is_some())
+                .then_with(|| b.email_confidence.cmp(&a.email_confidence))
+                .then_with(|| domain_key(a).cmp(&domain_key(b)))
+        }),
+    }
+}
+
+/// Reduces a domain or URL to the domain used for patterns and verification: the
+/// registrable domain, or the full host with `keep_subdomains`.
+fn normalize_domain(input: &str, config: &Config) -> Result<String> {
+    let host =
+        crate::utils::domain::get_domain_from_url_with_overrides(input, &config.dns_overrides)?;
+    if config.keep_subdomains {
+        return Ok(host);
+    }
+    let registrable = crate::utils::domain::registrable_domain(&host);
+    if registrable != host {
+        tracing::debug!(
+            "Using registrable domain '{}' for host '{}'",
+            registrable,
+            host
+        );
+    }
+    Ok(registrable)
 }
 
-fn validate_contact_input(record: &Contact) -> std::result::Result<ValidatedContact, String> {
+fn validate_contact_input(record: &Contact, config: &Config) -> Result<ValidatedContact> {
     let mut first_name = record
         .first_name
         .as_deref()
@@ -219,23 +612,39 @@ fn validate_contact_input(record: &Contact) -> std::result::Result<ValidatedCont
     }
 
     if !missing_parts.is_empty() {
-        return Err(format!("Missing {}", missing_parts.join(", ")));
+        return Err(AppError::InsufficientInput(format!(
+            "Missing {}",
+            missing_parts.join(", ")
+        )));
     }
 
-    let domain = match crate::utils::domain::get_domain_from_url(&domain_input_str) {
-        Ok(d) => d,
-        Err(e) => {
-            return Err(format!(
-                "Cannot extract domain from '{}': {}",
-                domain_input_str, e
-            ))
-        }
-    };
+    let domain = normalize_domain(&domain_input_str, config)?;
+    if config.disposable_domains.contains(&domain) {
+        return Err(AppError::InsufficientInput(format!(
+            "Domain {} is a disposable email domain",
+            domain
+        )));
+    }
 
-    let website_url = match crate::utils::domain::normalize_url(&domain_input_str) {
-        Ok(url) => url,
-        Err(e) => return Err(format!("Invalid URL '{}': {}", domain_input_str, e)),
-    };
+    let website_url = crate::utils::domain::normalize_url(&domain_input_str)?;
+
+    let mut additional_websites: Vec<Url> = Vec::new();
+    for site in record
+        .websites
+        .iter()
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+    {
+        match crate::utils::domain::normalize_url(site) {
+            Ok(url) if url != website_url && !additional_websites.contains(&url) => {
+                additional_websites.push(url)
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::warn!(target: "find_single_email", "Ignoring invalid website '{}': {}", site, e);
+            }
+        }
+    }
 
     let final_full_name = if !original_full_name.is_empty() {
         original_full_name
@@ -248,13 +657,18 @@ fn validate_contact_input(record: &Contact) -> std::result::Result<ValidatedCont
         last_name,
         full_name: final_full_name,
         website_url,
+        additional_websites,
         domain,
         original_contact: record.clone(),
     })
 }
 
 impl ProcessingResult {
-    fn skipped(contact: Contact, reason: String) -> Self {
+    fn skipped(contact: Contact, error: &AppError, config: &Config) -> Self {
+        let reason = match error {
+            AppError::InsufficientInput(message) => message.clone(),
+            other => other.to_string(),
+        };
         Self {
             contact_input: contact,
             email_discovery_results: None,
@@ -265,11 +679,16 @@ impl ProcessingResult {
             email_finding_skipped: true,
             email_finding_reason: Some(reason),
             email_verification_failed: false,
+            email_verification_inconclusive: false,
             email_finding_error: None,
+            email_finding_error_code: Some(error.code().to_string()),
+            debug_log: Vec::new(),
+            run_metadata: HashMap::new(),
         }
+        .with_run_metadata(config)
     }
 
-    fn error(contact: Contact, error_msg: String) -> Self {
+    fn error(contact: Contact, error: &AppError, config: &Config) -> Self {
         Self {
             contact_input: contact,
             email_discovery_results: None,
@@ -280,8 +699,13 @@ impl ProcessingResult {
             email_finding_skipped: false,
             email_finding_reason: None,
             email_verification_failed: false,
-            email_finding_error: Some(error_msg),
+            email_verification_inconclusive: false,
+            email_finding_error: Some(format!("Core processing error: {}", error)),
+            email_finding_error_code: Some(error.code().to_string()),
+            debug_log: Vec::new(),
+            run_metadata: HashMap::new(),
         }
+        .with_run_metadata(config)
     }
 
     fn success(contact: Contact, results: EmailResult, config: &Config) -> Self {
@@ -289,6 +713,7 @@ impl ProcessingResult {
             .found_emails
             .iter()
             .filter(|e| Some(&e.email) != results.most_likely_email.as_ref())
+            .filter(|e| e.confidence >= config.min_alternative_confidence)
             .take(config.max_alternatives)
             .map(|e| e.email.clone())
             .collect();
@@ -306,7 +731,398 @@ impl ProcessingResult {
             email_finding_skipped: false,
             email_finding_reason: None,
             email_verification_failed: false,
+            email_verification_inconclusive: false,
             email_finding_error: None,
+            email_finding_error_code: None,
+            debug_log: Vec::new(),
+            run_metadata: HashMap::new(),
+        }
+        .with_run_metadata(config)
+    }
+
+    /// Copies `config.run_metadata` into the record, dropping any `run_metadata` carried
+    /// over in the input fields from an earlier run's output so the key isn't written twice.
+    fn with_run_metadata(mut self, config: &Config) -> Self {
+        if !config.run_metadata.is_empty() {
+            self.contact_input.other_fields.remove("run_metadata");
+            self.run_metadata = config.run_metadata.clone();
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::future::BoxFuture;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, UdpSocket};
+    use trust_dns_resolver::config::{NameServerConfigGroup, ResolverConfig, ResolverOpts};
+    use trust_dns_resolver::TokioAsyncResolver;
+
+    /// Starts a DNS server on localhost that answers every query with NXDOMAIN, and
+    /// returns a resolver pointed at it, caching negative answers for at most
+    /// `negative_max_ttl`, along with its query count.
+    async fn nxdomain_resolver(
+        negative_max_ttl: Duration,
+    ) -> (TokioAsyncResolver, Arc<AtomicUsize>) {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let port = socket.local_addr().unwrap().port();
+        let queries = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&queries);
+        tokio::spawn(async move {
+            let mut buf = [0u8; 512];
+            while let Ok((len, peer)) = socket.recv_from(&mut buf).await {
+                counter.fetch_add(1, Ordering::SeqCst);
+                // Header and question only: QR/RD/RA set, RCODE 3 (NXDOMAIN).
+                let mut end = 12;
+                while end < len && buf[end] != 0 {
+                    end += buf[end] as usize + 1;
+                }
+                end += 5;
+                let mut reply = buf[..end.min(len)].to_vec();
+                reply[2] = 0x81;
+                reply[3] = 0x83;
+                reply[6..12].fill(0);
+                let _ = socket.send_to(&reply, peer).await;
+            }
+        });
+
+        let resolver_config = ResolverConfig::from_parts(
+            None,
+            Vec::new(),
+            NameServerConfigGroup::from_ips_clear(&["127.0.0.1".parse().unwrap()], port, true),
+        );
+        let mut opts = ResolverOpts::default();
+        opts.timeout = Duration::from_secs(2);
+        opts.attempts = 1;
+        opts.negative_max_ttl = Some(negative_max_ttl);
+        (TokioAsyncResolver::tokio(resolver_config, opts), queries)
+    }
+
+    fn contact(first: &str, last: &str, domain: &str) -> Contact {
+        Contact {
+            first_name: Some(first.to_string()),
+            last_name: Some(last.to_string()),
+            domain: Some(domain.to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// One contact at a time, with DNS before scraping so nothing but the resolver is used.
+    fn dead_domain_config() -> Config {
+        Config {
+            skip_dead_domains: true,
+            max_concurrency: 1,
+            stage_order: ["dns", "scrape", "api", "headless", "smtp"]
+                .map(String::from)
+                .to_vec(),
+            ..Default::default()
+        }
+    }
+
+    /// acme.test, with its mail server set through `dns_overrides` so no lookup is made.
+    fn acme_config() -> Config {
+        Config {
+            smtp_prefetch_catch_all: false,
+            dns_overrides: HashMap::from([(
+                "acme.test".to_string(),
+                vec!["mx.acme.test".to_string()],
+            )]),
+            ..Default::default()
+        }
+    }
+
+    async fn run_streaming(
+        config: Config,
+        contacts: Vec<Contact>,
+    ) -> (Vec<ProcessingResult>, usize) {
+        let (resolver, queries) = nxdomain_resolver(config.dead_domain_ttl).await;
+        let sleuth = initialize_sleuth_with_clients(&config, None, Some(resolver))
+            .await
+            .unwrap();
+        let mut results = Vec::new();
+        process_contacts_streaming(Arc::new(config), Arc::new(sleuth), contacts, |result| {
+            results.push(result);
+            Ok(())
+        })
+        .await
+        .unwrap();
+        (results, queries.load(Ordering::SeqCst))
+    }
+
+    #[tokio::test]
+    async fn test_streaming_skips_second_contact_at_dead_domain() {
+        let contacts = vec![
+            contact("Jane", "Doe", "defunct-widgets.com"),
+            contact("John", "Roe", "defunct-widgets.com"),
+        ];
+        let (results, queries) = run_streaming(dead_domain_config(), contacts.clone()).await;
+        let (_, first_queries) = run_streaming(dead_domain_config(), contacts[..1].to_vec()).await;
+
+        assert_eq!(results.len(), 2);
+        // The second contact made no lookups of its own.
+        assert_eq!(queries, first_queries);
+        assert!(!results[0].email_finding_skipped);
+        assert!(results[1].email_finding_skipped);
+        assert_eq!(
+            results[1].email_finding_error_code.as_deref(),
+            Some("domain_dead")
+        );
+        assert_eq!(results[1].contact_input.first_name.as_deref(), Some("John"));
+    }
+
+    #[tokio::test]
+    async fn test_expired_dead_domain_is_looked_up_again() {
+        let mut config = dead_domain_config();
+        config.dead_domain_ttl = Duration::ZERO;
+        let contacts = vec![
+            contact("Jane", "Doe", "defunct-widgets.com"),
+            contact("John", "Roe", "defunct-widgets.com"),
+        ];
+        let (first_only, first_queries) =
+            run_streaming(config.clone(), contacts[..1].to_vec()).await;
+        assert_eq!(first_only.len(), 1);
+
+        let (results, queries) = run_streaming(config, contacts).await;
+        assert!(results.iter().all(|result| !result.email_finding_skipped));
+        // The resolver caches the NXDOMAIN no longer than the mark, so DNS is asked again.
+        assert!(queries > first_queries);
+    }
+
+    /// A stage that records each candidate it sees, then answers with `status`.
+    struct RecordingStage {
+        name: &'static str,
+        calls: Arc<parking_lot::Mutex<Vec<String>>>,
+        status: Option<bool>,
+        definitive: bool,
+    }
+
+    impl VerificationStage for RecordingStage {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn verify<'a>(
+            &'a self,
+            context: StageContext<'a>,
+        ) -> BoxFuture<'a, Result<Option<StageOutcome>>> {
+            Box::pin(async move {
+                self.calls
+                    .lock()
+                    .push(format!("{} {}", self.name, context.email));
+                Ok(Some(StageOutcome {
+                    status: self.status,
+                    message: format!("checked by {}", self.name),
+                    confidence_boost: if self.definitive { 10 } else { 0 },
+                    definitive: self.definitive,
+                }))
+            })
+        }
+    }
+
+    /// Runs Jane Doe at acme.test (MX from `dns_overrides`) through DNS and then a
+    /// `RecordingStage` per `(name, status, definitive)`, returning the result and the
+    /// stage calls in order.
+    async fn run_custom_stages(
+        stages: &[(&'static str, Option<bool>, bool)],
+    ) -> (ProcessingResult, Vec<String>) {
+        let config = acme_config();
+        let calls = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let pipeline = std::iter::once(PipelineStage::Dns)
+            .chain(stages.iter().map(|&(name, status, definitive)| {
+                PipelineStage::Custom(Arc::new(RecordingStage {
+                    name,
+                    calls: Arc::clone(&calls),
+                    status,
+                    definitive,
+                }))
+            }))
+            .collect();
+        let sleuth = initialize_sleuth(&config)
+            .await
+            .unwrap()
+            .with_pipeline(pipeline);
+
+        let result = find_single_email(&config, &sleuth, contact("Jane", "Doe", "acme.test")).await;
+        let calls = calls.lock().clone();
+        (result, calls)
+    }
+
+    #[tokio::test]
+    async fn test_custom_stages_run_in_pipeline_order() {
+        let (result, calls) =
+            run_custom_stages(&[("directory", None, false), ("hr_system", Some(true), true)]).await;
+
+        let email = result
+            .email
+            .expect("the definitive stage verified a candidate");
+        assert_eq!(
+            calls,
+            vec![
+                format!("directory {}", email),
+                format!("hr_system {}", email)
+            ]
+        );
+        let found = &result.email_discovery_results.unwrap().found_emails;
+        assert_eq!(found[0].email, email);
+        assert_eq!(found[0].source, "hr_system");
+    }
+
+    #[tokio::test]
+    async fn test_definitive_custom_stage_ends_pipeline() {
+        let (result, calls) =
+            run_custom_stages(&[("hr_system", Some(true), true), ("directory", None, false)]).await;
+
+        let email = result
+            .email
+            .expect("the definitive stage verified a candidate");
+        assert_eq!(calls, vec![format!("hr_system {}", email)]);
+    }
+
+    /// Serves `html` over HTTP on localhost for every request and returns its base URL.
+    async fn serve_page(html: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 1024];
+                let _ = socket.read(&mut buf).await;
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    html.len(),
+                    html
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        format!("http://{}/", addr)
+    }
+
+    /// Jane Doe at acme.test in mx_only mode, with a team page listing her gmail.com address.
+    async fn run_mx_only_with_off_domain_page(exclude: bool) -> ProcessingResult {
+        let site =
+            serve_page("<html><body><div>Jane Doe<br>jane.doe@gmail.com</div></body></html>").await;
+        let config = Config {
+            verification_level: VerificationLevel::MxOnly,
+            exclude_off_domain_scraped: exclude,
+            common_pages_to_scrape: Vec::new(),
+            scraping_max_attempts: 1,
+            sleep_between_requests: (0.0, 0.0),
+            ..acme_config()
+        };
+        let sleuth = initialize_sleuth(&config).await.unwrap();
+        let contact = Contact {
+            websites: vec![site],
+            ..contact("Jane", "Doe", "acme.test")
+        };
+        find_single_email(&config, &sleuth, contact).await
+    }
+
+    #[tokio::test]
+    async fn test_mx_only_flags_off_domain_scraped_address() {
+        let result = run_mx_only_with_off_domain_page(false).await;
+
+        let email = result.email.clone();
+        let found = result.email_discovery_results.unwrap().found_emails;
+        let gmail = found
+            .iter()
+            .find(|data| data.email == "jane.doe@gmail.com")
+            .expect("the scraped address is kept as a candidate");
+        assert!(gmail.off_domain);
+        let on_domain = found
+            .iter()
+            .find(|data| data.email == "jane.doe@acme.test")
+            .unwrap();
+        assert!(gmail.confidence < on_domain.confidence);
+        assert_ne!(email.as_deref(), Some("jane.doe@gmail.com"));
+    }
+
+    #[tokio::test]
+    async fn test_mx_only_excludes_off_domain_scraped_address() {
+        let result = run_mx_only_with_off_domain_page(true).await;
+
+        let found = result.email_discovery_results.unwrap().found_emails;
+        assert!(!found.is_empty());
+        assert!(found.iter().all(|data| !data.email.ends_with("@gmail.com")));
+    }
+
+    /// A stage that panics on every candidate.
+    struct PanickingStage;
+
+    impl VerificationStage for PanickingStage {
+        fn name(&self) -> &str {
+            "panicking"
+        }
+
+        fn verify<'a>(
+            &'a self,
+            _context: StageContext<'a>,
+        ) -> BoxFuture<'a, Result<Option<StageOutcome>>> {
+            Box::pin(async { panic!("stage bug") })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_panicked_task_still_yields_result_and_frees_domain() {
+        let config = Config {
+            max_concurrency: 1,
+            ..acme_config()
+        };
+        let sleuth = initialize_sleuth(&config)
+            .await
+            .unwrap()
+            .with_pipeline(vec![
+                PipelineStage::Dns,
+                PipelineStage::Custom(Arc::new(PanickingStage)),
+            ]);
+        let (config, sleuth) = (Arc::new(config), Arc::new(sleuth));
+        let contacts = vec![
+            contact("Jane", "Doe", "acme.test"),
+            contact("John", "Roe", "acme.test"),
+        ];
+
+        let results = process_contacts(Arc::clone(&config), Arc::clone(&sleuth), &contacts).await;
+        assert_eq!(results.len(), 2);
+        assert!(results
+            .iter()
+            .all(|result| result.email_finding_error_code.as_deref() == Some("task")));
+        assert_eq!(results[1].contact_input.first_name.as_deref(), Some("John"));
+
+        let mut streamed = Vec::new();
+        process_contacts_streaming(config, sleuth, contacts, |result| {
+            streamed.push(result);
+            Ok(())
+        })
+        .await
+        .unwrap();
+        assert_eq!(streamed.len(), 2);
+        assert!(streamed
+            .iter()
+            .all(|result| result.email_finding_error_code.as_deref() == Some("task")));
+    }
+
+    #[tokio::test]
+    async fn test_best_guess_is_most_common_pattern() {
+        for seed in 0..8 {
+            let config = Config {
+                return_best_guess_on_failure: true,
+                random_seed: Some(seed),
+                ..acme_config()
+            };
+            // Nothing but DNS runs, so no candidate is verified.
+            let sleuth = initialize_sleuth(&config)
+                .await
+                .unwrap()
+                .with_pipeline(vec![PipelineStage::Dns]);
+
+            let result =
+                find_single_email(&config, &sleuth, contact("Jane", "Doe", "acme.test")).await;
+
+            assert_eq!(result.email.as_deref(), Some("jane.doe@acme.test"));
+            assert!(result.email_discovery_results.unwrap().best_guess);
         }
     }
 }
@@ -4,22 +4,31 @@
 //! This binary parses arguments, sets up configuration, initializes the core sleuth logic,
 //! processes contacts (either single or from a file), and handles output.
 
+#[cfg(feature = "smtp")]
+use email_sleuth_core::{check_sender_setup, check_smtp_connectivity};
 use email_sleuth_core::{
-    check_smtp_connectivity, find_single_email, initialize_sleuth, process_contacts, Config,
-    ConfigBuilder, Contact, EmailSleuth, ProcessingResult,
+    contact_log_layer, find_single_email, initialize_sleuth, process_contacts_streaming,
+    process_contacts_with_progress, sort_results, write_results, Config, ConfigBuilder, Contact,
+    ContactReader, DomainReport, DomainReports, EmailSleuth, FailureRecord, InputFormat,
+    JsonWriter, OutputOrder, OutputWriter, ProbeCharset, ProcessingResult, TlsVersion,
+    VerificationLevel, Warning, WarningCategory,
 };
+#[cfg(feature = "headless")]
+use email_sleuth_core::{run_selector_self_test, SelectorCheckStatus, SelfTestAddresses};
 
 // Dependencies specific to the CLI binary
 use anyhow::{Context, Result};
 use clap::{Parser, ValueEnum};
 use indicatif::{ProgressBar, ProgressStyle};
+use std::collections::HashMap;
 use std::fs::File;
-use std::io::{BufReader, BufWriter};
+use std::io::{BufRead, BufReader, BufWriter, Write};
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tracing;
-use tracing_subscriber::{fmt::format::FmtSpan, EnvFilter, FmtSubscriber};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::{fmt::format::FmtSpan, EnvFilter, Layer};
 
 mod service;
 
@@ -52,7 +61,8 @@ impl std::fmt::Display for VerificationMode {
     long_about = "Email Sleuth uses pattern generation, website scraping, and verification (SMTP, API, Headless) to find email addresses based on names and domains."
 )]
 struct AppArgs {
-    /// Path to the input JSON file containing contacts (required in file mode).
+    /// Path to the input file containing contacts (required in file mode): a JSON array,
+    /// NDJSON (.ndjson/.jsonl, one contact per line) or CSV (.csv, with a header row).
     #[arg(short, long, default_value = "input.json", env = "EMAIL_SLEUTH_INPUT")]
     input: String,
 
@@ -79,30 +89,141 @@ struct AppArgs {
     #[arg(long, default_value = "false", env = "EMAIL_SLEUTH_STDOUT")]
     stdout: bool,
 
-    /// Path to a configuration file (TOML format) to load settings from. CLI args override file settings.
+    /// Resume an interrupted batch run: keep results already in its progress log (or, failing
+    /// that, the output file) and only process input contacts that have none yet. Contacts
+    /// whose earlier attempt errored are retried.
+    #[arg(long, default_value = "false", env = "EMAIL_SLEUTH_RESUME")]
+    resume: bool,
+
+    /// Stream the batch: read contacts lazily and write each result as soon as it completes,
+    /// so memory use stays constant for any input size. Results are in completion order.
+    /// Best with NDJSON or CSV input; cannot be combined with --resume.
+    #[arg(long, default_value = "false", env = "EMAIL_SLEUTH_STREAM")]
+    stream: bool,
+
+    /// Path to a configuration file (TOML, JSON or YAML, by extension) to load settings from. CLI args override file settings.
     #[arg(long, env = "EMAIL_SLEUTH_CONFIG")]
     config_file: Option<String>,
 
+    /// Warn about (instead of rejecting) unknown keys in the configuration file.
+    #[arg(long, action = clap::ArgAction::SetTrue, env = "EMAIL_SLEUTH_LENIENT_CONFIG")]
+    lenient_config: Option<bool>,
+
     /// Maximum number of concurrent processing tasks.
     #[arg(short, long, env = "EMAIL_SLEUTH_CONCURRENCY")]
     concurrency: Option<usize>,
 
+    /// Start contacts in input order instead of spreading concurrent work over distinct domains.
+    #[arg(long, action = clap::ArgAction::SetTrue, env = "EMAIL_SLEUTH_NO_DOMAIN_FAIR_SCHEDULING")]
+    no_domain_fair_scheduling: Option<bool>,
+
     /// Sender email address for SMTP verification checks.
     #[arg(long, env = "EMAIL_SLEUTH_SMTP_SENDER")]
     smtp_sender: Option<String>,
 
+    /// Sender domain for SMTP checks; a random local part is generated per probe. Overrides --smtp-sender.
+    #[arg(long, env = "EMAIL_SLEUTH_SMTP_SENDER_DOMAIN")]
+    smtp_sender_domain: Option<String>,
+
+    /// Probe each new domain for catch-all behaviour once, when its first contact starts, and share the answer.
+    #[arg(long, action = clap::ArgAction::SetTrue, env = "EMAIL_SLEUTH_SMTP_PREFETCH_CATCH_ALL")]
+    smtp_prefetch_catch_all: Option<bool>,
+
+    /// After an accepted RCPT, also probe a plus-tagged variant of the address to detect subaddressing.
+    #[arg(long, action = clap::ArgAction::SetTrue, env = "EMAIL_SLEUTH_SMTP_SUBADDRESS_PROBE")]
+    smtp_subaddress_probe: Option<bool>,
+
+    /// After an accepted RCPT, also ask the server to expand the address (EXPN/VRFY) to detect distribution lists.
+    #[arg(long, action = clap::ArgAction::SetTrue, env = "EMAIL_SLEUTH_SMTP_EXPN_PROBE")]
+    smtp_expn_probe: Option<bool>,
+
+    /// On servers that advertise VRFY, also verify the address with VRFY as a corroborating signal.
+    #[arg(long, action = clap::ArgAction::SetTrue, env = "EMAIL_SLEUTH_SMTP_VRFY_PROBE")]
+    smtp_vrfy_probe: Option<bool>,
+
+    /// Fixed start of random probe addresses used for catch-all checks.
+    #[arg(long, env = "EMAIL_SLEUTH_SMTP_PROBE_PREFIX")]
+    smtp_probe_prefix: Option<String>,
+
+    /// Number of random characters in probe addresses after the prefix.
+    #[arg(long, env = "EMAIL_SLEUTH_SMTP_PROBE_LENGTH")]
+    smtp_probe_length: Option<usize>,
+
+    /// Characters for the random part of probe addresses (alphanumeric, letters, digits or hex).
+    #[arg(long, env = "EMAIL_SLEUTH_SMTP_PROBE_CHARSET")]
+    smtp_probe_charset: Option<String>,
+
+    /// Refuse to start unless the sender domain has SPF and this host's IP has reverse DNS.
+    #[arg(long, action = clap::ArgAction::SetTrue, env = "EMAIL_SLEUTH_SMTP_SENDER_CHECKS")]
+    smtp_sender_checks: Option<bool>,
+
+    /// TESTING ONLY: send every SMTP probe to this host:port (e.g. a local SMTP sink)
+    /// instead of the real mail server of each domain.
+    #[arg(long, env = "EMAIL_SLEUTH_SMTP_FORCE_TARGET")]
+    smtp_force_target: Option<String>,
+
+    /// Comma-separated TLDs (e.g. ru,co.jp) whose domains are never probed over SMTP.
+    #[arg(long, value_delimiter = ',', env = "EMAIL_SLEUTH_SMTP_SKIP_TLDS")]
+    smtp_skip_tlds: Option<Vec<String>>,
+
+    /// Maximum simultaneous SMTP connections to any one mail server.
+    #[arg(long, env = "EMAIL_SLEUTH_MAX_CONCURRENCY_PER_MX")]
+    max_concurrency_per_mx: Option<usize>,
+
+    /// Maximum SMTP probes sent to any one domain during the run, across all its contacts
+    /// (0 = no limit). Remaining candidates are checked with the other methods only.
+    #[arg(long, env = "EMAIL_SLEUTH_MAX_PROBES_PER_DOMAIN")]
+    max_probes_per_domain: Option<usize>,
+
+    /// Maximum attempts per SMTP check when the result is inconclusive.
+    #[arg(long, env = "EMAIL_SLEUTH_SMTP_MAX_ATTEMPTS")]
+    smtp_max_attempts: Option<u32>,
+
+    /// Maximum attempts per page fetch when scraping websites.
+    #[arg(long, env = "EMAIL_SLEUTH_SCRAPING_MAX_ATTEMPTS")]
+    scraping_max_attempts: Option<u32>,
+
+    /// Scrape the homepage, then only the K linked pages most likely to list contacts,
+    /// instead of every common page (0 = every common page).
+    #[arg(long, env = "EMAIL_SLEUTH_SCRAPE_TOP_K_PAGES")]
+    scrape_top_k_pages: Option<usize>,
+
+    /// Maximum attempts per headless check when the browser session fails.
+    #[arg(long, env = "EMAIL_SLEUTH_HEADLESS_MAX_ATTEMPTS")]
+    headless_max_attempts: Option<u32>,
+
     /// User agent string for HTTP scraping requests.
     #[arg(long, env = "EMAIL_SLEUTH_USER_AGENT")]
     user_agent: Option<String>,
 
+    /// Oldest TLS version accepted for HTTPS and SMTP STARTTLS (1.2 or 1.3).
+    #[arg(long, env = "EMAIL_SLEUTH_MIN_TLS_VERSION")]
+    min_tls_version: Option<String>,
+
     /// SMTP connection/command timeout in seconds.
     #[arg(long, env = "EMAIL_SLEUTH_SMTP_TIMEOUT")]
     smtp_timeout: Option<u64>,
 
+    /// Seconds to wait for the reply to RCPT TO before treating the server as stalled.
+    #[arg(long, env = "EMAIL_SLEUTH_SMTP_RCPT_TIMEOUT")]
+    smtp_rcpt_timeout: Option<u64>,
+
+    /// Do not send the MX hostname as SNI during STARTTLS (the handshake uses the server's IP).
+    #[arg(long, action = clap::ArgAction::SetTrue, env = "EMAIL_SLEUTH_NO_SMTP_TLS_SNI")]
+    no_smtp_tls_sni: Option<bool>,
+
     /// HTTP request timeout in seconds.
     #[arg(long, env = "EMAIL_SLEUTH_REQUEST_TIMEOUT")]
     request_timeout: Option<u64>,
 
+    /// Retries for a scraping or API request answered with 429 Too Many Requests (0 = none).
+    #[arg(long, env = "EMAIL_SLEUTH_RATE_LIMIT_MAX_RETRIES")]
+    rate_limit_max_retries: Option<u32>,
+
+    /// Longest Retry-After delay in seconds to wait for before retrying a 429 response.
+    #[arg(long, env = "EMAIL_SLEUTH_RATE_LIMIT_MAX_WAIT")]
+    rate_limit_max_wait: Option<u64>,
+
     /// DNS resolution timeout in seconds.
     #[arg(long, env = "EMAIL_SLEUTH_DNS_TIMEOUT")]
     dns_timeout: Option<u64>,
@@ -111,6 +232,23 @@ struct AppArgs {
     #[arg(long, value_delimiter = ',', env = "EMAIL_SLEUTH_DNS_SERVERS")]
     dns_servers: Option<Vec<String>>,
 
+    /// Maximum number of concurrent DNS lookups.
+    #[arg(long, env = "EMAIL_SLEUTH_DNS_CONCURRENCY")]
+    dns_concurrency: Option<usize>,
+
+    /// Validate DNSSEC signatures on DNS answers. Domains that fail validation are not checked.
+    #[arg(long, action = clap::ArgAction::SetTrue, env = "EMAIL_SLEUTH_DNSSEC")]
+    dnssec: Option<bool>,
+
+    /// Skip the remaining contacts at a domain once its lookup finds no such domain (NXDOMAIN)
+    /// or no MX/A records.
+    #[arg(long, action = clap::ArgAction::SetTrue, env = "EMAIL_SLEUTH_SKIP_DEAD_DOMAINS")]
+    skip_dead_domains: Option<bool>,
+
+    /// Seconds a domain stays dead for --skip-dead-domains before it is looked up again.
+    #[arg(long, env = "EMAIL_SLEUTH_DEAD_DOMAIN_TTL")]
+    dead_domain_ttl: Option<u64>,
+
     /// Enable experimental API-based verification checks (e.g., M365).
     #[arg(long, action = clap::ArgAction::SetTrue, env = "EMAIL_SLEUTH_ENABLE_API_CHECKS")]
     enable_api_checks: Option<bool>,
@@ -122,6 +260,63 @@ struct AppArgs {
     #[arg(long, env = "EMAIL_SLEUTH_EARLY_TERM_THRESHOLD")]
     early_termination_threshold: Option<u8>,
 
+    /// Verification level: mx_only (domain accepts mail, no probing), smtp, or full (default).
+    #[arg(long, env = "EMAIL_SLEUTH_VERIFICATION_LEVEL")]
+    verification_level: Option<String>,
+
+    /// Comma-separated stages in the order they run (e.g. scrape,dns,smtp,api,headless).
+    /// Stages left out do not run.
+    #[arg(long, value_delimiter = ',', env = "EMAIL_SLEUTH_STAGE_ORDER")]
+    stage_order: Option<Vec<String>>,
+
+    /// When a domain has mail servers but every probe is inconclusive, still return the
+    /// best pattern candidate, marked as unverified.
+    #[arg(long, action = clap::ArgAction::SetTrue, env = "EMAIL_SLEUTH_UNVERIFIED_FALLBACK")]
+    unverified_fallback: Option<bool>,
+
+    /// Confidence (0-10) given to an unverified fallback result.
+    #[arg(long, env = "EMAIL_SLEUTH_UNVERIFIED_FALLBACK_CONFIDENCE")]
+    unverified_fallback_confidence: Option<u8>,
+
+    /// When no email is selected at all, return the top-ranked candidate that was not
+    /// rejected, at confidence 1 and marked as a guess.
+    #[arg(long, action = clap::ArgAction::SetTrue, env = "EMAIL_SLEUTH_RETURN_BEST_GUESS_ON_FAILURE")]
+    return_best_guess_on_failure: Option<bool>,
+
+    /// Only rate an email Confident when two or more independent methods confirmed it
+    /// (e.g. SMTP and a headless check); single-method confirmations are Probable.
+    #[arg(long, action = clap::ArgAction::SetTrue, env = "EMAIL_SLEUTH_REQUIRE_CORROBORATION")]
+    require_corroboration: Option<bool>,
+
+    /// Keep the casing of scraped addresses' local parts instead of lowercasing them, for
+    /// servers with case-sensitive mailbox names. Domains are always lowercased.
+    #[arg(long, action = clap::ArgAction::SetTrue, env = "EMAIL_SLEUTH_PRESERVE_LOCAL_PART_CASE")]
+    preserve_local_part_case: Option<bool>,
+
+    /// Drop scraped addresses at other domains (e.g. gmail.com or a partner's) instead of
+    /// keeping them as flagged, low-confidence candidates.
+    #[arg(long, action = clap::ArgAction::SetTrue, env = "EMAIL_SLEUTH_EXCLUDE_OFF_DOMAIN_SCRAPED")]
+    exclude_off_domain_scraped: Option<bool>,
+
+    /// Skip pages the website's robots.txt disallows, reporting each as a warning.
+    #[arg(long, action = clap::ArgAction::SetTrue, env = "EMAIL_SLEUTH_RESPECT_ROBOTS_TXT")]
+    respect_robots_txt: Option<bool>,
+
+    /// Use the full host from --domain/input (e.g. mail.acme.co.uk) instead of reducing it
+    /// to the registrable domain (acme.co.uk).
+    #[arg(long, action = clap::ArgAction::SetTrue, env = "EMAIL_SLEUTH_KEEP_SUBDOMAINS")]
+    keep_subdomains: Option<bool>,
+
+    /// Warn about input domains that look like misspellings of well-known ones
+    /// (e.g. "gooogle.com") and suggest the likely intended domain.
+    #[arg(long, action = clap::ArgAction::SetTrue, env = "EMAIL_SLEUTH_DOMAIN_TYPO_CHECK")]
+    domain_typo_check: Option<bool>,
+
+    /// Seed for candidate ordering, so repeated runs over the same input try candidates
+    /// in the same order.
+    #[arg(long, env = "EMAIL_SLEUTH_SEED")]
+    seed: Option<u64>,
+
     /// URL of the running WebDriver instance (required if --enable-headless-checks is used).
     #[arg(long, env = "EMAIL_SLEUTH_WEBDRIVER_URL")]
     webdriver_url: Option<String>,
@@ -130,6 +325,20 @@ struct AppArgs {
     #[arg(long, env = "EMAIL_SLEUTH_CHROMEDRIVER_PATH")]
     chromedriver_path: Option<String>,
 
+    /// Number of ChromeDriver instances to run on consecutive ports starting at the WebDriver URL's port.
+    #[arg(long, env = "EMAIL_SLEUTH_CHROMEDRIVER_INSTANCES")]
+    chromedriver_instances: Option<usize>,
+
+    /// Port for the managed ChromeDriver when no WebDriver URL is given (default 4444).
+    /// Also used for the default WebDriver URL in comprehensive mode.
+    #[arg(long, env = "EMAIL_SLEUTH_WEBDRIVER_PORT")]
+    webdriver_port: Option<u16>,
+
+    /// Stop the ChromeDriver service when the run ends if this run started it
+    /// (including when interrupted with Ctrl-C).
+    #[arg(long, action = clap::ArgAction::SetTrue, env = "EMAIL_SLEUTH_STOP_CHROMEDRIVER_ON_EXIT")]
+    stop_chromedriver_on_exit: Option<bool>,
+
     /// Verification mode (determines which methods are enabled)
     #[arg(short, long, value_enum, default_value_t = VerificationMode::Basic)]
     mode: VerificationMode,
@@ -141,19 +350,73 @@ struct AppArgs {
     /// Number of log lines to show when using --service logs
     #[arg(long, default_value_t = 20)]
     log_lines: usize,
+
+    /// Order of results in the output file: confidence, input, or domain (default).
+    #[arg(long, env = "EMAIL_SLEUTH_SORT_OUTPUT")]
+    sort_output: Option<String>,
+
+    /// Write skipped, failed and inconclusive records to this file instead of the output file,
+    /// in a format that can be used as input for a later run.
+    #[arg(long, env = "EMAIL_SLEUTH_FAILURES_OUTPUT")]
+    failures_output: Option<String>,
+
+    /// Write a per-domain rollup (contacts resolved, naming convention, catch-all, MX
+    /// provider) to this file.
+    #[arg(long, env = "EMAIL_SLEUTH_DOMAIN_REPORT")]
+    domain_report: Option<String>,
+
+    /// Attach the log lines of each failed contact's task (at every level) to its result
+    /// and failure record as `debug_log`.
+    #[arg(long, action = clap::ArgAction::SetTrue, env = "EMAIL_SLEUTH_CAPTURE_FAILURE_LOGS")]
+    capture_failure_logs: Option<bool>,
+
+    /// Attach a `trace` of everything each stage returned (MX records, scraped addresses,
+    /// every candidate's SMTP and headless results) to each result. Makes output much larger.
+    #[arg(long, action = clap::ArgAction::SetTrue, env = "EMAIL_SLEUTH_CAPTURE_TRACE")]
+    capture_trace: Option<bool>,
+
+    /// KEY=VALUE labels for this run (e.g. campaign=q3-outreach), copied into every result
+    /// as `run_metadata`. Repeat the flag or separate pairs with commas.
+    #[arg(
+        long,
+        value_name = "KEY=VALUE",
+        value_delimiter = ',',
+        env = "EMAIL_SLEUTH_RUN_METADATA"
+    )]
+    run_metadata: Vec<String>,
+
+    /// Load and validate a configuration file, print the effective settings, and exit.
+    #[arg(long, value_name = "PATH")]
+    validate_config: Option<String>,
+
+    /// Check that the headless providers' page selectors still match, report stale ones, and exit.
+    /// Requires a running WebDriver.
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    selector_self_test: Option<bool>,
+
+    /// Microsoft address known to exist, used by --selector-self-test to check the "exists" outcome.
+    #[arg(long, env = "EMAIL_SLEUTH_SELF_TEST_MICROSOFT_ADDRESS")]
+    self_test_microsoft_address: Option<String>,
+
+    /// Yahoo address known to exist, used by --selector-self-test to check the "exists" outcome.
+    #[arg(long, env = "EMAIL_SLEUTH_SELF_TEST_YAHOO_ADDRESS")]
+    self_test_yahoo_address: Option<String>,
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
 
-    let subscriber = FmtSubscriber::builder()
-        .with_env_filter(env_filter)
+    let fmt_layer = tracing_subscriber::fmt::layer()
         .with_thread_names(true)
         .with_target(true)
         .with_span_events(FmtSpan::CLOSE)
         .compact()
-        .finish();
+        .with_filter(env_filter);
+    // Captures per-contact log lines for `capture_failure_logs`, independent of the log level.
+    let subscriber = tracing_subscriber::registry()
+        .with(fmt_layer)
+        .with(contact_log_layer());
 
     tracing::subscriber::set_global_default(subscriber)
         .context("Setting up tracing subscriber failed")?;
@@ -171,6 +434,9 @@ async fn main() -> Result<()> {
     if let Some(ref path) = args.config_file {
         config_builder = config_builder.config_file(path);
     }
+    if args.lenient_config == Some(true) {
+        config_builder = config_builder.lenient_config(true);
+    }
 
     match args.mode {
         VerificationMode::Basic => {
@@ -185,10 +451,7 @@ async fn main() -> Result<()> {
                 .enable_api_checks(true)
                 .enable_headless_checks(true);
 
-            if args.webdriver_url.is_none() {
-                config_builder = config_builder.webdriver_url(Some("http://localhost:4444"));
-                tracing::info!("Using default WebDriver URL: http://localhost:4444");
-            }
+            config_builder = config_builder.default_to_local_webdriver(true);
 
             tracing::info!(
                 "Comprehensive verification mode: Enabling API and headless browser checks"
@@ -199,18 +462,87 @@ async fn main() -> Result<()> {
     if let Some(c) = args.concurrency {
         config_builder = config_builder.max_concurrency(c);
     }
+    if args.no_domain_fair_scheduling == Some(true) {
+        config_builder = config_builder.domain_fair_scheduling(false);
+    }
     if let Some(ref s) = args.smtp_sender {
         config_builder = config_builder.smtp_sender_email(s);
     }
+    if let Some(ref d) = args.smtp_sender_domain {
+        config_builder = config_builder.smtp_sender_domain(d);
+    }
+    if let Some(ref tlds) = args.smtp_skip_tlds {
+        config_builder = config_builder.smtp_skip_tlds(tlds.clone());
+    }
+    if args.smtp_prefetch_catch_all == Some(true) {
+        config_builder = config_builder.smtp_prefetch_catch_all(true);
+    }
+    if args.smtp_subaddress_probe == Some(true) {
+        config_builder = config_builder.smtp_subaddress_probe(true);
+    }
+    if args.smtp_expn_probe == Some(true) {
+        config_builder = config_builder.smtp_expn_probe(true);
+    }
+    if args.smtp_vrfy_probe == Some(true) {
+        config_builder = config_builder.smtp_vrfy_probe(true);
+    }
+    if let Some(ref prefix) = args.smtp_probe_prefix {
+        config_builder = config_builder.smtp_probe_prefix(prefix);
+    }
+    if let Some(length) = args.smtp_probe_length {
+        config_builder = config_builder.smtp_probe_length(length);
+    }
+    if let Some(ref charset) = args.smtp_probe_charset {
+        config_builder = config_builder.smtp_probe_charset(charset.parse::<ProbeCharset>()?);
+    }
+    if args.smtp_sender_checks == Some(true) {
+        config_builder = config_builder.smtp_sender_checks(true);
+    }
+    if let Some(ref target) = args.smtp_force_target {
+        config_builder = config_builder.smtp_force_target(target);
+    }
+    if let Some(n) = args.max_concurrency_per_mx {
+        config_builder = config_builder.max_concurrency_per_mx(n);
+    }
+    if let Some(n) = args.max_probes_per_domain {
+        config_builder = config_builder.max_probes_per_domain(n);
+    }
+    if let Some(n) = args.smtp_max_attempts {
+        config_builder = config_builder.smtp_max_attempts(n);
+    }
+    if let Some(n) = args.scraping_max_attempts {
+        config_builder = config_builder.scraping_max_attempts(n);
+    }
+    if let Some(k) = args.scrape_top_k_pages {
+        config_builder = config_builder.scrape_top_k_pages(k);
+    }
+    if let Some(n) = args.headless_max_attempts {
+        config_builder = config_builder.headless_max_attempts(n);
+    }
     if let Some(ref ua) = args.user_agent {
         config_builder = config_builder.user_agent(ua);
     }
+    if let Some(ref version) = args.min_tls_version {
+        config_builder = config_builder.min_tls_version(version.parse::<TlsVersion>()?);
+    }
     if let Some(t) = args.smtp_timeout {
         config_builder = config_builder.smtp_timeout(Duration::from_secs(t));
     }
+    if let Some(t) = args.smtp_rcpt_timeout {
+        config_builder = config_builder.smtp_rcpt_timeout(Duration::from_secs(t));
+    }
+    if args.no_smtp_tls_sni == Some(true) {
+        config_builder = config_builder.smtp_tls_sni(false);
+    }
     if let Some(t) = args.request_timeout {
         config_builder = config_builder.request_timeout(Duration::from_secs(t));
     }
+    if let Some(n) = args.rate_limit_max_retries {
+        config_builder = config_builder.rate_limit_max_retries(n);
+    }
+    if let Some(t) = args.rate_limit_max_wait {
+        config_builder = config_builder.rate_limit_max_wait(Duration::from_secs(t));
+    }
     if let Some(t) = args.dns_timeout {
         config_builder = config_builder.dns_timeout(Duration::from_secs(t));
     }
@@ -219,6 +551,45 @@ async fn main() -> Result<()> {
             config_builder = config_builder.dns_servers(servers.clone());
         }
     }
+    if let Some(c) = args.dns_concurrency {
+        config_builder = config_builder.max_dns_concurrency(c);
+    }
+    if args.dnssec == Some(true) {
+        config_builder = config_builder.dnssec_validation(true);
+    }
+    if args.skip_dead_domains == Some(true) {
+        config_builder = config_builder.skip_dead_domains(true);
+    }
+    if let Some(t) = args.dead_domain_ttl {
+        config_builder = config_builder.dead_domain_ttl(Duration::from_secs(t));
+    }
+    if let Some(ref level) = args.verification_level {
+        config_builder = config_builder.verification_level(level.parse::<VerificationLevel>()?);
+    }
+    if let Some(ref stages) = args.stage_order {
+        config_builder = config_builder.stage_order(stages.clone());
+    }
+    if let Some(ref order) = args.sort_output {
+        config_builder = config_builder.sort_output(order.parse::<OutputOrder>()?);
+    }
+    if let Some(ref path) = args.failures_output {
+        config_builder = config_builder.failures_output(Some(path));
+    }
+    if let Some(ref path) = args.domain_report {
+        config_builder = config_builder.domain_report_output(Some(path));
+    }
+    if args.capture_failure_logs == Some(true) {
+        config_builder = config_builder.capture_failure_logs(true);
+    }
+    if args.capture_trace == Some(true) {
+        config_builder = config_builder.capture_trace(true);
+    }
+    for pair in &args.run_metadata {
+        let (key, value) = pair.split_once('=').ok_or_else(|| {
+            anyhow::anyhow!("Invalid --run-metadata '{}': expected KEY=VALUE", pair)
+        })?;
+        config_builder = config_builder.run_metadata(key.trim(), value.trim());
+    }
     if args.enable_api_checks == Some(true) {
         config_builder = config_builder.enable_api_checks(true);
     }
@@ -228,14 +599,57 @@ async fn main() -> Result<()> {
     if let Some(threshold) = args.early_termination_threshold {
         config_builder = config_builder.early_termination_threshold(threshold);
     }
+    if args.unverified_fallback == Some(true) {
+        config_builder = config_builder.unverified_fallback(true);
+    }
+    if let Some(confidence) = args.unverified_fallback_confidence {
+        config_builder = config_builder.unverified_fallback_confidence(confidence);
+    }
+    if args.return_best_guess_on_failure == Some(true) {
+        config_builder = config_builder.return_best_guess_on_failure(true);
+    }
+    if args.require_corroboration == Some(true) {
+        config_builder = config_builder.require_corroboration(true);
+    }
+    if args.preserve_local_part_case == Some(true) {
+        config_builder = config_builder.lowercase_local_part(false);
+    }
+    if args.exclude_off_domain_scraped == Some(true) {
+        config_builder = config_builder.exclude_off_domain_scraped(true);
+    }
+    if args.respect_robots_txt == Some(true) {
+        config_builder = config_builder.respect_robots_txt(true);
+    }
+    if args.keep_subdomains == Some(true) {
+        config_builder = config_builder.keep_subdomains(true);
+    }
+    if args.domain_typo_check == Some(true) {
+        config_builder = config_builder.domain_typo_check(true);
+    }
+    if let Some(seed) = args.seed {
+        config_builder = config_builder.random_seed(seed);
+    }
     if let Some(ref url) = args.webdriver_url {
         config_builder = config_builder.webdriver_url(Some(url));
     }
     if let Some(ref path) = args.chromedriver_path {
         config_builder = config_builder.chromedriver_path(Some(path));
     }
+    if let Some(n) = args.chromedriver_instances {
+        config_builder = config_builder.chromedriver_instances(n);
+    }
+    if let Some(port) = args.webdriver_port {
+        config_builder = config_builder.webdriver_port(port);
+    }
+    if let Some(stop) = args.stop_chromedriver_on_exit {
+        config_builder = config_builder.stop_chromedriver_on_exit(stop);
+    }
+
+    if let Some(ref path) = args.validate_config {
+        return handle_validate_config(config_builder.config_file(path), path);
+    }
 
-    let config = match config_builder.build() {
+    let config = match config_builder.build_with_remote_lists().await {
         Ok(cfg) => Arc::new(cfg),
         Err(e) => {
             tracing::error!("Configuration error: {}", e);
@@ -248,11 +662,23 @@ async fn main() -> Result<()> {
         return handle_service_command(service_cmd, args.log_lines, &config).await;
     }
 
-    if matches!(args.mode, VerificationMode::Comprehensive) {
-        if let Err(e) = ensure_chromedriver_running(&config).await {
-            tracing::warn!("ChromeDriver service issue: {}", e);
-            if args.webdriver_url.is_none() {
-                tracing::warn!("Comprehensive mode may not work fully due to ChromeDriver issues");
+    if args.selector_self_test == Some(true) {
+        return handle_selector_self_test(&config, &args).await;
+    }
+
+    let mut started_chromedriver = Vec::new();
+    let mut service_warnings = Vec::new();
+    if matches!(args.mode, VerificationMode::Comprehensive) && config.enable_headless_checks {
+        match ensure_chromedriver_running(&config, &mut service_warnings).await {
+            Ok(started) => started_chromedriver = started,
+            Err(e) => {
+                tracing::warn!("ChromeDriver service issue: {}", e);
+                service_warnings.push(format!("ChromeDriver service issue: {}", e));
+                if args.webdriver_url.is_none() {
+                    tracing::warn!(
+                        "Comprehensive mode may not work fully due to ChromeDriver issues"
+                    );
+                }
             }
         }
     }
@@ -268,25 +694,40 @@ async fn main() -> Result<()> {
         }
     };
 
-    match check_smtp_connectivity().await {
-        Ok(_) => tracing::info!(
-            "SMTP connectivity test to Google passed (outbound port 25 likely open)."
-        ),
-        Err(e) => {
-            tracing::error!("SMTP connectivity test failed: {}", e);
-            tracing::warn!("Standard SMTP verification (port 25) may fail or be unreliable.");
-            tracing::warn!("Check firewall rules or ISP restrictions if SMTP checks are needed.");
-        }
+    for message in service_warnings {
+        sleuth.add_warning(Warning {
+            category: WarningCategory::Service,
+            message,
+            contact: None,
+            domain: None,
+        });
     }
 
+    #[cfg(feature = "smtp")]
+    run_smtp_preflight(&config).await?;
+
     let is_cli_mode = args.name.is_some();
     let start_time = Instant::now();
 
-    let execution_result = if is_cli_mode {
-        process_cli_mode(&config, &sleuth, &args).await
-    } else {
-        process_file_mode(config.clone(), sleuth, &args, start_time).await
+    let execution = async {
+        if is_cli_mode {
+            process_cli_mode(&config, &sleuth, &args).await
+        } else if args.stream {
+            process_stream_mode(config.clone(), Arc::clone(&sleuth), &args).await
+        } else {
+            process_file_mode(config.clone(), Arc::clone(&sleuth), &args, start_time).await
+        }
     };
+    // Batch modes handle Ctrl-C themselves so they can write out the results so far.
+    let execution_result = tokio::select! {
+        result = execution => result,
+        _ = tokio::signal::ctrl_c(), if is_cli_mode => {
+            tracing::warn!("Interrupted, shutting down...");
+            Err(anyhow::anyhow!("Interrupted by Ctrl-C"))
+        }
+    };
+
+    shutdown(&config, &sleuth, &started_chromedriver).await;
 
     if let Err(e) = execution_result {
         tracing::error!("Execution failed: {}", e);
@@ -300,7 +741,10 @@ async fn main() -> Result<()> {
         );
     }
 
-    if matches!(args.mode, VerificationMode::Comprehensive) {
+    if matches!(args.mode, VerificationMode::Comprehensive)
+        && config.enable_headless_checks
+        && (started_chromedriver.is_empty() || !config.stop_chromedriver_on_exit)
+    {
         if let Ok(running) = service::chromedriver::status(&config).await {
             if running {
                 tracing::info!("ChromeDriver service is still running. You can stop it with: email-sleuth --service stop");
@@ -311,26 +755,170 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-/// Ensures the ChromeDriver service is running for comprehensive mode
-async fn ensure_chromedriver_running(config: &Config) -> Result<()> {
+/// Ensures the ChromeDriver service is running for comprehensive mode.
+/// Returns the PIDs of the instances this call started.
+async fn ensure_chromedriver_running(
+    config: &Config,
+    warnings: &mut Vec<String>,
+) -> Result<Vec<u32>> {
     if let Ok(running) = service::chromedriver::status(config).await {
         if running {
             tracing::info!("ChromeDriver service is already running");
-            return Ok(());
+            return Ok(Vec::new());
         }
     }
 
     tracing::info!("Starting ChromeDriver service for comprehensive verification...");
-    service::chromedriver::start(config)
+    service::chromedriver::start(config, warnings)
         .await
         .map_err(|e| anyhow::anyhow!("Failed to start ChromeDriver: {}", e))
 }
 
+/// Closes open browser sessions and, with `stop_chromedriver_on_exit`, stops the
+/// ChromeDriver instances this run started (`started_chromedriver`), leaving any others
+/// running. Runs on completion and on Ctrl-C.
+async fn shutdown(config: &Config, sleuth: &EmailSleuth, started_chromedriver: &[u32]) {
+    sleuth.shutdown().await;
+
+    if !started_chromedriver.is_empty() && config.stop_chromedriver_on_exit {
+        tracing::info!("Stopping the ChromeDriver instances started by this run...");
+        if let Err(e) = service::chromedriver::stop_started(started_chromedriver).await {
+            tracing::warn!("Failed to stop ChromeDriver: {}", e);
+        }
+    }
+}
+
+/// Validates a configuration file and prints either the effective settings or every problem found.
+fn handle_validate_config(config_builder: ConfigBuilder, path: &str) -> Result<()> {
+    match config_builder.validate() {
+        Ok(config) => {
+            let effective = config
+                .to_config_file()
+                .to_toml_string()
+                .map_err(|e| anyhow::anyhow!("Failed to render effective configuration: {}", e))?;
+            println!("OK: '{}' is valid. Effective configuration:", path);
+            println!("----------------------------------------");
+            println!("{}", effective.trim_end());
+            println!("----------------------------------------");
+            Ok(())
+        }
+        Err(problems) => {
+            println!(
+                "Configuration '{}' has {} problem(s):",
+                path,
+                problems.len()
+            );
+            for problem in &problems {
+                println!("- {}", problem);
+            }
+            Err(anyhow::anyhow!(
+                "Configuration validation failed for '{}'",
+                path
+            ))
+        }
+    }
+}
+
+/// Tests outbound SMTP connectivity and, with `smtp_sender_checks`, the sender setup.
+/// Fails only if the sender setup check finds problems.
+#[cfg(feature = "smtp")]
+async fn run_smtp_preflight(config: &Config) -> Result<()> {
+    match check_smtp_connectivity().await {
+        Ok(_) => tracing::info!(
+            "SMTP connectivity test to Google passed (outbound port 25 likely open)."
+        ),
+        Err(e) => {
+            tracing::error!("SMTP connectivity test failed: {}", e);
+            tracing::warn!("Standard SMTP verification (port 25) may fail or be unreliable.");
+            tracing::warn!("Check firewall rules or ISP restrictions if SMTP checks are needed.");
+        }
+    }
+
+    if config.smtp_sender_checks {
+        let report = check_sender_setup(config).await?;
+        if !report.passed() {
+            return Err(anyhow::anyhow!(
+                "Sender setup check failed for {}; probing now would risk getting blocklisted:\n  - {}\nFix the above, or disable smtp_sender_checks to probe anyway.",
+                report.sender_domain,
+                report.problems.join("\n  - ")
+            ));
+        }
+        tracing::info!(
+            "Sender setup check passed: {} has SPF, {} has reverse DNS {}.",
+            report.sender_domain,
+            report
+                .public_ip
+                .map(|ip| ip.to_string())
+                .unwrap_or_default(),
+            report.ptr_name.as_deref().unwrap_or_default()
+        );
+    }
+    Ok(())
+}
+
+/// Runs the headless selector self-test and prints one line per provider scenario.
+/// Fails if any provider's selectors look stale.
+#[cfg(feature = "headless")]
+async fn handle_selector_self_test(config: &Config, args: &AppArgs) -> Result<()> {
+    let addresses = SelfTestAddresses {
+        microsoft: args.self_test_microsoft_address.clone(),
+        yahoo: args.self_test_yahoo_address.clone(),
+    };
+    let checks = run_selector_self_test(config, &addresses)
+        .await
+        .map_err(|e| anyhow::anyhow!("Selector self-test could not run: {}", e))?;
+
+    println!("Headless selector self-test:");
+    for check in &checks {
+        let scenario = if check.expect_exists {
+            "existing"
+        } else {
+            "nonexistent"
+        };
+        let status = match check.status {
+            SelectorCheckStatus::Ok => "OK",
+            SelectorCheckStatus::Stale => "STALE",
+            SelectorCheckStatus::Blocked => "BLOCKED",
+            SelectorCheckStatus::Skipped => "SKIPPED",
+        };
+        println!(
+            "- {:<9} {:<11} {:<7} {}",
+            check.provider.to_string(),
+            scenario,
+            status,
+            check.detail
+        );
+    }
+
+    let mut stale: Vec<String> = checks
+        .iter()
+        .filter(|c| c.status == SelectorCheckStatus::Stale)
+        .map(|c| c.provider.to_string())
+        .collect();
+    stale.dedup();
+    if stale.is_empty() {
+        println!("No stale selectors found.");
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "Stale selectors for provider(s): {}",
+            stale.join(", ")
+        ))
+    }
+}
+
+#[cfg(not(feature = "headless"))]
+async fn handle_selector_self_test(_config: &Config, _args: &AppArgs) -> Result<()> {
+    Err(anyhow::anyhow!(
+        "The selector self-test is not available: this build does not include the 'headless' feature"
+    ))
+}
+
 /// Handles service management commands
 async fn handle_service_command(command: &str, log_lines: usize, config: &Config) -> Result<()> {
     match command {
         "start" => {
-            service::chromedriver::start(config)
+            service::chromedriver::start(config, &mut Vec::new())
                 .await
                 .map_err(|e| anyhow::anyhow!("Failed to start ChromeDriver service: {}", e))?;
             println!("ChromeDriver service started successfully");
@@ -362,7 +950,7 @@ async fn handle_service_command(command: &str, log_lines: usize, config: &Config
             }
         }
         "logs" => {
-            let logs = service::chromedriver::logs(log_lines)
+            let logs = service::chromedriver::logs(config, log_lines)
                 .map_err(|e| anyhow::anyhow!("Failed to retrieve ChromeDriver logs: {}", e))?;
 
             println!("ChromeDriver Logs (last {} lines):", log_lines);
@@ -385,7 +973,7 @@ async fn process_cli_mode(config: &Config, sleuth: &EmailSleuth, args: &AppArgs)
     let domain_input = args.domain.as_ref().cloned().unwrap();
 
     let name_parts: Vec<&str> = name.split_whitespace().collect();
-    let first_name = name_parts.get(0).map(|s| s.to_string());
+    let first_name = name_parts.first().map(|s| s.to_string());
     let last_name = name_parts.last().map(|s| s.to_string());
 
     let contact = Contact {
@@ -393,8 +981,7 @@ async fn process_cli_mode(config: &Config, sleuth: &EmailSleuth, args: &AppArgs)
         last_name,
         full_name: Some(name.clone()),
         domain: Some(domain_input.clone()),
-        company_domain: None,
-        other_fields: std::collections::HashMap::new(),
+        ..Default::default()
     };
 
     tracing::info!(
@@ -410,7 +997,7 @@ async fn process_cli_mode(config: &Config, sleuth: &EmailSleuth, args: &AppArgs)
         print_cli_results(&result, config);
     } else {
         tracing::info!("Saving result to '{}'...", args.output);
-        save_results(&[result], &args.output)?;
+        save_outputs(&[result], &args.output, config)?;
         tracing::info!("Result saved successfully to '{}'.", args.output);
     }
     tracing::info!("CLI mode finished. Duration: {:.2?}", start_time.elapsed());
@@ -429,60 +1016,123 @@ async fn process_file_mode(
         args.output,
         args.mode
     );
-    let input_path = Path::new(&args.input);
-    let output_path = Path::new(&args.output);
-
-    if !input_path.exists() || !input_path.is_file() {
-        return Err(anyhow::anyhow!(
-            "Input file not found or is not a file: {}",
-            args.input
-        ));
-    }
-    if let Some(parent_dir) = output_path.parent() {
-        if !parent_dir.as_os_str().is_empty() && !parent_dir.exists() {
-            tracing::debug!("Creating output directory: {}", parent_dir.display());
-            std::fs::create_dir_all(parent_dir).with_context(|| {
-                format!(
-                    "Failed to create output directory '{}'",
-                    parent_dir.display()
-                )
-            })?;
-        }
-    }
-    File::create(&args.output).with_context(|| {
-        format!(
-            "Cannot write to output file '{}'. Check permissions.",
-            args.output
-        )
-    })?;
+    prepare_file_paths(&config, args)?;
+    let previous_results = if args.resume {
+        load_previous_results(&args.output, config.failures_output.as_deref())?
+    } else {
+        Vec::new()
+    };
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&args.output)
+        .with_context(|| {
+            format!(
+                "Cannot write to output file '{}'. Check permissions.",
+                args.output
+            )
+        })?;
     tracing::debug!("Output path '{}' seems writable.", args.output);
 
     tracing::info!("Loading contacts from '{}'...", args.input);
-    let contacts = load_contacts(&args.input)?;
+    let mut contacts = load_contacts(&args.input)?;
     let total_records_loaded = contacts.len();
+
+    let completed_results = if args.resume {
+        let (completed, remaining) = split_completed_contacts(previous_results, contacts);
+        tracing::info!(
+            "Resuming: {} contacts already have results in '{}', {} left to process.",
+            completed.len(),
+            args.output,
+            remaining.len()
+        );
+        contacts = remaining;
+        completed
+    } else {
+        Vec::new()
+    };
+
+    if contacts.is_empty() && !completed_results.is_empty() {
+        tracing::info!("Nothing left to process; all input contacts already have results.");
+        let mut processed_results = completed_results;
+        sort_results(&mut processed_results, config.sort_output);
+        save_outputs(&processed_results, &args.output, &config)?;
+        remove_progress_log(&progress_log_path(&args.output));
+        return Ok(());
+    }
     if total_records_loaded == 0 {
         tracing::warn!(
             "Input file '{}' is empty or contains no valid contacts. Saving empty results file.",
             args.input
         );
-        save_results(&[], &args.output)?;
+        save_outputs(&[], &args.output, &config)?;
         return Ok(());
     }
     tracing::info!("Loaded {} records from input file.", total_records_loaded);
 
     tracing::info!(
         "Starting email discovery for {} records (Concurrency: {})...",
-        total_records_loaded,
+        contacts.len(),
         config.max_concurrency
     );
-    let pb = ProgressBar::new(total_records_loaded as u64);
+    let pb = ProgressBar::new(contacts.len() as u64);
     pb.set_style(ProgressStyle::default_bar()
          .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({percent}%) | ETA: {eta} | {msg}")
          .context("Failed to set progress bar template")?
          .progress_chars("=> "));
     pb.set_message("Processing contacts...");
 
-    let processed_results_unordered = process_contacts(config.clone(), sleuth, contacts).await;
+    let progress_path = progress_log_path(&args.output);
+    let mut progress_log = create_progress_log(&progress_path, &completed_results)?;
+    let snapshot_requested = snapshot_requests();
+    let mut completed_so_far: Vec<ProcessingResult> = Vec::new();
+    let run =
+        process_contacts_with_progress(config.clone(), Arc::clone(&sleuth), &contacts, |result| {
+            pb.inc(1);
+            if let Err(e) = append_progress(&mut progress_log, result) {
+                tracing::warn!(
+                    "Failed to record progress in '{}': {:#}",
+                    progress_path.display(),
+                    e
+                );
+            }
+            completed_so_far.push(result.clone());
+            if snapshot_requested.swap(false, Ordering::Relaxed) {
+                let mut snapshot: Vec<ProcessingResult> = completed_results
+                    .iter()
+                    .chain(&completed_so_far)
+                    .cloned()
+                    .collect();
+                sort_results(&mut snapshot, config.sort_output);
+                match save_outputs(&snapshot, &args.output, &config) {
+                    Ok(()) => tracing::info!(
+                        "Snapshot of {} completed results written to '{}'.",
+                        snapshot.len(),
+                        args.output
+                    ),
+                    Err(e) => tracing::warn!("Failed to write snapshot: {:#}", e),
+                }
+            }
+        });
+    let processed_results_unordered = tokio::select! {
+        results = run => results,
+        _ = tokio::signal::ctrl_c() => {
+            pb.abandon_with_message("Interrupted");
+            let mut partial: Vec<ProcessingResult> = completed_results
+                .into_iter()
+                .chain(completed_so_far)
+                .collect();
+            sort_results(&mut partial, config.sort_output);
+            save_outputs(&partial, &args.output, &config)?;
+            tracing::warn!(
+                "Interrupted: {} completed results written to '{}'. Rerun with --resume to continue.",
+                partial.len(),
+                args.output
+            );
+            return Err(anyhow::anyhow!("Interrupted by Ctrl-C"));
+        }
+    };
+    drop(completed_so_far);
 
     pb.set_position(processed_results_unordered.len() as u64); // Ensure bar shows full completion
     pb.finish_with_message(format!(
@@ -490,82 +1140,508 @@ async fn process_file_mode(
         processed_results_unordered.len()
     ));
 
-    let mut processed_results = processed_results_unordered;
-    tracing::info!("Sorting {} results...", processed_results.len());
-    processed_results.sort_by(|a, b| {
-        let domain_a = a
-            .contact_input
-            .domain
-            .as_deref()
-            .or(a.contact_input.company_domain.as_deref())
-            .unwrap_or("");
-        let domain_b = b
-            .contact_input
-            .domain
-            .as_deref()
-            .or(b.contact_input.company_domain.as_deref())
-            .unwrap_or("");
-        let name_a = a.contact_input.full_name.as_deref().unwrap_or("");
-        let name_b = b.contact_input.full_name.as_deref().unwrap_or("");
-        let lname_a = a.contact_input.last_name.as_deref().unwrap_or("");
-        let lname_b = b.contact_input.last_name.as_deref().unwrap_or("");
-        let fname_a = a.contact_input.first_name.as_deref().unwrap_or("");
-        let fname_b = b.contact_input.first_name.as_deref().unwrap_or("");
-
-        (domain_a, lname_a, fname_a, name_a).cmp(&(domain_b, lname_b, fname_b, name_b))
-    });
+    let mut processed_results = completed_results;
+    processed_results.extend(processed_results_unordered);
+    tracing::info!(
+        "Sorting {} results (order: {:?})...",
+        processed_results.len(),
+        config.sort_output
+    );
+    sort_results(&mut processed_results, config.sort_output);
 
     tracing::info!("Saving results to '{}'...", args.output);
-    save_results(&processed_results, &args.output)?;
+    save_outputs(&processed_results, &args.output, &config)?;
     tracing::info!("Results saved successfully.");
+    drop(progress_log);
+    remove_progress_log(&progress_path);
 
     log_summary(
         &processed_results,
         total_records_loaded,
         start_time.elapsed(),
+        &sleuth.take_warnings(),
+        &config.run_metadata,
     );
 
     Ok(())
 }
 
-fn load_contacts(file_path: &str) -> Result<Vec<Contact>> {
-    tracing::debug!("Opening input file: {}", file_path);
-    let file = File::open(file_path)
-        .with_context(|| format!("Failed to open input file '{}'", file_path))?;
-    let reader = BufReader::new(file);
+/// Processes the input file as a stream: contacts are read one at a time and each result
+/// is written as soon as it completes. Unreadable input records are logged and skipped.
+async fn process_stream_mode(
+    config: Arc<Config>,
+    sleuth: Arc<EmailSleuth>,
+    args: &AppArgs,
+) -> Result<()> {
+    tracing::info!(
+        "Running in Streaming mode. Input: '{}', Output: '{}' (Mode: {})",
+        args.input,
+        args.output,
+        args.mode
+    );
+    if args.resume {
+        return Err(anyhow::anyhow!(
+            "--resume cannot be combined with --stream."
+        ));
+    }
+    prepare_file_paths(&config, args)?;
+    if !InputFormat::from_path(Path::new(&args.input)).is_streamable() {
+        tracing::warn!(
+            "'{}' is read as a JSON array, which is loaded whole. Use NDJSON (.ndjson/.jsonl) or CSV input to keep memory use constant.",
+            args.input
+        );
+    }
 
-    tracing::debug!("Parsing JSON from file: {}", file_path);
-    let records: Vec<Contact> = serde_json::from_reader(reader).with_context(|| {
-        format!(
-            "Failed to parse JSON from '{}'. Ensure it's an array of contact objects.",
-            file_path
-        )
-    })?;
+    let reader = ContactReader::open(&args.input)
+        .with_context(|| format!("Failed to open input file '{}'", args.input))?;
+    let mut unreadable = 0usize;
+    let contacts = reader.filter_map(|record| match record {
+        Ok(contact) => Some(contact),
+        Err(e) => {
+            tracing::warn!(
+                "Skipping unreadable input record in '{}': {}",
+                args.input,
+                e
+            );
+            unreadable += 1;
+            None
+        }
+    });
+
+    let mut writer = JsonWriter::create(&args.output)
+        .with_context(|| format!("Failed to create/truncate output file '{}'", args.output))?;
+    let mut failures = match config.failures_output {
+        Some(ref path) => Some(
+            JsonWriter::create(path)
+                .with_context(|| format!("Failed to create/truncate failures file '{}'", path))?,
+        ),
+        None => None,
+    };
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_style(
+        ProgressStyle::default_spinner()
+            .template("{spinner:.green} [{elapsed_precise}] {pos} processed | {msg}")
+            .context("Failed to set progress bar template")?,
+    );
+    pb.set_message("Processing contacts...");
+
+    let mut found = 0usize;
+    let mut domain_reports = DomainReports::new();
+    let snapshot_requested = snapshot_requests();
+    let run = process_contacts_streaming(config.clone(), sleuth, contacts, |result| {
+        pb.inc(1);
+        if result.email.is_some() {
+            found += 1;
+        }
+        if config.domain_report_output.is_some() {
+            domain_reports.add(&result);
+        }
+        let failure = failures
+            .as_mut()
+            .and_then(|sink| FailureRecord::from_result(&result).map(|record| (sink, record)));
+        match failure {
+            Some((sink, record)) => sink.write_record(&record)?,
+            None => writer.write_result(&result)?,
+        }
+        if snapshot_requested.swap(false, Ordering::Relaxed) {
+            writer.flush()?;
+            if let Some(sink) = failures.as_mut() {
+                sink.flush()?;
+            }
+            tracing::info!(
+                "Flushed {} results streamed so far to '{}'.",
+                writer.written(),
+                args.output
+            );
+        }
+        Ok(())
+    });
+    // On Ctrl-C the results written so far are still closed off into a valid file.
+    let processed = tokio::select! {
+        processed = run => Some(
+            processed.with_context(|| format!("Failed to write results to '{}'", args.output))?,
+        ),
+        _ = tokio::signal::ctrl_c() => None,
+    };
+    let interrupted = processed.is_none();
+    let processed = processed
+        .unwrap_or_else(|| writer.written() + failures.as_ref().map_or(0, |sink| sink.written()));
+
+    writer
+        .finish()
+        .with_context(|| format!("Failed to write results to '{}'", args.output))?;
+    if let (Some(sink), Some(path)) = (failures.as_mut(), config.failures_output.as_deref()) {
+        sink.finish()
+            .with_context(|| format!("Failed to write failures to '{}'", path))?;
+        tracing::info!(
+            "Wrote {} failed/inconclusive records to '{}'.",
+            sink.written(),
+            path
+        );
+    }
+    if let Some(ref path) = config.domain_report_output {
+        save_domain_report(&domain_reports.finish(), path)?;
+    }
+    if interrupted {
+        pb.abandon_with_message("Interrupted");
+        tracing::warn!(
+            "Interrupted: {} results written to '{}'.",
+            processed,
+            args.output
+        );
+        return Err(anyhow::anyhow!("Interrupted by Ctrl-C"));
+    }
+    pb.finish_with_message(format!("Processed {} records", processed));
+
+    tracing::info!(
+        "Streamed {} results to '{}' ({} with an email, {} unreadable input records skipped).",
+        processed,
+        args.output,
+        found,
+        unreadable
+    );
+    log_run_metadata(&config.run_metadata);
+    Ok(())
+}
+
+/// A flag raised each time the process receives SIGHUP, asking a running batch to write out
+/// the results it has so far. The batch clears it once the snapshot is written.
+/// On platforms without SIGHUP it is never raised.
+fn snapshot_requests() -> Arc<AtomicBool> {
+    let requested = Arc::new(AtomicBool::new(false));
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::hangup()) {
+            Ok(mut hangups) => {
+                let requested = Arc::clone(&requested);
+                tokio::spawn(async move {
+                    while hangups.recv().await.is_some() {
+                        tracing::info!(
+                            "SIGHUP received; writing a snapshot when the next result completes."
+                        );
+                        requested.store(true, Ordering::Relaxed);
+                    }
+                });
+            }
+            Err(e) => tracing::warn!("Cannot listen for SIGHUP; snapshots are unavailable: {}", e),
+        }
+    }
+    requested
+}
+
+/// Checks that the input file exists and creates the directories of the output files.
+fn prepare_file_paths(config: &Config, args: &AppArgs) -> Result<()> {
+    let input_path = Path::new(&args.input);
+    if !input_path.exists() || !input_path.is_file() {
+        return Err(anyhow::anyhow!(
+            "Input file not found or is not a file: {}",
+            args.input
+        ));
+    }
+    let failures_path = config.failures_output.as_deref().map(Path::new);
+    let domain_report_path = config.domain_report_output.as_deref().map(Path::new);
+    for path in std::iter::once(Path::new(&args.output))
+        .chain(failures_path)
+        .chain(domain_report_path)
+    {
+        if let Some(parent_dir) = path.parent() {
+            if !parent_dir.as_os_str().is_empty() && !parent_dir.exists() {
+                tracing::debug!("Creating output directory: {}", parent_dir.display());
+                std::fs::create_dir_all(parent_dir).with_context(|| {
+                    format!(
+                        "Failed to create output directory '{}'",
+                        parent_dir.display()
+                    )
+                })?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn load_contacts(file_path: &str) -> Result<Vec<Contact>> {
+    tracing::debug!("Reading contacts from file: {}", file_path);
+    let records = ContactReader::open(file_path)
+        .and_then(|reader| reader.collect::<email_sleuth_core::Result<Vec<Contact>>>())
+        .with_context(|| {
+            format!(
+                "Failed to read contacts from '{}'. Expected a JSON array of contact objects, NDJSON (.ndjson/.jsonl) or CSV (.csv).",
+                file_path
+            )
+        })?;
 
     Ok(records)
 }
 
-/// Saves the processed results to the specified JSON file.
-/// Uses `serde_json` with pretty printing for human readability.
-fn save_results(results: &[ProcessingResult], file_path: &str) -> Result<()> {
-    tracing::debug!("Creating output file: {}", file_path);
-    let file = File::create(file_path)
-        .with_context(|| format!("Failed to create/truncate output file '{}'", file_path))?;
-    let writer = BufWriter::new(file);
+/// Where a batch run records each result as it completes: `<output>.progress.ndjson`.
+fn progress_log_path(output_path: &str) -> std::path::PathBuf {
+    std::path::PathBuf::from(format!("{}.progress.ndjson", output_path))
+}
+
+/// Starts the progress log for a batch run with the results carried over by `--resume`,
+/// so the log alone holds everything the run has finished.
+fn create_progress_log(path: &Path, carried_over: &[ProcessingResult]) -> Result<BufWriter<File>> {
+    let file = File::create(path)
+        .with_context(|| format!("Failed to create progress log '{}'", path.display()))?;
+    let mut writer = BufWriter::new(file);
+    for result in carried_over {
+        append_progress(&mut writer, result)
+            .with_context(|| format!("Failed to write progress log '{}'", path.display()))?;
+    }
+    Ok(writer)
+}
+
+/// Removes the progress log once the output file holds every result.
+fn remove_progress_log(path: &Path) {
+    match std::fs::remove_file(path) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => tracing::warn!("Failed to remove progress log '{}': {}", path.display(), e),
+    }
+}
+
+/// Appends one completed result to the progress log and flushes it to disk.
+fn append_progress(writer: &mut BufWriter<File>, result: &ProcessingResult) -> Result<()> {
+    serde_json::to_writer(&mut *writer, result)?;
+    writer.write_all(b"\n")?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Loads results written by an earlier run, for `--resume`.
+///
+/// An interrupted batch run leaves its progress log (see [`progress_log_path`]) next to
+/// the output file; when present it holds every result that run finished and is used
+/// instead of the output file. A line cut off by the interruption is skipped. Otherwise
+/// the output file must be the JSON array written by a finished (or snapshotted) batch
+/// run. A missing or empty output file means there is nothing to resume from.
+///
+/// A finished run with `failures_output` set wrote only its likely emails to the output
+/// file, so the records in `failures_path` are read back as results too.
+fn load_previous_results(
+    file_path: &str,
+    failures_path: Option<&str>,
+) -> Result<Vec<ProcessingResult>> {
+    let progress_path = progress_log_path(file_path);
+    if progress_path.exists() {
+        return load_progress_log(&progress_path);
+    }
+
+    let mut results = Vec::new();
+    if let Some(failures_path) = failures_path.filter(|path| has_content(Path::new(path))) {
+        let file = File::open(failures_path)
+            .with_context(|| format!("Failed to open failures file '{}'", failures_path))?;
+        let records: Vec<FailureRecord> = serde_json::from_reader(BufReader::new(file))
+            .with_context(|| {
+                format!(
+                    "Failed to parse previous failures from '{}'.",
+                    failures_path
+                )
+            })?;
+        tracing::debug!(
+            "Loaded {} previous failure records from '{}'.",
+            records.len(),
+            failures_path
+        );
+        results.extend(records.into_iter().map(FailureRecord::into_result));
+    }
 
+    let path = Path::new(file_path);
+    if !has_content(path) {
+        tracing::info!(
+            "No previous results found at '{}'; processing {} contacts.",
+            file_path,
+            if results.is_empty() {
+                "all"
+            } else {
+                "the remaining"
+            }
+        );
+        return Ok(results);
+    }
+
+    let file = File::open(path)
+        .with_context(|| format!("Failed to open previous results file '{}'", file_path))?;
+    let previous: Vec<ProcessingResult> = serde_json::from_reader(BufReader::new(file))
+        .with_context(|| {
+            format!(
+                "Failed to parse previous results from '{}'. --resume only reads the JSON array of results written by an earlier batch run (not --stream output or other formats).",
+                file_path
+            )
+        })?;
     tracing::debug!(
-        "Writing {} results as JSON to file: {}",
-        results.len(),
+        "Loaded {} previous results from '{}'.",
+        previous.len(),
         file_path
     );
-    serde_json::to_writer_pretty(writer, results)
-        .with_context(|| format!("Failed to serialize results to JSON for '{}'", file_path))?;
+    results.extend(previous);
+    Ok(results)
+}
+
+/// Whether `path` is an existing, non-empty file.
+fn has_content(path: &Path) -> bool {
+    std::fs::metadata(path)
+        .map(|m| m.len() > 0)
+        .unwrap_or(false)
+}
 
+/// Reads the results recorded in a progress log, skipping lines that don't parse.
+fn load_progress_log(path: &Path) -> Result<Vec<ProcessingResult>> {
+    let file = File::open(path)
+        .with_context(|| format!("Failed to open progress log '{}'", path.display()))?;
+    let mut results = Vec::new();
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let line =
+            line.with_context(|| format!("Failed to read progress log '{}'", path.display()))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<ProcessingResult>(&line) {
+            Ok(result) => results.push(result),
+            Err(e) => tracing::warn!(
+                "Skipping unreadable line {} of progress log '{}': {}",
+                index + 1,
+                path.display(),
+                e
+            ),
+        }
+    }
+    tracing::info!(
+        "Loaded {} results from the progress log of an interrupted run ('{}').",
+        results.len(),
+        path.display()
+    );
+    Ok(results)
+}
+
+/// Splits the input into contacts that already have a usable result and those still to process.
+///
+/// Results are matched to contacts by [`Contact::identity_key`]; duplicate input records each
+/// need their own result. Results that ended in a processing error are dropped so the contact
+/// is retried.
+fn split_completed_contacts(
+    previous_results: Vec<ProcessingResult>,
+    contacts: Vec<Contact>,
+) -> (Vec<ProcessingResult>, Vec<Contact>) {
+    let mut available: HashMap<String, Vec<ProcessingResult>> = HashMap::new();
+    for result in previous_results {
+        if result.email_finding_error.is_some() {
+            continue;
+        }
+        available
+            .entry(result.contact_input.identity_key())
+            .or_default()
+            .push(result);
+    }
+
+    let mut completed = Vec::new();
+    let mut remaining = Vec::new();
+    for contact in contacts {
+        match available
+            .get_mut(&contact.identity_key())
+            .and_then(|results| results.pop())
+        {
+            Some(result) => completed.push(result),
+            None => remaining.push(contact),
+        }
+    }
+    (completed, remaining)
+}
+
+/// Saves the processed results to `output_path` as JSON.
+///
+/// With `failures_output` configured, only results with a likely email go to `output_path`;
+/// the rest are written to the failures file as [`FailureRecord`]s. With `domain_report_output`
+/// configured, the per-domain rollup of `results` is written as well.
+fn save_outputs(results: &[ProcessingResult], output_path: &str, config: &Config) -> Result<()> {
+    if let Some(ref path) = config.domain_report_output {
+        save_domain_report(&DomainReport::from_results(results), path)?;
+    }
+
+    tracing::debug!("Creating output file: {}", output_path);
+    let mut writer = JsonWriter::create(output_path)
+        .with_context(|| format!("Failed to create/truncate output file '{}'", output_path))?;
+
+    let Some(ref failures_path) = config.failures_output else {
+        tracing::debug!(
+            "Writing {} results as JSON to file: {}",
+            results.len(),
+            output_path
+        );
+        return write_results(results, &mut writer)
+            .with_context(|| format!("Failed to write results to '{}'", output_path));
+    };
+
+    tracing::debug!("Creating failures file: {}", failures_path);
+    let mut failures = JsonWriter::create(failures_path).with_context(|| {
+        format!(
+            "Failed to create/truncate failures file '{}'",
+            failures_path
+        )
+    })?;
+    for result in results {
+        match FailureRecord::from_result(result) {
+            Some(failure) => failures
+                .write_record(&failure)
+                .with_context(|| format!("Failed to write failures to '{}'", failures_path))?,
+            None => writer
+                .write_result(result)
+                .with_context(|| format!("Failed to write results to '{}'", output_path))?,
+        }
+    }
+    writer
+        .finish()
+        .with_context(|| format!("Failed to write results to '{}'", output_path))?;
+    failures
+        .finish()
+        .with_context(|| format!("Failed to write failures to '{}'", failures_path))?;
+    tracing::info!(
+        "Wrote {} failed/inconclusive records to '{}'.",
+        failures.written(),
+        failures_path
+    );
     Ok(())
 }
 
+/// Writes per-domain reports to `path` as a JSON array.
+fn save_domain_report(reports: &[DomainReport], path: &str) -> Result<()> {
+    let mut writer = JsonWriter::create(path)
+        .with_context(|| format!("Failed to create/truncate domain report '{}'", path))?;
+    for report in reports {
+        writer
+            .write_record(report)
+            .with_context(|| format!("Failed to write domain report to '{}'", path))?;
+    }
+    writer
+        .finish()
+        .with_context(|| format!("Failed to write domain report to '{}'", path))?;
+    tracing::info!("Wrote reports for {} domains to '{}'.", reports.len(), path);
+    Ok(())
+}
+
+/// Logs the configured run metadata as sorted `key=value` pairs, if there is any.
+fn log_run_metadata(run_metadata: &HashMap<String, String>) {
+    if run_metadata.is_empty() {
+        return;
+    }
+    let mut pairs: Vec<_> = run_metadata
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect();
+    pairs.sort();
+    tracing::info!("Run Metadata                : {}", pairs.join(", "));
+}
+
 /// Logs a summary of the processing results to the console using `tracing::info`.
-fn log_summary(processed_results: &[ProcessingResult], original_total: usize, duration: Duration) {
+fn log_summary(
+    processed_results: &[ProcessingResult],
+    original_total: usize,
+    duration: Duration,
+    warnings: &[Warning],
+    run_metadata: &HashMap<String, String>,
+) {
     let total_records_processed_or_skipped = processed_results.len();
     let successful_finds = processed_results
         .iter()
@@ -587,6 +1663,7 @@ fn log_summary(processed_results: &[ProcessingResult], original_total: usize, du
         .count();
 
     tracing::info!("-------------------- Processing Summary --------------------");
+    log_run_metadata(run_metadata);
     tracing::info!("Total Records in Input File : {}", original_total);
     tracing::info!(
         "Records Processed/Attempted : {}",
@@ -601,6 +1678,38 @@ fn log_summary(processed_results: &[ProcessingResult], original_total: usize, du
         let rate = (total_records_processed_or_skipped as f64) / duration.as_secs_f64();
         tracing::info!("Processing Rate             : {:.2} records/sec", rate);
     }
+
+    let mut stage_totals: HashMap<&str, Duration> = HashMap::new();
+    for timings in processed_results
+        .iter()
+        .filter_map(|r| r.email_discovery_results.as_ref())
+        .map(|results| &results.stage_timings)
+    {
+        for (stage, duration) in timings {
+            *stage_totals.entry(stage.as_str()).or_default() += *duration;
+        }
+    }
+    if !stage_totals.is_empty() {
+        let mut stages: Vec<_> = stage_totals.into_iter().collect();
+        stages.sort_by_key(|(_, duration)| std::cmp::Reverse(*duration));
+        tracing::info!("Time by Stage (all contacts):");
+        for (stage, duration) in stages {
+            tracing::info!("  - {:<24}: {:.2?}", stage, duration);
+        }
+    }
+
+    if !warnings.is_empty() {
+        let mut by_category: HashMap<WarningCategory, usize> = HashMap::new();
+        for warning in warnings {
+            *by_category.entry(warning.category).or_default() += 1;
+        }
+        let mut categories: Vec<_> = by_category.into_iter().collect();
+        categories.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        tracing::info!("Warnings ({} total):", warnings.len());
+        for (category, count) in categories {
+            tracing::info!("  - {:<24}: {}", category, count);
+        }
+    }
     tracing::info!("----------------------------------------------------------");
 }
 
@@ -652,7 +1761,7 @@ fn print_cli_results(result: &ProcessingResult, config: &Config) {
         } else if result
             .email_discovery_results
             .as_ref()
-            .map_or(true, |r| r.found_emails.is_empty())
+            .is_none_or(|r| r.found_emails.is_empty())
         {
             println!("Reason: No potential email candidates were generated or found.");
         } else {
@@ -662,8 +1771,8 @@ fn print_cli_results(result: &ProcessingResult, config: &Config) {
 
     if !result.email_alternatives.is_empty() {
         println!(
-            "\n{BLUE}Alternative Emails (Confidence > 0, up to {}):{RESET}",
-            config.max_alternatives
+            "\n{BLUE}Alternative Emails (Confidence >= {}, up to {}):{RESET}",
+            config.min_alternative_confidence, config.max_alternatives
         );
         for alt in result.email_alternatives.iter() {
             let details = result
@@ -701,3 +1810,85 @@ fn print_cli_results(result: &ProcessingResult, config: &Config) {
 
     println!("{BLUE}=============================={RESET}\n");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use email_sleuth_core::FailureKind;
+
+    fn contact(first_name: &str) -> Contact {
+        Contact {
+            first_name: Some(first_name.to_string()),
+            last_name: Some("Doe".to_string()),
+            domain: Some("acme.com".to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn failure(first_name: &str, failure_kind: FailureKind, reason: &str) -> FailureRecord {
+        FailureRecord {
+            contact_input: contact(first_name),
+            failure_kind,
+            error_code: None,
+            reason: reason.to_string(),
+            debug_log: Vec::new(),
+            run_metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_resume_reads_failures_output() {
+        let dir = std::env::temp_dir().join(format!("email-sleuth-resume-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let output = dir.join("results.json");
+        let failures = dir.join("failures.json");
+        std::fs::write(
+            &output,
+            r#"[{"first_name": "Ann", "last_name": "Doe", "domain": "acme.com", "email": "ann.doe@acme.com", "email_discovery_results": null}]"#,
+        )
+        .unwrap();
+        let records = vec![
+            failure("Bob", FailureKind::Skipped, "Missing last name"),
+            failure(
+                "Cat",
+                FailureKind::Inconclusive,
+                "Candidates found, but none reached the confidence threshold",
+            ),
+            failure("Dan", FailureKind::Error, "Core processing error: timeout"),
+        ];
+        std::fs::write(&failures, serde_json::to_string(&records).unwrap()).unwrap();
+
+        let previous =
+            load_previous_results(output.to_str().unwrap(), Some(failures.to_str().unwrap()))
+                .unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(previous.len(), 4);
+
+        let contacts = ["Ann", "Bob", "Cat", "Dan", "Eve"].map(contact).to_vec();
+        let (completed, remaining) = split_completed_contacts(previous, contacts);
+        let remaining: Vec<_> = remaining
+            .iter()
+            .map(|contact| contact.first_name.as_deref().unwrap())
+            .collect();
+        // The errored contact is retried; the skipped and inconclusive ones are done.
+        assert_eq!(remaining, ["Dan", "Eve"]);
+        assert_eq!(completed.len(), 3);
+
+        // Written out again, the carried-over failures keep their records.
+        let rewritten: Vec<_> = completed
+            .iter()
+            .filter_map(FailureRecord::from_result)
+            .map(|record| (record.failure_kind, record.reason))
+            .collect();
+        assert_eq!(
+            rewritten,
+            [
+                (FailureKind::Skipped, "Missing last name".to_string()),
+                (
+                    FailureKind::Inconclusive,
+                    "Candidates found, but none reached the confidence threshold".to_string()
+                ),
+            ]
+        );
+    }
+}
@@ -1,10 +1,27 @@
 use email_sleuth_core::{AppError, Config, Result};
+use once_cell::sync::Lazy;
 use std::fs::{self, File};
 use std::path::PathBuf;
 use std::process::Command;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::time::sleep;
 
+/// Timeout for a single `/status` request. A local ChromeDriver answers in milliseconds.
+const STATUS_PROBE_TIMEOUT: Duration = Duration::from_millis(500);
+/// How long a freshly started instance gets to become responsive.
+const READINESS_TIMEOUT: Duration = Duration::from_secs(15);
+/// First and largest delay between readiness probes; the delay doubles after each probe.
+const READINESS_INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+const READINESS_MAX_BACKOFF: Duration = Duration::from_secs(2);
+
+/// Shared client for `/status` probes, so repeated probes reuse pooled keep-alive connections.
+static STATUS_CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
+    reqwest::Client::builder()
+        .timeout(STATUS_PROBE_TIMEOUT)
+        .build()
+        .unwrap_or_default()
+});
+
 /// Default paths for service files
 pub fn default_paths() -> (PathBuf, PathBuf, PathBuf) {
     let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
@@ -110,55 +127,257 @@ pub fn detect_driver_path(config: &Config) -> Result<PathBuf> {
     ))
 }
 
-/// Checks if ChromeDriver is responsive
-async fn is_responsive() -> bool {
-    match reqwest::Client::new()
-        .get("http://localhost:4444/status")
-        .timeout(Duration::from_secs(2))
-        .send()
-        .await
+/// Port whose instance keeps the original `chromedriver.pid`/`.log` file names.
+const BASE_PORT: u16 = 4444;
+
+/// Returns the ports of all ChromeDriver instances to manage.
+///
+/// The first port comes from `webdriver_url`, or `webdriver_port` (default 4444) if no URL
+/// is set; with `chromedriver_instances` > 1 the following ports are used for the
+/// additional instances.
+fn instance_ports(config: &Config) -> Vec<u16> {
+    let base = config
+        .webdriver_url
+        .as_deref()
+        .and_then(|u| url::Url::parse(u).ok())
+        .and_then(|u| u.port())
+        .unwrap_or(config.webdriver_port);
+    (0..config.chromedriver_instances.max(1) as u16)
+        .map(|i| base.saturating_add(i))
+        .collect()
+}
+
+/// PID and log file paths for the instance on `port`.
+///
+/// The instance on port 4444 keeps the original `chromedriver.pid`/`.log` names.
+fn instance_paths(port: u16) -> (PathBuf, PathBuf) {
+    let (_, pid_file, log_file) = default_paths();
+    if port == BASE_PORT {
+        return (pid_file, log_file);
+    }
+    let service_dir = pid_file
+        .parent()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("."));
+    (
+        service_dir.join(format!("chromedriver-{}.pid", port)),
+        service_dir.join(format!("chromedriver-{}.log", port)),
+    )
+}
+
+/// Lists every ChromeDriver PID file in the service directory, whatever the current config.
+fn all_pid_files() -> Vec<PathBuf> {
+    let (_, pid_file, _) = default_paths();
+    let Some(service_dir) = pid_file.parent() else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = fs::read_dir(service_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| {
+                    p.file_name()
+                        .and_then(|n| n.to_str())
+                        .is_some_and(|n| n.starts_with("chromedriver") && n.ends_with(".pid"))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+/// Reads a PID file, removing it if its content is invalid.
+fn read_pid(pid_file: &PathBuf) -> Result<Option<u32>> {
+    if !pid_file.exists() {
+        return Ok(None);
+    }
+    let pid_str = fs::read_to_string(pid_file)?;
+    match pid_str.trim().parse::<u32>() {
+        Ok(pid) => Ok(Some(pid)),
+        Err(e) => {
+            tracing::error!("Invalid PID in file {}: {}", pid_file.display(), e);
+            fs::remove_file(pid_file)?;
+            Ok(None)
+        }
+    }
+}
+
+/// Checks if a process with the given PID exists.
+#[cfg(unix)]
+fn is_process_alive(pid: u32) -> Result<bool> {
+    let output = Command::new("ps").arg("-p").arg(pid.to_string()).output()?;
+    Ok(output.status.success())
+}
+
+/// Finds the PID of the process listening on TCP `port`, via `lsof` or, failing that, `ss`.
+#[cfg(unix)]
+fn find_listening_pid(port: u16) -> Option<u32> {
+    if let Ok(output) = Command::new("lsof")
+        .args(["-t", "-sTCP:LISTEN"])
+        .arg(format!("-iTCP:{}", port))
+        .output()
     {
-        Ok(response) if response.status().is_success() => true,
-        _ => false,
+        if let Some(pid) = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find_map(|line| line.trim().parse().ok())
+        {
+            return Some(pid);
+        }
     }
+    // `ss` prints e.g. `users:(("chromedriver",pid=1234,fd=3))`.
+    let output = Command::new("ss")
+        .args(["-ltnpH"])
+        .arg(format!("sport = :{}", port))
+        .output()
+        .ok()?;
+    let listing = String::from_utf8_lossy(&output.stdout);
+    let pid_start = listing.find("pid=")? + "pid=".len();
+    listing[pid_start..]
+        .split(|c: char| !c.is_ascii_digit())
+        .next()?
+        .parse()
+        .ok()
 }
 
-/// Starts the ChromeDriver service
-pub async fn start(config: &Config) -> Result<()> {
-    let (_, pid_file, log_file) = default_paths();
+#[cfg(not(unix))]
+fn find_listening_pid(_port: u16) -> Option<u32> {
+    None
+}
 
+/// Checks whether the process with the given PID is a ChromeDriver.
+#[cfg(unix)]
+fn is_chromedriver_process(pid: u32) -> bool {
+    Command::new("ps")
+        .args(["-o", "comm=", "-p"])
+        .arg(pid.to_string())
+        .output()
+        .is_ok_and(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .to_lowercase()
+                .contains("chromedriver")
+        })
+}
+
+#[cfg(not(unix))]
+fn is_chromedriver_process(_pid: u32) -> bool {
+    false
+}
+
+/// Looks for a ChromeDriver listening on `port` without a PID file (e.g. the file was
+/// deleted or corrupted) and adopts it by writing its PID file.
+///
+/// Returns the adopted PID, or an error if another program holds the port.
+fn adopt_orphan(port: u16, pid_file: &PathBuf) -> Result<Option<u32>> {
+    let Some(pid) = find_listening_pid(port) else {
+        return Ok(None);
+    };
+    if !is_chromedriver_process(pid) {
+        return Err(AppError::Initialization(format!(
+            "Port {} is in use by another program (PID {}). Choose a different webdriver_port.",
+            port, pid
+        )));
+    }
+    tracing::warn!(
+        "Found an orphaned ChromeDriver (PID {}) on port {} without a PID file. Adopting it.",
+        pid,
+        port
+    );
+    fs::write(pid_file, pid.to_string())?;
+    Ok(Some(pid))
+}
+
+/// Checks if the ChromeDriver instance on `port` is responsive
+async fn is_responsive(port: u16) -> bool {
+    matches!(
+        STATUS_CLIENT
+            .get(format!("http://localhost:{}/status", port))
+            .send()
+            .await,
+        Ok(response) if response.status().is_success()
+    )
+}
+
+/// Polls `/status` with exponential backoff until the instance on `port` responds,
+/// `timeout` elapses, or `is_running` reports that the process has exited.
+async fn wait_until_responsive(
+    port: u16,
+    timeout: Duration,
+    mut is_running: impl FnMut() -> bool,
+) -> bool {
+    let deadline = Instant::now() + timeout;
+    let mut backoff = READINESS_INITIAL_BACKOFF;
+    loop {
+        if is_responsive(port).await {
+            return true;
+        }
+        if !is_running() {
+            tracing::error!("ChromeDriver on port {} exited before becoming ready", port);
+            return false;
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return false;
+        }
+        sleep(backoff.min(deadline - now)).await;
+        backoff = (backoff * 2).min(READINESS_MAX_BACKOFF);
+    }
+}
+
+/// Starts the ChromeDriver service (one instance per configured port).
+/// Returns the PIDs of the instances this call launched; ones already running are left out.
+/// Non-fatal issues met on the way, such as a stale PID file, are added to `warnings`.
+pub async fn start(config: &Config, warnings: &mut Vec<String>) -> Result<Vec<u32>> {
     let driver_path = detect_driver_path(config)?;
 
-    if pid_file.exists() {
-        let pid_str = fs::read_to_string(&pid_file)?;
-        let pid = pid_str
-            .trim()
-            .parse::<u32>()
-            .map_err(|e| AppError::Initialization(format!("Invalid PID in file: {}", e)))?;
+    let mut launched = Vec::new();
+    for port in instance_ports(config) {
+        launched.extend(start_instance(&driver_path, port, warnings).await?);
+    }
+    Ok(launched)
+}
 
+/// Starts a single ChromeDriver instance on `port`, unless a responsive one is already running.
+/// Returns the new process's PID if one was launched.
+async fn start_instance(
+    driver_path: &PathBuf,
+    port: u16,
+    warnings: &mut Vec<String>,
+) -> Result<Option<u32>> {
+    let (pid_file, log_file) = instance_paths(port);
+
+    if let Some(pid) = read_pid(&pid_file)? {
         // On Unix, check if process exists
         #[cfg(unix)]
         {
-            let output = Command::new("ps").arg("-p").arg(pid.to_string()).output()?;
-
-            if output.status.success() {
-                // Process exists
-                tracing::info!("ChromeDriver already running with PID: {}", pid);
+            if is_process_alive(pid)? {
+                tracing::info!(
+                    "ChromeDriver on port {} already running with PID: {}",
+                    port,
+                    pid
+                );
 
                 // Check if responsive
-                if is_responsive().await {
-                    tracing::info!("ChromeDriver service is responsive at http://localhost:4444");
-                    return Ok(());
+                if is_responsive(port).await {
+                    tracing::info!(
+                        "ChromeDriver service is responsive at http://localhost:{}",
+                        port
+                    );
+                    return Ok(None);
                 } else {
                     tracing::warn!(
                         "ChromeDriver process exists but is not responsive. Restarting..."
                     );
-                    stop(config).await?;
+                    stop_instance(&pid_file).await?;
                 }
             } else {
                 // Process doesn't exist, remove stale PID file
                 tracing::warn!("Found stale PID file, removing");
                 fs::remove_file(&pid_file)?;
+                warnings.push(format!(
+                    "Removed a stale ChromeDriver PID file for port {} (PID {} was not running)",
+                    port, pid
+                ));
             }
         }
 
@@ -166,12 +385,31 @@ pub async fn start(config: &Config) -> Result<()> {
         // #[cfg(windows)]
         // {
         //     tracing::warn!("Found existing PID file but cannot verify process on this platform. Attempting restart.");
-        //     stop(config).await?;
+        //     stop_instance(&pid_file).await?;
         // }
     }
 
+    if !pid_file.exists() {
+        if let Some(pid) = adopt_orphan(port, &pid_file)? {
+            if is_responsive(port).await {
+                tracing::info!(
+                    "ChromeDriver service is responsive at http://localhost:{} (PID {})",
+                    port,
+                    pid
+                );
+                return Ok(None);
+            }
+            tracing::warn!("Adopted ChromeDriver is not responsive. Restarting...");
+            stop_instance(&pid_file).await?;
+        }
+    }
+
     // Start ChromeDriver
-    tracing::info!("Starting ChromeDriver at {}", driver_path.display());
+    tracing::info!(
+        "Starting ChromeDriver at {} on port {}",
+        driver_path.display(),
+        port
+    );
 
     // Ensure log file directory exists
     if let Some(log_dir) = log_file.parent() {
@@ -180,8 +418,8 @@ pub async fn start(config: &Config) -> Result<()> {
 
     let log_file_handle = File::create(&log_file)?;
 
-    let child = Command::new(&driver_path)
-        .arg("--port=4444")
+    let mut child = Command::new(driver_path)
+        .arg(format!("--port={}", port))
         .arg("--whitelisted-ips=\"\"")
         .stdout(std::process::Stdio::from(log_file_handle.try_clone()?))
         .stderr(std::process::Stdio::from(log_file_handle))
@@ -190,41 +428,68 @@ pub async fn start(config: &Config) -> Result<()> {
     let pid = child.id();
     fs::write(&pid_file, pid.to_string())?;
 
-    // Give it a moment to start
-    sleep(Duration::from_secs(2)).await;
-
-    // Check if responsive
-    if !is_responsive().await {
-        sleep(Duration::from_secs(3)).await;
-        if !is_responsive().await {
-            tracing::error!("ChromeDriver started but is not responsive");
-            return Err(AppError::Initialization(
-                "ChromeDriver started but is not responding at http://localhost:4444".to_string(),
-            ));
-        }
+    let ready = wait_until_responsive(port, READINESS_TIMEOUT, || {
+        matches!(child.try_wait(), Ok(None))
+    })
+    .await;
+    if !ready {
+        tracing::error!("ChromeDriver started but is not responsive");
+        return Err(AppError::Initialization(format!(
+            "ChromeDriver started but is not responding at http://localhost:{} (see {})",
+            port,
+            log_file.display()
+        )));
     }
 
-    tracing::info!("ChromeDriver started successfully with PID {}", pid);
-    Ok(())
+    tracing::info!(
+        "ChromeDriver started successfully on port {} with PID {}",
+        port,
+        pid
+    );
+    Ok(Some(pid))
 }
 
-/// Stops the ChromeDriver service
-pub async fn stop(_config: &Config) -> Result<()> {
-    let (_, pid_file, _) = default_paths();
+/// Stops the ChromeDriver service, including every instance that has a PID file and any
+/// orphaned ChromeDriver still listening on a configured port
+pub async fn stop(config: &Config) -> Result<()> {
+    for port in instance_ports(config) {
+        let (pid_file, _) = instance_paths(port);
+        if !pid_file.exists() {
+            if let Err(e) = adopt_orphan(port, &pid_file) {
+                tracing::warn!("{}", e);
+            }
+        }
+    }
+    let pid_files = all_pid_files();
 
-    if !pid_file.exists() {
+    if pid_files.is_empty() {
         tracing::info!("ChromeDriver is not running (no PID file found)");
         return Ok(());
     }
 
-    let pid_str = fs::read_to_string(&pid_file)?;
-    let pid = match pid_str.trim().parse::<u32>() {
-        Ok(p) => p,
-        Err(e) => {
-            tracing::error!("Invalid PID in file: {}", e);
-            fs::remove_file(&pid_file)?;
-            return Ok(());
+    for pid_file in &pid_files {
+        stop_instance(pid_file).await?;
+    }
+    tracing::info!("ChromeDriver stopped ({} instance(s))", pid_files.len());
+
+    Ok(())
+}
+
+/// Stops only the ChromeDriver instances with the given PIDs (those launched by [`start`]),
+/// leaving instances started by other processes running.
+pub async fn stop_started(pids: &[u32]) -> Result<()> {
+    for pid_file in all_pid_files() {
+        if read_pid(&pid_file)?.is_some_and(|pid| pids.contains(&pid)) {
+            stop_instance(&pid_file).await?;
         }
+    }
+    Ok(())
+}
+
+/// Stops the ChromeDriver instance recorded in `pid_file` and removes the file.
+async fn stop_instance(pid_file: &PathBuf) -> Result<()> {
+    let Some(pid) = read_pid(pid_file)? else {
+        return Ok(());
     };
 
     tracing::info!("Stopping ChromeDriver (PID: {})", pid);
@@ -235,18 +500,14 @@ pub async fn stop(_config: &Config) -> Result<()> {
         Command::new("kill").arg(pid.to_string()).output()?;
 
         for _ in 0..10 {
-            let output = Command::new("ps").arg("-p").arg(pid.to_string()).output()?;
-
-            if !output.status.success() {
+            if !is_process_alive(pid)? {
                 break;
             }
             sleep(Duration::from_millis(500)).await;
         }
 
         // Force kill if still running
-        let output = Command::new("ps").arg("-p").arg(pid.to_string()).output()?;
-
-        if output.status.success() {
+        if is_process_alive(pid)? {
             tracing::warn!("ChromeDriver did not terminate gracefully, forcing...");
             Command::new("kill")
                 .arg("-9")
@@ -264,76 +525,104 @@ pub async fn stop(_config: &Config) -> Result<()> {
     // }
 
     // Remove PID file
-    fs::remove_file(&pid_file)?;
-    tracing::info!("ChromeDriver stopped");
+    fs::remove_file(pid_file)?;
 
     Ok(())
 }
 
-/// Checks the status of the ChromeDriver service
-pub async fn status(_config: &Config) -> Result<bool> {
-    let (_, pid_file, _) = default_paths();
-
-    if !pid_file.exists() {
-        tracing::info!("ChromeDriver is not running (no PID file found)");
-        return Ok(false);
-    }
+/// Checks the status of the ChromeDriver service.
+/// Returns true only if every configured instance is running and responsive.
+pub async fn status(config: &Config) -> Result<bool> {
+    let mut all_responsive = true;
+
+    for port in instance_ports(config) {
+        let (pid_file, _) = instance_paths(port);
+
+        let pid = match read_pid(&pid_file)? {
+            Some(pid) => pid,
+            None => match adopt_orphan(port, &pid_file) {
+                Ok(Some(pid)) => pid,
+                Ok(None) => {
+                    tracing::info!(
+                        "ChromeDriver on port {} is not running (no PID file found)",
+                        port
+                    );
+                    all_responsive = false;
+                    continue;
+                }
+                Err(e) => {
+                    tracing::warn!("{}", e);
+                    all_responsive = false;
+                    continue;
+                }
+            },
+        };
 
-    let pid_str = fs::read_to_string(&pid_file)?;
-    let pid = match pid_str.trim().parse::<u32>() {
-        Ok(p) => p,
-        Err(e) => {
-            tracing::error!("Invalid PID in file: {}", e);
-            fs::remove_file(&pid_file)?;
-            return Ok(false);
+        // Check if process is running - platform-specific code
+        #[cfg(unix)]
+        {
+            if !is_process_alive(pid)? {
+                tracing::info!(
+                    "ChromeDriver on port {} is not running (stale PID file)",
+                    port
+                );
+                fs::remove_file(&pid_file)?;
+                all_responsive = false;
+                continue;
+            }
         }
-    };
-
-    // Check if process is running - platform-specific code
-    #[cfg(unix)]
-    {
-        let output = Command::new("ps").arg("-p").arg(pid.to_string()).output()?;
 
-        if !output.status.success() {
-            tracing::info!("ChromeDriver is not running (stale PID file)");
-            fs::remove_file(&pid_file)?;
-            return Ok(false);
+        // Check if the service is responsive
+        if is_responsive(port).await {
+            tracing::info!(
+                "ChromeDriver on port {} is running with PID {} and is responsive",
+                port,
+                pid
+            );
+        } else {
+            tracing::warn!(
+                "ChromeDriver process on port {} exists (PID {}) but is not responding",
+                port,
+                pid
+            );
+            all_responsive = false;
         }
     }
 
-    // Check if the service is responsive
-    let is_responsive = is_responsive().await;
+    Ok(all_responsive)
+}
 
-    if is_responsive {
-        tracing::info!("ChromeDriver is running with PID {} and is responsive", pid);
-    } else {
-        tracing::warn!(
-            "ChromeDriver process exists (PID {}) but is not responding",
-            pid
-        );
-    }
+/// Gets the recent logs from every configured ChromeDriver instance
+pub fn logs(config: &Config, lines: usize) -> Result<String> {
+    let ports = instance_ports(config);
+    let mut sections = Vec::new();
 
-    Ok(is_responsive)
-}
+    for &port in &ports {
+        let (_, log_file) = instance_paths(port);
+        if !log_file.exists() {
+            continue;
+        }
+
+        let content = fs::read_to_string(&log_file)?;
+        let log_lines: Vec<&str> = content.lines().collect();
 
-/// Gets the recent logs from the ChromeDriver service
-pub fn logs(lines: usize) -> Result<String> {
-    let (_, _, log_file) = default_paths();
+        let lines_to_show = std::cmp::min(lines, log_lines.len());
+        let start_idx = log_lines.len().saturating_sub(lines_to_show);
 
-    if !log_file.exists() {
+        let recent_logs = log_lines[start_idx..].join("\n");
+        if ports.len() > 1 {
+            sections.push(format!("[port {}]\n{}", port, recent_logs));
+        } else {
+            sections.push(recent_logs);
+        }
+    }
+
+    if sections.is_empty() {
         return Err(AppError::Initialization(
             "ChromeDriver log file not found".to_string(),
         ));
     }
-
-    let content = fs::read_to_string(&log_file)?;
-    let log_lines: Vec<&str> = content.lines().collect();
-
-    let lines_to_show = std::cmp::min(lines, log_lines.len());
-    let start_idx = log_lines.len().saturating_sub(lines_to_show);
-
-    let recent_logs = log_lines[start_idx..].join("\n");
-    Ok(recent_logs)
+    Ok(sections.join("\n\n"))
 }
 
 /// Restarts the ChromeDriver service
@@ -343,5 +632,5 @@ pub async fn restart(config: &Config) -> Result<()> {
     // Small delay before restart
     sleep(Duration::from_secs(1)).await;
 
-    start(config).await
+    start(config, &mut Vec::new()).await.map(|_| ())
 }
@@ -0,0 +1,104 @@
+//! A fixed-size Bloom filter for remembering which addresses a run has already seen.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Set membership in a fixed number of bits: `insert` never forgets an item, but may
+/// report a new one as already seen at about the false-positive rate it was sized for.
+#[derive(Debug)]
+pub(crate) struct BloomFilter {
+    bits: Vec<u64>,
+    num_bits: u64,
+    num_hashes: u32,
+}
+
+impl BloomFilter {
+    /// A filter holding up to `expected_items` items at `false_positive_rate`.
+    pub(crate) fn new(expected_items: usize, false_positive_rate: f64) -> Self {
+        let n = expected_items.max(1) as f64;
+        let ln2 = std::f64::consts::LN_2;
+        let num_bits = (-n * false_positive_rate.ln() / (ln2 * ln2))
+            .ceil()
+            .max(64.0) as u64;
+        let num_hashes = ((num_bits as f64 / n) * ln2).round().clamp(1.0, 32.0) as u32;
+        Self {
+            bits: vec![0; num_bits.div_ceil(64) as usize],
+            num_bits,
+            num_hashes,
+        }
+    }
+
+    /// Adds `item` and returns whether it was new; `false` means it was (probably) seen.
+    pub(crate) fn insert(&mut self, item: &str) -> bool {
+        if self.contains(item) {
+            return false;
+        }
+        for index in self.indexes(item) {
+            self.bits[(index / 64) as usize] |= 1 << (index % 64);
+        }
+        true
+    }
+
+    /// Whether `item` was (probably) inserted before.
+    pub(crate) fn contains(&self, item: &str) -> bool {
+        self.indexes(item)
+            .all(|index| self.bits[(index / 64) as usize] & (1 << (index % 64)) != 0)
+    }
+
+    /// The size of the bit array in bytes.
+    pub(crate) fn memory_bytes(&self) -> usize {
+        self.bits.len() * std::mem::size_of::<u64>()
+    }
+
+    /// The bits `item` maps to, by double hashing.
+    fn indexes(&self, item: &str) -> impl Iterator<Item = u64> {
+        let mut first = DefaultHasher::new();
+        item.hash(&mut first);
+        let h1 = first.finish();
+        let mut second = DefaultHasher::new();
+        (h1, item).hash(&mut second);
+        let h2 = second.finish() | 1;
+        let num_bits = self.num_bits;
+        (0..u64::from(self.num_hashes)).map(move |i| h1.wrapping_add(i.wrapping_mul(h2)) % num_bits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bloom_filter_dedups_within_memory_bound() {
+        let expected = 10_000;
+        let rate = 0.01;
+        let mut filter = BloomFilter::new(expected, rate);
+
+        // -n ln p / (ln 2)^2 bits, rounded up to whole words.
+        let bound = (expected as f64 * -rate.ln() / std::f64::consts::LN_2.powi(2) / 8.0).ceil()
+            as usize
+            + 8;
+        assert!(
+            filter.memory_bytes() <= bound,
+            "{} > {}",
+            filter.memory_bytes(),
+            bound
+        );
+
+        for i in 0..expected {
+            filter.insert(&format!("person{}@example.com", i));
+        }
+        for i in 0..expected {
+            assert!(!filter.insert(&format!("person{}@example.com", i)));
+        }
+        assert!(filter.memory_bytes() <= bound);
+
+        let false_positives = (0..expected)
+            .filter(|i| filter.contains(&format!("other{}@example.org", i)))
+            .count();
+        assert!(
+            (false_positives as f64) < expected as f64 * rate * 2.0,
+            "{} false positives",
+            false_positives
+        );
+    }
+}
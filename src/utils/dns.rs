@@ -2,9 +2,12 @@
 
 use crate::core::config::Config;
 use crate::core::error::{AppError, Result};
+use crate::utils::domain::registrable_domain;
 use std::net::{IpAddr, SocketAddr};
 use std::str::FromStr;
 use trust_dns_resolver::config::{LookupIpStrategy, ResolverConfig, ResolverOpts};
+use trust_dns_resolver::error::{ResolveError, ResolveErrorKind};
+use trust_dns_resolver::proto::error::ProtoErrorKind;
 use trust_dns_resolver::TokioAsyncResolver;
 
 /// Represents the result of a mail server lookup.
@@ -13,8 +16,12 @@ pub(crate) struct MailServer {
     /// The domain name or IP address of the mail server.
     pub exchange: String,
     /// The preference value (lower is more preferred), typically from MX records.
-    /// Will be `u16::MAX` if derived from an A record.
+    /// Will be `0` for an implicit MX derived from an A/AAAA record.
     pub preference: u16,
+    /// True if the domain has no MX records and its own A/AAAA host is used instead (RFC 5321 5.1).
+    pub implicit: bool,
+    /// Lower-priority MX hosts, most preferred first. Empty for an implicit MX.
+    pub backups: Vec<String>,
 }
 
 /// Creates a configured DNS resolver instance.
@@ -59,9 +66,17 @@ pub(crate) async fn create_resolver(config: &Config) -> Result<TokioAsyncResolve
     resolver_opts.timeout = config.dns_timeout;
     resolver_opts.attempts = 2;
     resolver_opts.ip_strategy = LookupIpStrategy::Ipv4AndIpv6;
+    resolver_opts.validate = config.dnssec_validation;
+    if config.skip_dead_domains {
+        // A cached NXDOMAIN must not outlive the dead-domain mark it caused.
+        resolver_opts.negative_max_ttl = Some(config.dead_domain_ttl);
+    }
 
     let resolver = TokioAsyncResolver::tokio(resolver_config, resolver_opts);
-    tracing::debug!("DNS resolver configured with public servers and timeout.");
+    tracing::debug!(
+        "DNS resolver configured with public servers and timeout (DNSSEC validation: {}).",
+        config.dnssec_validation
+    );
     Ok(resolver)
 }
 
@@ -117,9 +132,24 @@ pub(crate) async fn resolve_mail_server(
                     exchange,
                     preference
                 );
+                let mut backups: Vec<String> = Vec::new();
+                for mx in mx_records.iter().skip(1) {
+                    let host = mx.exchange().to_utf8().trim_end_matches('.').to_string();
+                    if !host.is_empty()
+                        && !host.eq_ignore_ascii_case(&exchange)
+                        && !backups.iter().any(|b| b.eq_ignore_ascii_case(&host))
+                    {
+                        backups.push(host);
+                    }
+                }
+                if !backups.is_empty() {
+                    tracing::debug!("Backup MX hosts for {}: {:?}", domain, backups);
+                }
                 Ok(MailServer {
                     exchange,
                     preference,
+                    implicit: false,
+                    backups,
                 })
             } else {
                 tracing::warn!(
@@ -143,6 +173,9 @@ pub(crate) async fn resolve_mail_server(
             {
                 tracing::error!("Domain {} does not exist (NXDOMAIN)", domain);
                 Err(AppError::NxDomain(domain.to_string()))
+            } else if is_dnssec_failure(&e) {
+                tracing::error!("DNSSEC validation failed for {}: {}", domain, e);
+                Err(AppError::DnssecValidation(format!("{}: {}", domain, e)))
             } else if error_string.contains("Timeout") {
                 tracing::error!("DNS resolution timeout for {}", domain);
                 Err(AppError::DnsTimeout(domain.to_string()))
@@ -154,7 +187,36 @@ pub(crate) async fn resolve_mail_server(
     }
 }
 
-/// Attempts to resolve an A record for the domain as a fallback mail server.
+/// Returns true if a resolver error comes from DNSSEC validation, e.g. missing RRSIGs, an
+/// unverifiable DNSKEY chain or a negative answer that could not be proven.
+/// Such errors only occur when `dnssec_validation` is enabled.
+///
+/// trust-dns 0.23 reports a failed proof as a protocol error from its validating handle:
+/// `RrsigsNotPresent`, or a message naming what could not be validated.
+fn is_dnssec_failure(error: &ResolveError) -> bool {
+    let ResolveErrorKind::Proto(proto) = error.kind() else {
+        return false;
+    };
+    match proto.kind() {
+        ProtoErrorKind::RrsigsNotPresent { .. } => true,
+        ProtoErrorKind::Message(message) => is_dnssec_message(message),
+        ProtoErrorKind::Msg(message) => is_dnssec_message(message),
+        _ => false,
+    }
+}
+
+/// Whether a protocol error message is one of the DNSSEC validating handle's.
+fn is_dnssec_message(message: &str) -> bool {
+    let lower = message.to_lowercase();
+    ["rrsig", "dnskey", "validat", "no results to verify"]
+        .iter()
+        .any(|marker| lower.contains(marker))
+}
+
+/// Attempts to resolve an A/AAAA record for the domain as an implicit MX.
+///
+/// Per RFC 5321 section 5.1, a domain without MX records but with an address record
+/// receives mail at that host, treated as an MX with preference 0.
 async fn resolve_a_record_fallback(
     resolver: &TokioAsyncResolver,
     domain: &str,
@@ -163,15 +225,16 @@ async fn resolve_a_record_fallback(
     match resolver.lookup_ip(domain).await {
         Ok(a_response) => {
             if let Some(ip_addr) = a_response.iter().next() {
-                let mail_server_ip = ip_addr.to_string();
                 tracing::info!(
-                    "Using A record for {} as mail server: {}",
+                    "No MX for {}; using implicit MX at the domain's own address ({})",
                     domain,
-                    mail_server_ip
+                    ip_addr
                 );
                 Ok(MailServer {
-                    exchange: mail_server_ip,
-                    preference: u16::MAX,
+                    exchange: domain.trim_end_matches('.').to_string(),
+                    preference: 0,
+                    implicit: true,
+                    backups: Vec::new(),
                 })
             } else {
                 tracing::error!("No MX or A records found for {}", domain);
@@ -195,6 +258,13 @@ async fn resolve_a_record_fallback(
                     domain
                 );
                 Err(AppError::NxDomain(domain.to_string()))
+            } else if is_dnssec_failure(&e) {
+                tracing::error!(
+                    "DNSSEC validation failed during A record fallback for {}: {}",
+                    domain,
+                    e
+                );
+                Err(AppError::DnssecValidation(format!("{}: {}", domain, e)))
             } else if error_string.contains("Timeout") {
                 tracing::error!("DNS timeout during A record fallback for {}", domain);
                 Err(AppError::DnsTimeout(format!(
@@ -212,3 +282,99 @@ async fn resolve_a_record_fallback(
         }
     }
 }
+
+/// Names the mail provider behind `mx_host` for `domain`, using `config.mx_providers`.
+///
+/// The longest configured suffix matching the host wins. A host without a match is
+/// `self_hosted` when it belongs to the domain itself, otherwise `other`.
+pub(crate) fn classify_mx_provider(config: &Config, domain: &str, mx_host: &str) -> String {
+    let host = mx_host.trim().trim_end_matches('.').to_lowercase();
+    let provider = config
+        .mx_providers
+        .iter()
+        .filter(|(suffix, _)| {
+            host == **suffix
+                || host
+                    .strip_suffix(suffix.as_str())
+                    .is_some_and(|rest| rest.ends_with('.'))
+        })
+        .max_by_key(|(suffix, _)| suffix.len())
+        .map(|(_, provider)| provider.clone());
+    if let Some(provider) = provider {
+        return provider;
+    }
+    let domain = domain.trim().trim_end_matches('.').to_lowercase();
+    if registrable_domain(&host) == registrable_domain(&domain) {
+        "self_hosted".to_string()
+    } else {
+        "other".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::ConfigBuilder;
+
+    #[test]
+    fn test_is_dnssec_failure() {
+        use trust_dns_resolver::proto::error::ProtoError;
+        use trust_dns_resolver::proto::rr::{Name, RecordType};
+
+        let missing_rrsigs =
+            ResolveError::from(ProtoError::from(ProtoErrorKind::RrsigsNotPresent {
+                name: Name::from_str("acme.com.").unwrap(),
+                record_type: RecordType::MX,
+            }));
+        assert!(is_dnssec_failure(&missing_rrsigs));
+        let bad_keys = ResolveError::from(ProtoError::from("Could not validate all DNSKEYs"));
+        assert!(is_dnssec_failure(&bad_keys));
+
+        let timeout = ResolveError::from(ResolveErrorKind::Timeout);
+        assert!(!is_dnssec_failure(&timeout));
+        let other = ResolveError::from(ProtoError::from("connection refused"));
+        assert!(!is_dnssec_failure(&other));
+    }
+
+    #[test]
+    fn test_classify_mx_provider() {
+        let config = Config::default();
+        assert_eq!(
+            classify_mx_provider(&config, "acme.com", "ASPMX.L.GOOGLE.COM."),
+            "google"
+        );
+        assert_eq!(
+            classify_mx_provider(&config, "acme.com", "acme-com.mail.protection.outlook.com"),
+            "microsoft_365"
+        );
+        assert_eq!(
+            classify_mx_provider(
+                &config,
+                "outlook.com",
+                "outlook-com.olc.protection.outlook.com"
+            ),
+            "microsoft_consumer"
+        );
+        assert_eq!(
+            classify_mx_provider(&config, "acme.com", "mx.zoho.com"),
+            "zoho"
+        );
+        assert_eq!(
+            classify_mx_provider(&config, "acme.com", "mail.acme.com"),
+            "self_hosted"
+        );
+        assert_eq!(
+            classify_mx_provider(&config, "acme.com", "mx.notgoogle.com"),
+            "other"
+        );
+
+        let config = ConfigBuilder::new()
+            .mx_provider(".Mail.Example-Host.net.", "example_host")
+            .build()
+            .unwrap();
+        assert_eq!(
+            classify_mx_provider(&config, "acme.com", "mx1.mail.example-host.net"),
+            "example_host"
+        );
+    }
+}
@@ -1,8 +1,8 @@
 //! Utility functions for handling domain names and URLs.
 
-use crate::core::error::{AppError, Result};
-use tracing;
-use url::Url;
+use crate::core::error::{AppError, DomainErrorKind, Result};
+use std::collections::HashMap;
+use url::{Host, Url};
 
 /// Extracts the base domain name (e.g., "example.com") from a given URL or domain string.
 ///
@@ -13,12 +13,25 @@ use url::Url;
 /// - Removes common `www.` prefix.
 /// - Converts to lowercase.
 ///
-/// Returns `Err(AppError::DomainExtraction)` if the input is empty or a host cannot be parsed.
+/// Returns `Err(AppError::InvalidDomain)` with a [`DomainErrorKind`] describing the problem
+/// if the input is empty, not a URL, has no host or a non-HTTP scheme, is an IP address,
+/// or does not end in a known public suffix.
 pub(crate) fn get_domain_from_url(website_url_or_domain: &str) -> Result<String> {
+    get_domain_from_url_with_overrides(website_url_or_domain, &HashMap::new())
+}
+
+/// Like [`get_domain_from_url`], but also accepts domains (and their subdomains) that have
+/// an entry in `dns_overrides`, even when they do not end in a known public suffix
+/// (e.g. `acme.test` pointed at a local SMTP sink).
+pub(crate) fn get_domain_from_url_with_overrides(
+    website_url_or_domain: &str,
+    dns_overrides: &HashMap<String, Vec<String>>,
+) -> Result<String> {
     let trimmed_input = website_url_or_domain.trim();
     if trimmed_input.is_empty() {
         tracing::warn!("Received empty input for domain extraction.");
-        return Err(AppError::DomainExtraction(
+        return Err(domain_error(
+            DomainErrorKind::EmptyInput,
             "Input string is empty".to_string(),
         ));
     }
@@ -44,6 +57,7 @@ pub(crate) fn get_domain_from_url(website_url_or_domain: &str) -> Result<String>
                 && trimmed_input.contains('.')
                 && !trimmed_input.starts_with('.')
                 && !trimmed_input.ends_with('.')
+                && !trimmed_input.contains(char::is_whitespace)
             {
                 tracing::warn!(
                     "Input '{}' failed URL parsing but looks like a domain, attempting direct use.",
@@ -52,25 +66,64 @@ pub(crate) fn get_domain_from_url(website_url_or_domain: &str) -> Result<String>
                 let host = trimmed_input.strip_prefix("www.").unwrap_or(trimmed_input);
                 return Ok(host.to_lowercase());
             }
-            return Err(AppError::UrlParse(e).into());
+            return Err(domain_error(
+                DomainErrorKind::InvalidUrl,
+                format!("'{}' is not a valid URL or domain: {}", trimmed_input, e),
+            ));
         }
     };
 
-    let host = url.host_str().ok_or_else(|| {
-        tracing::warn!("Could not extract host component from parsed URL: {}", url);
-        AppError::DomainExtraction(format!("Could not extract host from parsed URL: {}", url))
-    })?;
+    if url.scheme() != "http" && url.scheme() != "https" {
+        return Err(domain_error(
+            DomainErrorKind::InvalidScheme,
+            format!(
+                "'{}' uses scheme '{}', expected http or https",
+                trimmed_input,
+                url.scheme()
+            ),
+        ));
+    }
+
+    let host = match url.host() {
+        Some(Host::Domain(host)) if !host.is_empty() => host,
+        Some(Host::Ipv4(_)) | Some(Host::Ipv6(_)) => {
+            return Err(domain_error(
+                DomainErrorKind::IpAddress,
+                format!("'{}' is an IP address, not a domain name", trimmed_input),
+            ));
+        }
+        _ => {
+            tracing::warn!("Could not extract host component from parsed URL: {}", url);
+            return Err(domain_error(
+                DomainErrorKind::NoHost,
+                format!("Could not extract host from '{}'", trimmed_input),
+            ));
+        }
+    };
 
     let domain = host.strip_prefix("www.").unwrap_or(host);
 
     let final_domain = domain.to_lowercase();
 
-    if !final_domain.contains('.') || final_domain.starts_with('.') || final_domain.ends_with('.') {
+    if final_domain.starts_with('.') || final_domain.ends_with('.') || final_domain.contains("..") {
         tracing::error!("Extracted domain '{}' appears invalid.", final_domain);
-        return Err(AppError::DomainExtraction(format!(
-            "Extracted domain appears invalid: {}",
+        return Err(domain_error(
+            DomainErrorKind::InvalidHost,
+            format!("Extracted domain appears invalid: {}", final_domain),
+        ));
+    }
+    if !is_overridden(&final_domain, dns_overrides)
+        && (!final_domain.contains('.')
+            || !psl::suffix(final_domain.as_bytes()).is_some_and(|suffix| suffix.is_known()))
+    {
+        tracing::error!(
+            "Extracted domain '{}' does not end in a known public suffix.",
             final_domain
-        )));
+        );
+        return Err(domain_error(
+            DomainErrorKind::UnrecognizedSuffix,
+            format!("'{}' does not end in a known public suffix", final_domain),
+        ));
     }
 
     tracing::debug!(
@@ -81,6 +134,105 @@ pub(crate) fn get_domain_from_url(website_url_or_domain: &str) -> Result<String>
     Ok(final_domain)
 }
 
+/// Whether `domain` or one of its parent domains has an entry in `dns_overrides`.
+fn is_overridden(domain: &str, dns_overrides: &HashMap<String, Vec<String>>) -> bool {
+    let mut candidate = domain;
+    loop {
+        if dns_overrides.contains_key(candidate) {
+            return true;
+        }
+        match candidate.split_once('.') {
+            Some((_, parent)) => candidate = parent,
+            None => return false,
+        }
+    }
+}
+
+fn domain_error(kind: DomainErrorKind, detail: String) -> AppError {
+    AppError::InvalidDomain { kind, detail }
+}
+
+/// Reduces a host name to its registrable domain using the public suffix list,
+/// e.g. "blog.acme.co.uk" -> "acme.co.uk".
+///
+/// Returns the host unchanged if it is itself a public suffix or cannot be matched.
+pub(crate) fn registrable_domain(host: &str) -> String {
+    psl::domain_str(host).unwrap_or(host).to_string()
+}
+
+/// Returns the entry of `known_domains` that `domain` looks like a misspelling of, if any.
+///
+/// A domain matches when it reads the same once look-alike characters are folded
+/// (`0`/`o`, `1`/`l`/`i`, `rn`/`m`, `vv`/`w`), or when its name differs by one edit
+/// (a changed, missing, extra or swapped character) and the suffix is the same. Names
+/// shorter than 4 characters are only compared by look-alikes, since one edit turns
+/// most short names into other real ones. A domain in `known_domains` never matches.
+pub(crate) fn likely_typo_of<'a>(domain: &str, known_domains: &'a [String]) -> Option<&'a str> {
+    let domain = domain.trim_end_matches('.').to_lowercase();
+    if known_domains.contains(&domain) {
+        return None;
+    }
+    let (name, suffix) = domain.split_once('.')?;
+    let skeleton = homoglyph_skeleton(&domain);
+    known_domains
+        .iter()
+        .find(|known| {
+            if homoglyph_skeleton(known) == skeleton {
+                return true;
+            }
+            match known.split_once('.') {
+                Some((known_name, known_suffix)) => {
+                    known_suffix == suffix
+                        && known_name.chars().count() >= 4
+                        && edit_distance(known_name, name) == 1
+                }
+                None => false,
+            }
+        })
+        .map(|known| known.as_str())
+}
+
+/// Folds characters that are easily confused in print or OCR into one form.
+fn homoglyph_skeleton(domain: &str) -> String {
+    domain
+        .replace("rn", "m")
+        .replace("vv", "w")
+        .chars()
+        .map(|c| match c {
+            '0' => 'o',
+            '1' | 'i' => 'l',
+            _ => c,
+        })
+        .collect()
+}
+
+/// Edit distance counting substitutions, insertions, deletions and swaps of adjacent
+/// characters (optimal string alignment).
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = best;
+        }
+    }
+    rows[a.len()][b.len()]
+}
+
 /// Parses the input website string into a valid `Url` object.
 ///
 /// Adds `https://` scheme if missing. Useful for ensuring a base URL for scraping.
@@ -177,6 +329,88 @@ mod tests {
         assert!(get_domain_from_url("https://example.").is_err()); // Trailing dot on host might be invalid contextually here
     }
 
+    #[test]
+    fn test_get_domain_from_url_error_kinds() {
+        let kind = |input: &str| match get_domain_from_url(input) {
+            Err(AppError::InvalidDomain { kind, .. }) => Some(kind),
+            _ => None,
+        };
+        assert_eq!(kind("  "), Some(DomainErrorKind::EmptyInput));
+        assert_eq!(kind("not a url"), Some(DomainErrorKind::InvalidUrl));
+        assert_eq!(
+            kind("ftp://example.com"),
+            Some(DomainErrorKind::InvalidScheme)
+        );
+        assert_eq!(
+            kind("http://192.168.1.10"),
+            Some(DomainErrorKind::IpAddress)
+        );
+        assert_eq!(kind("https://[::1]/"), Some(DomainErrorKind::IpAddress));
+        assert_eq!(kind("example"), Some(DomainErrorKind::UnrecognizedSuffix));
+        assert_eq!(kind("https://example."), Some(DomainErrorKind::InvalidHost));
+    }
+
+    #[test]
+    fn test_get_domain_from_url_accepts_overridden_domains() {
+        let mut overrides = HashMap::new();
+        overrides.insert("acme.test".to_string(), vec!["127.0.0.1".to_string()]);
+
+        assert_eq!(
+            get_domain_from_url_with_overrides("https://www.acme.test/team", &overrides).unwrap(),
+            "acme.test"
+        );
+        assert_eq!(
+            get_domain_from_url_with_overrides("mail.acme.test", &overrides).unwrap(),
+            "mail.acme.test"
+        );
+        assert!(matches!(
+            get_domain_from_url_with_overrides("other.test", &overrides),
+            Err(AppError::InvalidDomain {
+                kind: DomainErrorKind::UnrecognizedSuffix,
+                ..
+            })
+        ));
+        assert!(get_domain_from_url("acme.test").is_err());
+    }
+
+    #[test]
+    fn test_likely_typo_of() {
+        let known: Vec<String> = ["rajg.com", "google.com", "microsoft.com", "ibm.com"]
+            .iter()
+            .map(|d| d.to_string())
+            .collect();
+        assert_eq!(likely_typo_of("raig.com", &known), Some("rajg.com"));
+        assert_eq!(likely_typo_of("gooogle.com", &known), Some("google.com"));
+        assert_eq!(likely_typo_of("googel.com", &known), Some("google.com"));
+        assert_eq!(
+            likely_typo_of("rnicrosoft.com", &known),
+            Some("microsoft.com")
+        );
+        assert_eq!(likely_typo_of("g00gle.com", &known), Some("google.com"));
+
+        assert_eq!(likely_typo_of("google.com", &known), None);
+        assert_eq!(likely_typo_of("google.de", &known), None);
+        assert_eq!(likely_typo_of("ibn.com", &known), None);
+        assert_eq!(likely_typo_of("acme.com", &known), None);
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("google", "google"), 0);
+        assert_eq!(edit_distance("google", "googel"), 1);
+        assert_eq!(edit_distance("google", "gogle"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn test_registrable_domain() {
+        assert_eq!(registrable_domain("blog.acme.co.uk"), "acme.co.uk");
+        assert_eq!(registrable_domain("acme.co.uk"), "acme.co.uk");
+        assert_eq!(registrable_domain("mail.eu.example.com"), "example.com");
+        assert_eq!(registrable_domain("example.com"), "example.com");
+        assert_eq!(registrable_domain("co.uk"), "co.uk");
+    }
+
     #[test]
     fn test_normalize_url_valid() {
         assert_eq!(
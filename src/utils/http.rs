@@ -0,0 +1,105 @@
+//! Rate-limit aware sending of HTTP requests for scraping and API checks.
+
+use crate::core::config::{get_random_sleep_duration, Config};
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use reqwest::header::RETRY_AFTER;
+use reqwest::{RequestBuilder, Response, StatusCode};
+use std::time::Duration;
+use tokio::time::sleep;
+
+/// Sends the request built by `request`, retrying up to `rate_limit_max_retries` times while
+/// the server answers `429 Too Many Requests`.
+///
+/// Each retry waits for the delay in the response's `Retry-After` header, or a random
+/// `sleep_between_requests` pause when there is none or it cannot be read. A delay longer
+/// than `rate_limit_max_wait` is not waited for. Whatever response comes last is returned,
+/// so a request that stays rate limited ends with the 429.
+pub(crate) async fn send_with_rate_limit_retries<F>(
+    config: &Config,
+    request: F,
+) -> reqwest::Result<Response>
+where
+    F: Fn() -> RequestBuilder,
+{
+    let mut retries = 0;
+    loop {
+        let response = request().send().await?;
+        if response.status() != StatusCode::TOO_MANY_REQUESTS
+            || retries >= config.rate_limit_max_retries
+        {
+            return Ok(response);
+        }
+
+        let retry_after = response
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok());
+        let delay = match retry_after.and_then(|value| parse_retry_after(value, Utc::now())) {
+            Some(delay) => delay,
+            None => {
+                if let Some(value) = retry_after {
+                    tracing::debug!(target: "http", "Unreadable Retry-After '{}' from {}", value, response.url());
+                }
+                get_random_sleep_duration(config)
+            }
+        };
+        if delay > config.rate_limit_max_wait {
+            tracing::warn!(target: "http",
+                "{} is rate limited for {:.0?}, longer than rate_limit_max_wait ({:.0?}). Not retrying.",
+                response.url(), delay, config.rate_limit_max_wait
+            );
+            return Ok(response);
+        }
+
+        retries += 1;
+        tracing::info!(target: "http",
+            "{} returned 429 Too Many Requests. Retrying in {:.1?} ({}/{}).",
+            response.url(), delay, retries, config.rate_limit_max_retries
+        );
+        sleep(delay).await;
+    }
+}
+
+/// Reads a `Retry-After` value as the delay from `now`: either delta-seconds (`120`) or an
+/// HTTP-date (`Sun, 06 Nov 1994 08:49:37 GMT`, or the obsolete RFC 850 and asctime forms).
+/// A date in the past means no delay.
+pub(crate) fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = DateTime::parse_from_rfc2822(value)
+        .map(|date| date.with_timezone(&Utc))
+        .or_else(|_| {
+            NaiveDateTime::parse_from_str(value, "%A, %d-%b-%y %H:%M:%S GMT")
+                .or_else(|_| NaiveDateTime::parse_from_str(value, "%a %b %e %H:%M:%S %Y"))
+                .map(|date| date.and_utc())
+        })
+        .ok()?;
+    Some((date - now).to_std().unwrap_or(Duration::ZERO))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_parse_retry_after() {
+        let now = Utc.with_ymd_and_hms(1994, 11, 6, 8, 49, 0).unwrap();
+        assert_eq!(
+            parse_retry_after("120", now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            parse_retry_after("Sun, 06 Nov 1994 08:49:37 GMT", now),
+            Some(Duration::from_secs(37))
+        );
+        assert_eq!(
+            parse_retry_after("Sun, 06 Nov 1994 08:48:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+}
@@ -1,4 +1,10 @@
+#[cfg(feature = "bloom")]
+pub(crate) mod bloom;
 pub(crate) mod dns;
 pub(crate) mod domain;
+pub(crate) mod http;
+pub(crate) mod names;
 pub(crate) mod patterns;
+pub(crate) mod scraping;
+#[cfg(feature = "smtp")]
 pub(crate) mod smtp;
@@ -0,0 +1,123 @@
+//! Fuzzy matching of person names against free text, such as a team member's card on a
+//! scraped page.
+
+/// Score at or above which scraped text is taken to name the contact.
+pub(crate) const NAME_MATCH_THRESHOLD: f64 = 0.9;
+
+/// Jaro-Winkler similarity of two strings, from 0.0 (nothing in common) to 1.0 (equal).
+pub(crate) fn jaro_winkler(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let window = (a.len().max(b.len()) / 2).saturating_sub(1);
+    let mut a_matched = vec![false; a.len()];
+    let mut b_matched = vec![false; b.len()];
+    let mut matches = 0usize;
+    for (i, &ca) in a.iter().enumerate() {
+        let end = (i + window + 1).min(b.len());
+        for j in i.saturating_sub(window)..end {
+            if !b_matched[j] && b[j] == ca {
+                a_matched[i] = true;
+                b_matched[j] = true;
+                matches += 1;
+                break;
+            }
+        }
+    }
+    if matches == 0 {
+        return 0.0;
+    }
+
+    let a_seq = a
+        .iter()
+        .zip(&a_matched)
+        .filter(|(_, m)| **m)
+        .map(|(c, _)| c);
+    let b_seq = b
+        .iter()
+        .zip(&b_matched)
+        .filter(|(_, m)| **m)
+        .map(|(c, _)| c);
+    let transpositions = a_seq.zip(b_seq).filter(|(x, y)| x != y).count() / 2;
+
+    let m = matches as f64;
+    let jaro = (m / a.len() as f64 + m / b.len() as f64 + (m - transpositions as f64) / m) / 3.0;
+    let prefix = a.iter().zip(&b).take(4).take_while(|(x, y)| x == y).count();
+    jaro + prefix as f64 * 0.1 * (1.0 - jaro)
+}
+
+/// How well `text` names the person `first_name last_name`, from 0.0 to 1.0.
+///
+/// Looks for the name as "First Last" (allowing one middle name in between) or
+/// "Last, First" anywhere in the text, ignoring case and punctuation. An initial
+/// standing in for the first name ("J. Smith") counts as a near match.
+pub(crate) fn name_similarity(first_name: &str, last_name: &str, text: &str) -> f64 {
+    let first = first_name.trim().to_lowercase();
+    let last = last_name.trim().to_lowercase();
+    if first.is_empty() || last.is_empty() {
+        return 0.0;
+    }
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+
+    let mut best = 0.0f64;
+    for (i, word) in words.iter().enumerate() {
+        for next in words.iter().skip(i + 1).take(2) {
+            best = best.max((first_similarity(&first, word) + jaro_winkler(&last, next)) / 2.0);
+        }
+        if let Some(next) = words.get(i + 1) {
+            best = best.max((jaro_winkler(&last, word) + first_similarity(&first, next)) / 2.0);
+        }
+    }
+    best
+}
+
+fn first_similarity(first: &str, word: &str) -> f64 {
+    if word.chars().count() == 1 && first.starts_with(word) {
+        0.9
+    } else {
+        jaro_winkler(first, word)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() < 0.001
+    }
+
+    #[test]
+    fn test_jaro_winkler() {
+        assert!(approx(jaro_winkler("martha", "marhta"), 0.961));
+        assert!(approx(jaro_winkler("dwayne", "duane"), 0.84));
+        assert!(approx(jaro_winkler("dixon", "dicksonx"), 0.813));
+        assert_eq!(jaro_winkler("smith", "smith"), 1.0);
+        assert_eq!(jaro_winkler("abc", "xyz"), 0.0);
+        assert_eq!(jaro_winkler("", "x"), 0.0);
+    }
+
+    #[test]
+    fn test_name_similarity() {
+        let card = "Jane Doe Head of Sales Email";
+        assert!(name_similarity("Jane", "Doe", card) >= NAME_MATCH_THRESHOLD);
+        assert!(name_similarity("Jon", "Doe", "John Doe, CTO") >= NAME_MATCH_THRESHOLD);
+        assert!(name_similarity("Jane", "Doe", "Doe, Jane") >= NAME_MATCH_THRESHOLD);
+        assert!(name_similarity("Jane", "Doe", "J. Doe") >= NAME_MATCH_THRESHOLD);
+        assert!(name_similarity("Jane", "Doe", "Jane Marie Doe") >= NAME_MATCH_THRESHOLD);
+
+        assert!(name_similarity("Jane", "Doe", "John Doe") < NAME_MATCH_THRESHOLD);
+        assert!(name_similarity("Jane", "Doe", "Press enquiries") < NAME_MATCH_THRESHOLD);
+        assert_eq!(name_similarity("Jane", "Doe", ""), 0.0);
+    }
+}
@@ -1,8 +1,10 @@
 //! Generates potential email address patterns based on names and domain.
 
 use crate::core::config::Config;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
 use std::collections::HashSet;
-use tracing;
 
 /// Removes most non-alphanumeric characters, whitespace, and converts to lowercase.
 /// Designed to create usable parts for email local-part generation.
@@ -15,10 +17,35 @@ fn sanitize_name_part(part: &str) -> String {
         .to_lowercase()
 }
 
+/// Spellings of a sanitized first and last name to build patterns from, as
+/// (first, last) pairs: as written, without apostrophes (`o'brien` → `obrien`), and without
+/// apostrophes or hyphens (`jean-luc` → `jeanluc`). Duplicates and empty parts are left out.
+fn name_spellings(first: &str, last: &str) -> Vec<(String, String)> {
+    let strip_apostrophes = |part: &str| part.replace('\'', "");
+    let strip_all = |part: &str| part.replace(['\'', '-'], "");
+    let mut spellings: Vec<(String, String)> = Vec::new();
+    for spelling in [
+        (first.to_string(), last.to_string()),
+        (strip_apostrophes(first), strip_apostrophes(last)),
+        (strip_all(first), strip_all(last)),
+    ] {
+        if !spelling.0.is_empty() && !spelling.1.is_empty() && !spellings.contains(&spelling) {
+            spellings.push(spelling);
+        }
+    }
+    spellings
+}
+
 /// Generates a list of common email address patterns for a given name and domain.
 ///
+/// Names with apostrophes or hyphens (O'Brien, Jean-Luc) get patterns for each spelling
+/// from [`name_spellings`], e.g. both `o'brien` and `obrien`.
+///
 /// Uses the `email_regex` from the [`Config`] to validate generated patterns.
 /// Returns an empty vector if names are empty after sanitization or if the domain is invalid.
+///
+/// The patterns come back shuffled; with `random_seed` set, the order depends only on the
+/// seed and the contact (see [`contact_rng`]).
 pub(crate) fn generate_email_patterns(
     config: &Config,
     first_name: &str,
@@ -55,14 +82,47 @@ pub(crate) fn generate_email_patterns(
         return Vec::new();
     }
 
+    let mut patterns = HashSet::new();
+    for (first, last) in name_spellings(&first, &last) {
+        insert_name_patterns(&mut patterns, &first, &last);
+    }
+
+    // Sorted first so the shuffle below alone decides the order.
+    let mut local_parts: Vec<String> = patterns.into_iter().collect();
+    local_parts.sort();
+    local_parts.shuffle(&mut contact_rng(config, first_name, last_name, domain));
+
+    // Build full email addresses and validate against regex
+    let final_patterns: Vec<String> = local_parts
+        .into_iter()
+        .map(|local_part| format!("{}@{}", local_part, domain))
+        .filter(|p| {
+            let is_match = config.email_regex.is_match(p);
+            if !is_match {
+                tracing::trace!("Generated pattern failed regex validation: {}", p);
+            }
+            is_match
+        })
+        .collect();
+
+    tracing::debug!(
+        "Generated {} unique valid patterns for '{} {}' @ '{}'",
+        final_patterns.len(),
+        first_name,
+        last_name,
+        domain
+    );
+    final_patterns
+}
+
+/// Adds the local-part patterns for one spelling of a name to `patterns`.
+fn insert_name_patterns(patterns: &mut HashSet<String>, first: &str, last: &str) {
     let first_initial = first.chars().next().unwrap_or_default();
     let last_initial = last.chars().next().unwrap_or_default();
 
-    let mut patterns = HashSet::new();
-
     // Basic name patterns
-    patterns.insert(first.clone()); // john
-    patterns.insert(last.clone()); // doe
+    patterns.insert(first.to_string()); // john
+    patterns.insert(last.to_string()); // doe
     patterns.insert(format!("{}{}", first, last)); // johndoe
     patterns.insert(format!("{}{}", last, first)); // doejohn
     patterns.insert(format!("{}{}", first_initial, last)); // jdoe
@@ -97,28 +157,158 @@ pub(crate) fn generate_email_patterns(
     // Single-initial patterns
     patterns.insert(first_initial.to_string());
     patterns.insert(last_initial.to_string());
+}
 
-    // Build full email addresses and validate against regex
-    let final_patterns: Vec<String> = patterns
-        .into_iter()
-        .map(|local_part| format!("{}@{}", local_part, domain))
-        .filter(|p| {
-            let is_match = config.email_regex.is_match(p);
-            if !is_match {
-                tracing::trace!("Generated pattern failed regex validation: {}", p);
-            }
-            is_match
+/// Returns the random number generator for one contact: seeded from `random_seed`
+/// mixed with the contact's name and domain when a seed is configured, from entropy
+/// otherwise.
+fn contact_rng(config: &Config, first_name: &str, last_name: &str, domain: &str) -> StdRng {
+    let Some(seed) = config.random_seed else {
+        return StdRng::from_entropy();
+    };
+    // FNV-1a, so the mix is the same on every platform and Rust version.
+    let key = format!("{} {}@{}", first_name, last_name, domain).to_lowercase();
+    let hash = key.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    StdRng::seed_from_u64(seed ^ hash)
+}
+
+/// Local-part templates recognised when inferring a domain's naming convention.
+/// Ordered most specific first, so `john.doe` is read as `{first}.{last}` rather than `{first}`.
+const NAMING_CONVENTIONS: &[&str] = &[
+    "{first}.{last}",
+    "{last}.{first}",
+    "{f}.{last}",
+    "{first}.{l}",
+    "{first}_{last}",
+    "{last}_{first}",
+    "{f}_{last}",
+    "{first}-{last}",
+    "{last}-{first}",
+    "{f}-{last}",
+    "{first}{last}",
+    "{last}{first}",
+    "{f}{last}",
+    "{first}{l}",
+    "{first}",
+    "{last}",
+];
+
+/// Local-part templates by how widely companies use them, most common first. Generated
+/// patterns are shuffled before probing, so this is what ranks them when nothing verifies.
+const PATTERN_POPULARITY: &[&str] = &[
+    "{first}.{last}",
+    "{f}{last}",
+    "{first}",
+    "{first}{last}",
+    "{first}_{last}",
+    "{f}.{last}",
+    "{first}{l}",
+    "{last}",
+    "{first}-{last}",
+    "{last}.{first}",
+    "{last}{first}",
+    "{first}.{l}",
+    "{f}_{last}",
+    "{f}-{last}",
+    "{last}_{first}",
+    "{last}-{first}",
+];
+
+/// Placeholders a naming convention template may use.
+pub(crate) const CONVENTION_PLACEHOLDERS: &[&str] = &["{first}", "{last}", "{f}", "{l}"];
+
+/// Whether `template` is a usable naming convention: it has at least one placeholder and
+/// no unknown ones, and renders to a plausible local part.
+pub(crate) fn is_valid_naming_convention(template: &str) -> bool {
+    let rendered = render_convention(template, "a", "b");
+    rendered != template
+        && !rendered.contains(['{', '}', '@'])
+        && !rendered.chars().any(char::is_whitespace)
+}
+
+/// Fills a naming convention template with the sanitized name parts.
+fn render_convention(convention: &str, first: &str, last: &str) -> String {
+    let first_initial = first.chars().next().map(String::from).unwrap_or_default();
+    let last_initial = last.chars().next().map(String::from).unwrap_or_default();
+    convention
+        .replace("{first}", first)
+        .replace("{last}", last)
+        .replace("{f}", &first_initial)
+        .replace("{l}", &last_initial)
+}
+
+/// Infers the naming convention (e.g. `{first}.{last}`) behind a confirmed address.
+///
+/// Returns `None` if the local part does not match any known template for this name.
+pub(crate) fn infer_naming_convention(
+    first_name: &str,
+    last_name: &str,
+    email: &str,
+) -> Option<&'static str> {
+    let first = sanitize_name_part(first_name);
+    let last = sanitize_name_part(last_name);
+    if first.is_empty() || last.is_empty() {
+        return None;
+    }
+    let local_part = email.split('@').next()?.to_lowercase();
+    NAMING_CONVENTIONS
+        .iter()
+        .copied()
+        .find(|convention| render_convention(convention, &first, &last) == local_part)
+}
+
+/// How common the pattern behind `email` is for this name, 0 being the most common (see
+/// [`PATTERN_POPULARITY`]).
+///
+/// Returns `None` if the local part follows none of the ranked patterns for any spelling
+/// of the name.
+pub(crate) fn pattern_rank(first_name: &str, last_name: &str, email: &str) -> Option<usize> {
+    let first = sanitize_name_part(first_name);
+    let last = sanitize_name_part(last_name);
+    let local_part = email.split('@').next()?.to_lowercase();
+    name_spellings(&first, &last)
+        .iter()
+        .filter_map(|(first, last)| {
+            PATTERN_POPULARITY
+                .iter()
+                .position(|pattern| render_convention(pattern, first, last) == local_part)
         })
-        .collect();
+        .min()
+}
 
-    tracing::debug!(
-        "Generated {} unique valid patterns for '{} {}' @ '{}'",
-        final_patterns.len(),
-        first_name,
-        last_name,
+/// Builds the address a naming convention produces for a name at `domain`.
+pub(crate) fn apply_naming_convention(
+    convention: &str,
+    first_name: &str,
+    last_name: &str,
+    domain: &str,
+) -> Option<String> {
+    let first = sanitize_name_part(first_name);
+    let last = sanitize_name_part(last_name);
+    if first.is_empty() || last.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "{}@{}",
+        render_convention(convention, &first, &last),
         domain
-    );
-    final_patterns
+    ))
+}
+
+/// Normalizes the casing of an address: the domain is always lowercased, the local part
+/// only with `lowercase_local_part`. Surrounding whitespace is removed.
+pub(crate) fn normalize_email(email: &str, lowercase_local_part: bool) -> String {
+    let email = email.trim();
+    match email.rsplit_once('@') {
+        Some((local_part, domain)) if lowercase_local_part => {
+            format!("{}@{}", local_part.to_lowercase(), domain.to_lowercase())
+        }
+        Some((local_part, domain)) => format!("{}@{}", local_part, domain.to_lowercase()),
+        None if lowercase_local_part => email.to_lowercase(),
+        None => email.to_string(),
+    }
 }
 
 #[cfg(test)]
@@ -132,6 +322,52 @@ mod tests {
             .expect("Failed to build default config for test")
     }
 
+    #[test]
+    fn test_generate_email_patterns_seeded_order() {
+        let config = ConfigBuilder::new().random_seed(7).build().unwrap();
+        let first = generate_email_patterns(&config, "John", "Doe", "acme.com");
+        assert_eq!(
+            first,
+            generate_email_patterns(&config, "John", "Doe", "acme.com")
+        );
+
+        let mut sorted = first.clone();
+        sorted.sort();
+        let mut unseeded = generate_email_patterns(&test_config(), "John", "Doe", "acme.com");
+        unseeded.sort();
+        assert_eq!(sorted, unseeded);
+
+        let other = generate_email_patterns(&config, "Jane", "Roe", "acme.com");
+        assert_ne!(
+            first
+                .iter()
+                .map(|p| p.split('@').next().unwrap().len())
+                .collect::<Vec<_>>(),
+            other
+                .iter()
+                .map(|p| p.split('@').next().unwrap().len())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_is_valid_naming_convention() {
+        assert!(is_valid_naming_convention("{f}{last}"));
+        assert!(is_valid_naming_convention("{first}.{last}"));
+        assert!(!is_valid_naming_convention("firstlast"));
+        assert!(!is_valid_naming_convention("{first}.{surname}"));
+        assert!(!is_valid_naming_convention("{first}@acme.com"));
+        assert!(!is_valid_naming_convention("{first} {last}"));
+    }
+
+    #[test]
+    fn test_apply_naming_convention_with_custom_template() {
+        assert_eq!(
+            apply_naming_convention("{f}{last}", "John", "Doe", "acme.com").as_deref(),
+            Some("jdoe@acme.com")
+        );
+    }
+
     #[test]
     fn test_generate_patterns_basic() {
         let config = test_config();
@@ -158,6 +394,29 @@ mod tests {
         assert!(patterns.contains(&"j.o@starfleet.org".to_string()));
     }
 
+    #[test]
+    fn test_generate_patterns_apostrophe_spellings() {
+        let config = test_config();
+        let patterns = generate_email_patterns(&config, "Conor", "O'Brien", "acme.com");
+        assert!(patterns.contains(&"conor.o'brien@acme.com".to_string()));
+        assert!(patterns.contains(&"conor.obrien@acme.com".to_string()));
+        assert!(patterns.contains(&"cobrien@acme.com".to_string()));
+        assert!(patterns.contains(&"obrien@acme.com".to_string()));
+        assert!(patterns.contains(&"o'brien@acme.com".to_string()));
+    }
+
+    #[test]
+    fn test_generate_patterns_hyphen_spellings() {
+        let config = test_config();
+        let patterns = generate_email_patterns(&config, "Jean-Luc", "D'Angelo", "acme.com");
+        assert!(patterns.contains(&"jean-luc.d'angelo@acme.com".to_string()));
+        assert!(patterns.contains(&"jean-luc.dangelo@acme.com".to_string()));
+        assert!(patterns.contains(&"jeanluc.dangelo@acme.com".to_string()));
+        assert!(patterns.contains(&"jdangelo@acme.com".to_string()));
+        // Hyphens are only dropped together with apostrophes.
+        assert!(!patterns.contains(&"jeanluc.d'angelo@acme.com".to_string()));
+    }
+
     #[test]
     fn test_generate_patterns_sanitization() {
         let config = test_config();
@@ -246,4 +505,69 @@ mod tests {
         ]);
         assert_eq!(patterns.len(), expected_unique_local_parts.len());
     }
+
+    #[test]
+    fn test_infer_naming_convention() {
+        assert_eq!(
+            infer_naming_convention("Jane", "Doe", "jane.doe@acme.com"),
+            Some("{first}.{last}")
+        );
+        assert_eq!(
+            infer_naming_convention("Jane", "Doe", "JDoe@acme.com"),
+            Some("{f}{last}")
+        );
+        assert_eq!(
+            infer_naming_convention("Jane", "Doe", "jane@acme.com"),
+            Some("{first}")
+        );
+        assert_eq!(
+            infer_naming_convention("Jane", "Doe", "sales@acme.com"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_apply_naming_convention() {
+        assert_eq!(
+            apply_naming_convention("{first}.{last}", "John", "Smith", "acme.com"),
+            Some("john.smith@acme.com".to_string())
+        );
+        assert_eq!(
+            apply_naming_convention("{f}_{last}", "John", "Smith", "acme.com"),
+            Some("j_smith@acme.com".to_string())
+        );
+        assert_eq!(
+            apply_naming_convention("{first}", "", "Smith", "acme.com"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_pattern_rank() {
+        assert_eq!(pattern_rank("John", "Doe", "john.doe@example.com"), Some(0));
+        assert_eq!(pattern_rank("John", "Doe", "jdoe@example.com"), Some(1));
+        assert!(
+            pattern_rank("John", "Doe", "john@example.com")
+                < pattern_rank("John", "Doe", "doe.john@example.com")
+        );
+        assert_eq!(
+            pattern_rank("Mary", "O'Brien", "mary.obrien@example.com"),
+            Some(0)
+        );
+        assert_eq!(pattern_rank("John", "Doe", "jd@example.com"), None);
+        assert_eq!(pattern_rank("John", "Doe", "sales@example.com"), None);
+    }
+
+    #[test]
+    fn test_normalize_email() {
+        assert_eq!(
+            normalize_email(" John.Doe@Acme.COM ", true),
+            "john.doe@acme.com"
+        );
+        assert_eq!(
+            normalize_email("John.Doe@Acme.COM", false),
+            "John.Doe@acme.com"
+        );
+        assert_eq!(normalize_email("john@acme.com", true), "john@acme.com");
+    }
 }
@@ -0,0 +1,734 @@
+//! Fetches company web pages and extracts email addresses from them.
+
+use crate::core::config::{get_random_sleep_duration, Config, TlsVersion};
+use crate::core::error::{AppError, Result};
+use crate::utils::http::send_with_rate_limit_retries;
+use crate::verification::headless::render_page;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use reqwest::{Client, ClientBuilder, StatusCode};
+use scraper::{ElementRef, Html, Selector};
+use std::collections::{HashMap, HashSet};
+use tokio::time::sleep;
+use url::Url;
+
+/// Returns a client builder preconfigured with the settings used for website scraping
+/// and API checks.
+///
+/// Response decompression (gzip, brotli, deflate) is enabled explicitly. Some sites only
+/// serve compressed pages, and without decoding the body we would run the email regex
+/// over binary data and find nothing.
+///
+/// Servers that only offer TLS versions older than `min_tls_version` fail the handshake.
+pub(crate) fn http_client_builder(config: &Config) -> ClientBuilder {
+    Client::builder()
+        .user_agent(&config.user_agent)
+        .timeout(config.request_timeout)
+        .min_tls_version(reqwest_tls_version(config.min_tls_version))
+        .gzip(true)
+        .brotli(true)
+        .deflate(true)
+}
+
+fn reqwest_tls_version(version: TlsVersion) -> reqwest::tls::Version {
+    match version {
+        TlsVersion::Tls1_0 => reqwest::tls::Version::TLS_1_0,
+        TlsVersion::Tls1_1 => reqwest::tls::Version::TLS_1_1,
+        TlsVersion::Tls1_2 => reqwest::tls::Version::TLS_1_2,
+        TlsVersion::Tls1_3 => reqwest::tls::Version::TLS_1_3,
+    }
+}
+
+/// Builds the HTTP client used for website scraping and API checks.
+pub(crate) fn build_http_client(config: &Config) -> Result<Client> {
+    http_client_builder(config)
+        .build()
+        .map_err(|e| AppError::Initialization(format!("Failed to build HTTP client: {}", e)))
+}
+
+/// Lowercased page titles that only anti-bot interstitials use, paired with the service
+/// they identify. These mark a challenge whatever the status code.
+const ANTI_BOT_TITLES: &[(&str, &str)] = &[
+    ("<title>just a moment...</title>", "Cloudflare"),
+    ("attention required! | cloudflare", "Cloudflare"),
+];
+
+/// Lowercased snippets found on anti-bot interstitials (Cloudflare, DataDome, PerimeterX,
+/// Imperva, Sucuri), paired with the service they identify. Normal pages behind these
+/// services load some of the same scripts, so they only count on an error response.
+const ANTI_BOT_MARKERS: &[(&str, &str)] = &[
+    ("/cdn-cgi/challenge-platform/", "Cloudflare"),
+    ("cf-browser-verification", "Cloudflare"),
+    ("captcha-delivery.com", "DataDome"),
+    ("px-captcha", "PerimeterX"),
+    ("_incapsula_resource", "Imperva"),
+    ("sucuri website firewall", "Sucuri"),
+];
+
+/// Fetches a page and returns its decoded body text.
+///
+/// A `429 Too Many Requests` answer is retried after its `Retry-After` delay, up to
+/// `rate_limit_max_retries` times. Returns an error for transport failures and non-success
+/// status codes, and [`AppError::VerificationBlocked`] when an anti-bot challenge is served
+/// instead of the page, so the challenge is never scraped as content. A challenge is
+/// recognized by a `cf-mitigated: challenge` header, a challenge page title, or challenge
+/// markup on an error response.
+pub(crate) async fn fetch_page(client: &Client, config: &Config, url: &Url) -> Result<String> {
+    tracing::debug!(target: "scraping_task", "Fetching {}", url);
+    let response = send_with_rate_limit_retries(config, || client.get(url.clone())).await?;
+    let status_error = response.error_for_status_ref().err();
+    let challenged = response
+        .headers()
+        .get("cf-mitigated")
+        .is_some_and(|value| value.as_bytes().eq_ignore_ascii_case(b"challenge"));
+    let body = response.text().await?;
+
+    let service = if challenged {
+        Some("Cloudflare")
+    } else {
+        anti_bot_service(&body, status_error.is_some())
+    };
+    if let Some(service) = service {
+        return Err(AppError::VerificationBlocked(format!(
+            "{} served a {} anti-bot challenge instead of the page",
+            url, service
+        )));
+    }
+    match status_error {
+        Some(e) => Err(e.into()),
+        None => Ok(body),
+    }
+}
+
+/// Returns the anti-bot service whose challenge page `html` is, if any. `error_status`
+/// says whether it came with a non-2xx status, without which only the title counts.
+fn anti_bot_service(html: &str, error_status: bool) -> Option<&'static str> {
+    let html = html.to_lowercase();
+    let body_markers = if error_status { ANTI_BOT_MARKERS } else { &[] };
+    ANTI_BOT_TITLES
+        .iter()
+        .chain(body_markers)
+        .find(|(marker, _)| html.contains(marker))
+        .map(|(_, service)| *service)
+}
+
+/// Fetches a page, retrying up to `scraping_max_attempts` times.
+///
+/// Only transport failures and server errors are retried; other error statuses (e.g. 404)
+/// are returned immediately. `429 Too Many Requests` has its own retries in [`fetch_page`]. A handshake rejected because
+/// the server only offers TLS older than `min_tls_version` is returned immediately as
+/// [`AppError::TlsVersion`].
+pub(crate) async fn fetch_page_with_retries(
+    client: &Client,
+    config: &Config,
+    url: &Url,
+) -> Result<String> {
+    let max_attempts = config.scraping_max_attempts.max(1);
+    let mut attempt = 1;
+    loop {
+        match fetch_page(client, config, url)
+            .await
+            .map_err(|e| explain_tls_version_error(config, url, e))
+        {
+            Ok(html) => return Ok(html),
+            Err(e) if attempt < max_attempts && is_retriable_fetch_error(&e) => {
+                tracing::debug!(target: "scraping_task", "Attempt {}/{} to fetch {} failed: {}. Retrying.", attempt, max_attempts, url, e);
+                sleep(get_random_sleep_duration(config)).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Replaces a request error caused by a TLS version mismatch with [`AppError::TlsVersion`].
+fn explain_tls_version_error(config: &Config, url: &Url, error: AppError) -> AppError {
+    match &error {
+        // The handshake alert is only visible in the error's source chain.
+        AppError::Request(e) if e.status().is_none() => config
+            .min_tls_version
+            .explain_handshake_error(url.host_str().unwrap_or_default(), &format!("{:?}", e))
+            .map(AppError::TlsVersion)
+            .unwrap_or(error),
+        _ => error,
+    }
+}
+
+fn is_retriable_fetch_error(error: &AppError) -> bool {
+    match error {
+        AppError::Request(e) => match e.status() {
+            Some(status) => status.is_server_error(),
+            None => true,
+        },
+        _ => false,
+    }
+}
+
+/// Longest text, in characters, taken as the surroundings of a scraped address.
+const MAX_CONTEXT_CHARS: usize = 300;
+
+static MAILTO_SELECTOR: Lazy<Selector> =
+    Lazy::new(|| Selector::parse("a[href^='mailto:']").expect("valid selector"));
+
+/// An email address found on a page, with the text around it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ScrapedEmail {
+    pub email: String,
+    /// Text of the largest enclosing element that mentions no other address and is at
+    /// most [`MAX_CONTEXT_CHARS`] long, e.g. a team member's card. The address itself is
+    /// left out. Used to tell whose address it is.
+    pub context: String,
+}
+
+/// Extracts email addresses from `mailto:` links and the visible text of an HTML document,
+/// with the text around each.
+///
+/// Results are lowercased and deduplicated, in order of first appearance. An address that
+/// appears more than once keeps the first non-empty context.
+pub(crate) fn extract_emails_from_html(html: &str, email_regex: &Regex) -> Vec<ScrapedEmail> {
+    let document = Html::parse_document(html);
+    let mut index = HashMap::new();
+    let mut found: Vec<ScrapedEmail> = Vec::new();
+    let mut collect = |text: &str, element: Option<ElementRef>| {
+        for m in email_regex.find_iter(text) {
+            let email = m.as_str().to_lowercase();
+            let i = *index.entry(email.clone()).or_insert_with(|| {
+                found.push(ScrapedEmail {
+                    email: email.clone(),
+                    context: String::new(),
+                });
+                found.len() - 1
+            });
+            if let (true, Some(element)) = (found[i].context.is_empty(), element) {
+                found[i].context = element_context(element, &email, email_regex);
+            }
+        }
+    };
+
+    for element in document.select(&MAILTO_SELECTOR) {
+        if let Some(href) = element.value().attr("href") {
+            collect(mailto_address(href), Some(element));
+        }
+    }
+
+    for node in document.root_element().descendants() {
+        if let Some(text) = node.value().as_text() {
+            collect(text, node.parent().and_then(ElementRef::wrap));
+        }
+    }
+
+    found
+}
+
+fn mailto_address(href: &str) -> &str {
+    let address = href.trim_start_matches("mailto:");
+    address.split('?').next().unwrap_or("")
+}
+
+/// Text around `email`: the largest element from `element` upwards that mentions or links
+/// no other address and fits in [`MAX_CONTEXT_CHARS`], with addresses removed.
+///
+/// While the text found so far is nothing but addresses, other addresses do not stop the
+/// search, so two addresses listed together under one name (a personal and a role address
+/// in a bio) both get that name.
+fn element_context(element: ElementRef, email: &str, email_regex: &Regex) -> String {
+    let mut context = String::new();
+    for candidate in
+        std::iter::once(element).chain(element.ancestors().filter_map(ElementRef::wrap))
+    {
+        if matches!(candidate.value().name(), "body" | "html") {
+            break;
+        }
+        let text = candidate
+            .text()
+            .flat_map(str::split_whitespace)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let linked = candidate
+            .select(&MAILTO_SELECTOR)
+            .filter_map(|link| link.value().attr("href"))
+            .map(mailto_address)
+            .filter(|address| !address.is_empty());
+        if text.chars().count() > MAX_CONTEXT_CHARS {
+            break;
+        }
+        let mentions_other = email_regex
+            .find_iter(&text)
+            .map(|m| m.as_str())
+            .chain(linked)
+            .any(|other| !other.eq_ignore_ascii_case(email));
+        if mentions_other && context.chars().any(char::is_alphabetic) {
+            break;
+        }
+        context = email_regex
+            .replace_all(&text, "")
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        if mentions_other {
+            break;
+        }
+    }
+    context
+}
+
+/// Scrapes the website root and the configured common pages for email addresses.
+///
+/// With `scrape_top_k_pages` set, the root is fetched first and the common pages are
+/// replaced by the most relevant pages it links to (see [`most_relevant_links`]), or by the
+/// first `k` common pages if the root fails to load.
+///
+/// Pages under the `disallowed` robots.txt paths (see [`robots_disallowed_paths`]) are
+/// skipped and reported in `failures`.
+///
+/// Pages that fail to load are skipped, with a description of each failure added to
+/// `failures`. Once a page turns out to be an anti-bot challenge, the rest of the site is
+/// skipped, since it sits behind the same protection. Sleeps between requests according
+/// to `sleep_between_requests`.
+///
+/// With a `webdriver_url`, if no page yields an address, the pages that did load are
+/// rendered in the headless browser one at a time until one does.
+pub(crate) async fn scrape_website_for_emails(
+    client: &Client,
+    config: &Config,
+    base_url: &Url,
+    disallowed: &[String],
+    webdriver_url: Option<&str>,
+    failures: &mut Vec<String>,
+) -> Vec<ScrapedEmail> {
+    let top_k = config.scrape_top_k_pages;
+    let guessed = common_pages(config, base_url);
+    let mut pages = vec![base_url.clone()];
+    if top_k.is_none() {
+        pages.extend(guessed.iter().cloned());
+    }
+
+    let mut seen = HashSet::new();
+    let mut found: Vec<ScrapedEmail> = Vec::new();
+    let mut loaded_pages = Vec::new();
+    // `pages` grows after the root is fetched when the linked pages are ranked.
+    let mut index = 0;
+    while let Some(page_url) = pages.get(index).cloned() {
+        if is_disallowed(&page_url, disallowed) {
+            tracing::info!(target: "scraping_task", "Skipping {}: disallowed by robots.txt", page_url);
+            failures.push(format!("Skipped {}: disallowed by robots.txt", page_url));
+            index += 1;
+            continue;
+        }
+        if index > 0 {
+            sleep(get_random_sleep_duration(config)).await;
+        }
+        match fetch_page_with_retries(client, config, &page_url).await {
+            Ok(html) => {
+                merge_scraped(&mut found, &mut seen, &html, &config.email_regex);
+                if let (0, Some(k)) = (index, top_k) {
+                    let linked = most_relevant_links(&html, base_url, k);
+                    tracing::debug!(target: "scraping_task", "Most relevant pages linked from {}: {:?}", base_url, linked.iter().map(Url::as_str).collect::<Vec<_>>());
+                    pages.extend(linked);
+                }
+                loaded_pages.push(page_url);
+            }
+            Err(AppError::VerificationBlocked(reason)) => {
+                tracing::warn!(target: "scraping_task", "Blocked by anti-bot: {}. Skipping the remaining pages of {}.", reason, base_url);
+                failures.push(format!("Blocked by anti-bot: {}", reason));
+                break;
+            }
+            Err(e) => {
+                tracing::debug!(target: "scraping_task", "Failed to fetch {}: {}", page_url, e);
+                // Common pages are guesses, so most sites are expected not to have some.
+                if !(guessed.contains(&page_url) && is_not_found(&e)) {
+                    failures.push(format!("Failed to fetch {}: {}", page_url, e));
+                }
+                if let (0, Some(k)) = (index, top_k) {
+                    pages.extend(guessed.iter().take(k).cloned());
+                }
+            }
+        }
+        index += 1;
+    }
+
+    if let Some(webdriver_url) = webdriver_url.filter(|_| found.is_empty()) {
+        for page_url in loaded_pages {
+            tracing::debug!(target: "scraping_task", "No addresses in static HTML of {}; rendering it headlessly.", page_url);
+            match render_page(
+                page_url.as_str(),
+                webdriver_url,
+                &config.headless_browser_args,
+                config.request_timeout,
+            )
+            .await
+            {
+                Ok(html) => merge_scraped(&mut found, &mut seen, &html, &config.email_regex),
+                Err(e) => {
+                    tracing::debug!(target: "scraping_task", "Failed to render {}: {}", page_url, e);
+                    failures.push(format!("Failed to render {}: {}", page_url, e));
+                }
+            }
+            if !found.is_empty() {
+                break;
+            }
+        }
+    }
+
+    tracing::debug!(target: "scraping_task", "Scraped {} unique emails from {} pages of {}", found.len(), pages.len(), base_url);
+    found
+}
+
+/// Whether a fetch failed because the page does not exist (404 or 410).
+fn is_not_found(error: &AppError) -> bool {
+    match error {
+        AppError::Request(e) => e
+            .status()
+            .is_some_and(|status| status == StatusCode::NOT_FOUND || status == StatusCode::GONE),
+        _ => false,
+    }
+}
+
+/// Fetches the robots.txt of `base_url`'s site and returns the paths it disallows for all
+/// user agents. A missing or unreadable robots.txt disallows nothing.
+pub(crate) async fn robots_disallowed_paths(
+    client: &Client,
+    config: &Config,
+    base_url: &Url,
+) -> Vec<String> {
+    let Ok(robots_url) = base_url.join("/robots.txt") else {
+        return Vec::new();
+    };
+    match fetch_page(client, config, &robots_url).await {
+        Ok(robots_txt) => parse_robots_disallow(&robots_txt),
+        Err(e) => {
+            tracing::debug!(target: "scraping_task", "No usable robots.txt at {}: {}", robots_url, e);
+            Vec::new()
+        }
+    }
+}
+
+/// The `Disallow` paths of the groups in `robots_txt` that apply to every user agent
+/// (`User-agent: *`). `Allow` lines and wildcards inside paths are not interpreted.
+fn parse_robots_disallow(robots_txt: &str) -> Vec<String> {
+    let mut disallowed = Vec::new();
+    let mut group_applies = false;
+    let mut in_agent_lines = false;
+    for line in robots_txt.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        let Some((field, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match field.trim().to_lowercase().as_str() {
+            "user-agent" => {
+                if !in_agent_lines {
+                    group_applies = false;
+                    in_agent_lines = true;
+                }
+                group_applies |= value == "*";
+            }
+            "disallow" => {
+                in_agent_lines = false;
+                if group_applies && !value.is_empty() {
+                    disallowed.push(value.to_string());
+                }
+            }
+            _ => in_agent_lines = false,
+        }
+    }
+    disallowed
+}
+
+/// Whether `url`'s path starts with one of the `disallowed` paths.
+fn is_disallowed(url: &Url, disallowed: &[String]) -> bool {
+    let path = url.path();
+    disallowed.iter().any(|prefix| path.starts_with(prefix))
+}
+
+/// The configured `common_pages_to_scrape` under `base_url`, without duplicates or the
+/// root itself.
+fn common_pages(config: &Config, base_url: &Url) -> Vec<Url> {
+    let mut pages: Vec<Url> = Vec::new();
+    for path in &config.common_pages_to_scrape {
+        match base_url.join(path) {
+            Ok(url) => {
+                if url != *base_url && !pages.contains(&url) {
+                    pages.push(url);
+                }
+            }
+            Err(e) => {
+                tracing::debug!(target: "scraping_task", "Skipping invalid page path '{}': {}", path, e);
+            }
+        }
+    }
+    pages
+}
+
+/// Words in a link's path or text that suggest the page lists people or contact details,
+/// with their weight in [`page_relevance`].
+const RELEVANCE_KEYWORDS: &[(&str, u32)] = &[
+    ("contact", 5),
+    ("kontakt", 5),
+    ("team", 5),
+    ("people", 4),
+    ("staff", 4),
+    ("leadership", 4),
+    ("impressum", 4),
+    ("imprint", 4),
+    ("about", 3),
+    ("founders", 3),
+    ("management", 3),
+    ("company", 2),
+    ("press", 2),
+    ("board", 2),
+];
+
+static LINK_SELECTOR: Lazy<Selector> =
+    Lazy::new(|| Selector::parse("a[href]").expect("valid selector"));
+
+/// How likely the page at `url`, linked with `link_text`, is to list people or contact
+/// details: the weights of the [`RELEVANCE_KEYWORDS`] in its path plus those in the text.
+/// 0 means no keyword matched.
+fn page_relevance(url: &Url, link_text: &str) -> u32 {
+    let path = url.path().to_lowercase();
+    let text = link_text.to_lowercase();
+    RELEVANCE_KEYWORDS
+        .iter()
+        .map(|(keyword, weight)| {
+            let in_path = if path.contains(keyword) { *weight } else { 0 };
+            let in_text = if text.contains(keyword) { *weight } else { 0 };
+            in_path + in_text
+        })
+        .sum()
+}
+
+/// The `k` pages on the same site that `html` (the page at `base_url`) links to and that
+/// score highest on [`page_relevance`], best first. Links that match no keyword are left
+/// out, so fewer than `k` pages may come back.
+fn most_relevant_links(html: &str, base_url: &Url, k: usize) -> Vec<Url> {
+    let document = Html::parse_document(html);
+    let site = site_host(base_url);
+    let mut ranked: Vec<(u32, Url)> = Vec::new();
+    for link in document.select(&LINK_SELECTOR) {
+        let Some(mut url) = link
+            .value()
+            .attr("href")
+            .and_then(|href| base_url.join(href).ok())
+        else {
+            continue;
+        };
+        url.set_fragment(None);
+        if !matches!(url.scheme(), "http" | "https") || site_host(&url) != site || url == *base_url
+        {
+            continue;
+        }
+        let text = link.text().collect::<Vec<_>>().join(" ");
+        let score = page_relevance(&url, &text);
+        if score == 0 {
+            continue;
+        }
+        match ranked.iter_mut().find(|(_, existing)| *existing == url) {
+            Some(entry) => entry.0 = entry.0.max(score),
+            None => ranked.push((score, url)),
+        }
+    }
+    // Stable, so equally relevant links keep their order on the page.
+    ranked.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    ranked.into_iter().take(k).map(|(_, url)| url).collect()
+}
+
+/// The host of `url` without a leading `www.`, so both forms count as the same site.
+fn site_host(url: &Url) -> Option<String> {
+    url.host_str()
+        .map(|host| host.trim_start_matches("www.").to_lowercase())
+}
+
+/// Adds the addresses in `html` to `found`, filling in the context of addresses already
+/// found without one.
+fn merge_scraped(
+    found: &mut Vec<ScrapedEmail>,
+    seen: &mut HashSet<String>,
+    html: &str,
+    email_regex: &Regex,
+) {
+    for scraped in extract_emails_from_html(html, email_regex) {
+        if seen.insert(scraped.email.clone()) {
+            found.push(scraped);
+        } else if let Some(existing) = found
+            .iter_mut()
+            .find(|existing| existing.email == scraped.email && existing.context.is_empty())
+        {
+            existing.context = scraped.context;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Gzip-compressed contact page containing a mailto link and a plain-text address.
+    const GZIPPED_CONTACT_PAGE: &[u8] = &[
+        0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x03, 0x3d, 0x8e, 0x41, 0x0a, 0xc2,
+        0x40, 0x0c, 0x45, 0xaf, 0x12, 0x7a, 0x80, 0x09, 0x6e, 0x25, 0x8d, 0x82, 0x2e, 0xc4, 0x65,
+        0x6f, 0x90, 0xc6, 0xc8, 0x54, 0x66, 0x9a, 0xd2, 0x89, 0x0b, 0x6f, 0xaf, 0x55, 0x70, 0xf7,
+        0xf8, 0x3c, 0xf8, 0x8f, 0x72, 0xd4, 0xc2, 0x34, 0xfa, 0xed, 0xc5, 0x94, 0x77, 0x7c, 0xf2,
+        0x39, 0x44, 0x83, 0xf0, 0xc3, 0xb4, 0xf0, 0x60, 0xa2, 0x19, 0xae, 0x32, 0x1b, 0x48, 0x00,
+        0x09, 0xe4, 0xd5, 0xee, 0x7d, 0x57, 0x65, 0x2a, 0xe1, 0xfb, 0x6d, 0x4f, 0x67, 0xb7, 0xa3,
+        0x68, 0xb5, 0xa4, 0x5e, 0x0f, 0xed, 0x39, 0x3e, 0x4c, 0xa3, 0xbf, 0x4c, 0x1d, 0xdb, 0x66,
+        0x11, 0x0a, 0x83, 0xaf, 0xd0, 0xa4, 0x58, 0xfb, 0x7b, 0x89, 0x70, 0x61, 0xc2, 0xdf, 0x2b,
+        0x7e, 0x13, 0xde, 0x9c, 0xf0, 0xec, 0x76, 0x89, 0x00, 0x00, 0x00,
+    ];
+
+    #[test]
+    fn test_extract_emails_from_html() {
+        let config = Config::default();
+        let html = r#"<p>Write to <a href="mailto:John.Smith@Example.com?subject=Hi">John</a>
+            or john.smith@example.com, or info@example.com.</p>"#;
+        let emails: Vec<String> = extract_emails_from_html(html, &config.email_regex)
+            .into_iter()
+            .map(|found| found.email)
+            .collect();
+        assert_eq!(emails, vec!["john.smith@example.com", "info@example.com"]);
+    }
+
+    #[test]
+    fn test_extract_emails_from_html_apostrophe() {
+        let config = Config::default();
+        let html = "<p>Conor O'Brien: o'brien@acme.com, or 'sales@acme.com'</p>";
+        let emails: Vec<String> = extract_emails_from_html(html, &config.email_regex)
+            .into_iter()
+            .map(|found| found.email)
+            .collect();
+        assert_eq!(emails, vec!["o'brien@acme.com", "sales@acme.com"]);
+    }
+
+    #[test]
+    fn test_most_relevant_links() {
+        let base_url = Url::parse("https://www.acme.com/").unwrap();
+        let html = r#"<nav>
+            <a href="/products">Products</a>
+            <a href="/about">About us</a>
+            <a href="https://acme.com/company/our-team#top">Meet the team</a>
+            <a href="https://other.com/contact">Partner contact</a>
+            <a href="/">Home</a>
+            <a href="/contact">Get in touch</a>
+            <a href="mailto:info@acme.com">Contact</a>
+        </nav>"#;
+        let links: Vec<String> = most_relevant_links(html, &base_url, 2)
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert_eq!(
+            links,
+            vec![
+                "https://acme.com/company/our-team",
+                "https://www.acme.com/about"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_emails_from_html_context() {
+        let config = Config::default();
+        let html = r#"<body><div class="team">
+            <div class="card"><h3>Jane Doe</h3><p>Head of Sales</p>
+              <a href="mailto:jd@example.com">Email Jane</a></div>
+            <div class="card"><h3>Bob Stone</h3><p>bs@example.com</p></div>
+            </div><footer>General enquiries: info@example.com</footer></body>"#;
+        let emails = extract_emails_from_html(html, &config.email_regex);
+        assert_eq!(emails.len(), 3);
+        assert_eq!(emails[0].email, "jd@example.com");
+        assert_eq!(emails[0].context, "Jane Doe Head of Sales Email Jane");
+        assert_eq!(emails[1].email, "bs@example.com");
+        assert_eq!(emails[1].context, "Bob Stone");
+        assert_eq!(emails[2].context, "General enquiries:");
+    }
+
+    #[test]
+    fn test_extract_emails_from_html_shared_bio() {
+        let config = Config::default();
+        let html = r#"<body><div class="bio"><h3>Jane Doe</h3>
+            <p>jane@example.com</p><p>sales@example.com</p></div>
+            <div class="bio"><h3>Bob Stone</h3><p>bob@example.com</p></div></body>"#;
+        let emails = extract_emails_from_html(html, &config.email_regex);
+        assert_eq!(emails[0].context, "Jane Doe");
+        assert_eq!(emails[1].context, "Jane Doe");
+        assert_eq!(emails[2].context, "Bob Stone");
+    }
+
+    #[test]
+    fn test_anti_bot_service() {
+        let challenge = r#"<!DOCTYPE html><html><head><title>Just a moment...</title></head>
+            <body><script src="/cdn-cgi/challenge-platform/h/b/orchestrate/jsch/v1"></script></body></html>"#;
+        assert_eq!(anti_bot_service(challenge, false), Some("Cloudflare"));
+        assert_eq!(anti_bot_service(challenge, true), Some("Cloudflare"));
+        assert_eq!(
+            anti_bot_service(
+                r#"<script src="https://ct.captcha-delivery.com/c.js"></script>"#,
+                true
+            ),
+            Some("DataDome")
+        );
+        assert_eq!(
+            anti_bot_service(
+                "<title>Contact</title><p>Just a moment, we'll reply soon.</p>",
+                true
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_anti_bot_script_on_normal_page() {
+        // Sites behind Cloudflare's bot management load this script on every page.
+        let page = r#"<html><head><title>Contact us</title></head><body>
+            <p>Write to jane@example.com</p>
+            <script src="/cdn-cgi/challenge-platform/scripts/jsd/main.js"></script></body></html>"#;
+        assert_eq!(anti_bot_service(page, false), None);
+        assert_eq!(anti_bot_service(page, true), Some("Cloudflare"));
+    }
+
+    #[test]
+    fn test_parse_robots_disallow() {
+        let robots_txt = "User-agent: Googlebot\nDisallow: /private-google\n\n\
+            User-agent: Bingbot\nUser-agent: *\nDisallow: /team # staff only\nAllow: /\n\
+            Disallow:\n\nUser-agent: Other\nDisallow: /other\n";
+        let disallowed = parse_robots_disallow(robots_txt);
+        assert_eq!(disallowed, vec!["/team".to_string()]);
+
+        let base = Url::parse("https://acme.com/").unwrap();
+        assert!(is_disallowed(
+            &base.join("/team/leadership").unwrap(),
+            &disallowed
+        ));
+        assert!(!is_disallowed(&base.join("/contact").unwrap(), &disallowed));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_page_decodes_gzip_response() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = socket.read(&mut buf).await;
+            let header = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Encoding: gzip\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                GZIPPED_CONTACT_PAGE.len()
+            );
+            socket.write_all(header.as_bytes()).await.unwrap();
+            socket.write_all(GZIPPED_CONTACT_PAGE).await.unwrap();
+        });
+
+        let config = Config::default();
+        let client = build_http_client(&config).unwrap();
+        let url = Url::parse(&format!("http://{}/contact", addr)).unwrap();
+        let html = fetch_page(&client, &config, &url).await.unwrap();
+
+        let emails: Vec<String> = extract_emails_from_html(&html, &config.email_regex)
+            .into_iter()
+            .map(|found| found.email)
+            .collect();
+        assert_eq!(emails, vec!["jane.doe@acme.com", "sales@acme.com"]);
+    }
+}
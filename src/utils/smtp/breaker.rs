@@ -0,0 +1,129 @@
+//! Per-host circuit breaker for SMTP probes.
+//!
+//! After a run of consecutive failed probes against one mail server host, the breaker for
+//! that host opens and further probes are answered as inconclusive without connecting.
+//! Once the cooldown ends one probe is let through; if it fails too, the breaker reopens
+//! for twice as long, up to [`MAX_BREAKER_COOLDOWN`]. A successful probe closes it again.
+
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Longest a breaker stays open, however often it has reopened.
+const MAX_BREAKER_COOLDOWN: Duration = Duration::from_secs(30 * 60);
+
+#[derive(Debug, Default)]
+struct HostState {
+    consecutive_failures: usize,
+    /// Times the breaker opened without a successful probe in between.
+    trips: u32,
+    open_until: Option<Instant>,
+}
+
+/// Shared tracker of failing mail server hosts.
+#[derive(Debug)]
+pub(crate) struct HostBreaker {
+    threshold: usize,
+    cooldown: Duration,
+    hosts: Mutex<HashMap<String, HostState>>,
+}
+
+impl HostBreaker {
+    /// Creates a breaker that opens after `threshold` consecutive failures on a host
+    /// (0 disables it), initially for `cooldown`.
+    pub(crate) fn new(threshold: usize, cooldown: Duration) -> Self {
+        Self {
+            threshold,
+            cooldown,
+            hosts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the remaining cooldown if probes to `host` are currently short-circuited.
+    pub(crate) fn open_for(&self, host: &str) -> Option<Duration> {
+        let hosts = self.hosts.lock();
+        let until = hosts.get(&host.to_lowercase())?.open_until?;
+        until.checked_duration_since(Instant::now())
+    }
+
+    /// Records the outcome of a probe to `host`, opening the breaker if it has now failed
+    /// `threshold` times in a row.
+    pub(crate) fn record(&self, host: &str, failed: bool) {
+        if self.threshold == 0 {
+            return;
+        }
+        let mut hosts = self.hosts.lock();
+        let state = hosts.entry(host.to_lowercase()).or_default();
+        if !failed {
+            if state.trips > 0 {
+                tracing::info!(target: "smtp_task", "Mail server {} is responding again. Closing its circuit breaker.", host);
+            }
+            *state = HostState::default();
+            return;
+        }
+        let now = Instant::now();
+        if state.open_until.is_some_and(|until| now < until) {
+            return;
+        }
+
+        state.consecutive_failures += 1;
+        // After a cooldown, the first probe decides: one more failure reopens the breaker.
+        if state.consecutive_failures < self.threshold && state.trips == 0 {
+            return;
+        }
+        let cooldown = self
+            .cooldown
+            .saturating_mul(2u32.saturating_pow(state.trips))
+            .min(MAX_BREAKER_COOLDOWN);
+        state.trips += 1;
+        state.consecutive_failures = 0;
+        state.open_until = Some(now + cooldown);
+        tracing::warn!(target: "smtp_task",
+            "Mail server {} keeps failing SMTP probes. Skipping probes to it for {:?}.",
+            host, cooldown
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opens_after_consecutive_failures() {
+        let breaker = HostBreaker::new(3, Duration::from_secs(60));
+        breaker.record("mx.example.com", true);
+        breaker.record("mx.example.com", true);
+        breaker.record("mx.example.com", false);
+        breaker.record("mx.example.com", true);
+        breaker.record("mx.example.com", true);
+        assert!(breaker.open_for("mx.example.com").is_none());
+
+        breaker.record("MX.example.com", true);
+        assert!(breaker.open_for("mx.example.com").is_some());
+        assert!(breaker.open_for("mx.other.com").is_none());
+    }
+
+    #[test]
+    fn test_reopens_with_longer_cooldown() {
+        let breaker = HostBreaker::new(2, Duration::ZERO);
+        breaker.record("mx.example.com", true);
+        breaker.record("mx.example.com", true);
+        assert_eq!(breaker.hosts.lock()["mx.example.com"].trips, 1);
+
+        breaker.record("mx.example.com", true);
+        assert_eq!(breaker.hosts.lock()["mx.example.com"].trips, 2);
+
+        breaker.record("mx.example.com", false);
+        assert_eq!(breaker.hosts.lock()["mx.example.com"].trips, 0);
+    }
+
+    #[test]
+    fn test_disabled_with_zero_threshold() {
+        let breaker = HostBreaker::new(0, Duration::from_secs(60));
+        for _ in 0..10 {
+            breaker.record("mx.example.com", true);
+        }
+        assert!(breaker.open_for("mx.example.com").is_none());
+    }
+}
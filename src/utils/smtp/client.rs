@@ -1,21 +1,47 @@
 //! Provides the SmtpVerifier client for validating email addresses via SMTP.
 
+use super::breaker::HostBreaker;
 use super::error::handle_smtp_error;
-use super::result::SmtpVerificationResult;
-use crate::core::config::{get_random_sleep_duration, Config};
+use super::reputation::SenderReputation;
+use super::result::{
+    classify_rcpt_rejection, reply_text, EnhancedStatus, RcptRejection, SmtpFailure,
+    SmtpVerificationResult, AUTH_REQUIRED_REASON,
+};
+use crate::core::config::{get_random_sleep_duration, Config, SmtpDialogueStep, TlsVersion};
 use crate::core::error::{AppError, Result};
+use crate::core::models::SmtpProbeSignals;
 
-use lettre::transport::smtp::client::SmtpConnection;
-use lettre::transport::smtp::commands::{Ehlo, Mail, Rcpt};
+use lettre::transport::smtp::client::{
+    SmtpConnection, TlsParameters, TlsVersion as LettreTlsVersion,
+};
+use lettre::transport::smtp::commands::{Ehlo, Expn, Mail, Rcpt, Vrfy};
 use lettre::transport::smtp::response::{Code, Severity};
 use lettre::Address;
+use parking_lot::Mutex;
 use rand::Rng;
-use std::net::ToSocketAddrs;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
 use std::str::FromStr;
 use std::sync::Arc;
-use std::time::Duration;
-use tokio;
-use tracing;
+use std::time::{Duration, Instant};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Upper bound on how long a single retry waits, even if the server asks for longer.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(120);
+
+/// Upper bound on the random delay before connecting to a mail server, so probes that
+/// get a connection slot at the same moment do not hit the server in lockstep.
+const MAX_CONNECT_JITTER: Duration = Duration::from_millis(250);
+
+const RCPT_STALL_REASON: &str = "server stalled after RCPT";
+
+/// Consecutive RCPT stalls after which a mail server is skipped for a while.
+const RCPT_STALLS_BEFORE_SKIP: usize = 2;
+
+/// How long a stalling mail server is skipped at first; doubles while it keeps stalling.
+const RCPT_STALL_COOLDOWN: Duration = Duration::from_secs(5 * 60);
 
 /// Connection parameters for SMTP verification
 struct ConnectionParams {
@@ -23,18 +49,78 @@ struct ConnectionParams {
     helo_name: lettre::transport::smtp::extension::ClientId,
     timeout: Duration,
     use_tls: bool,
+    /// The domain is already known not to be catch-all, so no random address is probed.
+    skip_catch_all_check: bool,
 }
 
 /// Represents a client for verifying email addresses via SMTP.
 #[derive(Clone)]
 pub struct SmtpVerifier {
     config: Arc<Config>,
+    reputation: Arc<SenderReputation>,
+    /// Mail server hosts that keep failing; probes to them are skipped while open.
+    breaker: Arc<HostBreaker>,
+    /// Connection slots per mail server host, limited by `max_concurrency_per_mx`.
+    mx_slots: Arc<Mutex<HashMap<String, Arc<Semaphore>>>>,
+    /// Mail servers that repeatedly stopped responding after RCPT TO; skipped while open.
+    stalled_hosts: Arc<HostBreaker>,
+    /// Domains already known not to be catch-all; probes to them skip the catch-all check.
+    settled_domains: Arc<Mutex<HashSet<String>>>,
 }
 
 impl SmtpVerifier {
     /// Creates a new SmtpVerifier with the given configuration.
     pub fn new(config: Arc<Config>) -> Self {
-        Self { config }
+        let reputation = Arc::new(SenderReputation::new(
+            config.smtp_rejection_window,
+            config.smtp_rejection_threshold,
+            config.smtp_rejection_cooldown,
+        ));
+        let breaker = Arc::new(HostBreaker::new(
+            config.smtp_breaker_threshold,
+            config.smtp_breaker_cooldown,
+        ));
+        Self {
+            config,
+            reputation,
+            breaker,
+            mx_slots: Arc::new(Mutex::new(HashMap::new())),
+            stalled_hosts: Arc::new(HostBreaker::new(
+                RCPT_STALLS_BEFORE_SKIP,
+                RCPT_STALL_COOLDOWN,
+            )),
+            settled_domains: Arc::new(Mutex::new(HashSet::new())),
+        }
+    }
+
+    /// Records that `domain` rejects unknown recipients, so later probes to it skip the
+    /// random-address catch-all check.
+    pub(crate) fn mark_not_catch_all(&self, domain: &str) {
+        self.settled_domains.lock().insert(domain.to_lowercase());
+    }
+
+    /// Waits for a free connection slot on `mail_server`, then sleeps a short random jitter.
+    /// The slot is held until the returned permit is dropped.
+    async fn acquire_mx_slot(&self, mail_server: &str) -> Option<OwnedSemaphorePermit> {
+        let semaphore = self
+            .mx_slots
+            .lock()
+            .entry(mail_server.to_lowercase())
+            .or_insert_with(|| Arc::new(Semaphore::new(self.config.max_concurrency_per_mx.max(1))))
+            .clone();
+        let permit = semaphore.acquire_owned().await.ok()?;
+        let jitter_ms = rand::thread_rng().gen_range(0..=MAX_CONNECT_JITTER.as_millis() as u64);
+        tokio::time::sleep(Duration::from_millis(jitter_ms)).await;
+        Some(permit)
+    }
+
+    /// Returns the key under which sender reputation is tracked: the sender domain when
+    /// local parts are randomized, otherwise the full sender email.
+    fn sender_identity(&self) -> &str {
+        self.config
+            .smtp_sender_domain
+            .as_deref()
+            .unwrap_or(&self.config.smtp_sender_email)
     }
 
     /// Verifies an email using SMTP with retries for inconclusive results.
@@ -45,32 +131,84 @@ impl SmtpVerifier {
     /// * `mail_server` - The hostname or IP address of the mail server.
     ///
     /// # Returns
-    /// * `(Option<bool>, String)`: Tuple containing the verification status (Some(true), Some(false), or None)
-    ///   and a final descriptive message.
-    pub async fn verify_with_retries(
+    /// * `SmtpVerificationResult` for the last attempt: the verification status (Some(true),
+    ///   Some(false), or None), a final descriptive message, the RCPT probe signals from the
+    ///   last attempt that reached the RCPT stage, and why the check failed, if it did.
+    pub(crate) async fn verify_with_retries(
         &self,
         email: &str,
         domain: &str,
         mail_server: &str,
-    ) -> (Option<bool>, String) {
+    ) -> SmtpVerificationResult {
         let mut last_result: Option<bool> = None;
+        let mut last_signals: Option<SmtpProbeSignals> = None;
         let mut last_message = "SMTP check did not run or complete".to_string();
+        let mut last_failure: Option<SmtpFailure> = None;
+        let mut last_rejection: Option<RcptRejection> = None;
+        let mut tls_warning: Option<String> = None;
         let mut is_catch_all = false;
 
-        for attempt in 0..self.config.max_verification_attempts {
+        if let Some(remaining) = self.reputation.paused_for(self.sender_identity()) {
+            tracing::debug!(target: "smtp_task",
+                "Skipping SMTP check for <{}>: sender '{}' is cooling down for another {:?}.",
+                email, self.sender_identity(), remaining
+            );
+            return SmtpVerificationResult::inconclusive_no_retry(format!(
+                "SMTP paused: high sender rejection rate (resumes in {}s)",
+                remaining.as_secs()
+            ));
+        }
+
+        for attempt in 0..self.config.smtp_max_attempts {
             tracing::debug!(target: "smtp_task",
                 "Attempt {}/{} to verify <{}> via SMTP server {}",
                 attempt + 1,
-                self.config.max_verification_attempts,
+                self.config.smtp_max_attempts,
                 email,
                 mail_server
             );
 
-            match self.verify_email(email, domain, mail_server).await {
+            if let Some(remaining) = self.breaker.open_for(mail_server) {
+                tracing::debug!(target: "smtp_task",
+                    "Skipping SMTP check for <{}>: circuit breaker for {} is open for another {:?}.",
+                    email, mail_server, remaining
+                );
+                last_message = format!(
+                    "SMTP skipped: {} keeps failing (circuit open, retries in {}s)",
+                    mail_server,
+                    remaining.as_secs()
+                );
+                last_failure = Some(SmtpFailure::Connection);
+                break;
+            }
+
+            let mx_slot = self.acquire_mx_slot(mail_server).await;
+            let attempt_result = self.verify_email(email, domain, mail_server).await;
+            drop(mx_slot);
+
+            let retry_delay: Option<Duration>;
+            match attempt_result {
                 Ok(result) => {
                     last_result = result.exists;
                     last_message = result.message.clone();
                     is_catch_all = result.is_catch_all;
+                    last_failure = result.failure;
+                    last_rejection = result.rcpt_rejection;
+                    if result.tls_warning.is_some() {
+                        tls_warning = result.tls_warning.clone();
+                    }
+                    if result.signals.is_some() {
+                        last_signals = result.signals;
+                    }
+
+                    if result.is_sender_rejection() {
+                        self.reputation.record(self.sender_identity(), true);
+                    } else if result.exists.is_some() || result.is_catch_all {
+                        self.reputation.record(self.sender_identity(), false);
+                        self.breaker.record(mail_server, false);
+                    } else {
+                        self.breaker.record(mail_server, true);
+                    }
 
                     if result.exists.is_some() {
                         tracing::debug!(target: "smtp_task",
@@ -96,6 +234,7 @@ impl SmtpVerifier {
                         break;
                     }
 
+                    retry_delay = result.recommended_delay(get_random_sleep_duration(&self.config));
                     tracing::warn!(target: "smtp_task",
                         "SMTP check for <{}> inconclusive on attempt {}. Message: {}. Will retry if attempts remain.",
                         email, attempt + 1, result.message
@@ -107,12 +246,15 @@ impl SmtpVerifier {
                     );
                     last_message = format!("Internal error during SMTP check: {}", e);
                     last_result = None;
+                    last_failure = None;
                     break;
                 }
             }
 
-            if attempt < self.config.max_verification_attempts - 1 && last_result.is_none() {
-                let sleep_duration = get_random_sleep_duration(&self.config);
+            if attempt < self.config.smtp_max_attempts - 1 && last_result.is_none() {
+                let sleep_duration = retry_delay
+                    .unwrap_or_else(|| get_random_sleep_duration(&self.config))
+                    .min(MAX_RETRY_DELAY);
                 tracing::debug!(target: "smtp_task",
                     "Sleeping {:?} before next SMTP attempt for <{}>.", sleep_duration, email);
                 tokio::time::sleep(sleep_duration).await;
@@ -128,7 +270,17 @@ impl SmtpVerifier {
             email, mail_server, last_result, is_catch_all, last_message
         );
 
-        (last_result, last_message)
+        SmtpVerificationResult {
+            exists: last_result,
+            message: last_message,
+            should_retry: false,
+            is_catch_all,
+            retry_after: None,
+            signals: last_signals,
+            failure: last_failure,
+            rcpt_rejection: last_rejection,
+            tls_warning,
+        }
     }
 
     /// Performs the SMTP RCPT TO check for a single email address.
@@ -154,9 +306,21 @@ impl SmtpVerifier {
             domain
         );
 
-        let recipient_address = match Address::from_str(email) {
-            Ok(addr) => addr,
-            Err(e) => {
+        if let Some(remaining) = self.stalled_hosts.open_for(mail_server) {
+            tracing::debug!(target: "smtp_task",
+                "Skipping SMTP check for <{}>: {} keeps stalling after RCPT (retrying in {:?}).", email, mail_server, remaining);
+            return Ok(SmtpVerificationResult::inconclusive_no_retry(format!(
+                "{} (cached)",
+                AppError::SmtpInconclusive(RCPT_STALL_REASON.to_string())
+            ))
+            .with_failure(SmtpFailure::Connection));
+        }
+
+        // Local parts that need quoting are beyond lettre's address parser; they were
+        // checked against `email_regex` when the candidate was generated.
+        let recipient_path = smtp_path_address(email);
+        if recipient_path == email {
+            if let Err(e) = Address::from_str(email) {
                 tracing::error!(target: "smtp_task", "Invalid recipient email format '{}': {}", email, e);
                 return Ok(SmtpVerificationResult::conclusive(
                     false,
@@ -164,20 +328,27 @@ impl SmtpVerifier {
                     false,
                 ));
             }
-        };
+        }
 
-        let sender_address = Address::from_str(&self.config.smtp_sender_email)
-            .map_err(|e| AppError::Config(format!("Invalid sender email in config: {}", e)))?;
+        let sender_address = self.sender_address()?;
 
-        let skip_catch_all_check = false;
+        let skip_catch_all_check = self.settled_domains.lock().contains(&domain.to_lowercase());
         if skip_catch_all_check {
             tracing::debug!(target: "smtp_task",
-                "Will skip catch-all test for {} (provider rule SkipCatchAll matches)",
+                "Will skip catch-all test for {} (already known not to be catch-all)",
                 domain
             );
         }
 
-        let socket_addr = match (mail_server, 25_u16).to_socket_addrs()?.next() {
+        let resolved = match self.config.smtp_force_target {
+            Some(ref target) => {
+                tracing::debug!(target: "smtp_task",
+                    "smtp_force_target set: probing {} instead of {} for <{}>", target, mail_server, email);
+                target.to_socket_addrs()?.next()
+            }
+            None => (mail_server, 25_u16).to_socket_addrs()?.next(),
+        };
+        let socket_addr = match resolved {
             Some(addr) => addr,
             None => {
                 tracing::error!(target: "smtp_task", "Could not resolve mail server address: {}", mail_server);
@@ -196,28 +367,29 @@ impl SmtpVerifier {
             helo_name,
             timeout: self.config.smtp_timeout,
             use_tls: false,
+            skip_catch_all_check,
         };
 
         let connect_result = self
             .try_connection(
                 &params,
                 &sender_address,
-                &recipient_address,
+                &recipient_path,
                 email,
                 domain,
                 mail_server,
-                skip_catch_all_check,
             )
             .await;
 
         match &connect_result {
             Ok(result) => {
                 let msg = result.message.to_lowercase();
-                if msg.contains("starttls")
-                    || msg.contains("tls required")
-                    || (msg.contains("530")
-                        && msg.contains("5.7.0")
-                        && !msg.contains("authentication required"))
+                if !msg.contains(AUTH_REQUIRED_REASON)
+                    && (msg.contains("starttls")
+                        || msg.contains("tls required")
+                        || (msg.contains("530")
+                            && msg.contains("5.7.0")
+                            && !msg.contains("authentication required")))
                 {
                     tracing::info!(target: "smtp_task",
                         "Server {} appears to require STARTTLS, retrying connection with TLS enabled", mail_server);
@@ -227,19 +399,27 @@ impl SmtpVerifier {
                         helo_name: params.helo_name,
                         timeout: params.timeout,
                         use_tls: true,
+                        skip_catch_all_check,
                     };
 
+                    let warning = format!(
+                        "{} refused the plaintext session and required STARTTLS; the check was retried over TLS",
+                        mail_server
+                    );
                     return self
                         .try_connection(
                             &tls_params,
                             &sender_address,
-                            &recipient_address,
+                            &recipient_path,
                             email,
                             domain,
                             mail_server,
-                            skip_catch_all_check,
                         )
-                        .await;
+                        .await
+                        .map(|result| match result.tls_warning {
+                            Some(_) => result,
+                            None => result.with_tls_warning(warning),
+                        });
                 }
             }
             Err(e) => {
@@ -251,15 +431,52 @@ impl SmtpVerifier {
         connect_result
     }
 
+    /// Builds the TLS parameters for a STARTTLS upgrade with `mail_server`.
+    ///
+    /// With `smtp_tls_sni` disabled the server's IP address is used as the TLS server name,
+    /// so no SNI is sent, and the certificate hostname is not checked.
+    ///
+    /// Versions older than `min_tls_version` are not offered in the handshake; since rustls
+    /// only speaks TLS 1.2 and 1.3, anything older is raised to 1.2.
+    fn tls_parameters(&self, mail_server: &str, socket_addr: SocketAddr) -> Result<TlsParameters> {
+        let server_name = tls_server_name(mail_server, socket_addr, self.config.smtp_tls_sni);
+        TlsParameters::builder(server_name)
+            .dangerous_accept_invalid_hostnames(!self.config.smtp_tls_sni)
+            .set_min_tls_version(lettre_tls_version(self.config.min_tls_version.enforced()))
+            .build()
+            .map_err(|e| {
+                AppError::SmtpTls(format!(
+                    "Failed to create TLS parameters for {}: {}",
+                    mail_server, e
+                ))
+            })
+    }
+
+    /// Returns the `MAIL FROM` address for a probe.
+    ///
+    /// With `smtp_sender_domain` set, a new random local part is generated on every call so the
+    /// probing domain stays fixed while the mailbox rotates. Otherwise `smtp_sender_email` is used.
+    fn sender_address(&self) -> Result<Address> {
+        match self.config.smtp_sender_domain {
+            Some(ref domain) => {
+                let local_part = generate_sender_local_part();
+                Address::new(&local_part, domain).map_err(|e| {
+                    AppError::Config(format!("Invalid sender domain in config: {}", e))
+                })
+            }
+            None => Address::from_str(&self.config.smtp_sender_email)
+                .map_err(|e| AppError::Config(format!("Invalid sender email in config: {}", e))),
+        }
+    }
+
     async fn try_connection(
         &self,
         params: &ConnectionParams,
         sender_address: &Address,
-        recipient_address: &Address,
+        recipient_path: &str,
         email: &str,
         domain: &str,
         mail_server: &str,
-        skip_catch_all_check: bool,
     ) -> Result<SmtpVerificationResult> {
         tracing::debug!(target: "smtp_task",
             "Attempting SMTP connection to {} at {} (TLS: {})",
@@ -267,24 +484,17 @@ impl SmtpVerifier {
         );
 
         let tls_parameters = if params.use_tls {
-            Some(
-                lettre::transport::smtp::client::TlsParameters::new(mail_server.to_string())
-                    .map_err(|e| {
-                        AppError::SmtpTls(format!(
-                            "Failed to create TLS parameters for {}: {}",
-                            mail_server, e
-                        ))
-                    })?,
-            )
+            Some(self.tls_parameters(mail_server, params.socket_addr)?)
         } else {
             None
         };
 
+        // Connect in plaintext; TLS is negotiated with STARTTLS below.
         let mut smtp_conn = match SmtpConnection::connect(
             params.socket_addr,
             Some(params.timeout),
             &params.helo_name,
-            tls_parameters.as_ref(),
+            None,
             None,
         ) {
             Ok(conn) => conn,
@@ -304,34 +514,84 @@ impl SmtpVerifier {
                     return Ok(SmtpVerificationResult::inconclusive_no_retry(format!(
                         "Connection failed ({}): Port 25 access might be blocked.",
                         err_string
-                    )));
+                    ))
+                    .with_failure(SmtpFailure::Connection));
                 }
 
                 return Ok(handle_smtp_error(&e, mail_server));
             }
         };
 
+        if let Some(ref tls_parameters) = tls_parameters {
+            let sni = sni_name(tls_parameters.domain());
+            if let Err(e) = smtp_conn.starttls(tls_parameters, &params.helo_name) {
+                tracing::warn!(target: "smtp_task",
+                    "STARTTLS handshake with {} failed (SNI: {}): {}",
+                    mail_server, sni.unwrap_or("none"), e);
+                smtp_conn.abort();
+                let error = match self
+                    .config
+                    .min_tls_version
+                    .explain_handshake_error(mail_server, &format!("{:?}", e))
+                {
+                    Some(reason) => AppError::TlsVersion(reason),
+                    None => {
+                        AppError::SmtpTls(format!("STARTTLS with {} failed: {}", mail_server, e))
+                    }
+                };
+                let message = error.to_string();
+                return Ok(
+                    SmtpVerificationResult::inconclusive_no_retry(message.clone())
+                        .with_failure(SmtpFailure::Connection)
+                        .with_tls_warning(message),
+                );
+            }
+            tracing::debug!(target: "smtp_task",
+                "STARTTLS negotiated with {} (SNI: {})", mail_server, sni.unwrap_or("none"));
+        }
+
         tracing::debug!(target: "smtp_task",
             "Established {} connection to {}:{}",
             if params.use_tls { "TLS" } else { "plaintext" },
             mail_server,
             params.socket_addr.port());
 
-        match smtp_conn.command(Ehlo::new(params.helo_name.clone())) {
+        if !self.config.smtp_dialogue.is_empty() {
+            let result = self
+                .run_custom_dialogue(
+                    &mut smtp_conn,
+                    params,
+                    sender_address,
+                    email,
+                    domain,
+                    mail_server,
+                )
+                .await;
+            smtp_conn.quit().ok();
+            return Ok(result);
+        }
+
+        let supports_vrfy = match smtp_conn.command(Ehlo::new(params.helo_name.clone())) {
             Ok(response) => {
                 if response.is_positive() {
                     tracing::debug!(target: "smtp_task", "EHLO successful for {}: Code={}, Response: {:?}", mail_server, response.code(), response.message().collect::<Vec<&str>>());
                 } else {
                     tracing::warn!(target: "smtp_task", "EHLO command rejected by {}: {} {}", mail_server, response.code(), response.message().collect::<Vec<&str>>().join(" "));
                 }
+                response.is_positive()
+                    && response.message().any(|line| {
+                        line.split_whitespace()
+                            .next()
+                            .is_some_and(|keyword| keyword.eq_ignore_ascii_case("VRFY"))
+                    })
             }
             Err(e) => {
                 tracing::warn!(target: "smtp_task", "Error sending EHLO command to {}: {}", mail_server, e);
                 return Ok(handle_smtp_error(&e, mail_server));
             }
-        }
+        };
 
-        tracing::debug!(target: "smtp_task", "Sending MAIL FROM:<{}> to {}...", &self.config.smtp_sender_email, mail_server);
+        tracing::debug!(target: "smtp_task", "Sending MAIL FROM:<{}> to {}...", sender_address, mail_server);
         match smtp_conn.command(Mail::new(Some(sender_address.clone()), vec![])) {
             Ok(response) => {
                 if response.is_positive() {
@@ -340,7 +600,7 @@ impl SmtpVerifier {
                     let message = response.message().collect::<Vec<&str>>().join(" ");
                     tracing::error!(target: "smtp_task",
                         "SMTP sender '{}' rejected by {}: {} {:?}",
-                        &self.config.smtp_sender_email, mail_server, response.code(), message
+                        sender_address, mail_server, response.code(), message
                     );
 
                     if !params.use_tls
@@ -362,7 +622,8 @@ impl SmtpVerifier {
                         "MAIL FROM rejected: {} {}",
                         response.code(),
                         message
-                    )));
+                    ))
+                    .with_failure(SmtpFailure::SenderRejected));
                 }
             }
             Err(e) => {
@@ -373,57 +634,52 @@ impl SmtpVerifier {
         }
 
         tracing::debug!(target: "smtp_task", "Sending RCPT TO:<{}> to {}...", email, mail_server);
-        let rcpt_result = smtp_conn.command(Rcpt::new(recipient_address.clone(), vec![]));
+        if let Err(e) = smtp_conn.set_timeout(Some(self.config.smtp_rcpt_timeout)) {
+            tracing::debug!(target: "smtp_task", "Could not set RCPT read deadline for {}: {}", mail_server, e);
+        }
+        let rcpt_started = Instant::now();
+        let rcpt_result = smtp_conn.command(RawCommand(&format!("RCPT TO:<{}>", recipient_path)));
+        let rcpt_latency_ms = rcpt_started.elapsed().as_millis() as u64;
+        smtp_conn.set_timeout(Some(params.timeout)).ok();
 
+        if !matches!(&rcpt_result, Err(e) if is_stall_error(e)) {
+            self.stalled_hosts.record(mail_server, false);
+        }
         let (target_code, target_message): (Code, String) = match rcpt_result {
             Ok(response) => {
+                let message = reply_text(&response.message().collect::<Vec<&str>>().join("\n"));
                 tracing::info!(target: "smtp_task",
                     "RCPT TO:<{}> initial response from {}: Code={}, Msg='{}'",
-                    email, mail_server, response.code(), response.message().collect::<Vec<&str>>().join(" ")
+                    email, mail_server, response.code(), message
                 );
-                (
-                    response.code(),
-                    response.message().collect::<Vec<&str>>().join(" "),
+                (response.code(), message)
+            }
+            Err(e) if is_stall_error(&e) => {
+                tracing::warn!(target: "smtp_task",
+                    "{} did not answer RCPT TO:<{}> within {:?}; treating the server as stalled.",
+                    mail_server, email, self.config.smtp_rcpt_timeout);
+                self.stalled_hosts.record(mail_server, true);
+                // No QUIT: it would wait on the same unresponsive connection.
+                drop(smtp_conn);
+                return Ok(SmtpVerificationResult::inconclusive_no_retry(
+                    AppError::SmtpInconclusive(RCPT_STALL_REASON.to_string()).to_string(),
                 )
+                .with_failure(SmtpFailure::Connection)
+                .with_signals(SmtpProbeSignals::new(rcpt_latency_ms, None)));
             }
             Err(e) => {
-                let err_string = e.to_string();
-                let is_nonexistent_error = err_string.contains("550")
-                    && (err_string.contains("does not exist")
-                        || err_string.contains("no such user")
-                        || err_string.contains("user unknown")
-                        || err_string.contains("recipient not found")
-                        || err_string.contains("invalid mailbox")
-                        || err_string.contains("mailbox unavailable")
-                        || err_string.contains("NoSuchUser"));
-
-                if is_nonexistent_error {
-                    tracing::info!(target: "smtp_task",
-                        "RCPT TO rejected for <{}> by {}. Email likely does not exist. Error: {}",
-                        email, mail_server, e);
-                    smtp_conn.quit().ok();
-                    return Ok(SmtpVerificationResult::conclusive(
-                        false,
-                        format!("SMTP Rejected (User Likely Unknown): {}", err_string),
-                        false,
-                    ));
-                } else {
-                    tracing::error!(target: "smtp_task",
-                        "Error during RCPT TO for <{}> on {}: {}",
-                        email, mail_server, e);
-                    smtp_conn.quit().ok();
-                    return Ok(handle_smtp_error(&e, mail_server));
-                }
+                smtp_conn.quit().ok();
+                return Ok(rcpt_error_result(&e, email, mail_server, rcpt_latency_ms));
             }
         };
 
-        let mut is_catch_all = false;
+        let mut invalid_probe: Option<(bool, u64)> = None;
         let perform_catch_all_check: bool;
 
         if target_code.severity == Severity::PositiveCompletion {
-            if skip_catch_all_check {
+            if params.skip_catch_all_check {
                 tracing::debug!(target: "smtp_task",
-                    "Skipping catch-all check for domain {} (MX: {}) based on provider rules.",
+                    "Skipping catch-all check for domain {} (MX: {}): already known not to be catch-all.",
                     domain, mail_server
                 );
                 perform_catch_all_check = false;
@@ -436,11 +692,38 @@ impl SmtpVerifier {
         }
 
         if perform_catch_all_check {
-            is_catch_all = self
+            invalid_probe = self
                 .perform_catch_all_check(domain, mail_server, &mut smtp_conn)
                 .await;
         }
-        let final_result = self.evaluate_smtp_response(target_code, target_message, is_catch_all);
+        let plus_probe = if perform_catch_all_check && self.config.smtp_subaddress_probe {
+            self.perform_subaddress_probe(email, domain, mail_server, &mut smtp_conn)
+                .await
+        } else {
+            None
+        };
+
+        // Accepting a random mailbox makes the domain a catch-all whatever the plus-tag probe
+        // says; that result is only reported alongside, as `subaddressing`.
+        let is_catch_all = invalid_probe.is_some_and(|(accepted, _)| accepted);
+        let mut signals = SmtpProbeSignals::new(rcpt_latency_ms, invalid_probe);
+        signals.subaddressing = plus_probe;
+        if perform_catch_all_check && !is_catch_all && self.config.smtp_expn_probe {
+            signals.expansion_members =
+                self.perform_expansion_probe(email, mail_server, &mut smtp_conn);
+        }
+        if self.config.smtp_vrfy_probe && supports_vrfy {
+            signals.vrfy_accepted = self.perform_vrfy_probe(email, mail_server, &mut smtp_conn);
+        }
+        if let Some(delta) = signals.latency_delta_ms {
+            tracing::debug!(target: "smtp_task",
+                "RCPT latency for <{}> on {}: {}ms (invalid-address probe differs by {:+}ms)",
+                email, mail_server, rcpt_latency_ms, delta
+            );
+        }
+        let final_result = self
+            .evaluate_smtp_response(target_code, target_message, is_catch_all)
+            .with_signals(signals);
 
         smtp_conn
             .quit()
@@ -453,31 +736,110 @@ impl SmtpVerifier {
         Ok(final_result)
     }
 
-    /// Performs a catch-all check by testing a random non-existent email address
+    /// Runs the steps of `smtp_dialogue` in place of the built-in EHLO / MAIL FROM / RCPT TO
+    /// sequence. The reply to the first command mentioning `{email}` is evaluated like a
+    /// RCPT TO reply. If that reply is positive, a positive reply to any `{probe}` command
+    /// marks the domain as catch-all. A failed reply to any other command ends the dialogue.
+    async fn run_custom_dialogue(
+        &self,
+        smtp_conn: &mut SmtpConnection,
+        params: &ConnectionParams,
+        sender_address: &Address,
+        email: &str,
+        domain: &str,
+        mail_server: &str,
+    ) -> SmtpVerificationResult {
+        let sender = sender_address.to_string();
+        let helo = params.helo_name.to_string();
+        let probe = format!("{}@{}", random_probe_local_part(&self.config), domain);
+
+        let mut target_reply = None;
+        let mut rcpt_latency_ms = 0;
+        let mut probe_accepted = false;
+        for raw_step in &self.config.smtp_dialogue {
+            let step = match raw_step.parse::<SmtpDialogueStep>() {
+                Ok(step) => step,
+                Err(e) => return SmtpVerificationResult::inconclusive_no_retry(e.to_string()),
+            };
+            let is_target = target_reply.is_none() && step.targets_email();
+            let line = match step {
+                SmtpDialogueStep::Wait(pause) => {
+                    tokio::time::sleep(pause).await;
+                    continue;
+                }
+                SmtpDialogueStep::Command(line) => line,
+            };
+            let is_probe = line.contains("{probe}");
+            let command = line
+                .replace("{email}", &smtp_path_address(email))
+                .replace("{sender}", &sender)
+                .replace("{helo}", &helo)
+                .replace("{domain}", domain)
+                .replace("{probe}", &probe);
+
+            tracing::debug!(target: "smtp_task", "Custom dialogue: sending '{}' to {}...", command, mail_server);
+            let started = Instant::now();
+            let reply = smtp_conn.command(RawCommand(&command));
+            if is_target {
+                rcpt_latency_ms = started.elapsed().as_millis() as u64;
+                match reply {
+                    Ok(response) => {
+                        tracing::info!(target: "smtp_task",
+                            "Custom dialogue: '{}' answered by {}: Code={}",
+                            command, mail_server, response.code());
+                        target_reply = Some((
+                            response.code(),
+                            reply_text(&response.message().collect::<Vec<&str>>().join("\n")),
+                        ));
+                    }
+                    Err(e) => return rcpt_error_result(&e, email, mail_server, rcpt_latency_ms),
+                }
+            } else if is_probe {
+                probe_accepted |= reply.is_ok();
+            } else if let Err(e) = reply {
+                tracing::warn!(target: "smtp_task", "Custom dialogue: '{}' failed on {}: {}", command, mail_server, e);
+                return handle_smtp_error(&e, mail_server);
+            }
+        }
+
+        match target_reply {
+            Some((code, message)) => {
+                let is_catch_all = probe_accepted && code.severity == Severity::PositiveCompletion;
+                self.evaluate_smtp_response(code, message, is_catch_all)
+                    .with_signals(SmtpProbeSignals::new(rcpt_latency_ms, None))
+            }
+            None => SmtpVerificationResult::inconclusive_no_retry(
+                "Custom SMTP dialogue has no command with {email}".to_string(),
+            ),
+        }
+    }
+
+    /// Performs a catch-all check by testing a random non-existent email address.
+    ///
+    /// Returns whether the random address was accepted and how long the server took
+    /// to answer (in milliseconds), or `None` if the probe could not be completed.
     async fn perform_catch_all_check(
         &self,
         domain: &str,
         mail_server: &str,
         smtp_conn: &mut SmtpConnection,
-    ) -> bool {
-        let random_user = format!(
-            "no-reply-does-not-exist-{}-{:x}@{}",
-            rand::thread_rng().gen_range(10000..99999),
-            rand::thread_rng().gen::<u32>(),
-            domain
-        );
+    ) -> Option<(bool, u64)> {
+        let random_user = format!("{}@{}", random_probe_local_part(&self.config), domain);
 
         match Address::from_str(&random_user) {
             Ok(random_address) => {
                 tracing::debug!(target: "smtp_task", "Performing catch-all check with: RCPT TO:<{}> on {}", random_user, mail_server);
-                match smtp_conn.command(Rcpt::new(random_address, vec![])) {
+                let probe_started = Instant::now();
+                let probe_result = smtp_conn.command(Rcpt::new(random_address, vec![]));
+                let probe_latency_ms = probe_started.elapsed().as_millis() as u64;
+                match probe_result {
                     Ok(response) if response.code().severity == Severity::PositiveCompletion => {
                         // If the random email is ALSO accepted, it's likely a catch-all
                         tracing::warn!(target: "smtp_task",
                             "Domain {} (MX: {}) appears to be a catch-all (accepted random user {} with code {})",
                             domain, mail_server, random_user, response.code()
                         );
-                        true
+                        Some((true, probe_latency_ms))
                     }
                     Ok(response) => {
                         // If the random email is rejected, it's likely NOT a catch-all
@@ -485,29 +847,163 @@ impl SmtpVerifier {
                             "Catch-all check negative for {} (MX: {}). Random user {} rejected with code {}.",
                             domain, mail_server, random_user, response.code()
                         );
-                        false
+                        Some((false, probe_latency_ms))
                     }
                     Err(e) => {
                         tracing::warn!(target: "smtp_task", "Error during catch-all RCPT TO check for {} on {} (ignoring catch-all result): {}", random_user, mail_server, e);
-                        false
+                        None
                     }
                 }
             }
             Err(_) => {
                 tracing::error!(target: "smtp_task", "Failed to parse generated random email for catch-all check: {}", random_user);
-                false
+                None
+            }
+        }
+    }
+
+    /// Probes a plus-tagged variant (`local+tag@domain`) of the candidate address.
+    ///
+    /// Returns whether the server accepted it, or `None` if the probe could not be completed.
+    async fn perform_subaddress_probe(
+        &self,
+        email: &str,
+        domain: &str,
+        mail_server: &str,
+        smtp_conn: &mut SmtpConnection,
+    ) -> Option<bool> {
+        let local_part = email.split('@').next()?;
+        let base = local_part.split('+').next().unwrap_or(local_part);
+        let tagged = format!("{}+{:x}@{}", base, rand::thread_rng().gen::<u32>(), domain);
+
+        let tagged_address = match Address::from_str(&tagged) {
+            Ok(address) => address,
+            Err(_) => {
+                tracing::error!(target: "smtp_task", "Failed to parse generated subaddress for probe: {}", tagged);
+                return None;
+            }
+        };
+
+        tracing::debug!(target: "smtp_task", "Performing subaddress probe with: RCPT TO:<{}> on {}", tagged, mail_server);
+        match smtp_conn.command(Rcpt::new(tagged_address, vec![])) {
+            Ok(response) => {
+                let accepted = response.code().severity == Severity::PositiveCompletion;
+                tracing::debug!(target: "smtp_task",
+                    "Subaddress probe for {} (MX: {}) {} with code {}.",
+                    tagged, mail_server, if accepted { "accepted" } else { "rejected" }, response.code()
+                );
+                Some(accepted)
+            }
+            Err(e) => {
+                if e.is_permanent() {
+                    tracing::debug!(target: "smtp_task", "Subaddress probe {} rejected on {}: {}", tagged, mail_server, e);
+                    Some(false)
+                } else {
+                    tracing::warn!(target: "smtp_task", "Error during subaddress probe for {} on {} (ignoring result): {}", tagged, mail_server, e);
+                    None
+                }
+            }
+        }
+    }
+
+    /// Asks the server to expand `email` with EXPN, falling back to VRFY if EXPN is refused.
+    ///
+    /// Returns the number of recipients listed, or `None` if both commands were refused.
+    /// Most servers disable EXPN and answer VRFY with 252 ("cannot verify"), which counts
+    /// as refused.
+    fn perform_expansion_probe(
+        &self,
+        email: &str,
+        mail_server: &str,
+        smtp_conn: &mut SmtpConnection,
+    ) -> Option<u32> {
+        let expn = Expn::new(smtp_path_address(email).into_owned());
+        let vrfy = Vrfy::new(smtp_path_address(email).into_owned());
+        for (command, request) in [("EXPN", &expn as &dyn fmt::Display), ("VRFY", &vrfy)] {
+            match smtp_conn.command(request) {
+                Ok(response) if response.code().to_string() == "250" => {
+                    let members = response
+                        .message()
+                        .filter(|line| line.contains('@'))
+                        .count()
+                        .max(1) as u32;
+                    tracing::debug!(target: "smtp_task",
+                        "{} for <{}> on {} listed {} recipient(s).",
+                        command, email, mail_server, members
+                    );
+                    return Some(members);
+                }
+                Ok(response) => {
+                    tracing::debug!(target: "smtp_task", "{} for <{}> declined by {} with code {}.", command, email, mail_server, response.code());
+                }
+                Err(e) => {
+                    tracing::debug!(target: "smtp_task", "{} for <{}> refused by {}: {}", command, email, mail_server, e);
+                }
             }
         }
+        None
     }
 
-    /// Evaluates SMTP response codes and messages to determine email existence
+    /// Asks the server whether `email` exists with VRFY.
+    ///
+    /// Returns `Some(true)` for 250/251, `Some(false)` for 550/551/553, and `None` when the
+    /// server would not say (252 "cannot verify", command disabled, or an error).
+    fn perform_vrfy_probe(
+        &self,
+        email: &str,
+        mail_server: &str,
+        smtp_conn: &mut SmtpConnection,
+    ) -> Option<bool> {
+        let code = match smtp_conn.command(Vrfy::new(smtp_path_address(email).into_owned())) {
+            Ok(response) => response.code(),
+            Err(e) => match e.status() {
+                Some(code) => code,
+                None => {
+                    tracing::debug!(target: "smtp_task", "VRFY for <{}> failed on {}: {}", email, mail_server, e);
+                    return None;
+                }
+            },
+        };
+        let accepted = match code.to_string().as_str() {
+            "250" | "251" => Some(true),
+            "550" | "551" | "553" => Some(false),
+            _ => None,
+        };
+        tracing::debug!(target: "smtp_task", "VRFY for <{}> on {} answered {} ({:?}).", email, mail_server, code, accepted);
+        accepted
+    }
+
+    /// Evaluates SMTP response codes and messages to determine email existence.
+    ///
+    /// For negative replies the RFC 3463 enhanced status code, when there is one, says
+    /// what the failure means for the mailbox, and a 4.x.x code makes a 5xx reply
+    /// temporary. A 4xx reply stays temporary whatever its enhanced code.
     fn evaluate_smtp_response(
         &self,
         target_code: Code,
         target_message: String,
         is_catch_all: bool,
     ) -> SmtpVerificationResult {
-        match target_code.severity {
+        let reply = format!("{} {}", target_code, target_message);
+        let negative = matches!(
+            target_code.severity,
+            Severity::TransientNegativeCompletion | Severity::PermanentNegativeCompletion
+        );
+        if negative && classify_rcpt_rejection(&reply) == RcptRejection::MailboxFull {
+            // Over quota, whether for now (4.2.2) or for good (5.2.2): the mailbox exists.
+            return SmtpVerificationResult::conclusive(
+                true,
+                format!("SMTP Rejected (Mailbox Full, Exists): {}", reply),
+                false,
+            );
+        }
+        let severity = match EnhancedStatus::find(&reply) {
+            Some(status) if negative && status.is_transient() => {
+                Severity::TransientNegativeCompletion
+            }
+            _ => target_code.severity,
+        };
+        match severity {
             Severity::PositiveCompletion => {
                 if is_catch_all {
                     SmtpVerificationResult::inconclusive_no_retry(format!(
@@ -537,29 +1033,21 @@ impl SmtpVerifier {
                 ))
             }
             Severity::PermanentNegativeCompletion => {
-                // 5xx code - Permanent failure
-                let rejection_phrases = [
-                    "unknown",
-                    "no such",
-                    "unavailable",
-                    "rejected",
-                    "doesn't exist",
-                    "disabled",
-                    "invalid address",
-                    "recipient not found",
-                    "user unknown",
-                    "mailbox unavailable",
-                    "no mailbox",
-                    "address rejected",
-                    "invalid recipient",
-                    "policy violation",
-                ];
-                let message_lower = target_message.to_lowercase();
+                // 5xx code - Permanent failure. Only a mailbox problem means the address is
+                // invalid; a policy refusal (relaying denied, blocklisted sender) is no answer.
                 let code_str = target_code.to_string();
+                let stated = classify_rcpt_rejection(&reply);
+                let rejection = match stated {
+                    RcptRejection::Unclassified
+                        if ["550", "551", "553"].contains(&code_str.as_str()) =>
+                    {
+                        RcptRejection::UserUnknown
+                    }
+                    RcptRejection::Unclassified => RcptRejection::Policy,
+                    rejection => rejection,
+                };
 
-                if ["550", "551", "553"].contains(&code_str.as_str())
-                    || rejection_phrases.iter().any(|p| message_lower.contains(p))
-                {
+                if rejection == RcptRejection::UserUnknown {
                     SmtpVerificationResult::conclusive(
                         false,
                         format!(
@@ -568,21 +1056,231 @@ impl SmtpVerifier {
                         ),
                         false,
                     )
+                    .with_rcpt_rejection(stated)
+                } else if rejection == RcptRejection::AuthRequired {
+                    SmtpVerificationResult::inconclusive_no_retry(format!(
+                        "{} ({} {})",
+                        AppError::SmtpInconclusive(AUTH_REQUIRED_REASON.to_string()),
+                        target_code,
+                        target_message
+                    ))
+                } else if rejection == RcptRejection::System {
+                    SmtpVerificationResult::inconclusive_no_retry(format!(
+                        "SMTP Rejected (System): {} {}",
+                        target_code, target_message
+                    ))
                 } else {
-                    SmtpVerificationResult::conclusive(
-                        false,
-                        format!(
-                            "SMTP Rejected (Policy/Other 5xx): {} {}",
-                            target_code, target_message
-                        ),
-                        false,
-                    )
+                    SmtpVerificationResult::inconclusive_no_retry(format!(
+                        "SMTP Rejected (Policy/Other 5xx): {} {}",
+                        target_code, target_message
+                    ))
+                    .with_failure(SmtpFailure::SenderRejected)
                 }
             }
         }
     }
 }
 
+/// Turns a failed reply to the RCPT TO for `email` into a result: a mailbox rejection means
+/// the address does not exist and a full mailbox that it does; auth, policy and system
+/// refusals say nothing about it.
+///
+/// A 4.x.x enhanced status code makes the failure temporary even when the basic reply code
+/// is 5xx (e.g. "550 4.7.1 Greylisted"); a 4xx reply stays temporary whatever its
+/// enhanced code.
+fn rcpt_error_result(
+    e: &lettre::transport::smtp::Error,
+    email: &str,
+    mail_server: &str,
+    rcpt_latency_ms: u64,
+) -> SmtpVerificationResult {
+    let err_string = reply_text(&e.to_string());
+    let has_reply = e.is_permanent() || e.is_transient();
+    let transient = e.is_transient()
+        || (has_reply
+            && EnhancedStatus::find(&err_string).is_some_and(|status| status.is_transient()));
+    let rejection = match has_reply.then(|| classify_rcpt_rejection(&err_string)) {
+        Some(RcptRejection::MailboxFull) => RcptRejection::MailboxFull,
+        Some(rejection) if !transient => rejection,
+        _ => RcptRejection::Unclassified,
+    };
+
+    match rejection {
+        RcptRejection::MailboxFull => {
+            tracing::info!(target: "smtp_task",
+                "RCPT TO for <{}> refused by {} because the mailbox is full; the address exists. Error: {}",
+                email, mail_server, e);
+            SmtpVerificationResult::conclusive(
+                true,
+                format!("SMTP Rejected (Mailbox Full, Exists): {}", err_string),
+                false,
+            )
+            .with_signals(SmtpProbeSignals::new(rcpt_latency_ms, None))
+        }
+        RcptRejection::Unclassified if transient && !e.is_transient() => {
+            tracing::warn!(target: "smtp_task",
+                "RCPT TO for <{}> refused by {} with a temporary enhanced status code. Error: {}",
+                email, mail_server, e);
+            SmtpVerificationResult::inconclusive_retry(format!(
+                "SMTP Transient Error (4xx): {}",
+                err_string
+            ))
+            .with_signals(SmtpProbeSignals::new(rcpt_latency_ms, None))
+        }
+        RcptRejection::UserUnknown => {
+            tracing::info!(target: "smtp_task",
+                "RCPT TO rejected for <{}> by {}. Email likely does not exist. Error: {}",
+                email, mail_server, e);
+            SmtpVerificationResult::conclusive(
+                false,
+                format!("SMTP Rejected (User Likely Unknown): {}", err_string),
+                false,
+            )
+            .with_rcpt_rejection(RcptRejection::UserUnknown)
+            .with_signals(SmtpProbeSignals::new(rcpt_latency_ms, None))
+        }
+        RcptRejection::AuthRequired => {
+            tracing::warn!(target: "smtp_task",
+                "{} only accepts RCPT TO from authenticated sessions; cannot probe <{}>. Error: {}",
+                mail_server, email, e);
+            SmtpVerificationResult::inconclusive_no_retry(format!(
+                "{} ({})",
+                AppError::SmtpInconclusive(AUTH_REQUIRED_REASON.to_string()),
+                err_string
+            ))
+            .with_signals(SmtpProbeSignals::new(rcpt_latency_ms, None))
+        }
+        RcptRejection::Policy => {
+            tracing::warn!(target: "smtp_task",
+                "RCPT TO for <{}> refused by {} on policy grounds; says nothing about the address. Error: {}",
+                email, mail_server, e);
+            SmtpVerificationResult::inconclusive_no_retry(format!(
+                "SMTP Rejected (Policy): {}",
+                err_string
+            ))
+            .with_failure(SmtpFailure::SenderRejected)
+            .with_signals(SmtpProbeSignals::new(rcpt_latency_ms, None))
+        }
+        RcptRejection::System => {
+            tracing::warn!(target: "smtp_task",
+                "RCPT TO for <{}> refused by {} for a mail system problem; says nothing about the address. Error: {}",
+                email, mail_server, e);
+            SmtpVerificationResult::inconclusive_no_retry(format!(
+                "SMTP Rejected (System): {}",
+                err_string
+            ))
+            .with_signals(SmtpProbeSignals::new(rcpt_latency_ms, None))
+        }
+        RcptRejection::Unclassified => {
+            tracing::error!(target: "smtp_task",
+                "Error during RCPT TO for <{}> on {}: {}",
+                email, mail_server, e);
+            handle_smtp_error(e, mail_server)
+        }
+    }
+}
+
+/// Characters besides letters and digits that RFC 5322 allows unquoted in a local part.
+const ATEXT_SPECIALS: &str = "!#$%&'*+-/=?^_`{|}~";
+
+/// Whether `local_part` is a dot-atom: runs of letters, digits and [`ATEXT_SPECIALS`]
+/// separated by single dots. Non-ASCII letters count as letters, as under SMTPUTF8.
+fn is_dot_atom(local_part: &str) -> bool {
+    local_part.split('.').all(|atom| {
+        !atom.is_empty()
+            && atom
+                .chars()
+                .all(|c| c.is_alphanumeric() || ATEXT_SPECIALS.contains(c))
+    })
+}
+
+/// Writes `email` the way it goes between the angle brackets of `RCPT TO`, `VRFY` and
+/// `EXPN` (RFC 5321). Addresses whose local part is a dot-atom, such as `o'brien@acme.com`,
+/// are unchanged. Any other local part is sent as a quoted string with `"` and `\` escaped,
+/// e.g. `"john..doe"@acme.com`. Local parts that are already quoted are left alone.
+fn smtp_path_address(email: &str) -> Cow<'_, str> {
+    let Some((local_part, domain)) = email.rsplit_once('@') else {
+        return Cow::Borrowed(email);
+    };
+    let already_quoted =
+        local_part.len() >= 2 && local_part.starts_with('"') && local_part.ends_with('"');
+    if already_quoted || is_dot_atom(local_part) {
+        return Cow::Borrowed(email);
+    }
+    let mut quoted = String::with_capacity(email.len() + 4);
+    quoted.push('"');
+    for c in local_part.chars() {
+        if c == '"' || c == '\\' {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    Cow::Owned(format!("{}@{}", quoted, domain))
+}
+
+/// A command line sent verbatim: a step of `smtp_dialogue`, or a RCPT TO whose local part
+/// had to be quoted.
+struct RawCommand<'a>(&'a str);
+
+impl fmt::Display for RawCommand<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\r\n", self.0)
+    }
+}
+
+/// Generates the local part of a random, certainly-invalid probe address from
+/// `smtp_probe_prefix`, `smtp_probe_length` and `smtp_probe_charset`,
+/// e.g. `no-reply-does-not-exist-k3v9q0x7m2ab`.
+pub(crate) fn random_probe_local_part(config: &Config) -> String {
+    let alphabet = config.smtp_probe_charset.alphabet();
+    let mut rng = rand::thread_rng();
+    let random: String = (0..config.smtp_probe_length)
+        .map(|_| alphabet[rng.gen_range(0..alphabet.len())] as char)
+        .collect();
+    format!("{}{}", config.smtp_probe_prefix, random)
+}
+
+/// Generates a random sender local part such as `probe-3fa9c1d2`.
+fn generate_sender_local_part() -> String {
+    format!("probe-{:08x}", rand::thread_rng().gen::<u32>())
+}
+
+/// Whether an SMTP error means the server sent no reply before the read deadline.
+fn is_stall_error(error: &lettre::transport::smtp::Error) -> bool {
+    let message = error.to_string().to_lowercase();
+    message.contains("timed out")
+        || message.contains("would block")
+        || message.contains("temporarily unavailable")
+}
+
+/// The TLS server name for a handshake with `mail_server`: the MX hostname when SNI is
+/// enabled, otherwise the IP address being connected to.
+fn tls_server_name(mail_server: &str, socket_addr: SocketAddr, use_sni: bool) -> String {
+    if use_sni {
+        mail_server.trim_end_matches('.').to_string()
+    } else {
+        socket_addr.ip().to_string()
+    }
+}
+
+/// The SNI a TLS client sends for `server_name`. IP addresses are never sent as SNI (RFC 6066 3).
+fn sni_name(server_name: &str) -> Option<&str> {
+    match server_name.parse::<IpAddr>() {
+        Ok(_) => None,
+        Err(_) => Some(server_name),
+    }
+}
+
+fn lettre_tls_version(version: TlsVersion) -> LettreTlsVersion {
+    match version {
+        TlsVersion::Tls1_0 => LettreTlsVersion::Tlsv10,
+        TlsVersion::Tls1_1 => LettreTlsVersion::Tlsv11,
+        TlsVersion::Tls1_2 => LettreTlsVersion::Tlsv12,
+        TlsVersion::Tls1_3 => LettreTlsVersion::Tlsv13,
+    }
+}
+
 /// Tests basic SMTP connectivity to a known reliable server (Google).
 /// This helps diagnose if outbound port 25 is generally blocked.
 pub async fn test_smtp_connectivity() -> Result<()> {
@@ -649,3 +1347,142 @@ pub async fn test_smtp_connectivity() -> Result<()> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_random_probe_local_part_follows_config() {
+        let mut config = Config::default();
+        config.smtp_probe_prefix = "x-".to_string();
+        config.smtp_probe_length = 20;
+        config.smtp_probe_charset = crate::core::config::ProbeCharset::Digits;
+
+        let local_part = random_probe_local_part(&config);
+        let random = local_part.strip_prefix("x-").unwrap();
+        assert_eq!(random.len(), 20);
+        assert!(random.chars().all(|c| c.is_ascii_digit()));
+    }
+
+    #[test]
+    fn test_evaluate_smtp_response_keeps_4xx_transient() {
+        use lettre::transport::smtp::response::{Category, Detail};
+
+        let verifier = SmtpVerifier::new(Arc::new(Config::default()));
+        let temporary = Code::new(
+            Severity::TransientNegativeCompletion,
+            Category::MailSystem,
+            Detail::Zero,
+        );
+        let result =
+            verifier.evaluate_smtp_response(temporary, "5.1.1 User unknown".to_string(), false);
+        assert_eq!(result.exists, None);
+        assert!(result.should_retry);
+
+        let permanent = Code::new(
+            Severity::PermanentNegativeCompletion,
+            Category::MailSystem,
+            Detail::Zero,
+        );
+        let result =
+            verifier.evaluate_smtp_response(permanent, "5.1.1 User unknown".to_string(), false);
+        assert_eq!(result.exists, Some(false));
+
+        let greylisted = verifier.evaluate_smtp_response(
+            permanent,
+            "4.7.1 Greylisted, try again later".to_string(),
+            false,
+        );
+        assert_eq!(greylisted.exists, None);
+        assert!(greylisted.should_retry);
+    }
+
+    #[test]
+    fn test_tls_parameters_send_mx_host_as_sni() {
+        let socket_addr: SocketAddr = "192.0.2.10:25".parse().unwrap();
+
+        let config = Config {
+            smtp_tls_sni: true,
+            ..Default::default()
+        };
+        let verifier = SmtpVerifier::new(Arc::new(config));
+        let params = verifier
+            .tls_parameters("mx1.example.com.", socket_addr)
+            .unwrap();
+        assert_eq!(params.domain(), "mx1.example.com");
+        assert_eq!(sni_name(params.domain()), Some("mx1.example.com"));
+
+        // With SNI disabled the handshake is made against the IP address, which is never
+        // sent as SNI.
+        let config = Config {
+            smtp_tls_sni: false,
+            ..Default::default()
+        };
+        let verifier = SmtpVerifier::new(Arc::new(config));
+        let params = verifier
+            .tls_parameters("mx1.example.com.", socket_addr)
+            .unwrap();
+        assert_eq!(params.domain(), "192.0.2.10");
+        assert_eq!(sni_name(params.domain()), None);
+    }
+
+    #[test]
+    fn test_tls_parameters_raise_old_min_version() {
+        let socket_addr: SocketAddr = "192.0.2.10:25".parse().unwrap();
+        for version in [TlsVersion::Tls1_0, TlsVersion::Tls1_1, TlsVersion::Tls1_3] {
+            let config = Config {
+                min_tls_version: version,
+                ..Default::default()
+            };
+            let verifier = SmtpVerifier::new(Arc::new(config));
+            assert!(
+                verifier
+                    .tls_parameters("mx1.example.com", socket_addr)
+                    .is_ok(),
+                "min_tls_version {} should build TLS parameters",
+                version
+            );
+        }
+    }
+
+    #[test]
+    fn test_tls_server_name_strips_trailing_dot() {
+        let socket_addr: SocketAddr = "192.0.2.10:25".parse().unwrap();
+        assert_eq!(
+            tls_server_name("mx1.example.com.", socket_addr, true),
+            "mx1.example.com"
+        );
+    }
+
+    #[test]
+    fn test_no_sni_for_ip_mail_server() {
+        let socket_addr: SocketAddr = "127.0.0.1:25".parse().unwrap();
+        let server_name = tls_server_name("127.0.0.1", socket_addr, true);
+        assert_eq!(sni_name(&server_name), None);
+    }
+
+    #[test]
+    fn test_smtp_path_address() {
+        assert_eq!(smtp_path_address("john.doe@acme.com"), "john.doe@acme.com");
+        assert_eq!(smtp_path_address("o'brien@acme.com"), "o'brien@acme.com");
+        assert_eq!(smtp_path_address("jean-luc@acme.com"), "jean-luc@acme.com");
+        assert_eq!(
+            smtp_path_address("john..doe@acme.com"),
+            "\"john..doe\"@acme.com"
+        );
+        assert_eq!(smtp_path_address(".jd@acme.com"), "\".jd\"@acme.com");
+        assert_eq!(
+            smtp_path_address("john doe@acme.com"),
+            "\"john doe\"@acme.com"
+        );
+        assert_eq!(
+            smtp_path_address("say\"hi@acme.com"),
+            "\"say\\\"hi\"@acme.com"
+        );
+        assert_eq!(
+            smtp_path_address("\"john doe\"@acme.com"),
+            "\"john doe\"@acme.com"
+        );
+    }
+}
@@ -1,12 +1,30 @@
 //! Error handling utilities for SMTP verification operations.
 
-use super::result::SmtpVerificationResult;
+use super::result::{
+    classify_rcpt_rejection, reply_text, RcptRejection, SmtpFailure, SmtpVerificationResult,
+    AUTH_REQUIRED_REASON,
+};
+use crate::core::error::AppError;
 use lettre::transport::smtp::Error as SmtpError;
-use tracing;
 
 /// Interprets lettre::transport::smtp::Error into a structured SmtpVerificationResult
 pub(crate) fn handle_smtp_error(error: &SmtpError, server: &str) -> SmtpVerificationResult {
-    let err_string = error.to_string().to_lowercase();
+    let err_string = reply_text(&error.to_string()).to_lowercase();
+    let rejection = if error.is_permanent() {
+        classify_rcpt_rejection(&err_string)
+    } else {
+        RcptRejection::Unclassified
+    };
+
+    // Checked before STARTTLS: both use 530 5.7.0, but retrying does not help here.
+    if rejection == RcptRejection::AuthRequired {
+        tracing::warn!(target: "smtp_task", "SMTP server {} requires authentication; cannot probe: {}", server, error);
+        return SmtpVerificationResult::inconclusive_no_retry(format!(
+            "{} ({})",
+            AppError::SmtpInconclusive(AUTH_REQUIRED_REASON.to_string()),
+            error
+        ));
+    }
 
     if err_string.contains("starttls")
         || (err_string.contains("530")
@@ -20,23 +38,22 @@ pub(crate) fn handle_smtp_error(error: &SmtpError, server: &str) -> SmtpVerifica
         ));
     }
 
-    if err_string.contains("550")
-        && (err_string.contains("does not exist")
-            || err_string.contains("no such user")
-            || err_string.contains("user unknown")
-            || err_string.contains("recipient not found")
-            || err_string.contains("invalid mailbox")
-            || err_string.contains("mailbox unavailable")
-            || err_string.contains("address rejected")
-            || err_string.contains("invalid recipient")
-            || err_string.contains("nosuchuser"))
-    {
+    if rejection == RcptRejection::Policy {
+        tracing::warn!(target: "smtp_task", "SMTP rejection from {} is a policy refusal: {}", server, error);
+        return SmtpVerificationResult::inconclusive_no_retry(format!(
+            "SMTP Rejected (Policy): {}",
+            error
+        ))
+        .with_failure(SmtpFailure::SenderRejected);
+    }
+    if rejection == RcptRejection::UserUnknown {
         tracing::info!(target: "smtp_task", "SMTP error indicates user likely unknown on {}: {}", server, error);
         return SmtpVerificationResult::conclusive(
             false,
             format!("SMTP Rejected (User Likely Unknown): {}", error),
             false,
-        );
+        )
+        .with_rcpt_rejection(RcptRejection::UserUnknown);
     }
 
     if err_string.contains("timed out")
@@ -48,7 +65,8 @@ pub(crate) fn handle_smtp_error(error: &SmtpError, server: &str) -> SmtpVerifica
         return SmtpVerificationResult::inconclusive_no_retry(format!(
             "Connection Failed ({}) - Port 25 Blocked?",
             error
-        ));
+        ))
+        .with_failure(SmtpFailure::Connection);
     }
 
     if err_string.contains("4")
@@ -72,13 +90,15 @@ pub(crate) fn handle_smtp_error(error: &SmtpError, server: &str) -> SmtpVerifica
         return SmtpVerificationResult::inconclusive_no_retry(format!(
             "SMTP Permanent Error (5xx): {}",
             error
-        ));
+        ))
+        .with_failure(SmtpFailure::SenderRejected);
     }
 
     // Check for TLS-specific errors during handshake etc.
     if err_string.contains("tls") {
         tracing::warn!(target: "smtp_task", "SMTP TLS Error for {}: {}", server, error);
-        return SmtpVerificationResult::inconclusive_retry(format!("SMTP TLS Error: {}", error));
+        return SmtpVerificationResult::inconclusive_retry(format!("SMTP TLS Error: {}", error))
+            .with_failure(SmtpFailure::Connection);
     }
 
     if err_string.contains("connection reset") {
@@ -86,7 +106,8 @@ pub(crate) fn handle_smtp_error(error: &SmtpError, server: &str) -> SmtpVerifica
         return SmtpVerificationResult::inconclusive_retry(format!(
             "Connection reset by {} ({})",
             server, error
-        ));
+        ))
+        .with_failure(SmtpFailure::Connection);
     }
 
     tracing::error!(target: "smtp_task", "Unhandled SMTP Error for {}: {}", server, error);
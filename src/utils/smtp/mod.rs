@@ -1,5 +1,11 @@
+mod breaker;
 mod client;
 mod error;
+mod reputation;
 mod result;
+mod sender_check;
+pub(crate) use client::random_probe_local_part;
 pub use client::test_smtp_connectivity;
 pub use client::SmtpVerifier;
+pub(crate) use sender_check::check_sender_setup;
+pub use sender_check::SenderCheckReport;
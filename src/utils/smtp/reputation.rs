@@ -0,0 +1,110 @@
+//! Tracks how often remote servers reject our SMTP probes, per sender identity.
+//!
+//! When the recent rejection rate for a sender crosses the configured threshold,
+//! probing with that sender is paused for a cooldown to protect its reputation.
+
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+/// Rolling window of probe outcomes for one sender identity.
+#[derive(Debug, Default)]
+struct SenderWindow {
+    /// `true` = rejected, `false` = accepted. Oldest first.
+    outcomes: VecDeque<bool>,
+    paused_until: Option<Instant>,
+}
+
+/// Shared tracker of sender rejection rates.
+#[derive(Debug)]
+pub(crate) struct SenderReputation {
+    window_size: usize,
+    threshold: f32,
+    cooldown: Duration,
+    senders: Mutex<HashMap<String, SenderWindow>>,
+}
+
+impl SenderReputation {
+    /// Creates a tracker that pauses a sender once more than `threshold` (0.0-1.0) of its
+    /// last `window_size` probes were rejected.
+    pub(crate) fn new(window_size: usize, threshold: f32, cooldown: Duration) -> Self {
+        Self {
+            window_size: window_size.max(1),
+            threshold,
+            cooldown,
+            senders: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the remaining cooldown if probing with `sender` is currently paused.
+    pub(crate) fn paused_for(&self, sender: &str) -> Option<Duration> {
+        let mut senders = self.senders.lock();
+        let window = senders.get_mut(sender)?;
+        let until = window.paused_until?;
+        let now = Instant::now();
+        if now >= until {
+            tracing::info!(target: "smtp_task", "SMTP cooldown for sender identity '{}' has ended. Resuming probes.", sender);
+            window.paused_until = None;
+            return None;
+        }
+        Some(until - now)
+    }
+
+    /// Records the outcome of a probe and starts a cooldown if the rejection rate is too high.
+    pub(crate) fn record(&self, sender: &str, rejected: bool) {
+        let mut senders = self.senders.lock();
+        let window = senders.entry(sender.to_string()).or_default();
+        window.outcomes.push_back(rejected);
+        while window.outcomes.len() > self.window_size {
+            window.outcomes.pop_front();
+        }
+
+        if window.outcomes.len() < self.window_size {
+            return;
+        }
+        let rejections = window.outcomes.iter().filter(|r| **r).count();
+        let rate = rejections as f32 / window.outcomes.len() as f32;
+        if rate > self.threshold {
+            tracing::warn!(target: "smtp_task",
+                "Sender identity '{}' was rejected in {}/{} recent SMTP probes ({:.0}%, threshold {:.0}%). Pausing SMTP probing for {:?}.",
+                sender, rejections, window.outcomes.len(), rate * 100.0, self.threshold * 100.0, self.cooldown
+            );
+            window.paused_until = Some(Instant::now() + self.cooldown);
+            window.outcomes.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pauses_after_rejection_rate_exceeds_threshold() {
+        let tracker = SenderReputation::new(4, 0.5, Duration::from_secs(60));
+        tracker.record("probe.example.com", true);
+        tracker.record("probe.example.com", false);
+        tracker.record("probe.example.com", true);
+        assert!(tracker.paused_for("probe.example.com").is_none());
+
+        tracker.record("probe.example.com", true);
+        assert!(tracker.paused_for("probe.example.com").is_some());
+        assert!(tracker.paused_for("other.example.com").is_none());
+    }
+
+    #[test]
+    fn test_does_not_pause_at_threshold() {
+        let tracker = SenderReputation::new(4, 0.5, Duration::from_secs(60));
+        for rejected in [true, false, true, false, true, false] {
+            tracker.record("probe.example.com", rejected);
+        }
+        assert!(tracker.paused_for("probe.example.com").is_none());
+    }
+
+    #[test]
+    fn test_resumes_after_cooldown() {
+        let tracker = SenderReputation::new(1, 0.5, Duration::ZERO);
+        tracker.record("probe.example.com", true);
+        assert!(tracker.paused_for("probe.example.com").is_none());
+    }
+}
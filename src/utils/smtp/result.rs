@@ -1,6 +1,221 @@
 // src/utils/smtp/result.rs
 //! Defines the result type for SMTP verification operations.
 
+use crate::core::models::SmtpProbeSignals;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::time::Duration;
+
+/// Matches server hints such as "try again in 5 minutes" or "greylisted for 300 seconds".
+static RETRY_HINT_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)(?:try again|retry|greylist\w*|wait)\D{0,20}?(\d+)\s*(seconds?|secs?|s|minutes?|mins?|m|hours?|h)\b")
+        .expect("Retry hint regex failed to compile. This is a bug.")
+});
+
+/// Matches an RFC 3463 enhanced status code such as "5.1.1" or "5.7.606".
+static ENHANCED_STATUS_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b([245])\.(\d{1,3})\.(\d{1,3})\b")
+        .expect("Enhanced status regex failed to compile. This is a bug.")
+});
+
+/// An RFC 3463 enhanced status code, `class.subject.detail` (e.g. 5.1.1 or 4.2.2).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct EnhancedStatus {
+    /// 2 (success), 4 (persistent transient failure) or 5 (permanent failure).
+    pub(crate) class: u8,
+    pub(crate) subject: u16,
+    pub(crate) detail: u16,
+}
+
+impl EnhancedStatus {
+    /// Finds the enhanced status code of `reply`.
+    ///
+    /// RFC 2034 puts the code at the start of the text of every reply line, so a code
+    /// right after the reply code ("550 5.1.1", "550-5.1.1", lettre's "(550): 5.1.1") or at
+    /// the start of a line is preferred over one that is only quoted in the text.
+    pub(crate) fn find(reply: &str) -> Option<Self> {
+        let mut first = None;
+        for caps in ENHANCED_STATUS_REGEX.captures_iter(reply) {
+            let (Ok(class), Ok(subject), Ok(detail)) = (
+                caps[1].parse::<u8>(),
+                caps[2].parse::<u16>(),
+                caps[3].parse::<u16>(),
+            ) else {
+                continue;
+            };
+            let status = Self {
+                class,
+                subject,
+                detail,
+            };
+            let start = caps.get(0).map_or(0, |m| m.start());
+            let before = reply[..start].rsplit('\n').next().unwrap_or("").trim_end();
+            let after_reply_code = before
+                .trim_end_matches('-')
+                .rsplit(char::is_whitespace)
+                .next()
+                .is_some_and(|word| word.len() == 3 && word.bytes().all(|b| b.is_ascii_digit()));
+            if before.is_empty() || before.ends_with(':') || after_reply_code {
+                return Some(status);
+            }
+            first.get_or_insert(status);
+        }
+        first
+    }
+
+    /// Whether the code reports a temporary condition (4.x.x), so a later retry may succeed.
+    pub(crate) fn is_transient(&self) -> bool {
+        self.class == 4
+    }
+}
+
+impl std::fmt::Display for EnhancedStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.class, self.subject, self.detail)
+    }
+}
+
+/// Turns the text of a possibly multiline reply into one line.
+///
+/// Lines are joined with spaces, and the enhanced status code repeated at the start of
+/// every line after the first is dropped. lettre glues the lines of a failed reply
+/// together without a separator ("...Please try5.1.1 double-checking..."); those are
+/// split apart the same way.
+pub(crate) fn reply_text(reply: &str) -> String {
+    let mut text = reply.split(['\r', '\n']).collect::<Vec<_>>().join(" ");
+    if let Some(status) = EnhancedStatus::find(reply) {
+        let code = format!("{} ", status);
+        if let Some(first) = text.find(&code) {
+            let rest = text[first + code.len()..].replace(&code, " ");
+            text.truncate(first + code.len());
+            text.push_str(&rest);
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Wording of rejections made on policy grounds (relaying, blocklists, authentication),
+/// which say nothing about whether the mailbox exists.
+const POLICY_PHRASES: &[&str] = &[
+    "relay",
+    "not permitted",
+    "not authorized",
+    "blocked",
+    "blacklist",
+    "blocklist",
+    "spamhaus",
+    "reputation",
+    "policy",
+    "client host",
+    "spf",
+];
+
+/// Wording of replies that refuse to go on without an authenticated session.
+const AUTH_REQUIRED_PHRASES: &[&str] = &[
+    "authentication required",
+    "authentication is required",
+    "auth required",
+    "must authenticate",
+    "authenticate first",
+    "not authenticated",
+];
+
+/// Reason recorded when a server only accepts RCPT TO from authenticated sessions.
+pub(crate) const AUTH_REQUIRED_REASON: &str = "auth required, cannot probe";
+
+/// Wording of rejections because the mailbox does not exist.
+const USER_UNKNOWN_PHRASES: &[&str] = &[
+    "user unknown",
+    "unknown user",
+    "no such user",
+    "nosuchuser",
+    "does not exist",
+    "doesn't exist",
+    "recipient not found",
+    "invalid mailbox",
+    "invalid recipient",
+    "mailbox unavailable",
+    "no mailbox",
+];
+
+/// Why a server refused a recipient.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RcptRejection {
+    /// The mailbox does not exist (or is disabled).
+    UserUnknown,
+    /// Refused on policy grounds (relaying denied, blocklisted sender, ...); the address
+    /// may or may not exist.
+    Policy,
+    /// The server wants an authenticated session (530 5.7.0), which a probe can't have.
+    AuthRequired,
+    /// The mailbox exists but is over quota (x.2.2).
+    MailboxFull,
+    /// A mail system, routing, protocol or content problem (x.3.y, x.4.y, x.5.y, x.6.y)
+    /// that says nothing about the mailbox.
+    System,
+    /// Neither could be told from the reply.
+    Unclassified,
+}
+
+/// Classifies a rejection from the enhanced status code in `reply` (see
+/// [`EnhancedStatus::find`]), falling back to its wording when there is none. Whether the
+/// rejection is temporary is up to the caller; the class of the code is not considered here.
+///
+/// `x.1.1`, `x.1.3`, `x.1.6`, `x.1.10` and `x.2.1` are mailbox problems. `x.2.2` is a full
+/// mailbox, which exists. `x.1.7` and
+/// `x.1.8` refuse the sender, and any `x.7.y` is a security or policy refusal, except
+/// `530 5.7.0` and `5.7.57`, which ask for authentication (but not for STARTTLS). Other
+/// `x.3.y` to `x.6.y` codes are problems of the receiving system or the message.
+pub(crate) fn classify_rcpt_rejection(reply: &str) -> RcptRejection {
+    let enhanced = EnhancedStatus::find(reply);
+    let lower = reply.to_lowercase();
+    if !lower.contains("starttls") {
+        let enhanced_code = enhanced.map(|status| (status.subject, status.detail));
+        if AUTH_REQUIRED_PHRASES
+            .iter()
+            .any(|phrase| lower.contains(phrase))
+            || (lower.contains("530") && enhanced_code == Some((7, 0)))
+            || enhanced_code == Some((7, 57))
+        {
+            return RcptRejection::AuthRequired;
+        }
+    }
+    if let Some(status) = enhanced {
+        match (status.subject, status.detail) {
+            (1, 1) | (1, 3) | (1, 6) | (1, 10) | (2, 1) => return RcptRejection::UserUnknown,
+            (2, 2) => return RcptRejection::MailboxFull,
+            (1, 7) | (1, 8) | (7, _) => return RcptRejection::Policy,
+            (3..=6, _) => return RcptRejection::System,
+            _ => {}
+        }
+    }
+    // Policy wording first, as a refused sender is often told "mailbox unavailable" too;
+    // but Postfix says "User unknown in relay recipient table" for a missing mailbox.
+    if lower.contains("relay recipient table") {
+        RcptRejection::UserUnknown
+    } else if POLICY_PHRASES.iter().any(|phrase| lower.contains(phrase)) {
+        RcptRejection::Policy
+    } else if USER_UNKNOWN_PHRASES
+        .iter()
+        .any(|phrase| lower.contains(phrase))
+    {
+        RcptRejection::UserUnknown
+    } else {
+        RcptRejection::Unclassified
+    }
+}
+
+/// Why a check ended without an answer about the mailbox, when the caller needs to tell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SmtpFailure {
+    /// No usable SMTP session with the host: the connection was refused, reset or timed
+    /// out, the TLS handshake failed, or the host stalled or keeps failing.
+    Connection,
+    /// The server refused our sender or connection on policy grounds (MAIL FROM rejected,
+    /// a policy or otherwise unexplained 5xx) rather than answering for the recipient.
+    SenderRejected,
+}
+
 /// Represents the outcome of an SMTP verification attempt for an email address.
 #[derive(Debug, Clone)]
 pub struct SmtpVerificationResult {
@@ -12,6 +227,17 @@ pub struct SmtpVerificationResult {
     pub should_retry: bool,
     /// Indicates if the domain seems to accept all emails.
     pub is_catch_all: bool,
+    /// How long the server asked us to wait before retrying, if it said so.
+    pub retry_after: Option<Duration>,
+    /// RCPT timing and invalid-address probe signals, if the check got that far.
+    pub signals: Option<SmtpProbeSignals>,
+    /// Set when the check failed for a reason the caller acts on; see [`SmtpFailure`].
+    pub(crate) failure: Option<SmtpFailure>,
+    /// How the reply text itself classified a recipient rejection, when there was one.
+    /// `UserUnknown` here means the server said so, not just that it sent a bare 550.
+    pub(crate) rcpt_rejection: Option<RcptRejection>,
+    /// A non-fatal issue with the connection worth reporting, e.g. a retry over STARTTLS.
+    pub(crate) tls_warning: Option<String>,
 }
 #[allow(dead_code)]
 impl SmtpVerificationResult {
@@ -22,16 +248,27 @@ impl SmtpVerificationResult {
             message,
             should_retry: false,
             is_catch_all,
+            retry_after: None,
+            signals: None,
+            failure: None,
+            rcpt_rejection: None,
+            tls_warning: None,
         }
     }
 
     /// Creates an inconclusive result where retrying might help.
+    /// Picks up a retry delay from greylisting/rate-limit wording in the message, if any.
     pub fn inconclusive_retry(message: String) -> Self {
         Self {
             exists: None,
+            retry_after: parse_retry_hint(&message),
             message,
             should_retry: true,
             is_catch_all: false,
+            signals: None,
+            failure: None,
+            rcpt_rejection: None,
+            tls_warning: None,
         }
     }
 
@@ -42,9 +279,75 @@ impl SmtpVerificationResult {
             message,
             should_retry: false,
             is_catch_all: false,
+            retry_after: None,
+            signals: None,
+            failure: None,
+            rcpt_rejection: None,
+            tls_warning: None,
+        }
+    }
+
+    /// Attaches RCPT probe signals to this result.
+    pub fn with_signals(mut self, signals: SmtpProbeSignals) -> Self {
+        self.signals = Some(signals);
+        self
+    }
+
+    /// Marks why this check failed.
+    pub(crate) fn with_failure(mut self, failure: SmtpFailure) -> Self {
+        self.failure = Some(failure);
+        self
+    }
+
+    /// Attaches a TLS issue to report as a warning.
+    pub(crate) fn with_tls_warning(mut self, warning: String) -> Self {
+        self.tls_warning = Some(warning);
+        self
+    }
+
+    /// Records how the recipient rejection was classified.
+    pub(crate) fn with_rcpt_rejection(mut self, rejection: RcptRejection) -> Self {
+        self.rcpt_rejection = Some(rejection);
+        self
+    }
+
+    /// Whether the server refused the recipient because, in its own words, the mailbox
+    /// does not exist.
+    pub(crate) fn is_stated_user_unknown(&self) -> bool {
+        self.exists == Some(false) && self.rcpt_rejection == Some(RcptRejection::UserUnknown)
+    }
+
+    /// Whether the answer says too little about the mailbox, so a backup MX is worth
+    /// asking: no session, an inconclusive reply, or a host that accepts every address.
+    pub(crate) fn wants_backup_mx(&self) -> bool {
+        self.exists.is_none()
+            || self.is_catch_all
+            || self.message.to_lowercase().contains("catch-all")
+    }
+
+    /// Whether this answer from a backup MX can replace the primary's: an acceptance from
+    /// a host that is not itself a catch-all, or a rejection stating the mailbox is
+    /// unknown. Backups often refuse to relay for addresses they can't check, so relay
+    /// and policy refusals don't count.
+    pub(crate) fn is_definitive_backup_answer(&self) -> bool {
+        match self.exists {
+            Some(true) => !self.is_catch_all && !self.message.to_lowercase().contains("catch-all"),
+            Some(false) => self.is_stated_user_unknown(),
+            None => false,
         }
     }
 
+    /// Whether the check never got a usable session with the host.
+    pub(crate) fn is_connection_failure(&self) -> bool {
+        self.failure == Some(SmtpFailure::Connection)
+    }
+
+    /// Whether the server refused our sender or connection on policy grounds,
+    /// as opposed to answering for the recipient. Used for sender reputation tracking.
+    pub fn is_sender_rejection(&self) -> bool {
+        self.failure == Some(SmtpFailure::SenderRejected)
+    }
+
     /// Creates a result for a catch-all domain.
     pub fn catch_all(message: String) -> Self {
         Self {
@@ -52,6 +355,274 @@ impl SmtpVerificationResult {
             message,
             should_retry: false, // No need to retry catch-all checks
             is_catch_all: true,
+            retry_after: None,
+            signals: None,
+            failure: None,
+            rcpt_rejection: None,
+            tls_warning: None,
+        }
+    }
+
+    /// Returns how long to wait before re-checking, or `None` if a retry is not worthwhile.
+    ///
+    /// Uses the server's hint when one was parsed, otherwise `fallback`
+    /// (normally the configured delay between attempts).
+    pub fn recommended_delay(&self, fallback: Duration) -> Option<Duration> {
+        if !self.should_retry {
+            return None;
         }
+        Some(self.retry_after.unwrap_or(fallback))
+    }
+}
+
+/// Extracts a retry delay from an SMTP response message, e.g. "please try again in 5 minutes".
+pub(crate) fn parse_retry_hint(message: &str) -> Option<Duration> {
+    let caps = RETRY_HINT_REGEX.captures(message)?;
+    let value: u64 = caps.get(1)?.as_str().parse().ok()?;
+    let unit = caps.get(2)?.as_str().to_lowercase();
+    let secs = match unit.chars().next()? {
+        'h' => value.checked_mul(3600)?,
+        'm' => value.checked_mul(60)?,
+        _ => value,
+    };
+    Some(Duration::from_secs(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_retry_hint() {
+        assert_eq!(
+            parse_retry_hint("451 4.7.1 Greylisted, please try again in 5 minutes"),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(
+            parse_retry_hint("450 4.2.0 Recipient greylisted for 60 seconds"),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            parse_retry_hint("421 Too many connections, retry after 30s"),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(parse_retry_hint("451 4.3.0 Temporary failure"), None);
+    }
+
+    #[test]
+    fn test_catch_all_primary_defers_to_strict_backup() {
+        let primary = SmtpVerificationResult::conclusive(
+            true,
+            "SMTP Verification OK: Email likely exists (catch-all domain)".to_string(),
+            true,
+        );
+        assert!(primary.wants_backup_mx());
+
+        let unknown = SmtpVerificationResult::conclusive(
+            false,
+            "550 5.1.1 <jane@acme.com>: Recipient address rejected: User unknown".to_string(),
+            false,
+        )
+        .with_rcpt_rejection(RcptRejection::UserUnknown);
+        assert!(unknown.is_definitive_backup_answer());
+        let accepted = SmtpVerificationResult::conclusive(true, "250 2.1.5 OK".to_string(), false);
+        assert!(accepted.is_definitive_backup_answer());
+
+        let relay_refused = SmtpVerificationResult::conclusive(
+            false,
+            "550 5.7.1 Relaying denied".to_string(),
+            false,
+        )
+        .with_rcpt_rejection(RcptRejection::Policy);
+        assert!(!relay_refused.is_definitive_backup_answer());
+        assert!(!primary.is_definitive_backup_answer());
+
+        let strict_primary = SmtpVerificationResult::conclusive(true, "250 OK".to_string(), false);
+        assert!(!strict_primary.wants_backup_mx());
+    }
+
+    #[test]
+    fn test_classify_rcpt_rejection() {
+        assert_eq!(
+            classify_rcpt_rejection(
+                "550 5.1.1 <bob@acme.com>: Recipient address rejected: User unknown"
+            ),
+            RcptRejection::UserUnknown
+        );
+        assert_eq!(
+            classify_rcpt_rejection("550 5.7.1 Relaying denied"),
+            RcptRejection::Policy
+        );
+        assert_eq!(
+            classify_rcpt_rejection("554 5.7.606 Access denied, banned sending IP [203.0.113.5]"),
+            RcptRejection::Policy
+        );
+        assert_eq!(
+            classify_rcpt_rejection("550 Relay access denied"),
+            RcptRejection::Policy
+        );
+        assert_eq!(
+            classify_rcpt_rejection("550 No such user here"),
+            RcptRejection::UserUnknown
+        );
+        assert_eq!(
+            classify_rcpt_rejection(
+                "550 <jane@acme.com>: Recipient address rejected: User unknown in relay recipient table"
+            ),
+            RcptRejection::UserUnknown
+        );
+        assert_eq!(
+            classify_rcpt_rejection(
+                "550 Requested action not taken: mailbox unavailable (blocked by policy)"
+            ),
+            RcptRejection::Policy
+        );
+        assert_eq!(
+            classify_rcpt_rejection("550 Requested action not taken: mailbox unavailable"),
+            RcptRejection::UserUnknown
+        );
+        assert_eq!(
+            classify_rcpt_rejection("554 Transaction failed"),
+            RcptRejection::Unclassified
+        );
+        assert_eq!(
+            classify_rcpt_rejection("530 5.7.0 Authentication required"),
+            RcptRejection::AuthRequired
+        );
+        assert_eq!(
+            classify_rcpt_rejection("530 5.7.57 Client not authenticated to send mail"),
+            RcptRejection::AuthRequired
+        );
+        assert_eq!(
+            classify_rcpt_rejection("530 5.7.0 Must issue a STARTTLS command first"),
+            RcptRejection::Policy
+        );
+    }
+
+    #[test]
+    fn test_classify_rcpt_rejection_enhanced_codes() {
+        assert_eq!(
+            classify_rcpt_rejection(
+                "552 5.2.2 The email account that you tried to reach is over quota"
+            ),
+            RcptRejection::MailboxFull
+        );
+        assert_eq!(
+            classify_rcpt_rejection("452 4.2.2 Mailbox full"),
+            RcptRejection::MailboxFull
+        );
+        assert_eq!(
+            classify_rcpt_rejection("550 5.4.4 Unable to route: no mail hosts for domain"),
+            RcptRejection::System
+        );
+        // x.4.1 is "no answer from host", not a verdict on the mailbox.
+        assert_eq!(
+            classify_rcpt_rejection(
+                "550 5.4.1 Recipient address rejected: Access denied. AS(201806281)"
+            ),
+            RcptRejection::System
+        );
+        assert_eq!(
+            classify_rcpt_rejection("553 5.1.7 Sender address rejected: invalid domain"),
+            RcptRejection::Policy
+        );
+        // The code quoted in the text does not override the one the reply starts with.
+        assert_eq!(
+            classify_rcpt_rejection("550 5.2.2 Mailbox full (not 5.1.1 user unknown)"),
+            RcptRejection::MailboxFull
+        );
+    }
+
+    #[test]
+    fn test_enhanced_status_find() {
+        let status = EnhancedStatus::find("permanent error (550): 5.1.1 User unknown").unwrap();
+        assert_eq!((status.class, status.subject, status.detail), (5, 1, 1));
+        assert_eq!(status.to_string(), "5.1.1");
+        assert!(!status.is_transient());
+
+        let status = EnhancedStatus::find("Mailbox 5.1.1 moved\n450-4.2.0 Greylisted").unwrap();
+        assert_eq!(status.to_string(), "4.2.0");
+        assert!(status.is_transient());
+
+        assert_eq!(EnhancedStatus::find("550 Requested action not taken"), None);
+    }
+
+    #[test]
+    fn test_reply_text() {
+        assert_eq!(
+            reply_text(
+                "permanent error (550): 5.1.1 The email account that you tried to reach does \
+                 not exist. Please try5.1.1 double-checking the recipient's email address"
+            ),
+            "permanent error (550): 5.1.1 The email account that you tried to reach does not \
+             exist. Please try double-checking the recipient's email address"
+        );
+        assert_eq!(
+            reply_text("5.2.2 Mailbox full\r\n5.2.2 Try again later"),
+            "5.2.2 Mailbox full Try again later"
+        );
+        assert_eq!(reply_text("250 OK"), "250 OK");
+    }
+
+    #[test]
+    fn test_recommended_delay() {
+        let fallback = Duration::from_secs(2);
+        let hinted = SmtpVerificationResult::inconclusive_retry(
+            "451 Greylisted, try again in 90 seconds".to_string(),
+        );
+        assert_eq!(
+            hinted.recommended_delay(fallback),
+            Some(Duration::from_secs(90))
+        );
+
+        let unhinted = SmtpVerificationResult::inconclusive_retry("451 Try later".to_string());
+        assert_eq!(unhinted.recommended_delay(fallback), Some(fallback));
+
+        let final_result = SmtpVerificationResult::inconclusive_no_retry("554 Denied".to_string());
+        assert_eq!(final_result.recommended_delay(fallback), None);
+    }
+
+    #[test]
+    fn test_is_sender_rejection() {
+        let rejected =
+            SmtpVerificationResult::inconclusive_no_retry("550 5.7.1 Client host blocked".into())
+                .with_failure(SmtpFailure::SenderRejected);
+        assert!(rejected.is_sender_rejection());
+
+        // The wording alone doesn't count; only the kind set where the reply was classified.
+        let untagged =
+            SmtpVerificationResult::inconclusive_no_retry("MAIL FROM rejected: 550 denied".into());
+        assert!(!untagged.is_sender_rejection());
+    }
+
+    #[test]
+    fn test_is_stated_user_unknown() {
+        let stated =
+            SmtpVerificationResult::conclusive(false, "550 5.1.1 No such user".into(), false)
+                .with_rcpt_rejection(RcptRejection::UserUnknown);
+        assert!(stated.is_stated_user_unknown());
+
+        // A bare 550 is read as "unknown user" from the primary, but the server never said so.
+        let inferred = SmtpVerificationResult::conclusive(false, "550 Rejected".into(), false)
+            .with_rcpt_rejection(RcptRejection::Unclassified);
+        assert!(!inferred.is_stated_user_unknown());
+
+        let refused = SmtpVerificationResult::inconclusive_no_retry("Connection refused".into())
+            .with_failure(SmtpFailure::Connection);
+        assert!(refused.is_connection_failure());
+        assert!(!refused.is_sender_rejection());
+    }
+
+    #[test]
+    fn test_probe_signals_latency_delta() {
+        let result = SmtpVerificationResult::catch_all("250 OK".to_string())
+            .with_signals(SmtpProbeSignals::new(420, Some((true, 35))));
+        let signals = result.signals.unwrap();
+        assert_eq!(signals.invalid_probe_accepted, Some(true));
+        assert_eq!(signals.latency_delta_ms, Some(385));
+
+        let unprobed = SmtpProbeSignals::new(120, None);
+        assert_eq!(unprobed.invalid_probe_latency_ms, None);
+        assert_eq!(unprobed.latency_delta_ms, None);
     }
 }
@@ -0,0 +1,277 @@
+//! Pre-flight check of the sender setup used for SMTP probes.
+//!
+//! Receiving servers score probes by the reputation of the connecting IP and the
+//! `MAIL FROM` domain. A source IP without forward-confirmed reverse DNS, or a sender
+//! domain without SPF, gets a run blocklisted within a few hundred probes. Checking both
+//! before the first probe lets a misconfigured run stop with instructions instead.
+
+use crate::core::config::Config;
+use crate::core::error::{AppError, Result};
+use crate::utils::dns::create_resolver;
+
+use lettre::transport::smtp::client::SmtpConnection;
+use lettre::transport::smtp::commands::Ehlo;
+use lettre::transport::smtp::extension::ClientId;
+use std::net::{IpAddr, ToSocketAddrs};
+use std::time::Duration;
+use trust_dns_resolver::TokioAsyncResolver;
+
+/// Server whose EHLO reply reports the connecting IP (`... at your service, [203.0.113.5]`).
+const IP_ECHO_SERVER: &str = "gmail-smtp-in.l.google.com";
+
+/// What the sender check found.
+#[derive(Debug, Clone, Default)]
+pub struct SenderCheckReport {
+    /// Domain used in `MAIL FROM`.
+    pub sender_domain: String,
+    /// The public IP probes are sent from, as seen by a remote mail server.
+    pub public_ip: Option<IpAddr>,
+    /// Reverse DNS name of `public_ip` that resolves back to it.
+    pub ptr_name: Option<String>,
+    /// The sender domain's SPF record.
+    pub spf_record: Option<String>,
+    /// Problems found, each with what to do about it. Empty if the setup looks sound.
+    pub problems: Vec<String>,
+}
+
+impl SenderCheckReport {
+    /// True if no problems were found.
+    pub fn passed(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// Checks that the sender domain publishes SPF and the outbound IP has forward-confirmed
+/// reverse DNS.
+///
+/// The SPF record is only checked for presence and for authorizing at least one sender;
+/// `include:` and `redirect=` chains are not evaluated.
+pub(crate) async fn check_sender_setup(config: &Config) -> Result<SenderCheckReport> {
+    let sender_domain = match config.smtp_sender_domain {
+        Some(ref domain) => domain.clone(),
+        None => config
+            .smtp_sender_email
+            .rsplit_once('@')
+            .map(|(_, domain)| domain.to_string())
+            .ok_or_else(|| {
+                AppError::Config(format!(
+                    "Invalid sender email in config: {}",
+                    config.smtp_sender_email
+                ))
+            })?,
+    }
+    .trim_end_matches('.')
+    .to_lowercase();
+
+    let resolver = create_resolver(config).await?;
+    let mut report = SenderCheckReport {
+        sender_domain: sender_domain.clone(),
+        ..Default::default()
+    };
+
+    match lookup_spf(&resolver, &sender_domain).await {
+        Ok(records) if records.len() > 1 => report.problems.push(format!(
+            "{} publishes {} SPF records; receivers treat that as a permanent error. Merge them into one TXT record.",
+            sender_domain,
+            records.len()
+        )),
+        Ok(records) => match records.into_iter().next() {
+            Some(record) => {
+                if !spf_authorizes_anything(&record) {
+                    report.problems.push(format!(
+                        "The SPF record of {} (\"{}\") authorizes no senders. Add the probing IP, e.g. \"v=spf1 ip4:<your IP> -all\".",
+                        sender_domain, record
+                    ));
+                }
+                report.spf_record = Some(record);
+            }
+            None => report.problems.push(format!(
+                "{} has no SPF record. Publish a TXT record such as \"v=spf1 ip4:<your IP> -all\", or set smtp_sender_domain to a domain you control that has one.",
+                sender_domain
+            )),
+        },
+        Err(e) => report.problems.push(format!(
+            "Could not look up the SPF record of {}: {}",
+            sender_domain, e
+        )),
+    }
+
+    match discover_public_ip(config.smtp_timeout).await {
+        Ok(ip) => {
+            report.public_ip = Some(ip);
+            match confirmed_ptr(&resolver, ip).await {
+                Some(name) => report.ptr_name = Some(name),
+                None => report.problems.push(format!(
+                    "{} has no reverse DNS (PTR) record that resolves back to it. Ask your hosting provider to set one to a hostname under your control, or probe from a host that has one.",
+                    ip
+                )),
+            }
+        }
+        Err(e) => report.problems.push(format!(
+            "Could not determine the public IP probes are sent from, so its reverse DNS was not checked: {}",
+            e
+        )),
+    }
+
+    for problem in &report.problems {
+        tracing::warn!("Sender check: {}", problem);
+    }
+    Ok(report)
+}
+
+/// Returns the `v=spf1` TXT records of `domain`.
+async fn lookup_spf(resolver: &TokioAsyncResolver, domain: &str) -> Result<Vec<String>> {
+    let lookup = match resolver.txt_lookup(domain).await {
+        Ok(lookup) => lookup,
+        Err(e)
+            if matches!(
+                e.kind(),
+                trust_dns_resolver::error::ResolveErrorKind::NoRecordsFound { .. }
+            ) =>
+        {
+            return Ok(Vec::new())
+        }
+        Err(e) => return Err(e.into()),
+    };
+    Ok(lookup
+        .iter()
+        .map(|txt| {
+            txt.txt_data()
+                .iter()
+                .map(|part| String::from_utf8_lossy(part).into_owned())
+                .collect::<String>()
+        })
+        .filter(|record| is_spf_record(record))
+        .collect())
+}
+
+fn is_spf_record(record: &str) -> bool {
+    let record = record.trim().to_lowercase();
+    record == "v=spf1" || record.starts_with("v=spf1 ")
+}
+
+/// True if the SPF record has any term that can pass a sender, i.e. it is more than
+/// a bare `-all`/`~all`.
+fn spf_authorizes_anything(record: &str) -> bool {
+    record
+        .split_whitespace()
+        .skip(1)
+        .map(|term| term.to_lowercase())
+        .any(|term| {
+            if term.starts_with("redirect=") {
+                return true;
+            }
+            if term.starts_with(['-', '~']) {
+                return false;
+            }
+            let mechanism = term
+                .trim_start_matches(['+', '?'])
+                .split([':', '/'])
+                .next()
+                .unwrap_or("");
+            matches!(
+                mechanism,
+                "ip4" | "ip6" | "a" | "mx" | "include" | "exists" | "ptr" | "all"
+            )
+        })
+}
+
+/// Asks a remote mail server which IP the connection came from.
+///
+/// The SMTP session is blocking, so it runs on the blocking pool; `timeout` bounds the
+/// connection and each command.
+async fn discover_public_ip(timeout: Duration) -> Result<IpAddr> {
+    tokio::task::spawn_blocking(move || -> Result<Option<IpAddr>> {
+        let socket_addr = (IP_ECHO_SERVER, 25u16)
+            .to_socket_addrs()
+            .map_err(|e| {
+                AppError::Config(format!(
+                    "DNS resolution failed for {}: {}",
+                    IP_ECHO_SERVER, e
+                ))
+            })?
+            .next()
+            .ok_or_else(|| {
+                AppError::Config(format!(
+                    "Could not resolve any IP address for {}",
+                    IP_ECHO_SERVER
+                ))
+            })?;
+        let helo_name = ClientId::Domain("localhost".to_string());
+        let mut conn = SmtpConnection::connect(socket_addr, Some(timeout), &helo_name, None, None)?;
+        let response = conn.command(Ehlo::new(helo_name.clone()));
+        conn.quit().ok();
+        Ok(response?.message().find_map(bracketed_ip))
+    })
+    .await
+    .map_err(|e| AppError::Task(format!("Public IP probe did not complete: {}", e)))??
+    .ok_or_else(|| {
+        AppError::SmtpInconclusive(format!(
+            "{} did not report the connecting IP",
+            IP_ECHO_SERVER
+        ))
+    })
+}
+
+/// Extracts the IP from a greeting like `mx.google.com at your service, [203.0.113.5]`.
+fn bracketed_ip(line: &str) -> Option<IpAddr> {
+    let start = line.rfind('[')?;
+    let end = start + line[start..].find(']')?;
+    let inner = &line[start + 1..end];
+    inner.strip_prefix("IPv6:").unwrap_or(inner).parse().ok()
+}
+
+/// Returns a PTR name of `ip` whose A/AAAA records include `ip` (forward-confirmed rDNS).
+async fn confirmed_ptr(resolver: &TokioAsyncResolver, ip: IpAddr) -> Option<String> {
+    let names: Vec<String> = match resolver.reverse_lookup(ip).await {
+        Ok(lookup) => lookup
+            .iter()
+            .map(|name| name.to_utf8().trim_end_matches('.').to_string())
+            .collect(),
+        Err(e) => {
+            tracing::debug!("Reverse lookup of {} failed: {}", ip, e);
+            return None;
+        }
+    };
+    for name in names {
+        match resolver.lookup_ip(name.as_str()).await {
+            Ok(addresses) if addresses.iter().any(|addr| addr == ip) => return Some(name),
+            Ok(_) => tracing::debug!("PTR name {} of {} does not resolve back to it", name, ip),
+            Err(e) => tracing::debug!("Forward lookup of PTR name {} failed: {}", name, e),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bracketed_ip() {
+        assert_eq!(
+            bracketed_ip("mx.google.com at your service, [203.0.113.5]"),
+            Some("203.0.113.5".parse().unwrap())
+        );
+        assert_eq!(
+            bracketed_ip("mx.example at your service, [IPv6:2001:db8::1]"),
+            Some("2001:db8::1".parse().unwrap())
+        );
+        assert_eq!(bracketed_ip("mx.example Hello"), None);
+    }
+
+    #[test]
+    fn test_spf_records() {
+        assert!(is_spf_record("v=spf1 include:_spf.google.com ~all"));
+        assert!(!is_spf_record("v=spf10 a"));
+        assert!(!is_spf_record("google-site-verification=abc"));
+
+        assert!(spf_authorizes_anything("v=spf1 ip4:203.0.113.5 -all"));
+        assert!(spf_authorizes_anything(
+            "v=spf1 include:_spf.google.com ~all"
+        ));
+        assert!(spf_authorizes_anything("v=spf1 redirect=_spf.example.com"));
+        assert!(!spf_authorizes_anything("v=spf1 -all"));
+        assert!(!spf_authorizes_anything("v=spf1 ~all"));
+    }
+}
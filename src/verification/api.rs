@@ -2,10 +2,11 @@
 
 use crate::core::config::Config;
 use crate::core::error::{AppError, Result};
-use crate::core::models::FoundEmailData;
+use crate::core::models::{FoundEmailData, ResolutionStage};
+use crate::utils::http::send_with_rate_limit_retries;
+use chrono::Utc;
 
 use reqwest::Client;
-use tracing;
 use url::Url;
 
 /// Generates the potential OneDrive for Business URL for a Microsoft 365 account.
@@ -66,7 +67,8 @@ fn generate_onedrive_url(email_address: &str) -> Result<Url> {
 /// Sends a HEAD request to the predicted OneDrive URL. Specific HTTP status codes
 /// (like 403 Forbidden) can indicate user existence, while others (like 404 Not Found)
 /// often indicate non-existence. The interpretation is based on observed behavior and
-/// might change based on Microsoft's implementation.
+/// might change based on Microsoft's implementation. A `429 Too Many Requests` answer is
+/// retried after its `Retry-After` delay, up to `rate_limit_max_retries` times.
 ///
 /// This is an EXPERIMENTAL technique.
 ///
@@ -97,11 +99,12 @@ pub(crate) async fn check_m365_api(
 
     tracing::debug!(target: "verification_api", "{} Sending HEAD request to {}", task_label, url);
 
-    match http_client
-        .head(url.clone())
-        .timeout(config.request_timeout)
-        .send()
-        .await
+    match send_with_rate_limit_retries(config, || {
+        http_client
+            .head(url.clone())
+            .timeout(config.request_timeout)
+    })
+    .await
     {
         Ok(response) => {
             let status = response.status();
@@ -117,6 +120,14 @@ pub(crate) async fn check_m365_api(
                         is_generic: false,
                         verification_status: Some(true),
                         verification_message: "Verified via M365 API (403 Forbidden)".to_string(),
+                        stage: ResolutionStage::Api,
+                        smtp_signals: None,
+                        verified_at: Some(Utc::now()),
+                        mx_host: None,
+                        likely_distribution_list: false,
+                        corroborated_by: Vec::new(),
+                        off_domain: false,
+                        normalized_from: None,
                     }))
                 }
                 reqwest::StatusCode::NOT_FOUND => {
@@ -129,6 +140,14 @@ pub(crate) async fn check_m365_api(
                         verification_status: Some(false),
                         verification_message: "Non-existent per M365 API (404 Not Found)"
                             .to_string(),
+                        stage: ResolutionStage::Api,
+                        smtp_signals: None,
+                        verified_at: Some(Utc::now()),
+                        mx_host: None,
+                        likely_distribution_list: false,
+                        corroborated_by: Vec::new(),
+                        off_domain: false,
+                        normalized_from: None,
                     }))
                 }
                 reqwest::StatusCode::FOUND => {
@@ -7,7 +7,6 @@ use crate::core::models::FoundEmailData;
 use fantoccini::{Client, ClientBuilder};
 use serde_json::map::Map as JsonMap;
 use std::sync::Arc;
-use tracing;
 
 #[derive(Clone)]
 #[allow(dead_code)]
@@ -34,22 +33,10 @@ impl HeadlessVerifier {
         let mut caps = JsonMap::new();
         let mut chrome_opts = JsonMap::new();
 
-        let args = vec![
-            "--headless=new",
-            "--no-sandbox",
-            "--disable-gpu",
-            "--disable-dev-shm-usage",
-            "--window-size=1024,768",
-            "--disable-extensions",
-            "--disable-background-networking",
-            "--disable-sync",
-            "--disable-translate",
-            "--mute-audio",
-            "--safebrowsing-disable-auto-update",
-            "--ignore-certificate-errors",
-            "--log-level=1",
-        ];
-        chrome_opts.insert("args".to_string(), serde_json::json!(args));
+        chrome_opts.insert(
+            "args".to_string(),
+            serde_json::json!(self.config.headless_browser_args),
+        );
 
         caps.insert("browserName".to_string(), serde_json::json!("chrome"));
         caps.insert(
@@ -105,11 +92,21 @@ impl HeadlessVerifier {
         match domain.to_lowercase().as_str() {
             "yahoo.com" | "ymail.com" => {
                 tracing::info!(target: "verification_headless", "Detected Yahoo email, using Yahoo verification flow");
-                super::providers::yahoo::check_yahoo_headless(email, webdriver_url).await
+                super::providers::yahoo::check_yahoo_headless(
+                    email,
+                    webdriver_url,
+                    &self.config.headless_browser_args,
+                )
+                .await
             }
             "hotmail.com" | "outlook.com" | "live.com" | "msn.com" => {
                 tracing::info!(target: "verification_headless", "Detected Microsoft email, using Outlook verification flow");
-                super::providers::microsoft::check_hotmail_headless(email, webdriver_url).await
+                super::providers::microsoft::check_hotmail_headless(
+                    email,
+                    webdriver_url,
+                    &self.config.headless_browser_args,
+                )
+                .await
             }
             _ => {
                 tracing::info!(target: "verification_headless", "No specific headless verification method for domain: {}", domain);
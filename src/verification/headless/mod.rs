@@ -1,5 +1,76 @@
+#[cfg(feature = "headless")]
 mod client;
+mod provider;
+#[cfg(feature = "headless")]
 mod providers;
+#[cfg(feature = "headless")]
+mod render;
+#[cfg(feature = "headless")]
+mod self_test;
+#[cfg(feature = "headless")]
+mod session;
+#[cfg(feature = "headless")]
 mod utils;
+pub use provider::HeadlessProvider;
+#[cfg(feature = "headless")]
 pub use providers::microsoft::check_hotmail_headless;
+#[cfg(feature = "headless")]
 pub use providers::yahoo::check_yahoo_headless;
+#[cfg(feature = "headless")]
+pub(crate) use render::render_page;
+#[cfg(feature = "headless")]
+pub use self_test::{
+    run_selector_self_test, SelectorCheck, SelectorCheckStatus, SelfTestAddresses,
+};
+#[cfg(feature = "headless")]
+pub(crate) use session::close_all as close_open_sessions;
+
+#[cfg(not(feature = "headless"))]
+pub use disabled::{check_hotmail_headless, check_yahoo_headless};
+#[cfg(not(feature = "headless"))]
+pub(crate) use disabled::{close_open_sessions, render_page};
+
+/// Stand-ins for builds without the `headless` feature. Config validation turns
+/// `enable_headless_checks` and `headless_scrape_fallback` off in such builds, so these
+/// are not reached in practice.
+#[cfg(not(feature = "headless"))]
+mod disabled {
+    use crate::core::error::{AppError, Result};
+    use crate::core::models::FoundEmailData;
+    use std::time::Duration;
+
+    fn not_built() -> AppError {
+        AppError::Config(
+            "Headless checks are not available: built without the 'headless' feature".to_string(),
+        )
+    }
+
+    pub async fn check_hotmail_headless(
+        _email: &str,
+        _webdriver_url: &str,
+        _browser_args: &[String],
+    ) -> Result<Option<FoundEmailData>> {
+        Err(not_built())
+    }
+
+    pub async fn check_yahoo_headless(
+        _email: &str,
+        _webdriver_url: &str,
+        _browser_args: &[String],
+    ) -> Result<Option<FoundEmailData>> {
+        Err(not_built())
+    }
+
+    pub(crate) async fn render_page(
+        _url: &str,
+        _webdriver_url: &str,
+        _browser_args: &[String],
+        _timeout: Duration,
+    ) -> Result<String> {
+        Err(not_built())
+    }
+
+    pub(crate) async fn close_open_sessions() -> usize {
+        0
+    }
+}
@@ -0,0 +1,29 @@
+//! The providers with a headless verification flow.
+//!
+//! Kept outside the WebDriver code so configuration (e.g. `headless_confidence`) can name
+//! providers in builds without the `headless` feature.
+
+use std::fmt;
+
+/// A provider with a headless verification flow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HeadlessProvider {
+    /// Microsoft consumer and Microsoft 365 accounts (password reset flow).
+    Microsoft,
+    /// Yahoo Mail (password recovery flow).
+    Yahoo,
+}
+
+impl HeadlessProvider {
+    /// Every provider covered by the self-test.
+    pub const ALL: [HeadlessProvider; 2] = [HeadlessProvider::Microsoft, HeadlessProvider::Yahoo];
+}
+
+impl fmt::Display for HeadlessProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeadlessProvider::Microsoft => f.write_str("microsoft"),
+            HeadlessProvider::Yahoo => f.write_str("yahoo"),
+        }
+    }
+}
@@ -1,12 +1,14 @@
 //! Microsoft/Outlook specific email verification implementation.
 
+use crate::core::config::HeadlessConfidence;
 use crate::core::error::Result;
-use crate::core::models::FoundEmailData;
+use crate::core::models::{FoundEmailData, ResolutionStage};
+use crate::verification::headless::session::connect;
 use crate::verification::headless::utils::browser;
-use crate::verification::headless::utils::selectors::MicrosoftSelectors;
-use fantoccini::{Client, ClientBuilder};
+use crate::verification::headless::utils::selectors::{MicrosoftSelectors, MicrosoftWorkSelectors};
+use chrono::Utc;
+use fantoccini::{Client, Locator};
 use std::time::{Duration, Instant};
-use tracing;
 
 /// Checks Hotmail/Outlook/Live.com email existence using the password recovery flow via headless browser.
 ///
@@ -14,19 +16,21 @@ use tracing;
 /// * `config` - Application configuration
 /// * `email` - The email address to verify
 /// * `webdriver_url` - URL of the running WebDriver instance
+/// * `browser_args` - Command-line arguments for the browser
 ///
 /// # Returns
 /// * `Result<Option<FoundEmailData>>` - Verification result or error
 pub async fn check_hotmail_headless(
     email: &str,
     webdriver_url: &str,
+    browser_args: &[String],
 ) -> Result<Option<FoundEmailData>> {
     let task_label = format!("[Hotmail Headless: {}]", email);
     tracing::info!(target: "verification_headless", "{} Starting check via {}", task_label, webdriver_url);
     let start_time = Instant::now();
 
     // Create WebDriver client
-    let client = match create_client(webdriver_url).await {
+    let client = match connect(webdriver_url, browser_args).await {
         Ok(c) => c,
         Err(e) => {
             tracing::error!(target: "verification_headless", "{} Critical failure: Could not create WebDriver client: {}", task_label, e);
@@ -36,9 +40,7 @@ pub async fn check_hotmail_headless(
 
     let result = perform_microsoft_verification(&client, email, &task_label).await;
 
-    if let Err(e) = client.close().await {
-        tracing::warn!(target: "verification_headless", "{} Failed to close WebDriver client cleanly: {}", task_label, e);
-    }
+    client.close(&task_label).await;
 
     let duration = start_time.elapsed();
     match &result {
@@ -56,59 +58,85 @@ pub async fn check_hotmail_headless(
     result
 }
 
-/// Creates a WebDriver client for Microsoft verification.
-async fn create_client(webdriver_url: &str) -> Result<Client> {
-    let mut caps = serde_json::map::Map::new();
-    let mut chrome_opts = serde_json::map::Map::new();
-
-    let args = vec![
-        "--headless=new",
-        "--no-sandbox",
-        "--disable-gpu",
-        "--disable-dev-shm-usage",
-        "--window-size=1024,768",
-        "--disable-extensions",
-        "--disable-background-networking",
-        "--disable-sync",
-        "--disable-translate",
-        "--mute-audio",
-        "--safebrowsing-disable-auto-update",
-        "--ignore-certificate-errors",
-        "--log-level=1",
-    ];
-    chrome_opts.insert("args".to_string(), serde_json::json!(args));
-    caps.insert("browserName".to_string(), serde_json::json!("chrome"));
-    caps.insert(
-        "goog:chromeOptions".to_string(),
-        serde_json::json!(chrome_opts),
-    );
-
-    tracing::debug!(target: "verification_headless", "Connecting to WebDriver at {}...", webdriver_url);
-
-    let mut builder = ClientBuilder::native();
-
-    builder.capabilities(caps);
-
-    match builder.connect(webdriver_url).await {
-        Ok(client) => {
-            tracing::info!(target: "verification_headless", "WebDriver client connected successfully.");
-            Ok(client)
-        }
-        Err(e) => {
-            tracing::error!(target: "verification_headless", "Failed to connect to WebDriver at {}: {}", webdriver_url, e);
-            Err(e.into())
-        }
-    }
+const PAGE_LOAD_TIMEOUT: Duration = Duration::from_secs(25);
+pub(crate) const ELEMENT_WAIT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Host the recovery flow redirects work and school accounts to.
+const WORK_SIGN_IN_HOST: &str = "login.microsoftonline.com";
+
+/// Outcome indicators shown after submitting the recovery form, by name,
+/// with whether each one means the account exists. Child and family accounts get their
+/// own screens, but they are existing accounts too.
+pub(crate) fn outcome_selectors() -> Vec<(&'static str, Locator<'static>, bool)> {
+    vec![
+        // Email exists indicators
+        (
+            "exists_verify_identity",
+            MicrosoftSelectors::exists_verify_identity(),
+            true,
+        ),
+        (
+            "exists_authenticator",
+            MicrosoftSelectors::exists_authenticator(),
+            true,
+        ),
+        (
+            "exists_child_account",
+            MicrosoftSelectors::exists_child_account(),
+            true,
+        ),
+        (
+            "exists_parental_approval",
+            MicrosoftSelectors::exists_parental_approval(),
+            true,
+        ),
+        (
+            "not_exists_error1",
+            MicrosoftSelectors::not_exists_error1(),
+            false,
+        ),
+        (
+            "not_exists_error2",
+            MicrosoftSelectors::not_exists_error2(),
+            false,
+        ),
+    ]
 }
 
-/// Performs the Microsoft/Outlook verification process.
-async fn perform_microsoft_verification(
+/// What the browser showed after the recovery form was submitted.
+#[derive(Clone, Debug)]
+enum RecoveryOutcome {
+    /// A recovery flow indicator, by name, with whether it means the account exists.
+    Consumer(&'static str, bool),
+    /// The Azure AD sign-in page that work and school accounts are redirected to.
+    WorkSignIn,
+}
+
+/// Outcome indicators on the Azure AD sign-in page, by name,
+/// with whether each one means the account exists.
+pub(crate) fn work_account_outcome_selectors() -> Vec<(&'static str, Locator<'static>, bool)> {
+    vec![
+        (
+            "exists_password_prompt",
+            MicrosoftWorkSelectors::exists_password_prompt(),
+            true,
+        ),
+        (
+            "not_exists_username_error",
+            MicrosoftWorkSelectors::not_exists_username_error(),
+            false,
+        ),
+    ]
+}
+
+/// Opens the password reset page and submits `email`.
+pub(crate) async fn submit_recovery_form(
     client: &Client,
     email: &str,
     task_label: &str,
-) -> Result<Option<FoundEmailData>> {
-    let page_load_timeout = Duration::from_secs(25);
-    let element_wait_timeout = Duration::from_secs(15);
+) -> Result<()> {
+    let page_load_timeout = PAGE_LOAD_TIMEOUT;
+    let element_wait_timeout = ELEMENT_WAIT_TIMEOUT;
 
     tracing::debug!(target: "verification_headless", "{} Navigating to Microsoft password reset page...", task_label);
     browser::navigate_to(
@@ -135,7 +163,18 @@ async fn perform_microsoft_verification(
         element_wait_timeout,
         task_label,
     )
-    .await?;
+    .await
+}
+
+/// Performs the Microsoft/Outlook verification process.
+async fn perform_microsoft_verification(
+    client: &Client,
+    email: &str,
+    task_label: &str,
+) -> Result<Option<FoundEmailData>> {
+    let element_wait_timeout = ELEMENT_WAIT_TIMEOUT;
+
+    submit_recovery_form(client, email, task_label).await?;
 
     tracing::debug!(target: "verification_headless", "{} Checking for CAPTCHA...", task_label);
     let captcha_check_result = client
@@ -152,43 +191,48 @@ async fn perform_microsoft_verification(
 
     tracing::debug!(target: "verification_headless", "{} Checking for outcome indicators...", task_label);
 
-    let outcome_checks = vec![
-        // Email exists indicators
-        (MicrosoftSelectors::exists_verify_identity(), true),
-        (MicrosoftSelectors::exists_authenticator(), true),
-        (MicrosoftSelectors::not_exists_error1(), false),
-        (MicrosoftSelectors::not_exists_error2(), false),
-    ];
+    // The redirect to the work sign-in page can come well after the form is submitted,
+    // so its username field is waited for alongside the recovery flow's indicators.
+    let outcome_checks = outcome_selectors()
+        .into_iter()
+        .map(|(name, locator, exists)| (locator, RecoveryOutcome::Consumer(name, exists)))
+        .chain(std::iter::once((
+            MicrosoftWorkSelectors::email_input(),
+            RecoveryOutcome::WorkSignIn,
+        )))
+        .collect();
 
     // Check outcomes
     match browser::check_outcomes(client, outcome_checks, element_wait_timeout, task_label).await? {
-        Some(exists) => {
-            if exists {
+        Some(RecoveryOutcome::WorkSignIn) => {
+            tracing::debug!(target: "verification_headless", "{} Redirected to {} (work or school account)", task_label, WORK_SIGN_IN_HOST);
+            perform_work_account_verification(client, email, task_label).await
+        }
+        Some(RecoveryOutcome::Consumer(name, exists)) => {
+            if matches!(name, "exists_child_account" | "exists_parental_approval") {
+                tracing::info!(target: "verification_headless",
+                    "{} Determined account LIKELY EXISTS (child/family account screen found).", task_label);
+                Ok(Some(verdict(
+                    email,
+                    true,
+                    "Verified via Microsoft password recovery flow (child/family account screen shown)",
+                )))
+            } else if exists {
                 tracing::info!(target: "verification_headless", 
                     "{} Determined account LIKELY EXISTS (Verification options/code entry found).", task_label);
-                Ok(Some(FoundEmailData {
-                    email: email.to_string(),
-                    confidence: 7,
-                    source: "headless_hotmail".to_string(),
-                    is_generic: false,
-                    verification_status: Some(true),
-                    verification_message:
-                        "Verified via Microsoft password recovery flow (options/code shown)"
-                            .to_string(),
-                }))
+                Ok(Some(verdict(
+                    email,
+                    true,
+                    "Verified via Microsoft password recovery flow (options/code shown)",
+                )))
             } else {
                 tracing::info!(target: "verification_headless", 
                     "{} Determined account LIKELY DOES NOT EXIST (Error message found).", task_label);
-                Ok(Some(FoundEmailData {
-                    email: email.to_string(),
-                    confidence: 0,
-                    source: "headless_hotmail".to_string(),
-                    is_generic: false,
-                    verification_status: Some(false),
-                    verification_message:
-                        "Non-existent per Microsoft password recovery flow (error shown)"
-                            .to_string(),
-                }))
+                Ok(Some(verdict(
+                    email,
+                    false,
+                    "Non-existent per Microsoft password recovery flow (error shown)",
+                )))
             }
         }
         None => {
@@ -198,3 +242,87 @@ async fn perform_microsoft_verification(
         }
     }
 }
+
+/// Continues on the Azure AD sign-in page: submits `email` unless the page
+/// already has it, then tells a password prompt from a username error.
+async fn perform_work_account_verification(
+    client: &Client,
+    email: &str,
+    task_label: &str,
+) -> Result<Option<FoundEmailData>> {
+    let element_wait_timeout = ELEMENT_WAIT_TIMEOUT;
+
+    let input = client
+        .wait()
+        .at_most(element_wait_timeout)
+        .for_element(MicrosoftWorkSelectors::email_input())
+        .await?;
+    let prefilled = matches!(input.prop("value").await?, Some(value) if !value.is_empty());
+    if !prefilled {
+        input.send_keys(email).await?;
+        browser::wait_and_click(
+            client,
+            MicrosoftWorkSelectors::next_button(),
+            element_wait_timeout,
+            task_label,
+        )
+        .await?;
+    }
+
+    tracing::debug!(target: "verification_headless", "{} Checking for work account outcome indicators...", task_label);
+
+    let outcome_checks = work_account_outcome_selectors()
+        .into_iter()
+        .map(|(_, locator, exists)| (locator, exists))
+        .collect();
+
+    match browser::check_outcomes(client, outcome_checks, element_wait_timeout, task_label).await? {
+        Some(true) => {
+            tracing::info!(target: "verification_headless",
+                "{} Determined work account LIKELY EXISTS (password prompt found).", task_label);
+            Ok(Some(verdict(
+                email,
+                true,
+                "Verified via Microsoft work/school sign-in (password prompt shown)",
+            )))
+        }
+        Some(false) => {
+            tracing::info!(target: "verification_headless",
+                "{} Determined work account LIKELY DOES NOT EXIST (username error found).", task_label);
+            Ok(Some(verdict(
+                email,
+                false,
+                "Non-existent per Microsoft work/school sign-in (username error shown)",
+            )))
+        }
+        None => {
+            tracing::warn!(target: "verification_headless",
+                "{} Could not determine work account outcome (all indicators timed out).", task_label);
+            Ok(None)
+        }
+    }
+}
+
+/// Builds the result for a conclusive check.
+fn verdict(email: &str, exists: bool, message: &str) -> FoundEmailData {
+    FoundEmailData {
+        email: email.to_string(),
+        confidence: if exists {
+            HeadlessConfidence::default().microsoft
+        } else {
+            0
+        },
+        source: "headless_hotmail".to_string(),
+        is_generic: false,
+        verification_status: Some(exists),
+        verification_message: message.to_string(),
+        stage: ResolutionStage::Headless,
+        smtp_signals: None,
+        verified_at: Some(Utc::now()),
+        mx_host: None,
+        likely_distribution_list: false,
+        corroborated_by: Vec::new(),
+        off_domain: false,
+        normalized_from: None,
+    }
+}
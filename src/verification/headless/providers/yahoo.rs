@@ -1,13 +1,15 @@
 //! Yahoo Mail specific email verification implementation.
 
+use crate::core::config::HeadlessConfidence;
 use crate::core::error::Result;
-use crate::core::models::FoundEmailData;
+use crate::core::models::{FoundEmailData, ResolutionStage};
+use crate::verification::headless::session::connect;
 use crate::verification::headless::utils::browser;
 use crate::verification::headless::utils::selectors::YahooSelectors;
+use chrono::Utc;
 
-use fantoccini::Client;
+use fantoccini::{Client, Locator};
 use std::time::{Duration, Instant};
-use tracing;
 
 /// Checks Yahoo email existence using the password recovery flow via headless browser.
 ///
@@ -15,18 +17,20 @@ use tracing;
 /// * `config` - Application configuration
 /// * `email` - The email address to verify
 /// * `webdriver_url` - URL of the running WebDriver instance
+/// * `browser_args` - Command-line arguments for the browser
 ///
 /// # Returns
 /// * `Result<Option<FoundEmailData>>` - Verification result or error
 pub async fn check_yahoo_headless(
     email: &str,
     webdriver_url: &str,
+    browser_args: &[String],
 ) -> Result<Option<FoundEmailData>> {
     let task_label = format!("[Yahoo Headless: {}]", email);
     tracing::info!(target: "verification_headless", "{} Starting check via {}", task_label, webdriver_url);
     let start_time = Instant::now();
 
-    let client = match create_client(webdriver_url).await {
+    let client = match connect(webdriver_url, browser_args).await {
         Ok(c) => c,
         Err(e) => {
             tracing::error!(target: "verification_headless", "{} Critical failure: Could not create WebDriver client: {}", task_label, e);
@@ -36,9 +40,7 @@ pub async fn check_yahoo_headless(
 
     let result = perform_yahoo_verification(&client, email, &task_label).await;
 
-    if let Err(e) = client.close().await {
-        tracing::warn!(target: "verification_headless", "{} Failed to close WebDriver client cleanly: {}", task_label, e);
-    }
+    client.close(&task_label).await;
 
     let duration = start_time.elapsed();
     match &result {
@@ -56,62 +58,55 @@ pub async fn check_yahoo_headless(
     result
 }
 
-/// Creates a WebDriver client for Yahoo verification.
-async fn create_client(webdriver_url: &str) -> Result<Client> {
-    let mut caps = serde_json::map::Map::new();
-    let mut chrome_opts = serde_json::map::Map::new();
-
-    let args = vec![
-        "--headless=new",
-        "--no-sandbox",
-        "--disable-gpu",
-        "--disable-dev-shm-usage",
-        "--window-size=1024,768",
-        "--disable-extensions",
-        "--disable-background-networking",
-        "--disable-sync",
-        "--disable-translate",
-        "--mute-audio",
-        "--safebrowsing-disable-auto-update",
-        "--ignore-certificate-errors",
-        "--log-level=1",
-    ];
-    chrome_opts.insert("args".to_string(), serde_json::json!(args));
-    caps.insert("browserName".to_string(), serde_json::json!("chrome"));
-    caps.insert(
-        "goog:chromeOptions".to_string(),
-        serde_json::json!(chrome_opts),
-    );
-
-    tracing::debug!(target: "verification_headless", "Connecting to WebDriver at {}...", webdriver_url);
-    let mut builder = fantoccini::ClientBuilder::native();
-    let builder = builder.capabilities(caps);
-    match builder.connect(webdriver_url).await {
-        Ok(client) => {
-            tracing::info!(target: "verification_headless", "WebDriver client connected successfully.");
-            Ok(client)
-        }
-        Err(e) => {
-            tracing::error!(target: "verification_headless", "Failed to connect to WebDriver at {}: {}", webdriver_url, e);
-            Err(e.into())
-        }
-    }
+const PAGE_LOAD_TIMEOUT: Duration = Duration::from_secs(20);
+pub(crate) const ELEMENT_WAIT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Outcome indicators shown after submitting the recovery form, by name,
+/// with whether each one means the account exists.
+pub(crate) fn outcome_selectors() -> Vec<(&'static str, Locator<'static>, bool)> {
+    vec![
+        ("exists_recaptcha", YahooSelectors::exists_recaptcha(), true),
+        (
+            "exists_verification_code",
+            YahooSelectors::exists_verification_code(),
+            true,
+        ),
+        (
+            "exists_challenge_selector",
+            YahooSelectors::exists_challenge_selector(),
+            true,
+        ),
+        (
+            "not_exists_error",
+            YahooSelectors::not_exists_error(),
+            false,
+        ),
+        (
+            "account_disabled",
+            YahooSelectors::account_disabled(),
+            false,
+        ),
+    ]
 }
 
-/// Performs the Yahoo verification process.
-async fn perform_yahoo_verification(
+/// Opens the password recovery page and submits `email`.
+///
+/// Consent and region-selection interstitials Yahoo sometimes shows before the
+/// recovery form are dismissed first.
+pub(crate) async fn submit_recovery_form(
     client: &Client,
     email: &str,
     task_label: &str,
-) -> Result<Option<FoundEmailData>> {
-    let page_load_timeout = Duration::from_secs(20);
-    let element_wait_timeout = Duration::from_secs(15);
+) -> Result<()> {
+    let page_load_timeout = PAGE_LOAD_TIMEOUT;
+    let element_wait_timeout = ELEMENT_WAIT_TIMEOUT;
 
     tracing::debug!(target: "verification_headless", "{} Navigating to Yahoo password reset page...", task_label);
-    browser::navigate_to(
+    browser::navigate_past_interstitials(
         client,
         "https://login.yahoo.com/forgot",
         YahooSelectors::email_input(),
+        YahooSelectors::interstitial_dismissals(),
         page_load_timeout,
         task_label,
     )
@@ -132,17 +127,25 @@ async fn perform_yahoo_verification(
         element_wait_timeout,
         task_label,
     )
-    .await?;
+    .await
+}
+
+/// Performs the Yahoo verification process.
+async fn perform_yahoo_verification(
+    client: &Client,
+    email: &str,
+    task_label: &str,
+) -> Result<Option<FoundEmailData>> {
+    let element_wait_timeout = ELEMENT_WAIT_TIMEOUT;
+
+    submit_recovery_form(client, email, task_label).await?;
 
     tracing::debug!(target: "verification_headless", "{} Checking for outcome indicators...", task_label);
 
-    let outcome_checks = vec![
-        (YahooSelectors::exists_recaptcha(), true),
-        (YahooSelectors::exists_verification_code(), true),
-        (YahooSelectors::exists_challenge_selector(), true),
-        (YahooSelectors::not_exists_error(), false),
-        (YahooSelectors::account_disabled(), false),
-    ];
+    let outcome_checks = outcome_selectors()
+        .into_iter()
+        .map(|(_, locator, exists)| (locator, exists))
+        .collect();
 
     match browser::check_outcomes(client, outcome_checks, element_wait_timeout, task_label).await? {
         Some(exists) => {
@@ -151,13 +154,21 @@ async fn perform_yahoo_verification(
                     "{} Determined account LIKELY EXISTS (Verification/Captcha/Options found).", task_label);
                 Ok(Some(FoundEmailData {
                     email: email.to_string(),
-                    confidence: 8,
+                    confidence: HeadlessConfidence::default().yahoo,
                     source: "headless_yahoo".to_string(),
                     is_generic: false,
                     verification_status: Some(true),
                     verification_message:
                         "Verified via Yahoo password recovery flow (options/code/captcha shown)"
                             .to_string(),
+                    stage: ResolutionStage::Headless,
+                    smtp_signals: None,
+                    verified_at: Some(Utc::now()),
+                    mx_host: None,
+                    likely_distribution_list: false,
+                    corroborated_by: Vec::new(),
+                    off_domain: false,
+                    normalized_from: None,
                 }))
             } else {
                 tracing::info!(target: "verification_headless", 
@@ -170,6 +181,14 @@ async fn perform_yahoo_verification(
                     verification_status: Some(false),
                     verification_message:
                         "Non-existent or disabled per Yahoo password recovery flow".to_string(),
+                    stage: ResolutionStage::Headless,
+                    smtp_signals: None,
+                    verified_at: Some(Utc::now()),
+                    mx_host: None,
+                    likely_distribution_list: false,
+                    corroborated_by: Vec::new(),
+                    off_domain: false,
+                    normalized_from: None,
                 }))
             }
         }
@@ -0,0 +1,39 @@
+//! Renders web pages in the headless browser, for contact pages whose addresses are only
+//! inserted by JavaScript.
+
+use crate::core::error::Result;
+use crate::verification::headless::session::connect;
+
+use fantoccini::Locator;
+use std::time::Duration;
+use tokio::time::sleep;
+
+/// Time given to scripts to fill in the page after `<body>` appears.
+const SCRIPT_SETTLE_TIME: Duration = Duration::from_secs(2);
+
+/// Loads `url` in a fresh browser session and returns the HTML of the rendered DOM.
+pub(crate) async fn render_page(
+    url: &str,
+    webdriver_url: &str,
+    browser_args: &[String],
+    timeout: Duration,
+) -> Result<String> {
+    let task_label = format!("[Headless Render: {}]", url);
+    tracing::debug!(target: "verification_headless", "{} Rendering via {}", task_label, webdriver_url);
+    let client = connect(webdriver_url, browser_args).await?;
+
+    let result: Result<String> = async {
+        client.goto(url).await?;
+        client
+            .wait()
+            .at_most(timeout)
+            .for_element(Locator::Css("body"))
+            .await?;
+        sleep(SCRIPT_SETTLE_TIME).await;
+        Ok(client.source().await?)
+    }
+    .await;
+
+    client.close(&task_label).await;
+    result
+}
@@ -0,0 +1,206 @@
+//! Selector self-test for the headless verification providers.
+//!
+//! Provider pages change without notice, and once a selector stops matching every
+//! check for that provider comes back inconclusive. The self-test walks each
+//! provider's recovery flow with a made-up address and, when one is configured,
+//! an address known to exist, and reports which selectors no longer match.
+
+use crate::core::error::Result;
+use crate::verification::headless::provider::HeadlessProvider;
+use crate::verification::headless::providers::{microsoft, yahoo};
+use crate::verification::headless::session::connect;
+use crate::verification::headless::utils::browser;
+use crate::verification::headless::utils::selectors::MicrosoftSelectors;
+
+use fantoccini::{Client, Locator};
+use rand::Rng;
+
+impl HeadlessProvider {
+    /// A random address at the provider's consumer domain that should not exist.
+    fn nonexistent_address(self) -> String {
+        let domain = match self {
+            HeadlessProvider::Microsoft => "outlook.com",
+            HeadlessProvider::Yahoo => "yahoo.com",
+        };
+        let mut rng = rand::thread_rng();
+        format!(
+            "es-selftest-{:08x}{:08x}@{}",
+            rng.gen::<u32>(),
+            rng.gen::<u32>(),
+            domain
+        )
+    }
+}
+
+/// Addresses known to exist, used to exercise the "exists" branch of each provider's flow.
+#[derive(Debug, Clone, Default)]
+pub struct SelfTestAddresses {
+    pub microsoft: Option<String>,
+    pub yahoo: Option<String>,
+}
+
+impl SelfTestAddresses {
+    fn existing(&self, provider: HeadlessProvider) -> Option<&str> {
+        match provider {
+            HeadlessProvider::Microsoft => self.microsoft.as_deref(),
+            HeadlessProvider::Yahoo => self.yahoo.as_deref(),
+        }
+    }
+}
+
+/// Result of one self-test scenario.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectorCheckStatus {
+    /// The flow completed and an indicator for the expected outcome matched.
+    Ok,
+    /// A selector the flow relies on no longer matched, or the opposite outcome matched.
+    Stale,
+    /// The provider showed a CAPTCHA, so the outcome selectors could not be checked.
+    Blocked,
+    /// No known-existing address was given for the provider.
+    Skipped,
+}
+
+/// Outcome of running one provider's flow with one test address.
+#[derive(Debug, Clone)]
+pub struct SelectorCheck {
+    pub provider: HeadlessProvider,
+    /// Whether the test address was expected to exist.
+    pub expect_exists: bool,
+    /// The address submitted; `None` when the scenario was skipped.
+    pub address: Option<String>,
+    pub status: SelectorCheckStatus,
+    /// What matched, or why the check is stale, blocked or skipped.
+    pub detail: String,
+}
+
+/// Runs the self-test for every provider against the WebDriver at `webdriver_url`.
+///
+/// Returns an error only when no WebDriver session can be created; selector problems
+/// are reported in the returned checks.
+pub async fn run_selector_self_test(
+    webdriver_url: &str,
+    browser_args: &[String],
+    addresses: &SelfTestAddresses,
+) -> Result<Vec<SelectorCheck>> {
+    let mut checks = Vec::new();
+    for provider in HeadlessProvider::ALL {
+        let nonexistent = provider.nonexistent_address();
+        checks
+            .push(run_scenario(provider, webdriver_url, browser_args, &nonexistent, false).await?);
+
+        match addresses.existing(provider) {
+            Some(existing) => {
+                checks.push(
+                    run_scenario(provider, webdriver_url, browser_args, existing, true).await?,
+                );
+            }
+            None => checks.push(SelectorCheck {
+                provider,
+                expect_exists: true,
+                address: None,
+                status: SelectorCheckStatus::Skipped,
+                detail: "no known-existing address configured".to_string(),
+            }),
+        }
+    }
+    Ok(checks)
+}
+
+async fn run_scenario(
+    provider: HeadlessProvider,
+    webdriver_url: &str,
+    browser_args: &[String],
+    address: &str,
+    expect_exists: bool,
+) -> Result<SelectorCheck> {
+    let task_label = format!("[Selector Self-Test {}: {}]", provider, address);
+    let client = connect(webdriver_url, browser_args).await?;
+
+    let (status, detail) = check_flow(&client, provider, address, expect_exists, &task_label).await;
+
+    client.close(&task_label).await;
+    tracing::info!(target: "verification_headless", "{} {:?}: {}", task_label, status, detail);
+
+    Ok(SelectorCheck {
+        provider,
+        expect_exists,
+        address: Some(address.to_string()),
+        status,
+        detail,
+    })
+}
+
+/// Submits `address` through the provider's flow and checks which outcome indicator appears.
+async fn check_flow(
+    client: &Client,
+    provider: HeadlessProvider,
+    address: &str,
+    expect_exists: bool,
+    task_label: &str,
+) -> (SelectorCheckStatus, String) {
+    let (submitted, outcomes, timeout) = match provider {
+        HeadlessProvider::Microsoft => (
+            microsoft::submit_recovery_form(client, address, task_label).await,
+            microsoft::outcome_selectors()
+                .into_iter()
+                .chain(microsoft::work_account_outcome_selectors())
+                .collect(),
+            microsoft::ELEMENT_WAIT_TIMEOUT,
+        ),
+        HeadlessProvider::Yahoo => (
+            yahoo::submit_recovery_form(client, address, task_label).await,
+            yahoo::outcome_selectors(),
+            yahoo::ELEMENT_WAIT_TIMEOUT,
+        ),
+    };
+    if let Err(e) = submitted {
+        return (
+            SelectorCheckStatus::Stale,
+            format!("recovery form could not be filled in: {}", e),
+        );
+    }
+
+    let names: Vec<&str> = outcomes.iter().map(|(name, _, _)| *name).collect();
+    // `None` marks the CAPTCHA indicator: it says nothing about the account.
+    let mut indicators: Vec<(Locator<'static>, (&'static str, Option<bool>))> = outcomes
+        .into_iter()
+        .map(|(name, locator, exists)| (locator, (name, Some(exists))))
+        .collect();
+    if provider == HeadlessProvider::Microsoft {
+        indicators.push((MicrosoftSelectors::captcha(), ("captcha", None)));
+    }
+
+    match browser::check_outcomes(client, indicators, timeout, task_label).await {
+        Ok(Some((name, Some(exists)))) if exists == expect_exists => {
+            (SelectorCheckStatus::Ok, format!("matched '{}'", name))
+        }
+        Ok(Some((name, Some(_)))) => (
+            SelectorCheckStatus::Stale,
+            format!(
+                "matched '{}', which indicates the account {}",
+                name,
+                if expect_exists {
+                    "does not exist"
+                } else {
+                    "exists"
+                }
+            ),
+        ),
+        Ok(Some((_, None))) => (
+            SelectorCheckStatus::Blocked,
+            "CAPTCHA shown; outcome selectors not checked".to_string(),
+        ),
+        Ok(None) => (
+            SelectorCheckStatus::Stale,
+            format!(
+                "none of the outcome selectors matched ({})",
+                names.join(", ")
+            ),
+        ),
+        Err(e) => (
+            SelectorCheckStatus::Stale,
+            format!("outcome check failed: {}", e),
+        ),
+    }
+}
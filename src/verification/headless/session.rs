@@ -0,0 +1,96 @@
+//! Tracks open WebDriver sessions so they can be closed on shutdown.
+//!
+//! Every check closes its own session when it finishes, but a check that is cancelled
+//! (e.g. by Ctrl-C or a timeout dropping its future) never gets there, and its browser
+//! keeps running under ChromeDriver. [`close_all`] closes whatever is still registered.
+//!
+//! New sessions are opened with [`connect`], which starts Chrome with the configured
+//! `headless_browser_args`.
+
+use crate::core::error::Result;
+
+use fantoccini::{Client, ClientBuilder};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static OPEN_SESSIONS: Lazy<parking_lot::Mutex<HashMap<u64, Client>>> =
+    Lazy::new(|| parking_lot::Mutex::new(HashMap::new()));
+static NEXT_SESSION_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Connects to the WebDriver at `webdriver_url` and starts a Chrome session with
+/// `browser_args` on its command line.
+pub(crate) async fn connect(webdriver_url: &str, browser_args: &[String]) -> Result<Session> {
+    let mut caps = serde_json::map::Map::new();
+    caps.insert("browserName".to_string(), serde_json::json!("chrome"));
+    caps.insert(
+        "goog:chromeOptions".to_string(),
+        serde_json::json!({ "args": browser_args }),
+    );
+
+    tracing::debug!(target: "verification_headless", "Connecting to WebDriver at {}...", webdriver_url);
+    tracing::trace!(target: "verification_headless", "WebDriver capabilities: {:?}", caps);
+
+    let mut builder = ClientBuilder::native();
+    builder.capabilities(caps);
+    match builder.connect(webdriver_url).await {
+        Ok(client) => {
+            tracing::info!(target: "verification_headless", "WebDriver client connected successfully.");
+            Ok(Session::register(client))
+        }
+        Err(e) => {
+            tracing::error!(target: "verification_headless", "Failed to connect to WebDriver at {}: {}", webdriver_url, e);
+            Err(e.into())
+        }
+    }
+}
+
+/// A WebDriver client that stays registered until [`Session::close`] is called.
+pub(crate) struct Session {
+    id: u64,
+    client: Client,
+}
+
+impl Session {
+    /// Registers a freshly connected client.
+    pub(crate) fn register(client: Client) -> Self {
+        let id = NEXT_SESSION_ID.fetch_add(1, Ordering::Relaxed);
+        OPEN_SESSIONS.lock().insert(id, client.clone());
+        Self { id, client }
+    }
+
+    /// Closes the browser session, logging any error, and unregisters it.
+    pub(crate) async fn close(self, label: &str) {
+        if OPEN_SESSIONS.lock().remove(&self.id).is_none() {
+            // Already closed by `close_all`.
+            return;
+        }
+        if let Err(e) = self.client.close().await {
+            tracing::warn!(target: "verification_headless", "{} Failed to close WebDriver client cleanly: {}", label, e);
+        }
+    }
+}
+
+impl Deref for Session {
+    type Target = Client;
+
+    fn deref(&self) -> &Client {
+        &self.client
+    }
+}
+
+/// Closes every session that is still open and returns how many there were.
+pub(crate) async fn close_all() -> usize {
+    let sessions: Vec<Client> = OPEN_SESSIONS.lock().drain().map(|(_, c)| c).collect();
+    let count = sessions.len();
+    for client in sessions {
+        if let Err(e) = client.close().await {
+            tracing::warn!(target: "verification_headless", "Failed to close WebDriver session during shutdown: {}", e);
+        }
+    }
+    if count > 0 {
+        tracing::info!(target: "verification_headless", "Closed {} open WebDriver session(s)", count);
+    }
+    count
+}
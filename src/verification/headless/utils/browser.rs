@@ -95,6 +95,66 @@ pub async fn navigate_to(
     Ok(())
 }
 
+/// Navigates to a URL like [`navigate_to`], first dismissing any interstitial pages
+/// (cookie consent, region selection) that appear before the page is ready.
+///
+/// # Arguments
+/// * `client` - The WebDriver client
+/// * `url` - The URL to navigate to
+/// * `ready_locator` - Locator for element indicating page is ready
+/// * `dismiss_locators` - Buttons that dismiss a known interstitial, clicked when found
+/// * `timeout` - Maximum time to wait for each page to load
+/// * `label` - Task label for logging
+pub async fn navigate_past_interstitials(
+    client: &Client,
+    url: &str,
+    ready_locator: Locator<'static>,
+    dismiss_locators: Vec<(&'static str, Locator<'static>)>,
+    timeout: Duration,
+    label: &str,
+) -> Result<()> {
+    tracing::debug!(target: "verification_headless", "{} Navigating to: {}", label, url);
+
+    client.goto(url).await.map_err(|e| {
+        tracing::error!("{} Failed to navigate: {}", label, e);
+        AppError::from(e)
+    })?;
+
+    // Each interstitial is dismissed at most once, so a button that does nothing can't loop.
+    let mut dismissed = Vec::new();
+    loop {
+        let mut checks = vec![(ready_locator, None)];
+        checks.extend(
+            dismiss_locators
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| !dismissed.contains(i))
+                .map(|(i, (_, locator))| (*locator, Some(i))),
+        );
+
+        match check_outcomes(client, checks, timeout, label).await? {
+            Some(None) => break,
+            Some(Some(i)) => {
+                let (name, locator) = dismiss_locators[i];
+                tracing::info!(target: "verification_headless", "{} Dismissing '{}' interstitial", label, name);
+                wait_and_click(client, locator, timeout, label).await?;
+                dismissed.push(i);
+            }
+            None => {
+                return Err(AppError::FantocciniCmd(format!(
+                    "Page did not load within {:?}: {}",
+                    timeout, url
+                )))
+            }
+        }
+    }
+
+    tracing::debug!(target: "verification_headless", "{} Page loaded successfully", label);
+
+    sleep(Duration::from_millis(300)).await;
+    Ok(())
+}
+
 /// Checks for multiple outcome indicators concurrently with a timeout.
 ///
 /// # Arguments
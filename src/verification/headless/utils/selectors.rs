@@ -33,6 +33,31 @@ impl YahooSelectors {
     pub fn account_disabled() -> Locator<'static> {
         Locator::Css(".ctx-account_is_locked")
     }
+
+    /// "Accept all" on the cookie consent page (guce.yahoo.com) shown in some regions.
+    pub fn consent_accept() -> Locator<'static> {
+        Locator::Css("form.consent-form button[name='agree'], button.accept-all")
+    }
+
+    /// Continue button on the region/language selection page.
+    pub fn region_continue() -> Locator<'static> {
+        Locator::Css("#region-select-form button[type='submit'], button[name='continueBtn']")
+    }
+
+    /// "Not now" on the "Stay signed in?" prompt.
+    pub fn keep_signed_in_dismiss() -> Locator<'static> {
+        Locator::Css("button[name='skip'], #login-signin-skip")
+    }
+
+    /// Interstitials that can appear before the recovery form, by name,
+    /// with the button that dismisses each one.
+    pub fn interstitial_dismissals() -> Vec<(&'static str, Locator<'static>)> {
+        vec![
+            ("cookie_consent", Self::consent_accept()),
+            ("region_selection", Self::region_continue()),
+            ("keep_signed_in", Self::keep_signed_in_dismiss()),
+        ]
+    }
 }
 
 /// Microsoft/Outlook verification flow selectors.
@@ -55,6 +80,18 @@ impl MicrosoftSelectors {
         Locator::Id("iEnterVerification")
     }
 
+    /// Screen for a child account in a Microsoft family group, which a parent has to recover.
+    pub fn exists_child_account() -> Locator<'static> {
+        Locator::Css(
+            "#iChildAccountTitle, [data-bind*='isChildAccount'], a[href*='family.microsoft.com']",
+        )
+    }
+
+    /// Screen for an account that needs a parent's approval before it can be recovered.
+    pub fn exists_parental_approval() -> Locator<'static> {
+        Locator::Css("#iParentalConsentTitle, #iConsentPending, [data-bind*='parentalConsent']")
+    }
+
     pub fn not_exists_error1() -> Locator<'static> {
         Locator::Id("pMemberNameErr")
     }
@@ -67,3 +104,27 @@ impl MicrosoftSelectors {
         Locator::Css("#hipEnforcementContainer, iframe[src*='captcha'], iframe[title*='CAPTCHA']")
     }
 }
+
+/// Azure AD sign-in (login.microsoftonline.com) selectors, used when the
+/// recovery flow hands a work or school account over to the organisation's sign-in.
+pub struct MicrosoftWorkSelectors;
+
+impl MicrosoftWorkSelectors {
+    pub fn email_input() -> Locator<'static> {
+        Locator::Css("input[name='loginfmt']")
+    }
+
+    pub fn next_button() -> Locator<'static> {
+        Locator::Id("idSIButton9")
+    }
+
+    /// Password prompt shown once the username is recognised.
+    pub fn exists_password_prompt() -> Locator<'static> {
+        Locator::Css("input[name='passwd']")
+    }
+
+    /// "This username may be incorrect" error under the username field.
+    pub fn not_exists_username_error() -> Locator<'static> {
+        Locator::Id("usernameError")
+    }
+}